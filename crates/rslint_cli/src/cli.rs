@@ -1,107 +1,171 @@
 //! CLI options
 
-use crate::{lint_err, REPO_LINK};
-use ansi_term::Color::{Green, White, RGB};
+use crate::lint_err;
+use ansi_term::Color::{Cyan, Green, White, Yellow, RGB};
 use regex::{Captures, Regex};
-use rslint_core::get_rule_docs;
+use rslint_core::{get_rule_by_name, get_rule_suggestion, lint_file, CstRuleStore, RuleMeta};
+use rslint_errors::{
+    file::{Files, SimpleFiles},
+    Diagnostic, Emitter, Severity,
+};
 use rslint_lexer::{ansi_term, color};
 
-/// A structure for converting user facing markdown docs to ANSI colored terminal explanations.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A structure for converting the metadata of rules to ANSI colored terminal explanations.
+///
+/// The code examples of a rule are linted with the rule and rendered like any other diagnostic.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExplanationRunner {
-    pub rules: Vec<String>,
-    pub rule_names: Vec<String>,
+    pub rules: Vec<RuleMeta>,
 }
 
 impl ExplanationRunner {
-    /// Make a new runner and try to fetch the remote docs files for each rule.
+    /// Make a new runner with the metadata of each rule.
     /// This automatically issues any linter errors for invalid rules.
-    pub fn new(mut rules: Vec<String>) -> Self {
-        let rule_names = rules.clone();
-        rules = rules
+    pub fn new(rules: Vec<String>) -> Self {
+        let rules = rules
             .into_iter()
             .filter_map(|rule| {
-                let res = get_rule_docs(&rule).map(|x| x.to_string());
+                let res = get_rule_by_name(&rule).map(|rule| rule.meta());
                 if res.is_none() {
-                    lint_err!("Invalid rule: {}", rule);
+                    if let Some(suggestion) = get_rule_suggestion(&rule) {
+                        lint_err!("Invalid rule: {}, did you mean `{}`?", rule, suggestion);
+                    } else {
+                        lint_err!("Invalid rule: {}", rule);
+                    }
                 }
                 res
             })
             .collect();
 
-        Self { rules, rule_names }
+        Self { rules }
     }
 
-    pub fn replace_headers(&mut self) {
-        let regex = Regex::new("#+ (.*)").unwrap();
-        for rule in self.rules.iter_mut() {
-            *rule = regex
-                .replace_all(rule, |cap: &Captures| {
-                    White.bold().paint(cap.get(1).unwrap().as_str()).to_string()
-                })
-                .to_string();
+    pub fn print(self) {
+        for meta in &self.rules {
+            println!("{}", "-".repeat(10));
+            print_rule(meta);
         }
     }
+}
 
-    pub fn replace_code_blocks(&mut self) {
-        let regex = Regex::new("```js\n([\\s\\S]*?)\n```").unwrap();
-        for rule in self.rules.iter_mut() {
-            *rule = regex
-                .replace_all(rule, |cap: &Captures| {
-                    format!("\n{}\n", color(cap.get(1).unwrap().as_str()))
-                })
-                .to_string();
-        }
+fn print_rule(meta: &RuleMeta) {
+    let mut tags = vec![meta.group.to_string()];
+    if meta.recommended {
+        tags.push(Green.paint("recommended").to_string());
     }
-
-    pub fn strip_config_or_extra_examples(&mut self) {
-        for rule in self.rules.iter_mut() {
-            if let Some(idx) = rule.find("# Config") {
-                rule.truncate(idx - 1);
-            }
-            if let Some(idx) = rule.find("<details>") {
-                rule.truncate(idx - 1);
-            }
-        }
+    if meta.fixable {
+        tags.push(Cyan.paint("fixable").to_string());
     }
+    println!("{} [{}]\n", White.bold().paint(meta.name), tags.join(", "));
+    println!("{}\n", render_markdown(prose(meta.docs)));
 
-    pub fn replace_inline_code_blocks(&mut self) {
-        let regex = Regex::new("`(.+?)`").unwrap();
-        for rule in self.rules.iter_mut() {
-            *rule = regex
-                .replace_all(rule, |cap: &Captures| {
-                    let color = RGB(42, 42, 42);
-                    ansi_term::Style::new()
-                        .on(color)
-                        .fg(White)
-                        .paint(cap.get(1).unwrap().as_str())
-                        .to_string()
-                })
-                .to_string();
+    let mut files = SimpleFiles::new();
+    let mut diagnostics = vec![];
+    for (idx, code) in meta.invalid_examples.iter().enumerate() {
+        let file_id = files.add(format!("incorrect-{}.js", idx + 1), code.clone());
+        let reported = lint_example(meta.name, file_id, code);
+        if reported.is_empty() {
+            diagnostics.push(example(file_id, code, Severity::Error, "incorrect code"));
         }
+        diagnostics.extend(reported);
+    }
+    for (idx, code) in meta.valid_examples.iter().enumerate() {
+        let file_id = files.add(format!("correct-{}.js", idx + 1), code.clone());
+        diagnostics.push(example(file_id, code, Severity::Help, "correct code"));
+    }
+    if !diagnostics.is_empty() {
+        println!("{}\n", White.bold().paint("Examples"));
+        emit(&diagnostics, &files);
     }
 
-    pub fn append_link_to_docs(&mut self) {
-        for (docs, name) in self.rules.iter_mut().zip(self.rule_names.iter()) {
-            let group = rslint_core::get_rule_by_name(&name).unwrap().group();
-            let link = format!("{}/docs/rules/{}/{}.md", REPO_LINK, group, name);
-            docs.push_str(&format!("{}: {}\n", Green.paint("Docs").to_string(), link));
+    if !meta.options.is_empty() {
+        println!("{}\n", White.bold().paint("Config"));
+        for option in &meta.options {
+            println!(
+                "  {} ({}, default: {})",
+                Yellow.paint(&option.name),
+                option.ty,
+                color(&option.default.to_string())
+            );
         }
+        println!();
+    }
+    if let Some(url) = &meta.docs_url {
+        println!("{}: {}", Green.paint("Docs"), url);
     }
+}
 
-    pub fn render(&mut self) {
-        self.strip_config_or_extra_examples();
-        self.replace_headers();
-        self.replace_code_blocks();
-        self.replace_inline_code_blocks();
-        self.append_link_to_docs();
+/// The rule diagnostics of an example linted with the rule in its default configuration.
+fn lint_example(rule: &str, file_id: usize, code: &str) -> Vec<Diagnostic> {
+    let mut store = CstRuleStore::new();
+    store.load_rules(get_rule_by_name(rule));
+    // examples using `import` or `export` are modules
+    let result = match lint_file(file_id, code, false, &store, false) {
+        Ok(result) if result.parser_diagnostics.is_empty() => Ok(result),
+        _ => lint_file(file_id, code, true, &store, false),
+    };
+    result
+        .map(|result| {
+            result
+                .rule_results
+                .into_values()
+                .flat_map(|result| result.diagnostics)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A diagnostic pointing at a whole example, used for examples which have no diagnostics of their own.
+fn example(file_id: usize, code: &str, severity: Severity, title: &str) -> Diagnostic {
+    Diagnostic::new(file_id, severity, title).primary(0..code.len(), "")
+}
+
+fn emit(diagnostics: &[Diagnostic], files: &dyn Files) {
+    let mut emitter = Emitter::new(files);
+    for diagnostic in diagnostics {
+        if let Err(err) = emitter.emit_stdout(diagnostic, true) {
+            lint_err!("failed to emit diagnostic: {}", err);
+        }
     }
+}
 
-    pub fn print(mut self) {
-        self.render();
-        for rule in self.rules.into_iter() {
-            println!("{}", "-".repeat(10));
-            println!("{}", rule);
+/// The docs of a rule without its examples and config, which are rendered from the metadata.
+fn prose(docs: &str) -> &str {
+    let mut end = docs.len();
+    let mut offset = 0;
+    for line in docs.split_inclusive('\n') {
+        let trimmed = line.trim().to_ascii_lowercase();
+        let examples = trimmed.starts_with('#')
+            && ["invalid", "incorrect", "correct", "valid", "config"]
+                .iter()
+                .any(|heading| trimmed.contains(heading));
+        if examples || trimmed.starts_with("<details>") {
+            end = offset;
+            break;
         }
+        offset += line.len();
     }
+    docs[..end].trim_end()
+}
+
+fn render_markdown(docs: &str) -> String {
+    let headers = Regex::new("#+ (.*)").unwrap();
+    let code_blocks = Regex::new("```js\n([\\s\\S]*?)\n```").unwrap();
+    let inline_code = Regex::new("`(.+?)`").unwrap();
+
+    let docs = headers.replace_all(docs, |cap: &Captures| {
+        White.bold().paint(cap.get(1).unwrap().as_str()).to_string()
+    });
+    let docs = code_blocks.replace_all(&docs, |cap: &Captures| {
+        format!("\n{}\n", color(cap.get(1).unwrap().as_str()))
+    });
+    inline_code
+        .replace_all(&docs, |cap: &Captures| {
+            ansi_term::Style::new()
+                .on(RGB(42, 42, 42))
+                .fg(White)
+                .paint(cap.get(1).unwrap().as_str())
+                .to_string()
+        })
+        .into_owned()
 }
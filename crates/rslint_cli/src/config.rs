@@ -1,13 +1,21 @@
 //! Configuration file support.
+//!
+//! The config of a file is resolved from every `rslintrc.toml` in its directory and in the directories above it,
+//! up to a config with `root = true`. Configs can also extend other config files or built-in presets with `extends`.
+//! Configs are merged from the furthest to the nearest, and the configs a config extends are merged before it,
+//! so nearer configs override the rule levels and options of the configs they are merged onto.
 
-use crate::lint_warn;
+use crate::{lint_err, lint_warn, EnvConfig};
+use hashbrown::HashMap;
 use heck::{CamelCase, KebabCase};
 use rslint_core::{
-    get_group_rules_by_name, get_rule_by_name, get_rule_suggestion, CstRule, CstRuleStore,
-    RuleLevel,
+    get_group_rules_by_name, get_rule_by_name, get_rule_suggestion,
+    json::{JsonRuleStore, TrailingCommas, TrailingCommasPolicy},
+    util::find_best_match_for_name,
+    CstRule, CstRuleStore, RuleLevel,
 };
 use rslint_errors::{
-    file::{Files, SimpleFile},
+    file::{Files, PathDisplay, SimpleFile},
     Diagnostic,
 };
 use serde::de::{
@@ -15,25 +23,112 @@ use serde::de::{
 };
 use serde::{Deserialize, Deserializer, Serialize};
 use std::borrow::Borrow;
+use std::collections::BTreeMap;
 use std::env::current_dir;
 use std::fmt;
-use std::fs::read_to_string;
+use std::fs::{canonicalize, read_to_string};
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use std::thread::{self, JoinHandle};
 use toml::from_str;
 
 /// The name of the config file to search for.
 pub const CONFIG_NAME: &str = "rslintrc.toml";
 
-#[derive(Debug, Deserialize, Serialize)]
+/// The built-in presets which can be extended with `extends = ["rslint:<name>"]`.
+const PRESETS: [&str; 2] = ["recommended", "all"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub rules: Option<RulesConfig>,
     #[serde(default)]
     pub errors: ErrorsConfig,
+    #[serde(default)]
+    pub env: EnvConfig,
+    #[serde(default)]
+    pub json: JsonConfig,
+    #[serde(default)]
+    pub markdown: MarkdownConfig,
+    /// The config files this config was merged from, from the first to the last merged.
+    #[serde(skip)]
+    pub sources: Vec<PathBuf>,
+}
+
+/// A config file before the configs of parent directories are merged into it.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ConfigFile {
+    /// Stop searching parent directories for configs.
+    #[serde(default)]
+    root: bool,
+    /// Config files relative to this file or built-in presets which are merged before this config.
+    #[serde(default)]
+    extends: Vec<String>,
+    rules: Option<RulesConfig>,
+    errors: Option<ErrorsConfig>,
+    env: Option<EnvConfig>,
+    json: Option<JsonConfig>,
+    markdown: Option<MarkdownConfig>,
+    #[serde(skip)]
+    sources: Vec<PathBuf>,
+}
+
+impl ConfigFile {
+    /// Merge `other` onto this config, everything configured in `other` takes precedence.
+    fn merge(self, other: ConfigFile) -> ConfigFile {
+        ConfigFile {
+            root: other.root,
+            extends: vec![],
+            rules: match (self.rules, other.rules) {
+                (Some(rules), Some(other)) => Some(rules.merge(other)),
+                (rules, other) => other.or(rules),
+            },
+            errors: other.errors.or(self.errors),
+            env: other.env.or(self.env),
+            json: other.json.or(self.json),
+            markdown: other.markdown.or(self.markdown),
+            sources: self.sources.into_iter().chain(other.sources).collect(),
+        }
+    }
+
+    /// The config of a built-in preset.
+    fn preset(name: &str) -> Option<ConfigFile> {
+        let groups = match name {
+            "recommended" => vec!["errors"],
+            "all" => vec!["errors", "style", "promises", "node", "regex"],
+            _ => return None,
+        };
+        Some(ConfigFile {
+            rules: Some(RulesConfig {
+                groups: groups.into_iter().map(String::from).collect(),
+                ..RulesConfig::default()
+            }),
+            ..ConfigFile::default()
+        })
+    }
+}
+
+impl From<ConfigFile> for Config {
+    fn from(file: ConfigFile) -> Self {
+        Self {
+            rules: file.rules,
+            errors: file.errors.unwrap_or_default(),
+            env: file.env.unwrap_or_default(),
+            json: file.json.unwrap_or_default(),
+            markdown: file.markdown.unwrap_or_default(),
+            sources: file.sources,
+        }
+    }
+}
+
+/// A rule as it is configured after precedence is applied.
+enum ResolvedRule {
+    Error(Box<dyn CstRule>),
+    Warning(Box<dyn CstRule>),
+    Allowed,
 }
 
 #[serde(default)]
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct RulesConfig {
     #[serde(deserialize_with = "from_rule_objects")]
     pub errors: Vec<Box<dyn CstRule>>,
@@ -45,59 +140,216 @@ pub struct RulesConfig {
     pub allowed: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ErrorsConfig {
     pub formatter: String,
+    /// The directory the paths of files in reports are relative to, relative to the current directory.
+    pub path_root: Option<PathBuf>,
+    /// The number of columns a tab counts as in reported columns, this should match the tab width of editors.
+    pub tab_width: usize,
 }
 
 impl Default for ErrorsConfig {
     fn default() -> Self {
         Self {
             formatter: "long".to_string(),
+            path_root: None,
+            tab_width: 1,
+        }
+    }
+}
+
+impl ErrorsConfig {
+    /// How the paths of files are rendered in reports, relative to the current directory by default.
+    pub fn path_display(&self) -> PathDisplay {
+        let cwd = current_dir().unwrap_or_default();
+        let root = self.path_root.as_deref().unwrap_or_else(|| Path::new("."));
+        PathDisplay::relative_to(&root.to_string_lossy(), &cwd.to_string_lossy())
+    }
+}
+
+/// The `json` table, which enables linting JSON and JSONC files matched by the glob pattern.
+#[serde(default, rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct JsonConfig {
+    pub enabled: bool,
+    /// The policy of the `json/trailing-commas` rule, strict JSON never allows trailing commas.
+    pub trailing_commas: TrailingCommasPolicy,
+}
+
+impl JsonConfig {
+    /// The JSON rules to run, `None` if JSON files are not linted.
+    pub fn store(&self) -> Option<JsonRuleStore> {
+        if !self.enabled {
+            return None;
         }
+        let mut store = JsonRuleStore::new().builtins();
+        store.set(Box::new(TrailingCommas {
+            policy: self.trailing_commas,
+        }));
+        Some(store)
     }
 }
 
+/// The `markdown` table, which enables linting the JavaScript code blocks of Markdown files matched by the glob pattern.
+#[serde(default, rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MarkdownConfig {
+    pub enabled: bool,
+}
+
 impl Config {
-    /// Search for a config file in the current directory,
-    /// return None if there is no config or if its unreadable.
-    /// This returns a thread handle which was spawned for multithreaded IO.
-    pub fn new_threaded() -> JoinHandle<Option<Self>> {
+    /// Search for config files in the current directory and its parents,
+    /// return None if there is no config or if one of them is unreadable.
+    /// This returns a thread handle which was spawned for multithreaded IO, the loader
+    /// can be reused to resolve the configs of other directories.
+    pub fn new_threaded() -> JoinHandle<(Option<Self>, ConfigLoader)> {
         thread::spawn(|| {
-            let (source, path) = match current_dir()
-                .map(|path| path.join(CONFIG_NAME))
-                .and_then(|path| Ok((read_to_string(&path)?, path)))
-            {
-                Ok(val) => val,
+            let mut loader = ConfigLoader::default();
+            let dir = match current_dir() {
+                Ok(dir) => dir,
                 Err(err) => {
-                    crate::lint_warn!("failed to read config, using default config: {}", err);
-                    return None;
+                    lint_warn!("failed to read config, using default config: {}", err);
+                    return (None, loader);
                 }
             };
-
-            match from_str(&source) {
-                Ok(config) => Some(config),
-                Err(err) => {
-                    let files = SimpleFile::new(path.to_string_lossy().into(), source);
-                    let d = if let Some(idx) = err
-                        .line_col()
-                        .and_then(|(line, col)| Some(files.line_range(0, line)?.start + col))
-                    {
-                        let pos_regex = regex::Regex::new(" at line \\d+ column \\d+$").unwrap();
-                        let msg = err.to_string();
-                        let msg = pos_regex.replace(&msg, "");
-                        Diagnostic::error(0, "config", msg).primary(idx..idx, "")
-                    } else {
-                        Diagnostic::error(0, "config", err.to_string())
-                    };
-                    crate::emit_diagnostic(&d, &files);
-                    None
-                }
+            if !dir
+                .ancestors()
+                .any(|ancestor| ancestor.join(CONFIG_NAME).is_file())
+            {
+                lint_warn!(
+                    "no `{}` found in `{}` or its parents, using default config",
+                    CONFIG_NAME,
+                    dir.display()
+                );
+                return (None, loader);
             }
+            (loader.discover(&dir), loader)
         })
     }
 }
 
+/// Loads and merges config files, every file is only read once.
+#[derive(Debug, Default)]
+pub struct ConfigLoader {
+    /// Loaded files with their extended configs merged in by canonical path, `None` if the file is invalid.
+    files: HashMap<PathBuf, Option<ConfigFile>>,
+}
+
+impl ConfigLoader {
+    /// Resolve the config of the files in `dir` from the config files in it and its parents, up to the
+    /// first config with `root = true`.
+    ///
+    /// Returns `None` if there is no config file, or if one of them is invalid, in which case the errors are emitted.
+    pub fn discover(&mut self, dir: &Path) -> Option<Config> {
+        let mut layers = vec![];
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(CONFIG_NAME);
+            if !path.is_file() {
+                continue;
+            }
+            let file = self.load(&path, &mut vec![])?;
+            let root = file.root;
+            layers.push(file);
+            if root {
+                break;
+            }
+        }
+        if layers.is_empty() {
+            return None;
+        }
+        let merged = layers
+            .into_iter()
+            .rev()
+            .fold(ConfigFile::default(), ConfigFile::merge);
+        Some(merged.into())
+    }
+
+    /// Load a config file and merge the configs it extends into it.
+    fn load(&mut self, path: &Path, stack: &mut Vec<PathBuf>) -> Option<ConfigFile> {
+        let path = canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        if let Some(file) = self.files.get(&path) {
+            return file.clone();
+        }
+        if stack.contains(&path) {
+            lint_err!("config `{}` extends itself", path.display());
+            return None;
+        }
+
+        stack.push(path.clone());
+        let file = self.load_uncached(&path, stack);
+        stack.pop();
+        self.files.insert(path, file.clone());
+        file
+    }
+
+    fn load_uncached(&mut self, path: &Path, stack: &mut Vec<PathBuf>) -> Option<ConfigFile> {
+        let source = match read_to_string(path) {
+            Ok(source) => source,
+            Err(err) => {
+                lint_err!("failed to read config `{}`: {}", path.display(), err);
+                return None;
+            }
+        };
+        let mut file = parse_config(path, source)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let mut merged = ConfigFile::default();
+        for name in std::mem::take(&mut file.extends) {
+            let extended = if let Some(preset) = name.strip_prefix("rslint:") {
+                match ConfigFile::preset(preset) {
+                    Some(file) => file,
+                    None => {
+                        unknown_preset(preset, path);
+                        return None;
+                    }
+                }
+            } else {
+                self.load(&dir.join(&name), stack)?
+            };
+            merged = merged.merge(extended);
+        }
+        file.sources.push(path.to_owned());
+        Some(merged.merge(file))
+    }
+}
+
+fn unknown_preset(preset: &str, path: &Path) {
+    if let Some(suggestion) = find_best_match_for_name(PRESETS.iter().copied(), preset, None) {
+        lint_err!(
+            "unknown preset `rslint:{}` in `{}`, did you mean `rslint:{}`?",
+            preset,
+            path.display(),
+            suggestion
+        );
+    } else {
+        lint_err!("unknown preset `rslint:{}` in `{}`", preset, path.display());
+    }
+}
+
+fn parse_config(path: &Path, source: String) -> Option<ConfigFile> {
+    match from_str(&source) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            let files = SimpleFile::new(path.to_string_lossy().into(), source);
+            let d = if let Some(idx) = err
+                .line_col()
+                .and_then(|(line, col)| Some(files.line_range(0, line)?.start + col))
+            {
+                let pos_regex = regex::Regex::new(" at line \\d+ column \\d+$").unwrap();
+                let msg = err.to_string();
+                let msg = pos_regex.replace(&msg, "");
+                Diagnostic::error(0, "config", msg).primary(idx..idx, "")
+            } else {
+                Diagnostic::error(0, "config", err.to_string())
+            };
+            crate::emit_diagnostic(&d, &files);
+            None
+        }
+    }
+}
+
 impl RulesConfig {
     pub fn error_rule_names(&self) -> impl Iterator<Item = &str> {
         // grouped rules are errors by default
@@ -185,6 +437,57 @@ impl RulesConfig {
         store
     }
 
+    /// Merge `other` onto this config, rules configured in `other` override how they are configured in this config.
+    ///
+    /// Precedence is applied to each config before merging, the merged config declares every rule
+    /// explicitly and is sorted by rule name.
+    pub fn merge(self, other: RulesConfig) -> RulesConfig {
+        let (mut rules, mut unknown_groups) = self.resolve();
+        let (other_rules, other_unknown_groups) = other.resolve();
+        rules.extend(other_rules);
+        unknown_groups.extend(other_unknown_groups);
+
+        let mut merged = RulesConfig {
+            // kept so they are still reported when the store is created
+            groups: unknown_groups,
+            ..RulesConfig::default()
+        };
+        for (name, rule) in rules {
+            match rule {
+                ResolvedRule::Error(rule) => merged.errors.push(rule),
+                ResolvedRule::Warning(rule) => merged.warnings.push(rule),
+                ResolvedRule::Allowed => merged.allowed.push(name),
+            }
+        }
+        merged
+    }
+
+    /// How every rule mentioned in the config is configured, and the groups which do not exist.
+    fn resolve(self) -> (BTreeMap<String, ResolvedRule>, Vec<String>) {
+        let mut rules = BTreeMap::new();
+        let mut unknown_groups = vec![];
+        for group in self.groups {
+            match get_group_rules_by_name(&group) {
+                Some(group_rules) => {
+                    for rule in group_rules {
+                        rules.insert(rule.name().to_string(), ResolvedRule::Error(rule));
+                    }
+                }
+                None => unknown_groups.push(group),
+            }
+        }
+        for rule in self.warnings {
+            rules.insert(rule.name().to_string(), ResolvedRule::Warning(rule));
+        }
+        for rule in self.errors {
+            rules.insert(rule.name().to_string(), ResolvedRule::Error(rule));
+        }
+        for name in self.allowed {
+            rules.insert(name, ResolvedRule::Allowed);
+        }
+        (rules, unknown_groups)
+    }
+
     #[allow(clippy::needless_collect)]
     fn unique_rules(
         first: Vec<Box<dyn CstRule>>,
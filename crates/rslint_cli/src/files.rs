@@ -3,10 +3,14 @@
 use crate::lint_warn;
 use glob::Paths;
 use hashbrown::HashMap;
-use rslint_errors::file::{FileId, Files};
+use rslint_core::environment::{Environment, PackageType, PackageTypes};
+use rslint_core::extract::extractor_for_path;
+use rslint_core::json::JsonKind;
+use rslint_core::FileKind;
+use rslint_errors::file::{DisplayFiles, FileId, Files, PathDisplay};
 use std::fs::read_to_string;
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread::Builder;
 use walkdir::WalkDir;
@@ -17,7 +21,9 @@ static FILE_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
 /// A list of ignored-by-default directory/file names
 const IGNORED: [&str; 1] = ["node_modules"];
 /// A list of the extension of files linted
-const LINTED_FILES: [&str; 2] = ["js", "mjs"];
+const LINTED_FILES: [&str; 3] = ["js", "mjs", "cjs"];
+/// A list of the extensions of JSON files, which are only linted if the config enables it
+const JSON_FILES: [&str; 2] = ["json", "jsonc"];
 
 /// The structure for managing IO to and from the core runner.
 /// The walker uses multithreaded IO, spawning a thread for every file being loaded.
@@ -25,6 +31,10 @@ const LINTED_FILES: [&str; 2] = ["js", "mjs"];
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileWalker {
     pub files: HashMap<usize, JsFile>,
+    /// How the paths of the files are rendered in reports.
+    pub paths: PathDisplay,
+    /// The number of columns a tab counts as in reports.
+    pub tab_width: usize,
 }
 
 impl Files for FileWalker {
@@ -50,73 +60,69 @@ impl Files for FileWalker {
     fn line_range(&self, file_id: FileId, line_index: usize) -> Option<Range<usize>> {
         self.files.get(&file_id)?.line_range(line_index)
     }
+
+    fn tab_width(&self) -> usize {
+        self.tab_width
+    }
 }
 
 impl FileWalker {
     pub fn empty() -> Self {
         Self {
             files: HashMap::new(),
+            paths: PathDisplay::default(),
+            tab_width: 1,
         }
     }
 
     /// Make a new file walker from a compiled glob pattern. This also
     /// skips any unreadable files/dirs
     pub fn from_glob(paths: Paths) -> Self {
-        let mut threads = Vec::new();
-        for entry in paths.filter_map(Result::ok) {
-            if IGNORED.contains(
-                &entry
-                    .file_name()
-                    .map(|x| x.to_string_lossy().to_string())
-                    .unwrap_or_default()
-                    .as_str(),
-            ) {
-                continue;
-            }
+        let mut walker = Self::empty();
+        walker.load(linted_paths(paths));
+        walker
+    }
 
-            let walkdir = WalkDir::new(entry)
-                .into_iter()
-                .filter_entry(|entry| !IGNORED.contains(&&*entry.file_name().to_string_lossy()));
-
-            for file in walkdir.filter_map(Result::ok) {
-                if !LINTED_FILES.contains(
-                    &file
-                        .path()
-                        .extension()
-                        .map(|osstr| osstr.to_string_lossy().to_string())
-                        .unwrap_or_default()
-                        .as_str(),
-                ) {
-                    continue;
-                }
-                // Give each io thread a name so we can potentially debug any io failures easily
-                let thread = Builder::new()
-                    .name(format!("io-{}", file.file_name().to_string_lossy()))
-                    .spawn(move || {
-                        let path = file.path();
-                        let content = match read_to_string(path) {
-                            Ok(v) => v,
-                            Err(err) => {
-                                crate::lint_err!("failed to read file {}: {}", path.display(), err);
-                                return None;
-                            }
-                        };
-                        Some((content, file.path().to_owned()))
-                    })
-                    .expect("Failed to spawn IO thread");
-                threads.push(thread);
-            }
+    /// Read the files at `paths` and add them to the walker, unreadable files are skipped.
+    pub fn load(&mut self, paths: Vec<PathBuf>) {
+        let mut threads = Vec::new();
+        for path in paths {
+            // Give each io thread a name so we can potentially debug any io failures easily
+            let thread = Builder::new()
+                .name(format!(
+                    "io-{}",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                ))
+                .spawn(move || {
+                    let content = match read_to_string(&path) {
+                        Ok(v) => v,
+                        Err(err) => {
+                            crate::lint_err!("failed to read file {}: {}", path.display(), err);
+                            return None;
+                        }
+                    };
+                    Some((content, path))
+                })
+                .expect("Failed to spawn IO thread");
+            threads.push(thread);
         }
 
+        let mut packages = PackageTypes::default();
         let jsfiles = threads
             .into_iter()
             .map(|handle| handle.join())
             .flat_map(|res| res.ok().flatten())
-            .map(|(src, path)| JsFile::new_concrete(src, path))
-            .map(|file| (file.id, file))
-            .collect();
+            .map(|(src, path)| {
+                let package = packages.of(&path);
+                JsFile::new_concrete(src, path, package)
+            })
+            .map(|file| (file.id, file));
+        self.files.extend(jsfiles);
+    }
 
-        Self { files: jsfiles }
+    /// The files with their paths rendered for reports.
+    pub fn display(&self) -> DisplayFiles<'_> {
+        DisplayFiles::new(self, &self.paths, self.files.keys().copied())
     }
 
     pub fn line_start(&self, id: usize, line_index: usize) -> Option<usize> {
@@ -144,7 +150,66 @@ impl FileWalker {
     }
 }
 
-/// A structure representing either a concrete (in-disk) or virtual (temporary/non-disk) js or mjs file.
+/// Expand a compiled glob pattern to the paths of the files which should be linted, directories are walked
+/// recursively and ignored directories such as `node_modules` are skipped.
+pub fn linted_paths(paths: Paths) -> Vec<PathBuf> {
+    walk(paths, is_linted_file)
+}
+
+/// Expand a compiled glob pattern to the paths of the files which JavaScript is extracted from, such as HTML files
+/// and Vue components, like [`linted_paths`].
+pub fn embedded_paths(paths: Paths) -> Vec<PathBuf> {
+    walk(paths, |path| extractor_for_path(path).is_some())
+}
+
+/// Expand a compiled glob pattern to the paths of the JSON and JSONC files, like [`linted_paths`].
+pub fn json_paths(paths: Paths) -> Vec<PathBuf> {
+    walk(paths, |path| has_extension(path, &JSON_FILES))
+}
+
+fn walk(paths: Paths, filter: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut linted = Vec::new();
+    for entry in paths.filter_map(Result::ok) {
+        if IGNORED.contains(
+            &entry
+                .file_name()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default()
+                .as_str(),
+        ) {
+            continue;
+        }
+
+        let walkdir = WalkDir::new(entry)
+            .into_iter()
+            .filter_entry(|entry| !IGNORED.contains(&&*entry.file_name().to_string_lossy()));
+
+        for file in walkdir.filter_map(Result::ok) {
+            if filter(file.path()) {
+                linted.push(file.into_path());
+            }
+        }
+    }
+    linted
+}
+
+/// Whether the file at a path has the extension of a file which is linted.
+pub fn is_linted_file(path: &Path) -> bool {
+    has_extension(path, &LINTED_FILES)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    extensions.contains(
+        &path
+            .extension()
+            .map(|osstr| osstr.to_string_lossy().to_string())
+            .unwrap_or_default()
+            .as_str(),
+    )
+}
+
+/// A structure representing either a concrete (in-disk) or virtual (temporary/non-disk) js, mjs, cjs, or JSON file,
+/// or a file with embedded scripts such as an HTML file.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JsFile {
     pub source: String,
@@ -155,8 +220,10 @@ pub struct JsFile {
 
     /// The codespan id assigned to this file used to refer back to it.
     pub id: usize,
-    /// Whether this is a js or mjs file (script vs module).
+    /// Whether this is a script or a module, a JSON file, or a file with embedded scripts.
     pub kind: JsFileKind,
+    /// The type of the package the file is in, from the closest `package.json`.
+    pub package: Option<PackageType>,
     /// The cached line start locations in this file.
     pub line_starts: Vec<usize>,
 }
@@ -165,16 +232,19 @@ pub struct JsFile {
 pub enum JsFileKind {
     Script,
     Module,
+    Json(JsonKind),
+    /// A file which scripts are extracted from, such as an HTML file or a Vue component.
+    Embedded,
 }
 
 impl JsFile {
-    pub fn new_concrete(source: String, path: PathBuf) -> Self {
+    pub fn new_concrete(source: String, path: PathBuf, package: Option<PackageType>) -> Self {
         let id = FILE_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
-        let kind = if path
-            .extension()
-            .map_or("".into(), |ext| ext.to_string_lossy())
-            == "mjs"
-        {
+        let kind = if let Some(kind) = JsonKind::from_path(&path) {
+            JsFileKind::Json(kind)
+        } else if extractor_for_path(&path).is_some() {
+            JsFileKind::Embedded
+        } else if Environment::resolve(&path, &source, package).kind == FileKind::Module {
             JsFileKind::Module
         } else {
             JsFileKind::Script
@@ -189,10 +259,51 @@ impl JsFile {
             path: Some(path),
             id,
             kind,
+            package,
             line_starts,
         }
     }
 
+    /// The dialect of this file if it is a JSON file.
+    pub fn json_kind(&self) -> Option<JsonKind> {
+        match self.kind {
+            JsFileKind::Json(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a JavaScript script or module, which is linted as a whole.
+    pub fn is_js(&self) -> bool {
+        matches!(self.kind, JsFileKind::Script | JsFileKind::Module)
+    }
+
+    /// Whether this is a Markdown file, which are only linted if the config enables it.
+    pub fn is_markdown(&self) -> bool {
+        self.kind == JsFileKind::Embedded
+            && self
+                .path
+                .as_deref()
+                .and_then(extractor_for_path)
+                .filter(|extractor| extractor.name() == "markdown")
+                .is_some()
+    }
+
+    /// The environment the file runs in, the kind of the file stays the same when its source is updated.
+    pub fn environment(&self) -> Environment {
+        let path = self
+            .path
+            .as_deref()
+            .unwrap_or_else(|| Path::new(&self.name));
+        Environment {
+            kind: if self.kind == JsFileKind::Module {
+                FileKind::Module
+            } else {
+                FileKind::Script
+            },
+            ..Environment::resolve(path, &self.source, self.package)
+        }
+    }
+
     pub fn update_src(&mut self, new: String) {
         self.line_starts = Self::line_starts(&new).collect();
         self.source = new;
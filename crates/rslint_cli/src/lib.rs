@@ -2,85 +2,837 @@ mod cli;
 mod config;
 mod files;
 mod panic_hook;
+mod presets;
+mod watch;
 
-pub use self::{cli::ExplanationRunner, config::*, files::*, panic_hook::*};
-pub use rslint_core::Outcome;
+pub use self::{
+    cli::ExplanationRunner, config::*, files::*, panic_hook::*, presets::*, watch::run_watch,
+};
+pub use rslint_core::{autofix::FixPolicy, profile, FailurePolicy, Outcome, OutcomeSummary};
 pub use rslint_errors::{
-    file, file::Files, Diagnostic, Emitter, Formatter, LongFormatter, Severity, ShortFormatter,
+    file, file::Files, Diagnostic, Emitter, Formatter, GroupedFormatter, JsonFormatter,
+    LongFormatter, Severity, ShortFormatter,
 };
 
 use colored::*;
+use hashbrown::HashMap;
 use rayon::prelude::*;
 use rslint_core::autofix::recursively_apply_fixes;
-use rslint_core::{lint_file, util::find_best_match_for_name, CstRuleStore, LintResult, RuleLevel};
+use rslint_core::baseline::Baseline;
+use rslint_core::cache::LintCache;
+use rslint_core::code_climate::code_climate_issues;
+use rslint_core::environment::Environment;
+use rslint_core::extract::{extractor_for_path, shift_diagnostic, shift_result};
+use rslint_core::json::{lint_json, JsonKind, JsonRuleStore};
+use rslint_core::project::{lint_files, LintInput};
+use rslint_core::sarif::sarif_log;
+use rslint_core::util::globals::Globals;
+use rslint_core::{
+    lint_file_with_options, lint_stdin, util::find_best_match_for_name, CstRuleStore, FileKind,
+    LintResult, LinterOptions, RuleLevel, RuleResult,
+};
+use rslint_errors::file::{PathDisplay, SimpleFile};
 use std::fs::write;
+use std::io::{stdin, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The files which are kept between runs.
+#[derive(Debug, Clone, Default)]
+pub struct PersistentFiles {
+    /// The location of the cache of lint results, results are not cached if this is `None`.
+    pub cache: Option<PathBuf>,
+    /// The location of the baseline, the problems in it are not reported if it exists.
+    pub baseline: PathBuf,
+    /// Write every problem found to the baseline instead of reading it.
+    pub generate_baseline: bool,
+}
 
-pub(crate) const REPO_LINK: &str = "https://github.com/RDambrosio016/RSLint";
+/// The summary of a run which stopped before linting because of an error it reported, the error is counted so
+/// the run fails.
+fn failed_run() -> OutcomeSummary {
+    OutcomeSummary {
+        errors: 1,
+        ..OutcomeSummary::default()
+    }
+}
 
+/// Lint the files matching a glob and print the results, returns the summary of every file for deciding whether
+/// linting failed.
 #[allow(unused_must_use)]
-pub fn run(glob: String, verbose: bool, fix: bool, dirty: bool, formatter: Option<String>) {
+pub fn run(
+    glob: String,
+    verbose: bool,
+    fix: Option<FixPolicy>,
+    dirty: bool,
+    formatter: Option<String>,
+    shuffle: Option<Option<u64>>,
+    files: PersistentFiles,
+) -> OutcomeSummary {
     let res = glob::glob(&glob);
     if let Err(err) = res {
         lint_err!("Invalid glob pattern: {}", err);
-        return;
+        return failed_run();
     }
 
     let handle = config::Config::new_threaded();
     let mut walker = FileWalker::from_glob(res.unwrap());
+    // JSON files are only linted if the config of their directory enables it, which is checked once the
+    // stores are resolved
+    if let Ok(paths) = glob::glob(&glob) {
+        walker.load(json_paths(paths));
+    }
+    if let Ok(paths) = glob::glob(&glob) {
+        walker.load(embedded_paths(paths));
+    }
     let joined = handle.join();
-    let config = joined.expect("config thread paniced");
+    let (config, loader) = joined.expect("config thread paniced");
 
-    let store = if let Some(cfg) = config.as_ref().and_then(|cfg| cfg.rules.as_ref()) {
-        cfg.store()
-    } else {
-        CstRuleStore::new().builtins()
-    };
     let mut formatter = formatter
         .or_else(|| config.as_ref().map(|c| c.errors.formatter.clone()))
         .unwrap_or_else(|| String::from("long"));
+    walker.paths = path_display(config.as_ref());
+    walker.tab_width = tab_width(config.as_ref());
+    let stores = RuleStores::new(
+        config,
+        loader,
+        walker
+            .files
+            .values()
+            .filter_map(|file| file.path.as_deref()),
+    );
 
     verify_formatter(&mut formatter);
 
+    walker.files.retain(|_, file| match file.kind {
+        JsFileKind::Json(_) => stores.for_file(file).json.is_some(),
+        JsFileKind::Embedded if file.is_markdown() => stores.for_file(file).markdown,
+        _ => true,
+    });
     if walker.files.is_empty() {
         lint_err!("No matching files found");
-        return;
+        return failed_run();
     }
 
-    let mut results = walker
+    let base_options = LinterOptions {
+        verbose,
+        shuffle_seed: shuffle.map(shuffle_seed),
+        report_disabled_rules: verbose,
+        record_suppressed: verbose,
+        ..LinterOptions::default()
+    };
+    // the options of each config in `stores.dirs`, fix policies depend on the configured rule levels
+    let options = stores
+        .dirs
+        .iter()
+        .map(|dir| LinterOptions {
+            fix_policy: resolve_fix_policy(fix.clone(), &dir.store, dir.config.as_ref()),
+            ..base_options.clone()
+        })
+        .collect::<Vec<_>>();
+
+    // rule levels are applied after linting so they are part of the key, autofix and SARIF need full results
+    let mut cache = files.cache.clone().map(LintCache::load);
+    let keys = stores
+        .dirs
+        .iter()
+        .zip(&options)
+        .map(|(dir, options)| {
+            let levels = dir
+                .config
+                .as_ref()
+                .and_then(|cfg| serde_json::to_string(&cfg.rules).ok())
+                .unwrap_or_default();
+            let with_globals = |globals: &Globals| LinterOptions {
+                globals: globals.clone(),
+                ..options.clone()
+            };
+            (
+                LintCache::key(&dir.store, &with_globals(&dir.globals), &levels),
+                LintCache::key(&dir.test_store, &with_globals(&dir.test_globals), &levels),
+            )
+        })
+        .collect::<Vec<_>>();
+    let cache_key = |path: &Path, file: &JsFile| {
+        let idx = stores.index(path);
+        let key = if stores.dirs[idx].test_files.is_test_file(path) {
+            keys[idx].1
+        } else {
+            keys[idx].0
+        };
+        LintCache::environment_key(key, file.environment())
+    };
+    let cached = cache
+        .as_ref()
+        .filter(|_| fix.is_none() && !matches!(formatter.as_str(), "sarif" | "codeclimate"))
+        .map(|cache| {
+            walker
+                .files
+                .values()
+                .filter(|file| file.json_kind().is_none())
+                .filter_map(|file| {
+                    let path = file.path.as_ref()?;
+                    let diagnostics = cache.get(
+                        &path.to_string_lossy(),
+                        &file.source,
+                        cache_key(path, file),
+                        file.id,
+                    )?;
+                    Some((file.id, diagnostics))
+                })
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let json_diagnostics = walker
+        .files
+        .par_iter()
+        .filter_map(|(id, file)| {
+            let store = stores.for_file(file).json.as_ref()?;
+            Some((*id, lint_json(*id, &file.source, file.json_kind()?, store)))
+        })
+        .collect::<Vec<_>>();
+
+    let embedded_diagnostics = walker
         .files
-        .par_keys()
-        .map(|id| {
-            let file = walker.files.get(id).unwrap();
-            lint_file(
+        .par_iter()
+        .filter(|(id, file)| !cached.contains_key(*id) && file.kind == JsFileKind::Embedded)
+        .filter_map(|(id, file)| {
+            let path = file.path.as_ref()?;
+            let idx = stores.index(path);
+            let options = LinterOptions {
+                globals: stores.globals_for(path).clone(),
+                ..options[idx].clone()
+            };
+            Some((
                 *id,
-                &file.source.clone(),
-                file.kind == JsFileKind::Module,
-                &store,
-                verbose,
-            )
+                lint_embedded(*id, path, &file.source, &stores, options),
+            ))
         })
-        .filter_map(|res| {
-            if let Err(diagnostic) = res {
-                emit_diagnostic(&diagnostic, &walker);
-                None
-            } else {
-                res.ok()
+        .collect::<Vec<_>>();
+
+    let inputs = walker
+        .files
+        .iter()
+        .filter(|(id, file)| !cached.contains_key(*id) && file.is_js())
+        .map(|(id, file)| {
+            let (store, idx, globals) = match file.path {
+                Some(ref path) => (
+                    stores.for_path(path),
+                    stores.index(path),
+                    stores.globals_for(path),
+                ),
+                None => (&stores.default().store, 0, &stores.default().globals),
+            };
+            LintInput {
+                file_id: *id,
+                path: file
+                    .path
+                    .clone()
+                    .unwrap_or_else(|| file.name.clone().into()),
+                source: file.source.clone(),
+                kind: if file.kind == JsFileKind::Module {
+                    FileKind::Module
+                } else {
+                    FileKind::Script
+                },
+                store,
+                options: LinterOptions {
+                    globals: file.environment().globals(globals),
+                    ..options[idx].clone()
+                },
             }
         })
         .collect::<Vec<_>>();
+    let linted = lint_files(inputs, None);
+    for error in &linted.errors {
+        emit_diagnostic(error, &walker.display());
+    }
+    let mut results = linted.results;
 
-    let fix_count = if fix {
+    let fix_count = if fix.is_some() {
         apply_fixes(&mut results, &mut walker, dirty)
     } else {
         0
     };
-    print_results(
+    // rule levels are part of the cache key so they are applied before caching, and before the baseline since
+    // baselines are written from the reported diagnostics
+    for result in results.iter_mut() {
+        stores
+            .for_file(&walker.files[&result.file_id])
+            .remap(result);
+    }
+
+    if let Some(cache) = cache.as_mut() {
+        let linted = results
+            .iter()
+            .map(|result| {
+                let diagnostics = result
+                    .diagnostics()
+                    .chain(result.suppressed_diagnostics())
+                    .cloned()
+                    .collect();
+                (result.file_id, diagnostics)
+            })
+            .chain(embedded_diagnostics.iter().cloned());
+        for (id, diagnostics) in linted {
+            let file = &walker.files[&id];
+            if let Some(ref path) = file.path {
+                cache.insert(
+                    path.to_string_lossy(),
+                    &file.source,
+                    cache_key(path, file),
+                    diagnostics,
+                );
+            }
+        }
+        cache.prune();
+        if let Err(err) = cache.save() {
+            lint_warn!(
+                "failed to write the cache to `{}`: {}",
+                cache.location().display(),
+                err
+            );
+        }
+    }
+
+    let mut others = cached
+        .into_iter()
+        .chain(json_diagnostics)
+        .chain(embedded_diagnostics)
+        .collect::<Vec<_>>();
+    let baselined = apply_baseline(&files, &walker, &mut results, &mut others);
+
+    let summary = print_results(
         &mut results,
+        others
+            .into_iter()
+            .map(|(_, diagnostics)| diagnostics)
+            .collect(),
         &walker,
-        config.as_ref(),
         fix_count,
         &formatter,
     );
+    if baselined > 0 {
+        lint_note!(
+            "{} problem{} in the baseline {} not reported",
+            baselined,
+            if baselined == 1 { "" } else { "s" },
+            if baselined == 1 { "was" } else { "were" }
+        );
+    }
+    summary
+}
+
+/// Remove the problems in the baseline from the results, or write the baseline from the results if it is being
+/// generated. `others` are the diagnostics of files which are not in `results` by their file id. Returns the
+/// number of problems removed.
+fn apply_baseline(
+    files: &PersistentFiles,
+    walker: &FileWalker,
+    results: &mut [LintResult],
+    others: &mut [(usize, Vec<Diagnostic>)],
+) -> usize {
+    let path = |id: usize| {
+        let file = walker.files.get(&id)?;
+        let path = file.path.as_ref()?;
+        Some((walker.paths.display(&path.to_string_lossy()), file))
+    };
+
+    let baseline = if files.generate_baseline {
+        let mut baseline = Baseline::new();
+        for result in results.iter() {
+            if let Some((path, file)) = path(result.file_id) {
+                baseline.add(&path, &file.source, result.diagnostics());
+            }
+        }
+        for (id, diagnostics) in others.iter() {
+            if let Some((path, file)) = path(*id) {
+                baseline.add(&path, &file.source, diagnostics);
+            }
+        }
+        match baseline.save(&files.baseline) {
+            Ok(()) => lint_note!(
+                "wrote {} problem{} to the baseline at `{}`",
+                baseline.len(),
+                if baseline.len() == 1 { "" } else { "s" },
+                files.baseline.display()
+            ),
+            Err(err) => lint_err!(
+                "failed to write the baseline to `{}`: {}",
+                files.baseline.display(),
+                err
+            ),
+        }
+        baseline
+    } else {
+        match load_baseline(&files.baseline) {
+            Some(baseline) => baseline,
+            None => return 0,
+        }
+    };
+
+    let mut removed = 0;
+    for result in results.iter_mut() {
+        if let Some((path, _)) = path(result.file_id) {
+            removed += baseline.filter_result(&path, result);
+        }
+    }
+    for (id, diagnostics) in others.iter_mut() {
+        if let Some((path, file)) = path(*id) {
+            removed += baseline.filter(&path, &file.source, diagnostics);
+        }
+    }
+    removed
+}
+
+/// Load the baseline at `location`, `None` if there is no baseline or it is not valid.
+fn load_baseline(location: &Path) -> Option<Baseline> {
+    if !location.exists() {
+        return None;
+    }
+    Baseline::load(location)
+        .map_err(|err| {
+            lint_err!(
+                "failed to read the baseline at `{}`: {}",
+                location.display(),
+                err
+            );
+        })
+        .ok()
+}
+
+/// Lint the scripts embedded in a file such as an HTML file or a Vue component, each script is linted on its own
+/// and its diagnostics are moved to where it is in the file and mapped to the configured rule levels.
+///
+/// Embedded scripts are never fixed since the fixes apply to the extracted source.
+fn lint_embedded(
+    file_id: usize,
+    path: &Path,
+    src: &str,
+    stores: &RuleStores,
+    options: LinterOptions,
+) -> Vec<Diagnostic> {
+    let extractor = match extractor_for_path(path) {
+        Some(extractor) => extractor,
+        None => return vec![],
+    };
+    let dir = stores.dir(path);
+    let mut diagnostics = vec![];
+    for extracted in extractor.extract(src) {
+        let env = Environment {
+            kind: extracted.kind,
+            node: false,
+        };
+        let options = LinterOptions {
+            globals: env.globals(&options.globals),
+            ..options.clone()
+        };
+        let res = lint_file_with_options(
+            file_id,
+            &extracted.source,
+            extracted.kind == FileKind::Module,
+            dir.for_path(path),
+            options,
+        );
+        match res {
+            Ok(mut result) => {
+                dir.remap(&mut result);
+                shift_result(&mut result, extracted.offset);
+                diagnostics.extend(
+                    result
+                        .diagnostics()
+                        .chain(result.suppressed_diagnostics())
+                        .cloned(),
+                );
+            }
+            Err(mut diagnostic) => {
+                shift_diagnostic(&mut diagnostic, extracted.offset);
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Lint source code read from stdin as if it was the file at `path`, and print the diagnostics.
+///
+/// The config, the environment, and the kind of the file are resolved from `path`, so JSON files and files with
+/// embedded scripts are linted like they would be on disk. If `fix` is set the code fixed with the policy is printed
+/// to stdout instead of the outcome, which allows piping code through the linter from editors. The problems in the
+/// baseline at `baseline` are not reported. Returns the summary of the diagnostics.
+#[allow(unused_must_use)]
+pub fn run_stdin(
+    path: PathBuf,
+    verbose: bool,
+    fix: Option<FixPolicy>,
+    dirty: bool,
+    formatter: Option<String>,
+    shuffle: Option<Option<u64>>,
+    baseline: PathBuf,
+) -> OutcomeSummary {
+    let (config, loader) = config::Config::new_threaded()
+        .join()
+        .expect("config thread paniced");
+
+    let mut formatter = formatter
+        .or_else(|| config.as_ref().map(|c| c.errors.formatter.clone()))
+        .unwrap_or_else(|| String::from("long"));
+    verify_formatter(&mut formatter);
+
+    let name = path_display(config.as_ref()).display(&path.to_string_lossy());
+    let tab_width = tab_width(config.as_ref());
+    let stores = RuleStores::new(config, loader, std::iter::once(path.as_path()));
+    let dir = stores.dir(&path);
+    let store = dir.for_path(&path);
+    let options = LinterOptions {
+        verbose,
+        shuffle_seed: shuffle.map(shuffle_seed),
+        report_disabled_rules: verbose,
+        record_suppressed: verbose,
+        fix_policy: resolve_fix_policy(fix.clone(), store, dir.config.as_ref()),
+        globals: dir.globals_for(&path).clone(),
+        ..LinterOptions::default()
+    };
+
+    let json_kind = JsonKind::from_path(&path);
+    let extractor = extractor_for_path(&path);
+    if json_kind.is_some() || extractor.is_some() {
+        let mut source = String::new();
+        if let Err(err) = stdin().read_to_string(&mut source) {
+            lint_err!("failed to read source code: {}", err);
+            return failed_run();
+        }
+        let mut diagnostics = match (json_kind, dir.json.as_ref()) {
+            (Some(kind), Some(store)) => lint_json(0, &source, kind, store),
+            (Some(_), None) => {
+                lint_warn!("`{}` is not linted because `json.enabled` is not set", name);
+                vec![]
+            }
+            _ if extractor.filter(|e| e.name() == "markdown").is_some() && !dir.markdown => {
+                lint_warn!(
+                    "`{}` is not linted because `markdown.enabled` is not set",
+                    name
+                );
+                vec![]
+            }
+            _ => lint_embedded(0, &path, &source, &stores, options),
+        };
+        if let Some(known) = load_baseline(&baseline) {
+            known.filter(&name, &source, &mut diagnostics);
+        }
+        let file = SimpleFile::new(name, source.clone()).with_tab_width(tab_width);
+        emit_stdin_diagnostics(&formatter, fix.is_some(), &diagnostics, &[], &file);
+        // these files are never fixed, the source is printed as it is so piping it does not lose it
+        if fix.is_some() {
+            print!("{}", source);
+        } else if !matches!(formatter.as_str(), "json" | "sarif" | "codeclimate") {
+            print_outcome(Outcome::from(&diagnostics));
+        }
+        return OutcomeSummary::from(&diagnostics);
+    }
+
+    let mut res = match lint_stdin(stdin(), &path, store, options) {
+        Ok(res) => res,
+        Err(diagnostic) => {
+            let file = SimpleFile::new(name, String::new()).with_tab_width(tab_width);
+            emit_diagnostic(&diagnostic, &file);
+            return OutcomeSummary::from(std::iter::once(diagnostic));
+        }
+    };
+
+    let source = res.parsed.text().to_string();
+    dir.remap(&mut res);
+    // the fixes are applied to the full result, so syntax errors in the baseline still prevent them
+    let mut reported = res.clone();
+    if let Some(known) = load_baseline(&baseline) {
+        known.filter_result(&name, &mut reported);
+    }
+    let file = SimpleFile::new(name, source.clone()).with_tab_width(tab_width);
+    let diagnostics = reported
+        .diagnostics()
+        .chain(reported.suppressed_diagnostics())
+        .cloned()
+        .collect::<Vec<_>>();
+    emit_stdin_diagnostics(
+        &formatter,
+        fix.is_some(),
+        &diagnostics,
+        std::slice::from_ref(&reported),
+        &file,
+    );
+
+    if fix.is_some() {
+        let fixed = res.fix(dirty).unwrap_or_else(|| {
+            lint_note!("skipping autofix because the code contains syntax errors");
+            source
+        });
+        print!("{}", fixed);
+    } else if !matches!(formatter.as_str(), "json" | "sarif" | "codeclimate") {
+        print_outcome(reported.outcome());
+    }
+    reported.summary()
+}
+
+/// Emit the diagnostics of code linted from stdin, `results` are used for the SARIF and Code Climate formatters.
+fn emit_stdin_diagnostics(
+    formatter: &str,
+    fix: bool,
+    diagnostics: &[Diagnostic],
+    results: &[LintResult],
+    file: &SimpleFile,
+) {
+    if formatter == "sarif" {
+        emit_sarif(results, file);
+    } else if formatter == "codeclimate" {
+        emit_code_climate(results, file);
+    } else if fix && formatter == "json" {
+        // stdout is taken by the fixed code
+        if let Err(err) = JsonFormatter::default().emit_stderr(diagnostics, file) {
+            lint_err!("failed to emit diagnostic: {}", err);
+        }
+    } else {
+        emit_diagnostics(formatter, diagnostics, file);
+    }
+}
+
+fn print_outcome(outcome: Outcome) {
+    output_overall(
+        (outcome == Outcome::Failure) as usize,
+        (outcome == Outcome::Warning) as usize,
+        (outcome == Outcome::Success) as usize,
+        0,
+    );
+}
+
+/// The rule stores configured for the files of a directory.
+pub(crate) struct DirStores {
+    pub config: Option<config::Config>,
+    pub store: CstRuleStore,
+    /// The store used for test files, rules relaxed by environment presets are removed from it.
+    pub test_store: CstRuleStore,
+    pub test_files: TestFileMatcher,
+    /// The globals of files which are not test files.
+    pub globals: Globals,
+    /// The globals of test files, which include the globals of the test framework presets.
+    pub test_globals: Globals,
+    /// The JSON rules, `None` if JSON files are not linted.
+    pub json: Option<JsonRuleStore>,
+    /// Whether the code blocks of Markdown files are linted.
+    pub markdown: bool,
+}
+
+impl DirStores {
+    pub fn new(config: Option<config::Config>) -> Self {
+        let mut store = if let Some(cfg) = config.as_ref().and_then(|cfg| cfg.rules.as_ref()) {
+            cfg.store()
+        } else {
+            CstRuleStore::new().builtins()
+        };
+        let env = config
+            .as_ref()
+            .map(|cfg| cfg.env.clone())
+            .unwrap_or_default();
+        if !env.node {
+            let rules = config.as_ref().and_then(|cfg| cfg.rules.as_ref());
+            if let Some(rule) = rules
+                .into_iter()
+                .flat_map(|rules| rules.errors.iter().chain(rules.warnings.iter()))
+                .find(|rule| rule.group() == "node")
+            {
+                lint_warn!(
+                    "the `{}` rule is configured but it is not run because the `node` environment is not enabled",
+                    rule.name()
+                );
+            }
+            store.rules.retain(|rule| rule.group() != "node");
+        }
+        let json = config.as_ref().and_then(|cfg| cfg.json.store());
+        let markdown = config.as_ref().filter(|cfg| cfg.markdown.enabled).is_some();
+        let relaxed = env.relaxed_rules();
+        let mut test_store = store.clone();
+        test_store
+            .rules
+            .retain(|rule| !relaxed.contains(&rule.name()));
+        let globals = env.globals();
+        let mut test_globals = globals.clone();
+        test_globals.merge(&env.test_globals());
+
+        Self {
+            config,
+            store,
+            test_store,
+            test_files: env.test_file_matcher(),
+            globals,
+            test_globals,
+            json,
+            markdown,
+        }
+    }
+
+    /// The globals of the file at `path`.
+    pub fn globals_for(&self, path: &Path) -> &Globals {
+        if self.test_files.is_test_file(path) {
+            &self.test_globals
+        } else {
+            &self.globals
+        }
+    }
+
+    /// The store which should be used for the file at `path`.
+    pub fn for_path(&self, path: &Path) -> &CstRuleStore {
+        if self.test_files.is_test_file(path) {
+            &self.test_store
+        } else {
+            &self.store
+        }
+    }
+
+    /// Map the diagnostics of every rule in a result to the configured level of the rule.
+    pub fn remap(&self, result: &mut LintResult) {
+        if let Some(conf) = self.config.as_ref().and_then(|cfg| cfg.rules.as_ref()) {
+            for (rule_name, rule_result) in result.rule_results.iter_mut() {
+                let level = conf.rule_level_by_name(rule_name);
+                remap_diagnostics_to_level(&mut rule_result.diagnostics, level);
+                remap_diagnostics_to_level(&mut rule_result.suppressed, level);
+            }
+        }
+    }
+}
+
+/// The rule stores configured for a linting run, files use the config resolved for their directory.
+pub(crate) struct RuleStores {
+    /// The stores of each distinct config, the first one is the config of the current directory.
+    pub dirs: Vec<DirStores>,
+    /// The index of the stores of every directory whose config was resolved.
+    resolved: HashMap<PathBuf, usize>,
+}
+
+impl RuleStores {
+    /// Create the stores for the config of the current directory and the configs of the directories
+    /// of `paths`. Files in other directories use the config of the current directory.
+    pub fn new<'a>(
+        config: Option<config::Config>,
+        mut loader: ConfigLoader,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) -> Self {
+        let mut dirs = vec![DirStores::new(config)];
+        let mut resolved = HashMap::new();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        for path in paths {
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            if resolved.contains_key(dir) {
+                continue;
+            }
+            let absolute = cwd.join(dir);
+            let absolute = std::fs::canonicalize(&absolute).unwrap_or(absolute);
+            let config = loader.discover(&absolute);
+            let sources = config.as_ref().map(|cfg| &cfg.sources);
+            let idx = match dirs
+                .iter()
+                .position(|stores| stores.config.as_ref().map(|cfg| &cfg.sources) == sources)
+            {
+                Some(idx) => idx,
+                None => {
+                    dirs.push(DirStores::new(config));
+                    dirs.len() - 1
+                }
+            };
+            resolved.insert(dir.to_owned(), idx);
+        }
+        Self { dirs, resolved }
+    }
+
+    /// The stores of the config of the current directory.
+    pub fn default(&self) -> &DirStores {
+        &self.dirs[0]
+    }
+
+    /// The index of the stores configured for the file at `path` in `dirs`.
+    pub fn index(&self, path: &Path) -> usize {
+        path.parent()
+            .and_then(|dir| self.resolved.get(dir))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The stores configured for the file at `path`.
+    pub fn dir(&self, path: &Path) -> &DirStores {
+        &self.dirs[self.index(path)]
+    }
+
+    /// The store which should be used for the file at `path`.
+    pub fn for_path(&self, path: &Path) -> &CstRuleStore {
+        self.dir(path).for_path(path)
+    }
+
+    /// The globals of the file at `path`.
+    pub fn globals_for(&self, path: &Path) -> &Globals {
+        self.dir(path).globals_for(path)
+    }
+
+    /// The stores configured for a file, files without a path use the config of the current directory.
+    pub fn for_file(&self, file: &JsFile) -> &DirStores {
+        match file.path {
+            Some(ref path) => self.dir(path),
+            None => self.default(),
+        }
+    }
+}
+
+/// How file paths are rendered in reports, according to the `errors` table of the config.
+pub(crate) fn path_display(config: Option<&config::Config>) -> PathDisplay {
+    match config {
+        Some(cfg) => cfg.errors.path_display(),
+        None => ErrorsConfig::default().path_display(),
+    }
+}
+
+/// The number of columns a tab counts as in reports, according to the `errors` table of the config.
+pub(crate) fn tab_width(config: Option<&config::Config>) -> usize {
+    config.map_or(1, |cfg| cfg.errors.tab_width)
+}
+
+/// Resolve the fix policy given on the command line for a store.
+///
+/// Rule diagnostics are only remapped to the configured level of their rule after linting, so a minimum severity
+/// is resolved to the rules whose configured level meets it instead of the severity the rules report.
+pub(crate) fn resolve_fix_policy(
+    policy: Option<FixPolicy>,
+    store: &CstRuleStore,
+    config: Option<&config::Config>,
+) -> FixPolicy {
+    match policy.unwrap_or_default() {
+        FixPolicy::MinSeverity(severity) => FixPolicy::Rules(
+            store
+                .rules
+                .iter()
+                .map(|rule| rule.name())
+                .filter(|name| {
+                    let level = config
+                        .and_then(|cfg| cfg.rules.as_ref())
+                        .map_or(RuleLevel::Error, |rules| rules.rule_level_by_name(name));
+                    match level {
+                        RuleLevel::Error => Severity::Error >= severity,
+                        RuleLevel::Warning => Severity::Warning >= severity,
+                    }
+                })
+                .map(String::from)
+                .collect(),
+        ),
+        policy => policy,
+    }
+}
+
+/// Resolve the seed used for `--shuffle`, generating one if none was given.
+pub(crate) fn shuffle_seed(seed: Option<u64>) -> u64 {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    });
+    lint_note!("shuffling rule order with seed {}", seed);
+    seed
 }
 
 pub fn apply_fixes(results: &mut Vec<LintResult>, walker: &mut FileWalker, dirty: bool) -> usize {
@@ -104,19 +856,16 @@ pub fn apply_fixes(results: &mut Vec<LintResult>, walker: &mut FileWalker, dirty
             );
             continue;
         }
-        let original_problem_num = res
-            .rule_results
-            .iter()
-            .filter(|(_, x)| x.outcome() == Outcome::Warning || x.outcome() == Outcome::Failure)
-            .map(|(_, res)| res.diagnostics.len())
-            .sum::<usize>();
+        let problems = |res: &LintResult| {
+            res.rule_results
+                .values()
+                .map(RuleResult::summary)
+                .sum::<OutcomeSummary>()
+                .problems()
+        };
+        let original_problem_num = problems(res);
         let fixed = recursively_apply_fixes(res);
-        let new_problem_num = res
-            .rule_results
-            .iter()
-            .filter(|(_, x)| x.outcome() == Outcome::Warning || x.outcome() == Outcome::Failure)
-            .map(|(_, res)| res.diagnostics.len())
-            .sum::<usize>();
+        let new_problem_num = problems(res);
         let path = file.path.as_ref().unwrap();
         if let Err(err) = write(path, fixed.clone()) {
             lint_err!("failed to write to `{:#?}`: {}", path, err.to_string());
@@ -128,59 +877,92 @@ pub fn apply_fixes(results: &mut Vec<LintResult>, walker: &mut FileWalker, dirty
     fix_count
 }
 
+/// Print the results of linting, `cached` are the diagnostics of files which were not linted again because their
+/// results were cached, and of JSON files. Results must already be mapped to the rule levels of their files.
+/// Returns the summary of every file.
 pub(crate) fn print_results(
     results: &mut Vec<LintResult>,
+    cached: Vec<Vec<Diagnostic>>,
     walker: &FileWalker,
-    config: Option<&config::Config>,
     fix_count: usize,
     formatter: &str,
-) {
-    // Map each diagnostic to the correct level according to configured rule level
-    for result in results.iter_mut() {
-        for (rule_name, diagnostics) in result
-            .rule_results
-            .iter_mut()
-            .map(|x| (x.0, &mut x.1.diagnostics))
-        {
-            if let Some(conf) = config.and_then(|cfg| cfg.rules.as_ref()) {
-                remap_diagnostics_to_level(diagnostics, conf.rule_level_by_name(rule_name));
-            }
-        }
-    }
-
-    let failures = results
+) -> OutcomeSummary {
+    let outcomes = results
         .iter()
-        .filter(|res| res.outcome() == Outcome::Failure)
-        .count();
-    let warnings = results
-        .iter()
-        .filter(|res| res.outcome() == Outcome::Warning)
-        .count();
-    let successes = results
+        .map(|res| res.outcome())
+        .chain(cached.iter().map(Outcome::from))
+        .collect::<Vec<_>>();
+    let failures = outcomes.iter().filter(|x| **x == Outcome::Failure).count();
+    let warnings = outcomes.iter().filter(|x| **x == Outcome::Warning).count();
+    let successes = outcomes.iter().filter(|x| **x == Outcome::Success).count();
+
+    let overall = Outcome::merge(outcomes);
+    let summary = results
         .iter()
-        .filter(|res| res.outcome() == Outcome::Success)
-        .count();
+        .map(LintResult::summary)
+        .chain(cached.iter().map(OutcomeSummary::from))
+        .sum();
+    let files = walker.display();
 
-    let overall = Outcome::merge(results.iter().map(|res| res.outcome()));
+    if formatter == "sarif" {
+        emit_sarif(results, &files);
+        return summary;
+    }
+    if formatter == "codeclimate" {
+        emit_code_climate(results, &files);
+        return summary;
+    }
 
-    for result in results.iter_mut() {
-        emit_diagnostics(
-            formatter,
-            &result.diagnostics().cloned().collect::<Vec<_>>(),
-            walker,
-        );
+    // the json formatter emits a single document for every file and nothing else so it can be parsed,
+    // the grouped formatter needs all diagnostics at once to group them
+    if matches!(formatter, "json" | "grouped") {
+        let diagnostics = results
+            .iter()
+            .flat_map(|res| {
+                res.diagnostics()
+                    .chain(res.suppressed_diagnostics())
+                    .cloned()
+            })
+            .chain(cached.into_iter().flatten())
+            .collect::<Vec<_>>();
+        emit_diagnostics(formatter, &diagnostics, &files);
+    } else {
+        for result in results.iter_mut() {
+            emit_diagnostics(
+                formatter,
+                &result
+                    .diagnostics()
+                    .chain(result.suppressed_diagnostics())
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                &files,
+            );
+        }
+        for diagnostics in &cached {
+            emit_diagnostics(formatter, diagnostics, &files);
+        }
+    }
+    if formatter == "json" {
+        return summary;
     }
 
     output_overall(failures, warnings, successes, fix_count);
     if overall == Outcome::Failure {
         println!("\nhelp: for more information about the errors try the explain command: `rslint explain <rules>`");
     }
+    summary
 }
 
+/// The names of all of the formatters which can be used.
+const FORMATTERS: [&str; 6] = ["short", "long", "grouped", "json", "sarif", "codeclimate"];
+
 pub fn verify_formatter(formatter: &mut String) {
-    if !matches!(formatter.as_str(), "short" | "long") {
+    if !matches!(
+        formatter.as_str(),
+        "short" | "long" | "grouped" | "json" | "sarif" | "codeclimate"
+    ) {
         if let Some(suggestion) =
-            find_best_match_for_name(vec!["short", "long"].into_iter(), formatter, None)
+            find_best_match_for_name(FORMATTERS.iter().copied(), formatter, None)
         {
             lint_err!(
                 "unknown formatter `{}`, using default formatter, did you mean `{}`?",
@@ -206,9 +988,18 @@ pub fn emit_diagnostics(formatter: &str, diagnostics: &[Diagnostic], files: &dyn
                 lint_err!("failed to emit diagnostic: {}", err);
             }
         }
+        "grouped" => {
+            if let Err(err) = GroupedFormatter.emit_stderr(diagnostics, files) {
+                lint_err!("failed to emit diagnostic: {}", err);
+            }
+        }
+        "json" => {
+            if let Err(err) = JsonFormatter::default().emit_stdout(diagnostics, files) {
+                lint_err!("failed to emit diagnostic: {}", err);
+            }
+        }
         f => {
-            if let Some(suggestion) =
-                find_best_match_for_name(vec!["short", "long"].into_iter(), f, None)
+            if let Some(suggestion) = find_best_match_for_name(FORMATTERS.iter().copied(), f, None)
             {
                 lint_err!("unknown formatter `{}`, did you mean `{}`?", f, suggestion);
             } else {
@@ -218,8 +1009,31 @@ pub fn emit_diagnostics(formatter: &str, diagnostics: &[Diagnostic], files: &dyn
     }
 }
 
+/// Emit the results as a SARIF log to stdout, this is used for uploading results to
+/// services such as GitHub code scanning.
+pub fn emit_sarif(results: &[LintResult], files: &dyn Files) {
+    let log = sarif_log(results, files);
+    let stdout = std::io::stdout();
+    let res = serde_json::to_writer_pretty(stdout.lock(), &log);
+    if let Err(err) = res {
+        lint_err!("failed to emit SARIF log: {}", err);
+    }
+    println!();
+}
+
+/// Emit the results as a Code Climate report to stdout, this is the format of GitLab code quality reports.
+pub fn emit_code_climate(results: &[LintResult], files: &dyn Files) {
+    let issues = code_climate_issues(results, files);
+    let stdout = std::io::stdout();
+    let res = serde_json::to_writer_pretty(stdout.lock(), &issues);
+    if let Err(err) = res {
+        lint_err!("failed to emit Code Climate report: {}", err);
+    }
+    println!();
+}
+
 #[allow(unused_must_use)]
-fn output_overall(failures: usize, warnings: usize, successes: usize, fix_count: usize) {
+pub(crate) fn output_overall(failures: usize, warnings: usize, successes: usize, fix_count: usize) {
     println!(
         "{}: {} fail, {} warn, {} success{}",
         "Outcome".white(),
@@ -238,6 +1052,83 @@ fn output_overall(failures: usize, warnings: usize, successes: usize, fix_count:
     );
 }
 
+/// Whether the results of linting fail under a policy, an error says so if the results only fail because of their
+/// warnings since nothing else explains it.
+pub fn failing(summary: OutcomeSummary, policy: FailurePolicy) -> bool {
+    let failing = summary.failing(policy);
+    if failing && summary.bugs + summary.errors == 0 {
+        let plural = if summary.warnings == 1 { "" } else { "s" };
+        if let FailurePolicy::MaxWarnings(max) = policy {
+            lint_err!(
+                "found {} warning{}, which is more than the maximum of {}",
+                summary.warnings,
+                plural,
+                max
+            );
+        } else {
+            lint_err!(
+                "found {} warning{} while treating warnings as errors",
+                summary.warnings,
+                plural
+            );
+        }
+    }
+    failing
+}
+
+/// Lint on `threads` threads, `0` uses the number of CPUs. Must be called before anything is linted.
+pub fn set_threads(threads: usize) {
+    if let Err(err) = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+    {
+        lint_err!("failed to create the thread pool: {}", err);
+    }
+}
+
+/// Print the time and memory each rule used to stderr, so the output of formatters is not affected.
+pub fn print_profile() {
+    let profiles = profile::report();
+    if profiles.is_empty() {
+        return;
+    }
+    let width = profiles.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    eprintln!(
+        "{}\n{:width$}  {:>6}  {:>10}  {:>12}  {:>10}",
+        "Profile".white(),
+        "rule",
+        "runs",
+        "time",
+        "allocations",
+        "bytes",
+        width = width
+    );
+    for profile in profiles {
+        eprintln!(
+            "{:width$}  {:>6}  {:>10}  {:>12}  {:>10}",
+            profile.name,
+            profile.runs,
+            format!("{:.2?}", profile.time),
+            profile.allocations,
+            format_bytes(profile.bytes),
+            width = width
+        );
+    }
+    if !profile::counts_allocations() {
+        lint_note!(
+            "allocations are only counted if rslint is built with the `alloc-profiling` feature"
+        );
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
 /// Remap each error diagnostic to a warning diagnostic based on the rule's level.
 /// this leaves warnings untouched because rules should be able to emit errors and warnings for context without
 /// the warnings being remapped to errors.
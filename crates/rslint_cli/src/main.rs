@@ -1,13 +1,19 @@
-use rslint_cli::ExplanationRunner;
+use rslint_cli::{ExplanationRunner, FailurePolicy, FixPolicy, PersistentFiles, Severity};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
+#[cfg(feature = "alloc-profiling")]
+#[global_allocator]
+static ALLOC: rslint_cli::profile::CountingAllocator = rslint_cli::profile::CountingAllocator;
+
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "rslint",
     about = "An extremely fast and configurable JavaScript linter"
 )]
 pub(crate) struct Options {
-    /// Whether to include potentially spammy details in rule diagnostics.
+    /// Whether to include potentially spammy details in rule diagnostics, a note for files
+    /// on which some rules were not run, and the diagnostics suppressed by directives.
     #[structopt(short, long)]
     verbose: bool,
     /// A glob pattern to lint.
@@ -18,17 +24,72 @@ pub(crate) struct Options {
     /// Automatically attempt to fix any issues which can be fixed
     #[structopt(short, long)]
     fix: bool,
+    /// Only apply the fixes of these rules when using `--fix`
+    #[structopt(
+        long,
+        use_delimiter = true,
+        requires = "fix",
+        conflicts_with = "fix-errors"
+    )]
+    fix_only: Vec<String>,
+    /// Only apply the fixes of rules configured as errors when using `--fix`
+    #[structopt(long, requires = "fix")]
+    fix_errors: bool,
     /// Attempt to run autofixes even if the code contains syntax errors (may produce weird fixes or more errors)
     #[structopt(short = "D", long)]
     dirty: bool,
-    /// The error formatter to use, either "short" or "long" (default)
+    /// The error formatter to use, either "short", "long" (default), "grouped", "json", "sarif", or "codeclimate"
     #[structopt(short = "F", long)]
     formatter: Option<String>,
+    /// Lint source code from stdin, if `--fix` is used the fixed code is printed to stdout
+    #[structopt(long)]
+    stdin: bool,
+    /// The path of the file being linted through stdin, used for resolving the configuration, the environment,
+    /// and the kind of the file (JavaScript, JSON, HTML, Vue, or Markdown)
+    #[structopt(
+        long,
+        alias = "stdin-filepath",
+        default_value = "stdin.js",
+        parse(from_os_str)
+    )]
+    stdin_filename: PathBuf,
+    /// Debugging option which shuffles the order rules and nodes are run in with an optional seed
+    #[structopt(long, hidden = true)]
+    shuffle: Option<Option<u64>>,
+    /// Keep running and relint files when they change, only printing the problems which were added or fixed
+    #[structopt(short, long, conflicts_with_all = &["fix", "stdin"])]
+    watch: bool,
+    /// Only lint files which changed since the last run, the results of other files are read from a cache
+    #[structopt(long)]
+    cache: bool,
+    /// The file the cache is stored in when using `--cache`
+    #[structopt(long, default_value = ".rslintcache", parse(from_os_str))]
+    cache_location: PathBuf,
+    /// Write every problem found to the baseline, the problems in the baseline are not reported by later runs
+    #[structopt(long, conflicts_with_all = &["stdin", "watch"])]
+    generate_baseline: bool,
+    /// The file the baseline is read from and written to with `--generate-baseline`
+    #[structopt(long, default_value = ".rslintbaseline", parse(from_os_str))]
+    baseline_location: PathBuf,
+    /// Exit with an error if there are more than this many warnings in all of the files
+    #[structopt(long, conflicts_with_all = &["warnings-as-errors", "watch"])]
+    max_warnings: Option<usize>,
+    /// Exit with an error if there are any warnings
+    #[structopt(long, conflicts_with = "watch")]
+    warnings_as_errors: bool,
+    /// Print the time each rule took and the memory it allocated after linting, allocations are only counted
+    /// if rslint is built with the `alloc-profiling` feature
+    #[structopt(long, conflicts_with = "watch")]
+    profile: bool,
+    /// The number of threads to lint with, defaults to the number of CPUs. `--threads 1` lints every file on a
+    /// single thread
+    #[structopt(long)]
+    threads: Option<usize>,
 }
 
 #[derive(Debug, StructOpt)]
 pub(crate) enum SubCommand {
-    /// Explain a list of rules, ex: `explain getter-return, no-cond-assign`
+    /// Explain a list of rules with their docs, code examples, and config, ex: `explain getter-return, no-cond-assign`
     Explain { rules: Vec<String> },
 }
 
@@ -37,10 +98,74 @@ fn main() {
     std::panic::set_hook(Box::new(rslint_cli::panic_hook));
 
     let opt = Options::from_args();
+    let fix = if !opt.fix {
+        None
+    } else if !opt.fix_only.is_empty() {
+        Some(FixPolicy::Rules(opt.fix_only))
+    } else if opt.fix_errors {
+        Some(FixPolicy::MinSeverity(Severity::Error))
+    } else {
+        Some(FixPolicy::All)
+    };
+
+    if opt.profile {
+        rslint_cli::profile::enable();
+    }
+    if let Some(threads) = opt.threads {
+        rslint_cli::set_threads(threads);
+    }
 
-    if let Some(SubCommand::Explain { rules }) = opt.cmd {
+    let policy = if opt.warnings_as_errors {
+        FailurePolicy::Warnings
+    } else if let Some(max) = opt.max_warnings {
+        FailurePolicy::MaxWarnings(max)
+    } else {
+        FailurePolicy::Errors
+    };
+
+    let summary = if let Some(SubCommand::Explain { rules }) = opt.cmd {
         ExplanationRunner::new(rules).print();
+        None
+    } else if opt.stdin {
+        Some(rslint_cli::run_stdin(
+            opt.stdin_filename,
+            opt.verbose,
+            fix,
+            opt.dirty,
+            opt.formatter,
+            opt.shuffle,
+            opt.baseline_location,
+        ))
+    } else if opt.watch {
+        rslint_cli::run_watch(opt.files, opt.verbose, opt.formatter, opt.shuffle);
+        None
     } else {
-        rslint_cli::run(opt.files, opt.verbose, opt.fix, opt.dirty, opt.formatter);
+        Some(rslint_cli::run(
+            opt.files,
+            opt.verbose,
+            fix,
+            opt.dirty,
+            opt.formatter,
+            opt.shuffle,
+            PersistentFiles {
+                cache: if opt.cache {
+                    Some(opt.cache_location)
+                } else {
+                    None
+                },
+                baseline: opt.baseline_location,
+                generate_baseline: opt.generate_baseline,
+            },
+        ))
+    };
+
+    if opt.profile {
+        rslint_cli::print_profile();
+    }
+    if summary
+        .filter(|summary| rslint_cli::failing(*summary, policy))
+        .is_some()
+    {
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,208 @@
+//! Environment presets which adjust the linter for files ran in a specific environment, such as test frameworks.
+
+use crate::lint_warn;
+use glob::Pattern;
+use rslint_core::util::globals::{environment, Globals, BUILTIN, ES2021, NODE};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A test framework environment preset.
+///
+/// A preset declares the globals the framework injects, the files which are considered to be
+/// test files by default, and the rules which are relaxed inside of those test files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    Jest,
+    Mocha,
+    Jasmine,
+}
+
+impl Preset {
+    /// The global variables the framework makes available inside of test files.
+    pub fn globals(self) -> &'static [&'static str] {
+        match self {
+            Preset::Jest => &[
+                "afterAll",
+                "afterEach",
+                "beforeAll",
+                "beforeEach",
+                "describe",
+                "expect",
+                "fdescribe",
+                "fit",
+                "it",
+                "jest",
+                "test",
+                "xdescribe",
+                "xit",
+                "xtest",
+            ],
+            Preset::Mocha => &[
+                "after",
+                "afterEach",
+                "before",
+                "beforeEach",
+                "context",
+                "describe",
+                "it",
+                "mocha",
+                "run",
+                "setup",
+                "specify",
+                "suite",
+                "suiteSetup",
+                "suiteTeardown",
+                "teardown",
+                "test",
+                "xcontext",
+                "xdescribe",
+                "xit",
+                "xspecify",
+            ],
+            Preset::Jasmine => &[
+                "afterAll",
+                "afterEach",
+                "beforeAll",
+                "beforeEach",
+                "describe",
+                "expect",
+                "fail",
+                "fdescribe",
+                "fit",
+                "it",
+                "jasmine",
+                "pending",
+                "spyOn",
+                "spyOnProperty",
+                "xdescribe",
+                "xit",
+            ],
+        }
+    }
+
+    /// The glob patterns of files the framework runs as tests by default.
+    pub fn default_test_files(self) -> &'static [&'static str] {
+        match self {
+            Preset::Jest => &[
+                "**/__tests__/**",
+                "**/*.test.js",
+                "**/*.test.mjs",
+                "**/*.spec.js",
+                "**/*.spec.mjs",
+            ],
+            Preset::Mocha => &["test/**", "**/*.test.js", "**/*.spec.js"],
+            Preset::Jasmine => &["spec/**", "**/*[sS]pec.js"],
+        }
+    }
+
+    /// Rules which are not run inside of test files.
+    ///
+    /// Tests commonly use empty callbacks for mocks and run async assertions sequentially,
+    /// which are fine in tests but usually a mistake elsewhere.
+    pub fn relaxed_rules(self) -> &'static [&'static str] {
+        &["no-await-in-loop", "no-empty"]
+    }
+}
+
+/// The `env` section of the config.
+#[serde(default, rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EnvConfig {
+    /// The environment presets to enable.
+    pub presets: Vec<Preset>,
+    /// Glob patterns of test files, this overrides the default test files of every preset.
+    pub test_files: Option<Vec<String>>,
+    /// Whether the code is run by Node.js, the rules of the `node` group are only run if this is enabled.
+    pub node: bool,
+    /// Sets of global variables defined by the config by their name, such as `browser = ["window", "document"]`.
+    pub globals: BTreeMap<String, Vec<String>>,
+    /// The sets of global variables available to every file, either sets defined in `globals` or the built-in
+    /// `builtin`, `es2021`, and `node` sets. The `builtin` and `es2021` sets are always available, and the
+    /// `node` set is available if `node` is enabled.
+    pub environments: Vec<String>,
+}
+
+impl EnvConfig {
+    /// The globals of files which are not test files.
+    pub fn globals(&self) -> Globals {
+        let mut globals = Globals::new();
+        globals.extend(BUILTIN).extend(ES2021);
+        if self.node {
+            globals.extend(NODE);
+        }
+        for env in &self.environments {
+            if let Some(names) = self.globals.get(env) {
+                globals.extend(names);
+            } else if let Some(names) = environment(env) {
+                globals.extend(names);
+            } else {
+                lint_warn!(
+                    "ignoring unknown environment `{}`, it is not defined in `env.globals`",
+                    env
+                );
+            }
+        }
+        globals
+    }
+
+    /// The globals made available to test files by all of the enabled presets.
+    pub fn test_globals(&self) -> Globals {
+        let mut globals = Globals::new();
+        for preset in &self.presets {
+            globals.extend(preset.globals());
+        }
+        globals
+    }
+
+    /// The rules which should not be run on test files.
+    pub fn relaxed_rules(&self) -> Vec<&'static str> {
+        let mut rules: Vec<_> = self
+            .presets
+            .iter()
+            .flat_map(|preset| preset.relaxed_rules().iter().copied())
+            .collect();
+        rules.sort_unstable();
+        rules.dedup();
+        rules
+    }
+
+    /// Compile the configured test file patterns, or the default ones of each preset.
+    pub fn test_file_matcher(&self) -> TestFileMatcher {
+        let patterns = match self.test_files {
+            Some(ref files) => files
+                .iter()
+                .filter_map(|glob| match Pattern::new(glob) {
+                    Ok(pattern) => Some(pattern),
+                    Err(err) => {
+                        lint_warn!("ignoring invalid test file pattern `{}`: {}", glob, err);
+                        None
+                    }
+                })
+                .collect(),
+            None => self
+                .presets
+                .iter()
+                .flat_map(|preset| preset.default_test_files().iter())
+                .map(|glob| Pattern::new(glob).expect("invalid default test file pattern"))
+                .collect(),
+        };
+        TestFileMatcher { patterns }
+    }
+}
+
+/// A matcher which decides whether a file is a test file.
+#[derive(Debug, Clone, Default)]
+pub struct TestFileMatcher {
+    patterns: Vec<Pattern>,
+}
+
+impl TestFileMatcher {
+    pub fn is_test_file(&self, path: &Path) -> bool {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches_path(path))
+    }
+}
@@ -0,0 +1,381 @@
+//! The `--watch` mode, which keeps the rule stores resident and relints files as they change.
+//!
+//! After the initial run only the diagnostics which were added or removed by a change are printed,
+//! followed by the overall outcome of every watched file.
+
+use crate::{
+    config, emit_diagnostic, is_linted_file, lint_err, lint_note, linted_paths, output_overall,
+    path_display, print_results, shuffle_seed, tab_width, verify_formatter, FileWalker, JsFile,
+    JsFileKind, RuleStores,
+};
+use colored::*;
+use hashbrown::HashMap;
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
+use rayon::prelude::*;
+use rslint_core::environment::PackageType;
+use rslint_core::{lint_file_with_options, LintResult, LinterOptions, Outcome};
+use rslint_errors::{file::Files, Diagnostic, Severity};
+use std::fs::{canonicalize, read_to_string};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+/// How long to wait for more events before relinting, editors often write a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A diagnostic as it was last reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Reported {
+    /// The parts of the diagnostic which identify it across edits, the location is not included
+    /// so diagnostics are not reported again when code above them changes.
+    key: (Severity, Option<String>, String, String),
+    /// The compact rendering of the diagnostic.
+    line: String,
+}
+
+impl Reported {
+    fn new(diagnostic: &Diagnostic, files: &dyn Files) -> Self {
+        let source = files.source(diagnostic.file_id).unwrap_or_default();
+        let (snippet, location) = match diagnostic.primary {
+            Some(ref primary) => {
+                let (line, column) = files
+                    .location(diagnostic.file_id, primary.span.range.start)
+                    .unwrap_or((1, 1));
+                (
+                    source
+                        .get(primary.span.range.clone())
+                        .unwrap_or_default()
+                        .to_string(),
+                    format!(":{}:{}", line, column),
+                )
+            }
+            None => (String::new(), String::new()),
+        };
+
+        let severity = match diagnostic.severity {
+            Severity::Bug | Severity::Error => "error".red(),
+            Severity::Warning => "warning".yellow(),
+            Severity::Note => "note".cyan(),
+            Severity::Help => "help".green(),
+        };
+        let line = format!(
+            "{}{} {}{}: {}",
+            files.name(diagnostic.file_id).unwrap_or_default(),
+            location,
+            severity,
+            diagnostic
+                .code
+                .as_ref()
+                .map(|code| format!("[{}]", code))
+                .unwrap_or_default(),
+            diagnostic.title.trim()
+        );
+
+        Self {
+            key: (
+                diagnostic.severity,
+                diagnostic.code.clone(),
+                diagnostic.title.clone(),
+                snippet,
+            ),
+            line,
+        }
+    }
+}
+
+/// The diagnostics which were added and removed between two runs on a file.
+fn delta(old: Vec<Reported>, new: &[Reported]) -> (Vec<&Reported>, Vec<Reported>) {
+    let mut removed = old;
+    let mut added = vec![];
+    for reported in new {
+        match removed.iter().position(|old| old.key == reported.key) {
+            Some(idx) => {
+                removed.remove(idx);
+            }
+            None => added.push(reported),
+        }
+    }
+    (added, removed)
+}
+
+/// The directory which has to be watched to see changes to every file matched by a glob pattern,
+/// this is the part of the pattern before the first component with a wildcard.
+fn watch_root(glob: &str) -> PathBuf {
+    let root = Path::new(glob)
+        .components()
+        .take_while(|component| match component {
+            Component::Normal(part) => !part.to_string_lossy().contains(&['*', '?', '['][..]),
+            _ => true,
+        })
+        .collect::<PathBuf>();
+    if root.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        root
+    }
+}
+
+/// The state of a watch run.
+struct Session<'a> {
+    glob: String,
+    walker: FileWalker,
+    stores: &'a RuleStores,
+    options: LinterOptions,
+    /// The file ids of every linted file by canonicalized path.
+    ids: HashMap<PathBuf, usize>,
+    reported: HashMap<usize, Vec<Reported>>,
+    outcomes: HashMap<usize, Outcome>,
+}
+
+impl<'a> Session<'a> {
+    fn lint(&self, ids: &[usize]) -> Vec<LintResult<'a>> {
+        ids.par_iter()
+            .filter_map(|id| {
+                let file = self.walker.files.get(id)?;
+                let (store, globals) = match file.path {
+                    Some(ref path) => (self.stores.for_path(path), self.stores.globals_for(path)),
+                    None => (&self.stores.default().store, &self.stores.default().globals),
+                };
+                let res = lint_file_with_options(
+                    *id,
+                    &file.source,
+                    file.kind == JsFileKind::Module,
+                    store,
+                    LinterOptions {
+                        globals: file.environment().globals(globals),
+                        ..self.options.clone()
+                    },
+                );
+                if let Err(ref diagnostic) = res {
+                    emit_diagnostic(diagnostic, &self.walker.display());
+                }
+                res.ok()
+            })
+            .collect()
+    }
+
+    fn record(&mut self, result: &LintResult) -> Vec<Reported> {
+        let files = self.walker.display();
+        let reported = result
+            .diagnostics()
+            .map(|diagnostic| Reported::new(diagnostic, &files))
+            .collect::<Vec<_>>();
+        self.outcomes.insert(result.file_id, result.outcome());
+        self.reported.insert(result.file_id, reported.clone());
+        reported
+    }
+
+    fn remap(&self, results: &mut [LintResult]) {
+        for result in results.iter_mut() {
+            if let Some(file) = self.walker.files.get(&result.file_id) {
+                self.stores.for_file(file).remap(result);
+            }
+        }
+    }
+
+    /// Reload a changed file or load a new file, returning its id if it should be relinted.
+    fn load(
+        &mut self,
+        path: &Path,
+        known: &mut Option<HashMap<PathBuf, PathBuf>>,
+    ) -> Option<usize> {
+        let canonical = canonicalize(path).ok()?;
+        if !is_linted_file(&canonical) {
+            return None;
+        }
+
+        if let Some(id) = self.ids.get(&canonical).copied() {
+            match read_to_string(&canonical) {
+                Ok(src) => self.walker.files.get_mut(&id)?.update_src(src),
+                Err(err) => lint_err!("failed to read file {}: {}", path.display(), err),
+            }
+            return Some(id);
+        }
+
+        // expanding the glob again is only needed when a file is created
+        let known = known.get_or_insert_with(|| {
+            glob::glob(&self.glob)
+                .map(|paths| {
+                    linted_paths(paths)
+                        .into_iter()
+                        .filter_map(|path| Some((canonicalize(&path).ok()?, path)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        // use the path as it is matched by the glob so it is displayed like the other files
+        let path = known.get(&canonical)?.to_owned();
+        let src = read_to_string(&canonical).ok()?;
+        let package = PackageType::find(&path);
+        let file = JsFile::new_concrete(src, path, package);
+        let id = file.id;
+        self.walker.files.insert(id, file);
+        self.ids.insert(canonical, id);
+        Some(id)
+    }
+
+    fn remove(&mut self, path: &Path) -> Vec<Reported> {
+        let path = std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_owned());
+        let id = match self.ids.remove(&path) {
+            Some(id) => id,
+            None => return vec![],
+        };
+        self.walker.files.remove(&id);
+        self.outcomes.remove(&id);
+        self.reported.remove(&id).unwrap_or_default()
+    }
+
+    fn handle(&mut self, events: Vec<DebouncedEvent>) {
+        let mut changed = vec![];
+        let mut removed = vec![];
+        for event in events {
+            match event {
+                DebouncedEvent::Create(path) | DebouncedEvent::Write(path) => changed.push(path),
+                DebouncedEvent::Remove(path) => removed.push(path),
+                DebouncedEvent::Rename(from, to) => {
+                    removed.push(from);
+                    changed.push(to);
+                }
+                DebouncedEvent::Error(err, _) => lint_err!("failed to watch files: {}", err),
+                _ => {}
+            }
+        }
+
+        let mut added_lines = vec![];
+        let mut removed_lines = removed
+            .iter()
+            .flat_map(|path| self.remove(path))
+            .collect::<Vec<_>>();
+
+        let mut known = None;
+        let mut ids = changed
+            .iter()
+            .filter_map(|path| self.load(path, &mut known))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() && removed_lines.is_empty() {
+            return;
+        }
+
+        let mut results = self.lint(&ids);
+        self.remap(&mut results);
+        for result in &results {
+            let old = self
+                .reported
+                .get(&result.file_id)
+                .cloned()
+                .unwrap_or_default();
+            let new = self.record(result);
+            let (added, removed) = delta(old, &new);
+            added_lines.extend(added.into_iter().map(|reported| reported.line.clone()));
+            removed_lines.extend(removed);
+        }
+
+        for line in &removed_lines {
+            println!("{} {}", "-".red(), line.line);
+        }
+        for line in &added_lines {
+            println!("{} {}", "+".green(), line);
+        }
+        if added_lines.is_empty() && removed_lines.is_empty() {
+            println!("{}", "no new or fixed problems".truecolor(140, 140, 140));
+        }
+        self.output_overall();
+    }
+
+    fn output_overall(&self) {
+        let count = |outcome| self.outcomes.values().filter(|x| **x == outcome).count();
+        output_overall(
+            count(Outcome::Failure),
+            count(Outcome::Warning),
+            count(Outcome::Success),
+            0,
+        );
+    }
+}
+
+/// Lint the files matched by a glob pattern, then watch them and relint files when they change.
+#[allow(unused_must_use)]
+pub fn run_watch(
+    glob: String,
+    verbose: bool,
+    formatter: Option<String>,
+    shuffle: Option<Option<u64>>,
+) {
+    let res = glob::glob(&glob);
+    if let Err(err) = res {
+        lint_err!("Invalid glob pattern: {}", err);
+        return;
+    }
+
+    let (config, loader) = config::Config::new_threaded()
+        .join()
+        .expect("config thread paniced");
+    let mut walker = FileWalker::from_glob(res.unwrap());
+    let mut formatter = formatter
+        .or_else(|| config.as_ref().map(|c| c.errors.formatter.clone()))
+        .unwrap_or_else(|| String::from("long"));
+    walker.paths = path_display(config.as_ref());
+    walker.tab_width = tab_width(config.as_ref());
+    let stores = RuleStores::new(
+        config,
+        loader,
+        walker
+            .files
+            .values()
+            .filter_map(|file| file.path.as_deref()),
+    );
+    verify_formatter(&mut formatter);
+
+    let ids = walker
+        .files
+        .values()
+        .filter_map(|file| Some((canonicalize(file.path.as_ref()?).ok()?, file.id)))
+        .collect();
+    let mut session = Session {
+        glob: glob.clone(),
+        walker,
+        stores: &stores,
+        options: LinterOptions {
+            verbose,
+            shuffle_seed: shuffle.map(shuffle_seed),
+            report_disabled_rules: verbose,
+            ..LinterOptions::default()
+        },
+        ids,
+        reported: HashMap::new(),
+        outcomes: HashMap::new(),
+    };
+
+    let all = session.walker.files.keys().copied().collect::<Vec<_>>();
+    let mut results = session.lint(&all);
+    session.remap(&mut results);
+    print_results(&mut results, vec![], &session.walker, 0, &formatter);
+    for result in &results {
+        session.record(result);
+    }
+    drop(results);
+
+    let (tx, rx) = channel();
+    let mut watcher = match watcher(tx, DEBOUNCE) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            lint_err!("failed to watch files: {}", err);
+            return;
+        }
+    };
+    let root = watch_root(&glob);
+    if let Err(err) = watcher.watch(&root, RecursiveMode::Recursive) {
+        lint_err!("failed to watch `{}`: {}", root.display(), err);
+        return;
+    }
+    lint_note!("watching `{}` for changes", root.display());
+
+    while let Ok(event) = rx.recv() {
+        let events = std::iter::once(event).chain(rx.try_iter()).collect();
+        session.handle(events);
+    }
+}
@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn rslint(dir: &Path, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rslint_cli"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn failure(dir: &Path, args: &[&str], stdin: &[u8], error: &str) {
+    let output = rslint(dir, args, stdin);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(error));
+}
+
+#[test]
+fn runs_which_can_not_lint_fail() {
+    let dir = std::env::temp_dir().join(format!("rslint-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    failure(&dir, &["[.js"], b"", "Invalid glob pattern");
+    failure(&dir, &["*.js"], b"", "No matching files found");
+    failure(
+        &dir,
+        &["--stdin", "--stdin-filename", "data.json"],
+        &[0xff, 0xfe],
+        "failed to read source code",
+    );
+
+    std::fs::write(dir.join("index.js"), "const a = 5;\n").unwrap();
+    assert!(rslint(&dir, &["*.js"], b"").status.success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
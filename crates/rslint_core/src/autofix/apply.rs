@@ -1,7 +1,7 @@
-use crate::{lint_file_inner, LintResult, RuleResult};
+use super::FixPolicy;
+use crate::{apply_parser_severities, lint_file_inner, LintResult, RuleResults};
 use rslint_parser::*;
 use rslint_text_edit::{apply_indels, Indel};
-use std::collections::HashMap;
 
 pub const MAX_FIX_ITERATIONS: usize = 10;
 
@@ -31,13 +31,18 @@ fn get_runnable_indels(mut tagged: Vec<TaggedIndel>) -> Vec<TaggedIndel> {
 }
 
 pub fn recursively_apply_fixes(result: &mut LintResult) -> String {
+    let _phase = crate::profile::start_phase(crate::profile::Phase::Fix);
     let script = result.parsed.kind() == SyntaxKind::SCRIPT;
     let mut parsed = result.parsed.clone();
     let file_id = result.file_id;
     let mut cur_results = result.rule_results.clone();
+    result.invalidate_index();
 
     for _ in 0..=MAX_FIX_ITERATIONS {
-        let indels = get_runnable_indels(rule_results_to_tagged_indels(&cur_results));
+        let indels = get_runnable_indels(rule_results_to_tagged_indels(
+            &cur_results,
+            &result.options.fix_policy,
+        ));
 
         if indels.is_empty() {
             break;
@@ -50,11 +55,13 @@ pub fn recursively_apply_fixes(result: &mut LintResult) -> String {
         parsed = if script {
             let res = parse_text(&string, file_id);
             // this needs to be updated for when fixes are applied "dirty" (when there are parser errors)
-            result.parser_diagnostics = res.errors().to_owned();
+            result.parser_diagnostics =
+                apply_parser_severities(res.errors().to_owned(), &result.options);
             res.syntax()
         } else {
             let res = parse_module(&string, file_id);
-            result.parser_diagnostics = res.errors().to_owned();
+            result.parser_diagnostics =
+                apply_parser_severities(res.errors().to_owned(), &result.options);
             res.syntax()
         };
 
@@ -64,7 +71,7 @@ pub fn recursively_apply_fixes(result: &mut LintResult) -> String {
             vec![],
             file_id,
             result.store,
-            result.verbose,
+            result.options.clone(),
         );
         if let Ok(res) = res {
             cur_results = res.rule_results;
@@ -76,9 +83,10 @@ pub fn recursively_apply_fixes(result: &mut LintResult) -> String {
     parsed.text().to_string()
 }
 
-fn rule_results_to_tagged_indels(results: &HashMap<&'static str, RuleResult>) -> Vec<TaggedIndel> {
+fn rule_results_to_tagged_indels(results: &RuleResults, policy: &FixPolicy) -> Vec<TaggedIndel> {
     results
         .iter()
+        .filter(|(tag, res)| policy.allows(tag, res))
         .filter_map(|(tag, res)| Some((tag, res.fixer.clone()?)))
         .flat_map(|(tag, fixer)| {
             fixer
@@ -2,7 +2,7 @@
 
 mod apply;
 
-use crate::{Span, SyntaxKind};
+use crate::{RuleResult, Severity, Span, SyntaxKind};
 use rslint_lexer::{Lexer, Token};
 use rslint_parser::{ast, AstNode, SyntaxNode, SyntaxNodeExt};
 use rslint_text_edit::apply_indels;
@@ -12,6 +12,32 @@ use std::sync::Arc;
 
 pub use apply::{recursively_apply_fixes, MAX_FIX_ITERATIONS};
 
+/// Which fixes are applied by [`recursively_apply_fixes`], fixes are selected by rule since a rule's fixes
+/// are applied all at once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FixPolicy {
+    /// Apply the fixes of every rule.
+    #[default]
+    All,
+    /// Only apply the fixes of rules which reported a diagnostic of at least this severity.
+    MinSeverity(Severity),
+    /// Only apply the fixes of the rules with these names.
+    Rules(Vec<String>),
+}
+
+impl FixPolicy {
+    /// Whether the fixes of a rule should be applied.
+    pub fn allows(&self, rule: &str, result: &RuleResult) -> bool {
+        match self {
+            FixPolicy::All => true,
+            FixPolicy::MinSeverity(severity) => {
+                result.diagnostics.iter().any(|d| d.severity >= *severity)
+            }
+            FixPolicy::Rules(rules) => rules.iter().any(|name| name == rule),
+        }
+    }
+}
+
 /// A simple interface for applying changes to source code
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Fixer {
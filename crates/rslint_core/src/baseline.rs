@@ -0,0 +1,219 @@
+//! Baselines of the problems a project already has, for adopting the linter in an existing code base without
+//! fixing every problem first.
+//!
+//! A baseline records each problem by the path of its file, the rule which reported it, and a fingerprint of its
+//! message and the code it points to. Fingerprints do not include the position of the problem, so problems stay
+//! in the baseline when code above them changes. Identical problems in a file are counted, so another copy of a
+//! problem in the baseline is still reported.
+//!
+//! Only errors and warnings with a primary label are recorded, and [`Baseline::filter`] only removes diagnostics
+//! which are in the baseline. The baseline is stored as a JSON document sorted by path so it can be checked in.
+
+use crate::cache::{hash, HASH_SEED};
+use crate::LintResult;
+use rslint_errors::{Diagnostic, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The default name of the baseline file.
+pub const DEFAULT_BASELINE_LOCATION: &str = ".rslintbaseline";
+
+/// The version of the format of the baseline file, baselines with a different version can not be loaded.
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BaselineEntry {
+    rule: String,
+    fingerprint: String,
+    count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    files: BTreeMap<String, Vec<BaselineEntry>>,
+}
+
+/// The number of times each problem occurs in a file by its rule and fingerprint.
+type Problems = BTreeMap<(String, u64), usize>;
+
+/// The known problems of the files of a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    files: BTreeMap<String, Problems>,
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the baseline at `location`, baselines which are not valid or were written by another version of the
+    /// baseline format are an [`InvalidData`](io::ErrorKind::InvalidData) error.
+    pub fn load(location: impl AsRef<Path>) -> io::Result<Self> {
+        let file = serde_json::from_slice::<BaselineFile>(&fs::read(location)?)?;
+        if file.version != BASELINE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported baseline version {}", file.version),
+            ));
+        }
+
+        let mut baseline = Self::new();
+        for (path, entries) in file.files {
+            let problems = baseline.files.entry(path).or_default();
+            for entry in entries {
+                let fingerprint = u64::from_str_radix(&entry.fingerprint, 16).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid fingerprint `{}`", entry.fingerprint),
+                    )
+                })?;
+                *problems.entry((entry.rule, fingerprint)).or_default() += entry.count;
+            }
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline to `location`.
+    pub fn save(&self, location: impl AsRef<Path>) -> io::Result<()> {
+        let files = self
+            .files
+            .iter()
+            .map(|(path, problems)| {
+                let entries = problems
+                    .iter()
+                    .map(|((rule, fingerprint), count)| BaselineEntry {
+                        rule: rule.clone(),
+                        fingerprint: format!("{:016x}", fingerprint),
+                        count: *count,
+                    })
+                    .collect();
+                (path.clone(), entries)
+            })
+            .collect();
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            files,
+        };
+        let mut json = serde_json::to_vec_pretty(&file)?;
+        json.push(b'\n');
+        fs::write(location, json)
+    }
+
+    /// The number of problems in the baseline.
+    pub fn len(&self) -> usize {
+        self.files
+            .values()
+            .flat_map(|problems| problems.values())
+            .sum()
+    }
+
+    /// Whether the baseline has no problems.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add the problems of the file at `path` to the baseline, `source` is the source code of the file.
+    pub fn add<'a>(
+        &mut self,
+        path: &str,
+        source: &str,
+        diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+    ) {
+        for diagnostic in diagnostics {
+            if let Some(problem) = problem(diagnostic, source) {
+                *self
+                    .files
+                    .entry(path.to_string())
+                    .or_default()
+                    .entry(problem)
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    /// Remove the diagnostics of the file at `path` which are in the baseline, returning how many were removed.
+    pub fn filter(&self, path: &str, source: &str, diagnostics: &mut Vec<Diagnostic>) -> usize {
+        let mut matcher = self.matcher(path);
+        let len = diagnostics.len();
+        diagnostics.retain(|diagnostic| !matcher.matches(diagnostic, source));
+        len - diagnostics.len()
+    }
+
+    /// Remove the parser, rule, and directive diagnostics of a result which are in the baseline, returning how
+    /// many were removed. Diagnostics suppressed by directives are kept.
+    pub fn filter_result(&self, path: &str, result: &mut LintResult) -> usize {
+        let source = result.parsed.text().to_string();
+        let mut matcher = self.matcher(path);
+        let mut removed = 0;
+        let mut filter = |diagnostics: &mut Vec<Diagnostic>| {
+            let len = diagnostics.len();
+            diagnostics.retain(|diagnostic| !matcher.matches(diagnostic, &source));
+            removed += len - diagnostics.len();
+        };
+
+        filter(&mut result.parser_diagnostics);
+        for rule_result in result.rule_results.values_mut() {
+            filter(&mut rule_result.diagnostics);
+        }
+        filter(&mut result.directive_diagnostics);
+        if removed > 0 {
+            result.invalidate_index();
+        }
+        removed
+    }
+
+    fn matcher(&self, path: &str) -> Matcher {
+        Matcher {
+            remaining: self
+                .files
+                .get(path)
+                .map(|problems| problems.clone().into_iter().collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Matches the diagnostics of a file against its problems in the baseline, each problem matches as many
+/// diagnostics as it was counted.
+struct Matcher {
+    remaining: HashMap<(String, u64), usize>,
+}
+
+impl Matcher {
+    fn matches(&mut self, diagnostic: &Diagnostic, source: &str) -> bool {
+        let count = match problem(diagnostic, source).and_then(|p| self.remaining.get_mut(&p)) {
+            Some(count) if *count > 0 => count,
+            _ => return false,
+        };
+        *count -= 1;
+        true
+    }
+}
+
+/// The rule and fingerprint of a diagnostic, `None` if it is not a problem which can be baselined.
+fn problem(diagnostic: &Diagnostic, source: &str) -> Option<(String, u64)> {
+    if diagnostic.suppressed_by.is_some()
+        || !matches!(
+            diagnostic.severity,
+            Severity::Bug | Severity::Error | Severity::Warning
+        )
+    {
+        return None;
+    }
+    let snippet = source.get(diagnostic.primary.as_ref()?.span.range.clone())?;
+    let rule = diagnostic
+        .code
+        .clone()
+        .unwrap_or_else(|| "syntax".to_string());
+    let fingerprint = [rule.as_str(), &diagnostic.title, snippet]
+        .iter()
+        .fold(HASH_SEED, |key, part| {
+            hash(hash(key, part.as_bytes()), &[0])
+        });
+    Some((rule, fingerprint))
+}
@@ -0,0 +1,215 @@
+//! A persistent cache of lint results which lets hosts skip files which did not change since the last run.
+//!
+//! Entries are keyed by the path of a file and store a hash of its source code and a hash of the configuration
+//! it was linted with (see [`LintCache::key`]). An entry is only used if both hashes match, so changing the
+//! file, the rules, their config, or the linter version invalidates it.
+//!
+//! The cache is stored as a single JSON document. A cache which cannot be read or was written by another
+//! version of the cache format is treated as empty instead of failing.
+
+use crate::environment::Environment;
+use crate::{CstRuleStore, FileKind, LinterOptions};
+use rslint_errors::{file::FileId, Diagnostic};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The default name of the cache file.
+pub const DEFAULT_CACHE_LOCATION: &str = ".rslintcache";
+
+/// The version of the format of the cache file, caches with a different version are ignored.
+const CACHE_VERSION: u32 = 2;
+
+pub(crate) const HASH_SEED: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// A stable 64 bit FNV-1a hash, the std hashers are not guaranteed to be stable across releases.
+pub(crate) fn hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CacheEntry {
+    source: u64,
+    key: u64,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Lint results of files from previous runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintCache {
+    location: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    changed: bool,
+}
+
+impl LintCache {
+    /// An empty cache which is saved to `location`.
+    pub fn new(location: impl Into<PathBuf>) -> Self {
+        Self {
+            location: location.into(),
+            entries: HashMap::new(),
+            changed: false,
+        }
+    }
+
+    /// Load the cache at `location`, the cache is empty if the file does not exist or is not a valid cache.
+    pub fn load(location: impl Into<PathBuf>) -> Self {
+        let mut cache = Self::new(location);
+        let file = fs::read(&cache.location)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheFile>(&bytes).ok())
+            .filter(|file| file.version == CACHE_VERSION);
+        if let Some(file) = file {
+            cache.entries = file.entries;
+        }
+        cache
+    }
+
+    /// The path the cache is saved to.
+    pub fn location(&self) -> &Path {
+        &self.location
+    }
+
+    /// The number of files in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache has no files.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hash the configuration which determines the results of linting a file, this includes the version of the
+    /// linter, the rules of the store and their config, and the linter options.
+    ///
+    /// `extra` is any other configuration of the host which affects the results, such as its rule levels.
+    pub fn key(store: &CstRuleStore, options: &LinterOptions, extra: &str) -> u64 {
+        let mut key = hash(HASH_SEED, env!("CARGO_PKG_VERSION").as_bytes());
+        let mut rules = store
+            .rules
+            .iter()
+            .map(|rule| serde_json::to_string(&**rule).unwrap_or_else(|_| rule.name().to_string()))
+            .collect::<Vec<_>>();
+        rules.sort();
+        for rule in rules {
+            key = hash(key, rule.as_bytes());
+            key = hash(key, &[0]);
+        }
+
+        key = hash(key, &[options.verbose as u8]);
+        key = hash(key, &[options.record_suppressed as u8]);
+        for (name, writable) in options.globals.iter() {
+            key = hash(key, name.as_bytes());
+            key = hash(key, &[writable as u8]);
+        }
+        let mut severities = options.parser_severities.iter().collect::<Vec<_>>();
+        severities.sort_by(|a, b| a.0.cmp(b.0));
+        for (code, severity) in severities {
+            key = hash(key, format!("{}={:?};", code, severity).as_bytes());
+        }
+        hash(key, extra.as_bytes())
+    }
+
+    /// Combine a key from [`LintCache::key`] with the environment of a file.
+    ///
+    /// The environment depends on the `package.json` of the file and not only on its path and source, so hosts
+    /// which resolve environments should use this key for each file.
+    pub fn environment_key(key: u64, environment: Environment) -> u64 {
+        hash(
+            key,
+            &[
+                (environment.kind == FileKind::Module) as u8,
+                environment.node as u8,
+            ],
+        )
+    }
+
+    /// The cached diagnostics of a file if its source code and key did not change since it was cached.
+    ///
+    /// The file ids of the diagnostics are replaced with `file_id`.
+    pub fn get(
+        &self,
+        path: &str,
+        source: &str,
+        key: u64,
+        file_id: FileId,
+    ) -> Option<Vec<Diagnostic>> {
+        let entry = self.entries.get(path)?;
+        if entry.source != hash(HASH_SEED, source.as_bytes()) || entry.key != key {
+            return None;
+        }
+        Some(
+            entry
+                .diagnostics
+                .iter()
+                .cloned()
+                .map(|diagnostic| with_file_id(diagnostic, file_id))
+                .collect(),
+        )
+    }
+
+    /// Cache the diagnostics of a file.
+    pub fn insert(
+        &mut self,
+        path: impl Into<String>,
+        source: &str,
+        key: u64,
+        diagnostics: Vec<Diagnostic>,
+    ) {
+        let entry = CacheEntry {
+            source: hash(HASH_SEED, source.as_bytes()),
+            key,
+            diagnostics,
+        };
+        self.changed |= self.entries.insert(path.into(), entry.clone()) != Some(entry);
+    }
+
+    /// Remove the files which do not exist anymore.
+    pub fn prune(&mut self) {
+        let len = self.entries.len();
+        self.entries.retain(|path, _| Path::new(path).exists());
+        self.changed |= self.entries.len() != len;
+    }
+
+    /// Write the cache to its location if it changed since it was loaded.
+    pub fn save(&self) -> io::Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            entries: self.entries.clone(),
+        };
+        fs::write(&self.location, serde_json::to_vec(&file)?)
+    }
+}
+
+fn with_file_id(mut diagnostic: Diagnostic, file_id: FileId) -> Diagnostic {
+    diagnostic.file_id = file_id;
+    let spans = diagnostic
+        .primary
+        .iter_mut()
+        .chain(diagnostic.children.iter_mut())
+        .map(|sub| &mut sub.span)
+        .chain(
+            diagnostic
+                .suggestions
+                .iter_mut()
+                .map(|suggestion| &mut suggestion.span),
+        );
+    for span in spans {
+        span.file = file_id;
+    }
+    diagnostic
+}
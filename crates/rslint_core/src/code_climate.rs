@@ -0,0 +1,138 @@
+//! Conversion of lint results to the [Code Climate](https://github.com/codeclimate/platform/blob/master/spec/analyzers/SPEC.md#data-types)
+//! issue format, which is the format of GitLab's code quality reports.
+//!
+//! The report is built with [`code_climate_issues`] and is a JSON array of the issues, it can be serialized
+//! with any serde serializer which supports JSON, e.g. `serde_json`.
+
+use crate::cache::{hash, HASH_SEED};
+use crate::LintResult;
+use rslint_errors::{
+    file::{Files, PathDisplay},
+    Diagnostic, Severity,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A single problem found in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    /// Always `"issue"`.
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+    /// The rule which reported the issue, or `"syntax"` for parser errors.
+    pub check_name: String,
+    pub description: String,
+    pub categories: Vec<&'static str>,
+    pub location: Location,
+    pub severity: &'static str,
+    /// An identifier of the issue which does not change when code above the issue changes.
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub path: String,
+    pub positions: Positions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Positions {
+    pub begin: Position,
+    pub end: Position,
+}
+
+/// A position in a file, lines and columns are one based and columns count unicode scalar values
+/// with tabs expanded to the [tab width](Files::tab_width) of the files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Convert the diagnostics of a collection of lint results to Code Climate issues.
+///
+/// Diagnostics without a primary label cannot be located and are left out. `files` must contain
+/// the source code of every linted file.
+pub fn code_climate_issues(results: &[LintResult], files: &dyn Files) -> Vec<Issue> {
+    let mut issues = vec![];
+    // identical problems in a file are told apart by the order they appear in
+    let mut occurrences = HashMap::new();
+    for result in results {
+        let mut diagnostics = result.diagnostics().collect::<Vec<_>>();
+        diagnostics.sort_by_key(|diagnostic| {
+            diagnostic
+                .primary
+                .as_ref()
+                .map(|primary| primary.span.range.start)
+        });
+        for diagnostic in diagnostics {
+            let category = match diagnostic
+                .code
+                .as_ref()
+                .and_then(|code| result.store.get_shared(code))
+            {
+                Some(rule) if rule.group() == "style" => "Style",
+                _ => "Bug Risk",
+            };
+            if let Some((mut issue, key)) = issue(diagnostic, category, files) {
+                let occurrence = occurrences.entry(key).or_insert(0u32);
+                issue.fingerprint = format!("{:016x}", hash(key, &occurrence.to_le_bytes()));
+                *occurrence += 1;
+                issues.push(issue);
+            }
+        }
+    }
+    issues
+}
+
+/// The issue of a diagnostic without its fingerprint, and the key the fingerprint is derived from.
+fn issue(
+    diagnostic: &Diagnostic,
+    category: &'static str,
+    files: &dyn Files,
+) -> Option<(Issue, u64)> {
+    let primary = diagnostic.primary.as_ref()?;
+    let range = primary.span.range.clone();
+    let path = PathDisplay::default().display(files.name(diagnostic.file_id).unwrap_or_default());
+    let check_name = diagnostic
+        .code
+        .clone()
+        .unwrap_or_else(|| "syntax".to_string());
+    let snippet = files.source(diagnostic.file_id)?.get(range.clone())?;
+
+    let key = [path.as_str(), &check_name, &diagnostic.title, snippet]
+        .iter()
+        .fold(HASH_SEED, |key, part| {
+            hash(hash(key, part.as_bytes()), &[0])
+        });
+    let position = |offset| {
+        let (line, column) = files.location(diagnostic.file_id, offset)?;
+        Some(Position { line, column })
+    };
+
+    let issue = Issue {
+        ty: "issue",
+        check_name,
+        description: diagnostic.title.clone(),
+        categories: vec![category],
+        location: Location {
+            path,
+            positions: Positions {
+                begin: position(range.start)?,
+                end: position(range.end)?,
+            },
+        },
+        severity: severity(diagnostic.severity),
+        fingerprint: String::new(),
+    };
+    Some((issue, key))
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "critical",
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+        Severity::Note | Severity::Help => "info",
+    }
+}
@@ -0,0 +1,118 @@
+//! Reports of how often each rule fires over a corpus of files, for finding rules which are dead or under-tested.
+//!
+//! Results of linting the corpus are added to a [`CorpusCoverage`], which builds a [`CoverageReport`] that can be
+//! serialized as JSON. The number of test fixtures of each rule is not known at runtime, so tools which can read
+//! the tests of rules (e.g. `cargo xtask rule-coverage`) pass them to [`CorpusCoverage::report`].
+
+use crate::{CstRuleStore, LintResult};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The number of test fixtures of a rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FixtureCount {
+    /// Code which the rule must report.
+    pub invalid: usize,
+    /// Code which the rule must not report.
+    pub valid: usize,
+}
+
+/// How a single rule fired over the corpus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleCoverage {
+    pub name: &'static str,
+    pub group: &'static str,
+    /// The number of diagnostics the rule reported.
+    pub diagnostics: usize,
+    /// The number of files the rule reported diagnostics in.
+    pub files: usize,
+    /// The test fixtures of the rule, if they are known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixtures: Option<FixtureCount>,
+}
+
+/// The coverage of every rule of a store over a corpus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    /// The number of linted files.
+    pub files: usize,
+    /// Every rule, sorted by the number of diagnostics it reported with the most reported first.
+    pub rules: Vec<RuleCoverage>,
+    /// The rules which did not report any diagnostic, sorted by name.
+    pub never_fired: Vec<&'static str>,
+    /// The rules which have no invalid test fixtures, sorted by name. This is empty if no fixtures were given.
+    pub untested: Vec<&'static str>,
+}
+
+/// Collects how often the rules of a store fire over a corpus.
+#[derive(Debug, Clone)]
+pub struct CorpusCoverage<'s> {
+    store: &'s CstRuleStore,
+    files: usize,
+    /// The number of diagnostics and files of each rule which fired.
+    counts: HashMap<&'static str, (usize, usize)>,
+}
+
+impl<'s> CorpusCoverage<'s> {
+    pub fn new(store: &'s CstRuleStore) -> Self {
+        Self {
+            store,
+            files: 0,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Add the results of linting a file of the corpus.
+    pub fn add(&mut self, result: &LintResult) {
+        self.files += 1;
+        for (name, rule_result) in &result.rule_results {
+            if rule_result.diagnostics.is_empty() {
+                continue;
+            }
+            let counts = self.counts.entry(name).or_default();
+            counts.0 += rule_result.diagnostics.len();
+            counts.1 += 1;
+        }
+    }
+
+    /// Build the report, `fixtures` are the test fixtures of rules by rule name.
+    pub fn report(&self, fixtures: &HashMap<String, FixtureCount>) -> CoverageReport {
+        let mut rules = self
+            .store
+            .rules
+            .iter()
+            .map(|rule| {
+                let (diagnostics, files) =
+                    self.counts.get(rule.name()).copied().unwrap_or_default();
+                RuleCoverage {
+                    name: rule.name(),
+                    group: rule.group(),
+                    diagnostics,
+                    files,
+                    fixtures: fixtures.get(rule.name()).copied(),
+                }
+            })
+            .collect::<Vec<_>>();
+        rules.sort_by(|a, b| b.diagnostics.cmp(&a.diagnostics).then(a.name.cmp(b.name)));
+
+        let mut never_fired = rules
+            .iter()
+            .filter(|rule| rule.diagnostics == 0)
+            .map(|rule| rule.name)
+            .collect::<Vec<_>>();
+        never_fired.sort_unstable();
+        let mut untested = rules
+            .iter()
+            .filter(|rule| !fixtures.is_empty() && rule.fixtures.unwrap_or_default().invalid == 0)
+            .map(|rule| rule.name)
+            .collect::<Vec<_>>();
+        untested.sort_unstable();
+
+        CoverageReport {
+            files: self.files,
+            rules,
+            never_fired,
+            untested,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! A simple protocol for serving lints from a persistent background process.
+//!
+//! Starting the linter has a noticeable cost, a daemon lets many short-lived clients (editors, git hooks, etc)
+//! share a single process which keeps its rule store around. The module is transport independent, the daemon
+//! serves any stream implementing [`Read`] and [`Write`], such as a TCP or unix socket, or stdio.
+//!
+//! Every message is a JSON document prefixed by its length in bytes as a big endian `u32`.
+//! Clients send a [`Request`] and receive exactly one [`Response`] for it. A connection is served
+//! until the client closes it or asks the daemon to shut down.
+
+use crate::environment::{Environment, PackageType};
+use crate::{lint_file_with_options, CstRuleStore, FileKind, LinterOptions};
+use rslint_errors::{file::SimpleFile, termcolor::NoColor, Diagnostic, Formatter, JsonFormatter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, ErrorKind, Read, Write};
+
+/// The maximum length of a single message, longer messages are rejected to avoid huge allocations
+/// from corrupt length prefixes.
+pub const MAX_MESSAGE_LEN: u32 = 64 * 1024 * 1024;
+
+/// A request sent from a client to the daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Request {
+    /// Lint source code as if it was the file at `path`.
+    Lint {
+        path: String,
+        source: String,
+        /// Whether to also autofix the code, the fixed code is included in the response.
+        #[serde(default)]
+        fix: bool,
+    },
+    /// Reload the configuration of the daemon, e.g. after the config file changed.
+    ReloadConfig,
+    /// Stop serving after responding to this request.
+    Shutdown,
+}
+
+/// A response sent from the daemon to a client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Response {
+    Lint {
+        /// The diagnostics of the file in the same schema as the `json` formatter of `rslint_errors`.
+        diagnostics: Value,
+        /// The fixed code if a fix was requested and the file had no syntax errors.
+        fixed: Option<String>,
+    },
+    ConfigReloaded,
+    ShuttingDown,
+    Error {
+        message: String,
+    },
+}
+
+/// A function which loads a new rule store when a client asks the daemon to reload its config.
+pub type ReloadFn = Box<dyn FnMut() -> Result<CstRuleStore, String> + Send>;
+
+/// The state of a daemon serving lint requests.
+pub struct Daemon {
+    store: CstRuleStore,
+    options: LinterOptions,
+    reload: Option<ReloadFn>,
+}
+
+impl Daemon {
+    pub fn new(store: CstRuleStore, options: LinterOptions) -> Self {
+        Self {
+            store,
+            options,
+            reload: None,
+        }
+    }
+
+    /// Set the function used to load a new rule store on [`Request::ReloadConfig`].
+    /// Reload requests are answered with an error if this is not set.
+    pub fn on_reload(
+        mut self,
+        reload: impl FnMut() -> Result<CstRuleStore, String> + Send + 'static,
+    ) -> Self {
+        self.reload = Some(Box::new(reload));
+        self
+    }
+
+    /// The rule store currently used for linting.
+    pub fn store(&self) -> &CstRuleStore {
+        &self.store
+    }
+
+    /// Handle a single request.
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Lint { path, source, fix } => self.lint(path, source, fix),
+            Request::ReloadConfig => match self.reload.as_mut().map(|reload| reload()) {
+                Some(Ok(store)) => {
+                    self.store = store;
+                    Response::ConfigReloaded
+                }
+                Some(Err(message)) => Response::Error { message },
+                None => Response::Error {
+                    message: "the daemon does not support reloading its config".to_string(),
+                },
+            },
+            Request::Shutdown => Response::ShuttingDown,
+        }
+    }
+
+    fn lint(&self, path: String, source: String, fix: bool) -> Response {
+        // the package is looked up for every request since it may have changed while the daemon is running
+        let env = Environment::resolve(&path, &source, PackageType::find(&path));
+        let options = LinterOptions {
+            globals: env.globals(&self.options.globals),
+            ..self.options.clone()
+        };
+        let module = env.kind == FileKind::Module;
+        let (diagnostics, fixed) =
+            match lint_file_with_options(0, &source, module, &self.store, options) {
+                Ok(mut result) => {
+                    let fixed = if fix { result.fix(false) } else { None };
+                    (result.diagnostics().cloned().collect::<Vec<_>>(), fixed)
+                }
+                Err(diagnostic) => (vec![diagnostic], None),
+            };
+
+        match json_diagnostics(&diagnostics, SimpleFile::new(path, source)) {
+            Ok(diagnostics) => Response::Lint { diagnostics, fixed },
+            Err(err) => Response::Error {
+                message: format!("failed to encode diagnostics: {}", err),
+            },
+        }
+    }
+
+    /// Serve requests from a stream until it is closed or a client asks the daemon to shut down.
+    ///
+    /// Returns `true` if the daemon should shut down. Malformed requests are answered with
+    /// [`Response::Error`] and do not end the connection.
+    pub fn serve(&mut self, mut stream: impl Read + Write) -> io::Result<bool> {
+        loop {
+            let request = match read_message::<Request>(&mut stream) {
+                Ok(Some(request)) => request,
+                Ok(None) => return Ok(false),
+                Err(err) if err.kind() == ErrorKind::InvalidData => {
+                    write_message(
+                        &mut stream,
+                        &Response::Error {
+                            message: format!("invalid request: {}", err),
+                        },
+                    )?;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let shutdown = request == Request::Shutdown;
+            write_message(&mut stream, &self.handle(request))?;
+            if shutdown {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+fn json_diagnostics(diagnostics: &[Diagnostic], file: SimpleFile) -> io::Result<Value> {
+    let mut writer = NoColor::new(Vec::new());
+    JsonFormatter::default().emit_with_writer(diagnostics, &file, &mut writer)?;
+    Ok(serde_json::from_slice(&writer.into_inner())?)
+}
+
+/// Send a request to a daemon and wait for its response.
+pub fn request(stream: &mut (impl Read + Write), request: &Request) -> io::Result<Response> {
+    write_message(stream, request)?;
+    read_message(stream)?.ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::UnexpectedEof,
+            "the daemon closed the connection without responding",
+        )
+    })
+}
+
+/// Read a single length prefixed message, returns `None` if the stream was closed before the message started.
+///
+/// Messages which are too long or not valid are an [`InvalidData`](ErrorKind::InvalidData) error, their body is
+/// still read so the next message can be read from the stream.
+pub fn read_message<T: DeserializeOwned>(reader: &mut impl Read) -> io::Result<Option<T>> {
+    let mut len = [0; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let len = u32::from_be_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        let skipped = io::copy(&mut reader.take(len as u64), &mut io::sink())?;
+        if skipped < len as u64 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("message of {} bytes exceeds the maximum length", len),
+        ));
+    }
+
+    let mut buf = vec![0; len as usize];
+    reader.read_exact(&mut buf)?;
+    serde_json::from_slice(&buf)
+        .map(Some)
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+/// Write a single length prefixed message and flush the writer.
+pub fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+    let buf = serde_json::to_vec(message)?;
+    if buf.len() > MAX_MESSAGE_LEN as usize {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "message exceeds the maximum length",
+        ));
+    }
+    writer.write_all(&(buf.len() as u32).to_be_bytes())?;
+    writer.write_all(&buf)?;
+    writer.flush()
+}
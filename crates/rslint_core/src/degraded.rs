@@ -0,0 +1,156 @@
+//! A degraded linting mode for files with catastrophic syntax errors.
+//!
+//! Rules are never run on `ERROR` nodes, therefore a file which is mostly made of syntax errors would
+//! contribute nothing but parser errors. When a file is [`catastrophic`], the runner instead:
+//! - only runs rules which return `true` from [`CstRule::tolerates_parse_errors`](crate::CstRule::tolerates_parse_errors),
+//!   these rules are given the root and every token, including tokens inside of `ERROR` nodes, but never individual nodes.
+//! - extracts the top level declarations of the file by scanning its tokens, see [`scan_top_level_declarations`].
+//!
+//! Directives keep working as usual since they only rely on comments.
+
+use rslint_parser::{SyntaxKind, SyntaxNode, SyntaxToken, TextRange};
+
+/// The minimum number of parser errors after which a file is always considered catastrophic.
+const MAX_PARSER_ERRORS: usize = 50;
+/// The fraction of tokens inside of `ERROR` nodes after which a file is considered catastrophic.
+const MAX_ERROR_TOKEN_RATIO: f64 = 0.25;
+
+/// The kind of a top level declaration found by scanning tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeclarationKind {
+    Function,
+    Class,
+    Var,
+    Let,
+    Const,
+}
+
+/// A top level declaration found by scanning the tokens of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopLevelDeclaration {
+    pub kind: DeclarationKind,
+    pub name: String,
+    /// The range of the declared name.
+    pub range: TextRange,
+}
+
+/// Whether a parsed file has so many syntax errors that it should be linted in degraded mode.
+///
+/// This is the case if there are at least 50 parser errors, or if at least a quarter of the
+/// file's tokens are inside of `ERROR` nodes.
+pub fn catastrophic(root: &SyntaxNode, parser_error_count: usize) -> bool {
+    if parser_error_count == 0 {
+        return false;
+    }
+    if parser_error_count >= MAX_PARSER_ERRORS {
+        return true;
+    }
+
+    let (mut total, mut errors) = (0usize, 0usize);
+    for token in lossy_tokens(root) {
+        total += 1;
+        if token
+            .parent()
+            .ancestors()
+            .any(|node| node.kind() == SyntaxKind::ERROR)
+        {
+            errors += 1;
+        }
+    }
+    total != 0 && errors as f64 / total as f64 >= MAX_ERROR_TOKEN_RATIO
+}
+
+/// Find the top level function, class, and variable declarations of a file using only its tokens.
+///
+/// This is a heuristic which does not rely on the structure of the syntax tree, so it works on
+/// files which could not be parsed. It tracks bracket depth and looks for declaration keywords
+/// followed by identifiers at depth zero. Since brackets are often unbalanced in broken code,
+/// a declaration keyword at the very start of a line is always considered to be top level.
+pub fn scan_top_level_declarations(root: &SyntaxNode) -> Vec<TopLevelDeclaration> {
+    let src = root.to_string();
+    let tokens = lossy_tokens(root).collect::<Vec<_>>();
+    let mut declarations = vec![];
+    let mut depth = 0usize;
+    // the kind of the variable declaration we are currently inside of, used for `let a = 1, b = 2`
+    let mut current_var: Option<DeclarationKind> = None;
+    let mut idx = 0;
+
+    let ident_at = |idx: usize| {
+        tokens
+            .get(idx)
+            .filter(|tok| tok.kind() == SyntaxKind::IDENT)
+            .cloned()
+    };
+
+    while idx < tokens.len() {
+        let token = &tokens[idx];
+        let start = usize::from(token.text_range().start());
+        if (start == 0 || src.as_bytes()[start - 1] == b'\n') && declaration_kind(token).is_some() {
+            depth = 0;
+        }
+
+        match token.kind() {
+            SyntaxKind::L_CURLY | SyntaxKind::L_PAREN | SyntaxKind::L_BRACK => {
+                depth += 1;
+            }
+            SyntaxKind::R_CURLY | SyntaxKind::R_PAREN | SyntaxKind::R_BRACK => {
+                depth = depth.saturating_sub(1);
+            }
+            _ if depth != 0 => {}
+            SyntaxKind::SEMICOLON => current_var = None,
+            SyntaxKind::COMMA if current_var.is_some() => {
+                if let Some(ident) = ident_at(idx + 1) {
+                    declarations.push(declaration(current_var.unwrap(), &ident));
+                    idx += 1;
+                }
+            }
+            _ => {
+                if let Some(kind) = declaration_kind(token) {
+                    current_var = None;
+                    let mut name_idx = idx + 1;
+                    // generator functions
+                    if kind == DeclarationKind::Function
+                        && tokens.get(name_idx).map(|tok| tok.kind()) == Some(SyntaxKind::STAR)
+                    {
+                        name_idx += 1;
+                    }
+                    if let Some(ident) = ident_at(name_idx) {
+                        declarations.push(declaration(kind, &ident));
+                        if !matches!(kind, DeclarationKind::Function | DeclarationKind::Class) {
+                            current_var = Some(kind);
+                        }
+                        idx = name_idx;
+                    }
+                }
+            }
+        }
+        idx += 1;
+    }
+    declarations
+}
+
+/// The kind of declaration a keyword starts, `let` is lexed as an identifier so keywords are compared by text.
+fn declaration_kind(token: &SyntaxToken) -> Option<DeclarationKind> {
+    Some(match token.text().as_str() {
+        "function" => DeclarationKind::Function,
+        "class" => DeclarationKind::Class,
+        "var" => DeclarationKind::Var,
+        "let" => DeclarationKind::Let,
+        "const" => DeclarationKind::Const,
+        _ => return None,
+    })
+}
+
+fn declaration(kind: DeclarationKind, ident: &SyntaxToken) -> TopLevelDeclaration {
+    TopLevelDeclaration {
+        kind,
+        name: ident.text().to_string(),
+        range: ident.text_range(),
+    }
+}
+
+fn lossy_tokens(root: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    root.descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .filter(|tok| !tok.kind().is_trivia())
+}
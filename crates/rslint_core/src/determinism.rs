@@ -0,0 +1,98 @@
+//! Guarantees that linting the same file gives the same output every run.
+//!
+//! Rules are run in parallel, so anything which depends on the order threads finish in, such as collecting
+//! results as they arrive or iterating a `HashMap`, can make the output of a run differ from the last one.
+//! A [`RunFingerprint`] hashes everything which can be observed of a lint run: the diagnostics in the order
+//! they are reported, the code after applying every fix, and the statistics of the file.
+//! [`lint_deterministic`] lints a file several times and asserts that every run has the same fingerprint.
+
+use crate::{lint_file_with_options, CstRuleStore, Diagnostic, LintResult, LinterOptions};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Hashes of the observable output of a lint run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RunFingerprint {
+    /// The diagnostics of the run in the order they are reported, including suppressed diagnostics
+    /// and the rule which emitted each diagnostic.
+    pub diagnostics: u64,
+    /// The code after applying every fix, even if the file has syntax errors.
+    pub fixed_code: u64,
+    /// The summary of the diagnostics and the counts of the directives of the file.
+    pub stats: u64,
+}
+
+impl RunFingerprint {
+    pub fn of(result: &LintResult) -> Self {
+        let rules = result
+            .rule_results
+            .iter()
+            .map(|(name, result)| (name, &result.diagnostics, &result.suppressed))
+            .collect::<Vec<_>>();
+        let diagnostics = hash(&(
+            &result.parser_diagnostics,
+            rules,
+            &result.directive_diagnostics,
+        ));
+        let fixed_code = hash(&result.clone().fix(true));
+        let stats = hash(&(result.summary(), &result.suppressions));
+
+        Self {
+            diagnostics,
+            fixed_code,
+            stats,
+        }
+    }
+
+    /// The names of the parts of the output which differ between two fingerprints.
+    pub fn differences(&self, other: &Self) -> Vec<&'static str> {
+        let mut differences = vec![];
+        if self.diagnostics != other.diagnostics {
+            differences.push("diagnostics");
+        }
+        if self.fixed_code != other.fixed_code {
+            differences.push("fixed code");
+        }
+        if self.stats != other.stats {
+            differences.push("stats");
+        }
+        differences
+    }
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Lint a file `runs` times and return the result of the first run.
+///
+/// # Panics
+/// Panics if the output of any run differs from the output of the first run, the message names the parts
+/// of the output which differ.
+pub fn lint_deterministic<'s>(
+    file_id: usize,
+    file_source: impl AsRef<str>,
+    module: bool,
+    store: &'s CstRuleStore,
+    options: LinterOptions,
+    runs: usize,
+) -> Result<LintResult<'s>, Diagnostic> {
+    let src = file_source.as_ref();
+    let first = lint_file_with_options(file_id, src, module, store, options.clone())?;
+    let expected = RunFingerprint::of(&first);
+
+    for run in 1..runs {
+        let result = lint_file_with_options(file_id, src, module, store, options.clone())?;
+        let fingerprint = RunFingerprint::of(&result);
+        assert!(
+            fingerprint == expected,
+            "lint run {} of {} differs from the first run in its {}",
+            run + 1,
+            runs,
+            fingerprint.differences(&expected).join(", ")
+        );
+    }
+    Ok(first)
+}
@@ -11,13 +11,22 @@
 //!   +-------------------------------------------------------------------------------+
 //!                                      Directive
 //! ```
+//!
+//! Rules can also be disabled for a range of the file with `rslint-disable-next-line` or with
+//! `rslint-disable` and `rslint-enable`, see [`DisabledRanges`].
+//!
+//! A file can be parsed as a script or a module regardless of how the host would parse it with
+//! `rslint-parser script` or `rslint-parser module` at the top of the file, see [`ParserMode`].
 
 mod parser;
 
 pub use self::parser::*;
 
-use crate::{rule_tests, CstRule, CstRuleStore, Diagnostic, SyntaxNode};
-use rslint_parser::util::*;
+use crate::{rule_tests, CstRule, CstRuleStore, Diagnostic, RuleResult, SyntaxNode};
+use rslint_parser::{util::*, TextRange, TextSize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Range;
+use std::sync::OnceLock;
 
 // TODO: More complex warnings, things like ignoring node directives because of file level directives
 
@@ -116,6 +125,506 @@ pub fn skip_node(directives: &[Directive], node: &SyntaxNode, rule: &dyn CstRule
     false
 }
 
+/// What a node level directive suppresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Suppression {
+    All,
+    Rules(Vec<String>),
+}
+
+/// An index of the rules suppressed by node level directives, by the comment of the directive.
+///
+/// This answers the same question as [`skip_node`] without searching every directive for every node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuppressionIndex {
+    nodes: HashMap<TextRange, Suppression>,
+}
+
+impl SuppressionIndex {
+    pub fn new(directives: &[Directive]) -> Self {
+        let mut nodes = HashMap::new();
+        for directive in directives {
+            let range = directive.comment.token.text_range();
+            for command in &directive.commands {
+                let suppression = nodes
+                    .entry(range)
+                    .or_insert_with(|| Suppression::Rules(vec![]));
+                match (command, &mut *suppression) {
+                    (Command::IgnoreNode(_), _) => *suppression = Suppression::All,
+                    (Command::IgnoreRules(rules, _), Suppression::Rules(names)) => {
+                        names.extend(rules.iter().map(|rule| rule.name().to_string()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Self { nodes }
+    }
+
+    /// Whether `rule` should not be run on `node` and its descendants.
+    pub fn suppressed(&self, node: &SyntaxNode, rule: &str) -> bool {
+        let comment = match node.first_token().and_then(|t| t.comment()) {
+            Some(comment) => comment,
+            None => return false,
+        };
+        match self.nodes.get(&comment.token.text_range()) {
+            Some(Suppression::All) => true,
+            Some(Suppression::Rules(rules)) => rules.iter().any(|name| name == rule),
+            None => false,
+        }
+    }
+}
+
+/// Counts of the directives of a file which suppress rules, used for keeping track of how much code
+/// is exempt from linting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SuppressionStats {
+    /// The ranges of the comments of every directive which suppresses rules, `rslint-enable` directives
+    /// are not counted.
+    pub directives: Vec<TextRange>,
+    /// The number of directives which suppress every rule, such as `rslint-ignore` or `rslint-disable`
+    /// without any rules.
+    pub all_rules: usize,
+    /// The number of directives which suppress each rule.
+    pub rules: BTreeMap<String, usize>,
+}
+
+impl SuppressionStats {
+    pub fn new(directives: &[Directive]) -> Self {
+        let mut stats = Self::default();
+        for directive in directives {
+            let mut suppresses = false;
+            let mut all = false;
+            let mut names = BTreeSet::new();
+            for command in &directive.commands {
+                let rules = match command {
+                    Command::IgnoreFile | Command::IgnoreNode(_) => None,
+                    Command::IgnoreRules(rules, _) | Command::IgnoreRulesFile(rules) => Some(rules),
+                    Command::DisableNextLine(rules) | Command::Disable(rules) => rules.as_ref(),
+                    Command::Enable(_) | Command::Parser(..) => continue,
+                };
+                suppresses = true;
+                match rules {
+                    Some(rules) => names.extend(rules.iter().map(|rule| rule.name().to_string())),
+                    None => all = true,
+                }
+            }
+            if !suppresses {
+                continue;
+            }
+            stats.directives.push(directive.comment.token.text_range());
+            stats.all_rules += usize::from(all);
+            for name in names {
+                *stats.rules.entry(name).or_default() += 1;
+            }
+        }
+        stats
+    }
+
+    /// The number of directives which suppress rules.
+    pub fn total(&self) -> usize {
+        self.directives.len()
+    }
+
+    /// Add the counts of another file or another part of the same file, such as another script of a Vue component.
+    pub fn merge(&mut self, other: &SuppressionStats) {
+        self.directives.extend(other.directives.iter().copied());
+        self.directives.sort_by_key(|range| range.start());
+        self.all_rules += other.all_rules;
+        for (name, count) in &other.rules {
+            *self.rules.entry(name.clone()).or_default() += count;
+        }
+    }
+}
+
+/// A range of source code where some rules, or every rule if `rules` is `None`, are disabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisabledRange {
+    pub range: Range<usize>,
+    pub rules: Option<Vec<String>>,
+    /// The range of the comment of the directive which disabled the rules.
+    pub directive: Range<usize>,
+}
+
+impl DisabledRange {
+    fn disables(&self, offset: usize, rule: &str) -> bool {
+        self.range.contains(&offset)
+            && match self.rules {
+                Some(ref rules) => rules.iter().any(|name| name == rule),
+                None => true,
+            }
+    }
+}
+
+/// The ranges of a file where rules are disabled by `rslint-disable-next-line`, or by `rslint-disable`
+/// until the next `rslint-enable` or the end of the file.
+///
+/// Unlike node level directives these do not stop rules from running, instead the diagnostics and fixes
+/// of rules which start in a disabled range are thrown out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisabledRanges {
+    pub ranges: Vec<DisabledRange>,
+}
+
+impl DisabledRanges {
+    /// Collect the disabled ranges of the directives of a file. Directives must be in the file of `file_id`
+    /// whose source is `src`, diagnostics for `rslint-enable` directives which do not enable anything
+    /// are added to `diagnostics`.
+    pub fn new(
+        directives: &[Directive],
+        src: &str,
+        file_id: usize,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Self {
+        // comments at the start of nested nodes are found once for every node
+        let mut seen = HashSet::new();
+        let mut commands = directives
+            .iter()
+            .filter(|directive| seen.insert(directive.comment.token.text_range()))
+            .flat_map(|directive| {
+                let range = directive.comment.token.text_range();
+                let range = usize::from(range.start())..usize::from(range.end());
+                directive
+                    .commands
+                    .iter()
+                    .filter(|command| command.ranged())
+                    .map(move |command| (range.clone(), command))
+            })
+            .collect::<Vec<_>>();
+        commands.sort_by_key(|(range, _)| range.start);
+
+        let names = |rules: &[Box<dyn CstRule>]| {
+            rules
+                .iter()
+                .map(|rule| rule.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        let mut ranges = vec![];
+        let mut all: Option<Range<usize>> = None;
+        let mut open: Vec<(String, Range<usize>)> = vec![];
+
+        for (range, command) in commands {
+            match command {
+                Command::DisableNextLine(rules) => {
+                    if let Some(line) = next_line(src, range.end) {
+                        ranges.push(DisabledRange {
+                            range: line,
+                            rules: rules.as_deref().map(names),
+                            directive: range.clone(),
+                        });
+                    }
+                }
+                Command::Disable(None) => {
+                    all.get_or_insert(range.clone());
+                }
+                Command::Disable(Some(rules)) => {
+                    for name in names(rules) {
+                        if !open.iter().any(|(open, _)| *open == name) {
+                            open.push((name, range.clone()));
+                        }
+                    }
+                }
+                Command::Enable(None) => {
+                    if all.is_none() && open.is_empty() {
+                        diagnostics.push(
+                            Diagnostic::warning(
+                                file_id,
+                                "directives",
+                                "`rslint-enable` without a previous `rslint-disable`",
+                            )
+                            .primary(range.clone(), "this directive does not enable any rules"),
+                        );
+                    }
+                    if let Some(directive) = all.take() {
+                        ranges.push(DisabledRange {
+                            range: directive.end..range.start,
+                            rules: None,
+                            directive,
+                        });
+                    }
+                    for (name, directive) in open.drain(..) {
+                        ranges.push(DisabledRange {
+                            range: directive.end..range.start,
+                            rules: Some(vec![name]),
+                            directive,
+                        });
+                    }
+                }
+                Command::Enable(Some(rules)) => {
+                    for name in names(rules) {
+                        if let Some(idx) = open.iter().position(|(open, _)| *open == name) {
+                            let (name, directive) = open.remove(idx);
+                            ranges.push(DisabledRange {
+                                range: directive.end..range.start,
+                                rules: Some(vec![name]),
+                                directive,
+                            });
+                            continue;
+                        }
+                        let mut warn = Diagnostic::warning(
+                            file_id,
+                            "directives",
+                            format!("`{}` is not disabled by a previous `rslint-disable`", name),
+                        )
+                        .primary(range.clone(), "this directive does not enable it");
+                        if all.is_some() {
+                            warn = warn.footer_help(
+                                "rules disabled by `rslint-disable` without a list of rules can only be enabled with `rslint-enable`",
+                            );
+                        }
+                        diagnostics.push(warn);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(directive) = all {
+            ranges.push(DisabledRange {
+                range: directive.end..src.len(),
+                rules: None,
+                directive,
+            });
+        }
+        for (name, directive) in open {
+            ranges.push(DisabledRange {
+                range: directive.end..src.len(),
+                rules: Some(vec![name]),
+                directive,
+            });
+        }
+        Self { ranges }
+    }
+
+    /// Whether `rule` is disabled at `offset`.
+    pub fn disabled(&self, offset: usize, rule: &str) -> bool {
+        self.ranges.iter().any(|range| range.disables(offset, rule))
+    }
+
+    /// Remove the diagnostics and fixes of a rule which start in a range where the rule is disabled.
+    pub fn apply(&self, rule: &str, result: &mut RuleResult) {
+        if self.ranges.is_empty() {
+            return;
+        }
+        result
+            .diagnostics
+            .retain(|diagnostic| match diagnostic.primary {
+                Some(ref primary) => !self.disabled(primary.span.range.start, rule),
+                None => true,
+            });
+        if let Some(fixer) = result.fixer.as_mut() {
+            fixer
+                .indels
+                .retain(|indel| !self.disabled(usize::from(indel.delete.start()), rule));
+        }
+    }
+}
+
+/// The range of the line after the line of `offset`, excluding the line break.
+fn next_line(src: &str, offset: usize) -> Option<Range<usize>> {
+    let start = offset + src.get(offset..)?.find('\n')? + 1;
+    let end = src[start..].find('\n').map_or(src.len(), |len| start + len);
+    Some(start..end)
+}
+
+/// The parsed directives of a file.
+#[derive(Debug, Clone)]
+pub struct FileDirectives {
+    pub directives: Vec<Directive>,
+    /// Diagnostics for invalid or expired directives.
+    pub diagnostics: Vec<Diagnostic>,
+    pub index: SuppressionIndex,
+    pub disabled: DisabledRanges,
+}
+
+/// The directive which suppresses a diagnostic, see [`FileDirectives::suppression`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuppressedBy {
+    /// A top level directive which ignores the rule in the whole file, with the range of its comment.
+    File(TextRange),
+    /// A directive on a node which contains the diagnostic, with the range of its comment.
+    Node(TextRange),
+    /// A range where the rule is disabled by `rslint-disable` or `rslint-disable-next-line`, with the
+    /// range of the comment which disabled it.
+    Range {
+        disabled: Range<usize>,
+        directive: Range<usize>,
+    },
+}
+
+impl SuppressedBy {
+    /// The range of the comment of the suppressing directive.
+    pub fn directive(&self) -> Range<usize> {
+        match self {
+            SuppressedBy::File(range) | SuppressedBy::Node(range) => {
+                usize::from(range.start())..usize::from(range.end())
+            }
+            SuppressedBy::Range { directive, .. } => directive.clone(),
+        }
+    }
+}
+
+impl FileDirectives {
+    /// Which directive suppresses a diagnostic of `rule` in the file of `root`, `None` if the diagnostic is
+    /// not suppressed.
+    ///
+    /// Node level directives stop rules from running on the node instead of throwing out diagnostics, so
+    /// this answers whether the diagnostic would be emitted if the node was linted. Diagnostics are
+    /// located by the start of their primary label, diagnostics without one are only suppressed by top
+    /// level directives.
+    pub fn suppression(
+        &self,
+        root: &SyntaxNode,
+        rule: &str,
+        diagnostic: &Diagnostic,
+    ) -> Option<SuppressedBy> {
+        match diagnostic.primary {
+            Some(ref primary) => self.suppression_at(root, rule, primary.span.range.start),
+            None => self.file_suppression(rule),
+        }
+    }
+
+    /// Which directive suppresses `rule` at `offset` in the file of `root`, see [`suppression`](Self::suppression).
+    pub fn suppression_at(
+        &self,
+        root: &SyntaxNode,
+        rule: &str,
+        offset: usize,
+    ) -> Option<SuppressedBy> {
+        if let Some(by) = self.file_suppression(rule) {
+            return Some(by);
+        }
+        if let Some(disabled) = self
+            .disabled
+            .ranges
+            .iter()
+            .find(|range| range.disables(offset, rule))
+        {
+            return Some(SuppressedBy::Range {
+                disabled: disabled.range.clone(),
+                directive: disabled.directive.clone(),
+            });
+        }
+
+        let offset = TextSize::from(offset as u32);
+        if !root.text_range().contains_inclusive(offset) {
+            return None;
+        }
+        let token = root.token_at_offset(offset).right_biased()?;
+        token
+            .ancestors()
+            .find(|node| self.index.suppressed(node, rule))
+            .and_then(|node| node.first_token()?.comment())
+            .map(|comment| SuppressedBy::Node(comment.token.text_range()))
+    }
+
+    fn file_suppression(&self, rule: &str) -> Option<SuppressedBy> {
+        self.directives
+            .iter()
+            .find(|directive| {
+                directive.commands.iter().any(|command| match command {
+                    Command::IgnoreFile => true,
+                    Command::IgnoreRulesFile(rules) => {
+                        rules.iter().any(|other| other.name() == rule)
+                    }
+                    _ => false,
+                })
+            })
+            .map(|directive| SuppressedBy::File(directive.comment.token.text_range()))
+    }
+
+    /// Move the suppressed diagnostics of a rule run without node level directives to
+    /// [`RuleResult::suppressed`], marking each with the comment of the directive which suppressed it,
+    /// and throw out the fixes which start where the rule is suppressed.
+    pub fn record_suppressed(&self, root: &SyntaxNode, rule: &str, result: &mut RuleResult) {
+        let mut kept = vec![];
+        for diagnostic in std::mem::take(&mut result.diagnostics) {
+            match self.suppression(root, rule, &diagnostic) {
+                Some(by) => result
+                    .suppressed
+                    .push(diagnostic.suppressed_by(by.directive())),
+                None => kept.push(diagnostic),
+            }
+        }
+        result.diagnostics = kept;
+        if let Some(fixer) = result.fixer.as_mut() {
+            fixer.indels.retain(|indel| {
+                self.suppression_at(root, rule, usize::from(indel.delete.start()))
+                    .is_none()
+            });
+        }
+    }
+}
+
+/// The directives of a file, which are parsed the first time they are needed.
+///
+/// Rules only need directives for nodes which start with a comment, so the linter parses directives
+/// concurrently with running rules, and a rule which reaches such a node before parsing is finished waits for it.
+pub struct LazyDirectives<'store> {
+    root: SyntaxNode,
+    file_id: usize,
+    store: &'store CstRuleStore,
+    parsed: OnceLock<Result<FileDirectives, Box<Diagnostic>>>,
+}
+
+impl<'store> LazyDirectives<'store> {
+    pub fn new(root: SyntaxNode, file_id: usize, store: &'store CstRuleStore) -> Self {
+        Self {
+            root,
+            file_id,
+            store,
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// The directives of the file, parsing them if they were not parsed yet.
+    pub fn get(&self) -> Result<&FileDirectives, &Diagnostic> {
+        let parsed = self.parsed.get_or_init(|| {
+            let results = DirectiveParser::new(self.root.clone(), self.file_id, self.store)
+                .get_file_directives()
+                .map_err(Box::new)?;
+            let mut diagnostics = vec![];
+            let directives = results
+                .into_iter()
+                .map(|res| {
+                    diagnostics.extend(res.diagnostics);
+                    res.directive
+                })
+                .collect::<Vec<_>>();
+            let disabled = DisabledRanges::new(
+                &directives,
+                &self.root.to_string(),
+                self.file_id,
+                &mut diagnostics,
+            );
+            Ok(FileDirectives {
+                index: SuppressionIndex::new(&directives),
+                disabled,
+                directives,
+                diagnostics,
+            })
+        });
+        parsed.as_ref().map_err(|err| &**err)
+    }
+
+    /// Whether `rule` should not be run on `node` and its descendants, directives are only parsed if
+    /// the node starts with a comment.
+    pub fn suppressed(&self, node: &SyntaxNode, rule: &str) -> bool {
+        if node.first_token().and_then(|t| t.comment()).is_none() {
+            return false;
+        }
+        match self.get() {
+            Ok(parsed) => parsed.index.suppressed(node, rule),
+            Err(_) => false,
+        }
+    }
+
+    pub fn into_inner(self) -> Result<FileDirectives, Box<Diagnostic>> {
+        let _ = self.get();
+        self.parsed.into_inner().unwrap()
+    }
+}
+
 rule_tests! {
     crate::groups::errors::NoEmpty::default(),
     err: {
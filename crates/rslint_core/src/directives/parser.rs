@@ -1,11 +1,15 @@
-use crate::{util::find_best_match_for_name, CstRule, CstRuleStore, Diagnostic, Severity};
+use crate::{
+    util::find_best_match_for_name, CstRule, CstRuleStore, Diagnostic, FileKind, Severity,
+};
 use rslint_lexer::Lexer as RawLexer;
 use rslint_parser::{
     util::Comment, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTokenExt, TextRange, T,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::iter::Peekable;
 use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct DirectiveParseResult {
     pub diagnostics: Vec<Diagnostic>,
@@ -24,6 +28,14 @@ pub enum Command {
     IgnoreNode(TextRange),
     /// Ignore rules for an entire file.
     IgnoreRulesFile(Vec<Box<dyn CstRule>>),
+    /// Ignore some rules, or every rule if `None`, on the line after the directive.
+    DisableNextLine(Option<Vec<Box<dyn CstRule>>>),
+    /// Ignore some rules, or every rule if `None`, until they are enabled again or the file ends.
+    Disable(Option<Vec<Box<dyn CstRule>>>),
+    /// Stop ignoring rules disabled by a previous [`Command::Disable`], or every rule if `None`.
+    Enable(Option<Vec<Box<dyn CstRule>>>),
+    /// Parse the file in a mode other than the one chosen by the host, along with the range of the mode.
+    Parser(ParserMode, Range<usize>),
 }
 
 impl Command {
@@ -31,6 +43,46 @@ impl Command {
     pub fn top_level(&self) -> bool {
         matches!(self, Command::IgnoreFile | Command::IgnoreRulesFile(_))
     }
+
+    /// Whether this command applies to a range of the source code instead of a node or the file.
+    pub fn ranged(&self) -> bool {
+        matches!(
+            self,
+            Command::DisableNextLine(_) | Command::Disable(_) | Command::Enable(_)
+        )
+    }
+}
+
+/// The mode a file is parsed in, given with `rslint-parser <mode>` at the top of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParserMode {
+    Script,
+    Module,
+    Jsx,
+    Ts,
+}
+
+impl ParserMode {
+    const NAMES: [&'static str; 4] = ["script", "module", "jsx", "ts"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "script" => ParserMode::Script,
+            "module" => ParserMode::Module,
+            "jsx" => ParserMode::Jsx,
+            "ts" => ParserMode::Ts,
+            _ => return None,
+        })
+    }
+
+    /// The kind of file this mode parses, `None` for the modes the parser does not support yet.
+    pub fn kind(self) -> Option<FileKind> {
+        match self {
+            ParserMode::Script => Some(FileKind::Script),
+            ParserMode::Module => Some(FileKind::Module),
+            ParserMode::Jsx | ParserMode::Ts => None,
+        }
+    }
 }
 
 /// A command given to the linter by an inline comment.
@@ -40,6 +92,84 @@ impl Command {
 pub struct Directive {
     pub commands: Vec<Command>,
     pub comment: Comment,
+    /// The date given with `until:YYYY-MM-DD` in the comment of the directive, after which the directive
+    /// no longer applies.
+    pub expires: Option<Date>,
+}
+
+/// A global variable declared by a `/* global name */` comment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GlobalDeclaration {
+    pub name: String,
+    /// Whether the name was declared with `: writable`, globals are read-only by default.
+    pub writable: bool,
+    /// The range of the name in the file.
+    pub range: Range<usize>,
+}
+
+/// A calendar date used for directive expiry dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// The current date in UTC.
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs() / 86400);
+        Self::from_days_since_epoch(days)
+    }
+
+    fn from_days_since_epoch(days: u64) -> Self {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+        Self {
+            year: year as u32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    /// Parse a date in the `YYYY-MM-DD` format.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.split('-');
+        let mut part = |len: usize| {
+            parts
+                .next()
+                .filter(|part| part.len() == len && part.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|part| part.parse::<u32>().ok())
+        };
+        let date = Self {
+            year: part(4)?,
+            month: part(2)?,
+            day: part(2)?,
+        };
+        if parts.next().is_some()
+            || !(1..=12).contains(&date.month)
+            || !(1..=31).contains(&date.day)
+        {
+            return None;
+        }
+        Some(date)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
 }
 
 struct RawCommand {
@@ -57,6 +187,8 @@ pub struct DirectiveParser<'store> {
     pub root_node: SyntaxNode,
     /// A string denoting the start of a directive, `rslint-` by default.
     pub declarator: String,
+    /// The date used to check if directives expired, the current date by default.
+    pub today: Date,
     file_id: usize,
     store: &'store CstRuleStore,
 }
@@ -75,12 +207,98 @@ impl<'store> DirectiveParser<'store> {
         Self {
             root_node,
             declarator: "rslint-".to_string(),
+            today: Date::today(),
             file_id,
             store,
         }
     }
 
     pub fn get_file_directives(&self) -> Result<Vec<DirectiveParseResult>, Diagnostic> {
+        Ok(self
+            .raw_directives()?
+            .into_iter()
+            .map(|raw| self.bake_raw_directive(raw))
+            .collect())
+    }
+
+    /// The rule names given to every directive of the file and the range of each name, unlike
+    /// [`get_file_directives`](Self::get_file_directives) this includes the names of rules which do not exist.
+    pub fn rule_names(&self) -> Result<Vec<(String, Range<usize>)>, Diagnostic> {
+        let mut names = self
+            .raw_directives()?
+            .into_iter()
+            .flat_map(|raw| raw.commands)
+            .flat_map(|command| command.tokens)
+            .map(|token| (token.raw, token.range))
+            .collect::<Vec<_>>();
+        names.sort_by_key(|(_, range)| range.start);
+        names.dedup_by_key(|(_, range)| range.start);
+        Ok(names)
+    }
+
+    /// The mode given by the first `rslint-parser` directive at the top of the file.
+    ///
+    /// This is `None` if there is no such directive or if the directives at the top of the file are invalid.
+    pub fn parser_mode(&self) -> Option<ParserMode> {
+        self.extract_top_level_directives()
+            .ok()?
+            .into_iter()
+            .flat_map(|directive| directive.commands)
+            .find_map(|command| match command.kind {
+                Command::Parser(mode, _) => Some(mode),
+                _ => None,
+            })
+    }
+
+    /// The globals declared by block comments such as `/* global foo, bar: writable */` anywhere in the file.
+    ///
+    /// Names followed by `: writable` or `: true` are writable, every other name is read-only.
+    pub fn global_declarations(&self) -> Vec<GlobalDeclaration> {
+        let mut declarations = vec![];
+        for token in self
+            .root_node
+            .descendants_with_tokens()
+            .filter_map(|elem| elem.into_token())
+            .filter(|token| token.kind() == SyntaxKind::COMMENT)
+        {
+            let text = token.text().as_str();
+            let content = match text.strip_prefix("/*") {
+                Some(content) => content.strip_suffix("*/").unwrap_or(content),
+                None => continue,
+            };
+            let trimmed = content.trim_start();
+            let names = match trimmed
+                .strip_prefix("globals")
+                .or_else(|| trimmed.strip_prefix("global"))
+                .filter(|rest| rest.starts_with(char::is_whitespace))
+            {
+                Some(names) => names,
+                None => continue,
+            };
+            let mut offset =
+                usize::from(token.text_range().start()) + "/*".len() + content.len() - names.len();
+
+            for entry in names.split(',') {
+                let (name, value) = match entry.find(':') {
+                    Some(idx) => (&entry[..idx], Some(entry[idx + 1..].trim())),
+                    None => (entry, None),
+                };
+                let start = offset + name.len() - name.trim_start().len();
+                let name = name.trim();
+                if !name.is_empty() {
+                    declarations.push(GlobalDeclaration {
+                        name: name.to_string(),
+                        writable: matches!(value, Some("writable") | Some("true")),
+                        range: start..start + name.len(),
+                    });
+                }
+                offset += entry.len() + 1;
+            }
+        }
+        declarations
+    }
+
+    fn raw_directives(&self) -> Result<Vec<RawDirective>, Diagnostic> {
         let mut raw = self.extract_top_level_directives()?;
         // descendants yields the root node first, so we need to skip it
         for descendant in self.root_node.descendants().skip(1) {
@@ -91,11 +309,8 @@ impl<'store> DirectiveParser<'store> {
                 }
             }
         }
-
-        Ok(raw
-            .into_iter()
-            .map(|raw| self.bake_raw_directive(raw))
-            .collect())
+        raw.extend(self.extract_ranged_directives(&raw)?);
+        Ok(raw)
     }
 
     fn err(&self, message: impl AsRef<str>) -> Diagnostic {
@@ -105,20 +320,62 @@ impl<'store> DirectiveParser<'store> {
     fn bake_raw_directive(&self, directive: RawDirective) -> DirectiveParseResult {
         let mut diagnostics = vec![];
         let mut commands = vec![];
+        let mut expires = None;
+
+        match self.expiry(&directive.comment) {
+            Some((None, range)) => diagnostics.push(
+                Diagnostic::warning(self.file_id, "directives", "invalid directive expiry date")
+                    .primary(range, "expected a date such as `until:2021-12-31`"),
+            ),
+            Some((Some(date), range)) if date < self.today => {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        self.file_id,
+                        "directives",
+                        format!("suppression expired on {}", date),
+                    )
+                    .primary(range, "this directive no longer ignores anything")
+                    .footer_help("fix the issues or extend the expiry date of the directive"),
+                );
+                return DirectiveParseResult {
+                    directive: Directive {
+                        commands,
+                        comment: directive.comment,
+                        expires: Some(date),
+                    },
+                    diagnostics,
+                };
+            }
+            Some((date, _)) => expires = date,
+            None => {}
+        }
 
         for raw_command in directive.commands.into_iter() {
+            if let Command::Parser(mode, ref range) = raw_command.kind {
+                if mode.kind().is_none() {
+                    diagnostics.push(self.unsupported_parser_mode(mode, range.to_owned()));
+                }
+                commands.push(raw_command.kind);
+                continue;
+            }
             let (diags, rules) = self.bake_ignore_command(&raw_command);
             diagnostics.extend(diags);
             let command = match raw_command.kind {
-                Command::IgnoreFile | Command::IgnoreNode(_) => raw_command.kind,
+                Command::IgnoreFile | Command::IgnoreNode(_) | Command::Parser(..) => {
+                    raw_command.kind
+                }
                 Command::IgnoreRules(_, node) => Command::IgnoreRules(rules, node),
                 Command::IgnoreRulesFile(_) => Command::IgnoreRulesFile(rules),
+                Command::DisableNextLine(list) => Command::DisableNextLine(list.map(|_| rules)),
+                Command::Disable(list) => Command::Disable(list.map(|_| rules)),
+                Command::Enable(list) => Command::Enable(list.map(|_| rules)),
             };
             commands.push(command);
         }
         let directive = Directive {
             commands,
             comment: directive.comment,
+            expires,
         };
 
         DirectiveParseResult {
@@ -127,6 +384,24 @@ impl<'store> DirectiveParser<'store> {
         }
     }
 
+    /// Find the `until:YYYY-MM-DD` expiry date in the comment part of a directive (after `--`).
+    /// The date is `None` if it is malformed.
+    fn expiry(&self, comment: &Comment) -> Option<(Option<Date>, Range<usize>)> {
+        let text = comment.token.text();
+        let start = usize::from(comment.token.text_range().start())
+            + text.find(comment.content.as_str())?;
+        let comment_start = comment.content.find("--")? + 2;
+        let rest = &comment.content[comment_start..];
+        let offset = rest.find("until:")?;
+        let date_text = rest[offset + "until:".len()..]
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .next()
+            .unwrap_or_default();
+        let range = start + comment_start + offset
+            ..start + comment_start + offset + "until:".len() + date_text.len();
+        Some((Date::parse(date_text), range))
+    }
+
     fn bake_ignore_command(
         &self,
         command: &RawCommand,
@@ -152,7 +427,7 @@ impl<'store> DirectiveParser<'store> {
                 unique.insert(raw, range);
             }
 
-            if let Some(rule) = CstRuleStore::new().builtins().get(raw) {
+            if let Some(rule) = CstRuleStore::new().builtins().external().get(raw) {
                 if self.store.get(raw).is_none() {
                     let warn = self
                         .err(format!(
@@ -174,6 +449,7 @@ impl<'store> DirectiveParser<'store> {
                 if let Some(suggestion) = find_best_match_for_name(
                     CstRuleStore::new()
                         .builtins()
+                        .external()
                         .rules
                         .iter()
                         .map(|x| x.name()),
@@ -210,6 +486,42 @@ impl<'store> DirectiveParser<'store> {
         self.parse_comments(comments)
     }
 
+    /// Extract directives with `disable` or `enable` commands from comments which are not at the start of a node,
+    /// such as a comment before the closing brace of a block. Other commands in these comments are dropped
+    /// since they have no node to apply to.
+    fn extract_ranged_directives(
+        &self,
+        found: &[RawDirective],
+    ) -> Result<Vec<RawDirective>, Diagnostic> {
+        let found = found
+            .iter()
+            .map(|directive| directive.comment.token.text_range())
+            .collect::<HashSet<_>>();
+        let mut directives = vec![];
+
+        for token in self
+            .root_node
+            .descendants_with_tokens()
+            .filter_map(|elem| elem.into_token())
+        {
+            if token.kind() != SyntaxKind::COMMENT || found.contains(&token.text_range()) {
+                continue;
+            }
+            let comment = token.comment().unwrap();
+            let command = match comment.content.trim_start().strip_prefix(&self.declarator) {
+                Some(command) => command,
+                None => continue,
+            };
+            if !command.starts_with("disable") && !command.starts_with("enable") {
+                continue;
+            }
+            let mut commands = self.parse_directive(token, None)?;
+            commands.retain(|command| command.kind.ranged());
+            directives.push(RawDirective { commands, comment });
+        }
+        Ok(directives)
+    }
+
     fn parse_comments(&self, comments: Vec<Comment>) -> Result<Vec<RawDirective>, Diagnostic> {
         let mut directives = Vec::with_capacity(comments.len());
         for comment in comments {
@@ -229,11 +541,11 @@ impl<'store> DirectiveParser<'store> {
             .trim_start()
             .strip_prefix(&self.declarator)
             .unwrap();
-        let declaration_offset = comment.text().len() - inner_text.len();
+        // block comments also end with `*/`, so the content has to be searched for
+        let declaration_offset = comment.text().find(inner_text.as_str()).unwrap_or_default();
         let offset = usize::from(comment.text_range().start())
-            + (inner_text.trim_start().len() - stripped_text.len())
-            + declaration_offset
-            + 1;
+            + (inner_text.len() - stripped_text.len())
+            + declaration_offset;
         let string = self.root_node.to_string();
         let mut lexer = Lexer::new(stripped_text, offset, self.file_id, string.as_str());
 
@@ -271,13 +583,7 @@ impl<'store> DirectiveParser<'store> {
         let word = lexer.word()?;
         match word.raw.as_str() {
             "ignore" => {
-                if lexer
-                    .peek_no_whitespace()
-                    .map(|t| t.kind)
-                    .filter(|kind| kind == &T![ident] || kind.is_keyword())
-                    .is_some()
-                {
-                    let tokens = lexer.rule_list()?;
+                if let Some(tokens) = lexer.optional_rule_list()? {
                     let kind = if let Some(node) = node {
                         Command::IgnoreRules(vec![], node.text_range())
                     } else {
@@ -298,21 +604,104 @@ impl<'store> DirectiveParser<'store> {
                     })
                 }
             }
-            text => {
-                const COMMANDS: [&str; 1] = ["ignore"];
-
-                let mut err = self
-                    .err(format!("unknown directive command `{}`", text))
-                    .primary(word.range, "");
-
-                if let Some(suggestion) =
-                    find_best_match_for_name(COMMANDS.iter().cloned(), text, None)
-                {
-                    err = err.footer_help(format!("did you mean `{}`", suggestion));
+            "disable" if lexer.peek().map(|t| t.kind) == Some(T![-]) => {
+                // `-` directly after the word is part of `disable-next-line`, separators are surrounded by whitespace
+                let mut name = word.raw.clone();
+                let mut range = word.range.clone();
+                while lexer.peek().map(|t| t.kind) == Some(T![-]) {
+                    lexer.next();
+                    let part = lexer.word()?;
+                    name.push('-');
+                    name.push_str(&part.raw);
+                    range.end = part.range.end;
+                }
+                if name != "disable-next-line" {
+                    return Err(self.unknown_command(&name, range));
                 }
-                Err(err)
+                let tokens = lexer.optional_rule_list()?;
+                Ok(RawCommand {
+                    kind: Command::DisableNextLine(tokens.as_ref().map(|_| vec![])),
+                    tokens: tokens.unwrap_or_default(),
+                })
             }
+            "disable" | "enable" => {
+                let tokens = lexer.optional_rule_list()?;
+                let rules = tokens.as_ref().map(|_| vec![]);
+                Ok(RawCommand {
+                    kind: if word.raw == "disable" {
+                        Command::Disable(rules)
+                    } else {
+                        Command::Enable(rules)
+                    },
+                    tokens: tokens.unwrap_or_default(),
+                })
+            }
+            "parser" => {
+                if node.is_some() {
+                    return Err(self
+                        .err("the `parser` command can only be used at the top of the file")
+                        .primary(word.range, "")
+                        .footer_help(
+                            "move this directive before the first statement of the file",
+                        ));
+                }
+                let mode = lexer.word()?;
+                match ParserMode::from_name(&mode.raw) {
+                    Some(kind) => Ok(RawCommand {
+                        tokens: vec![],
+                        kind: Command::Parser(kind, mode.range),
+                    }),
+                    None => {
+                        let mut err = self
+                            .err(format!("unknown parser mode `{}`", mode.raw))
+                            .primary(mode.range, "");
+                        if let Some(suggestion) = find_best_match_for_name(
+                            ParserMode::NAMES.iter().cloned(),
+                            &mode.raw,
+                            None,
+                        ) {
+                            err = err.footer_help(format!("did you mean `{}`?", suggestion));
+                        }
+                        Err(err)
+                    }
+                }
+            }
+            text => Err(self.unknown_command(text, word.range)),
+        }
+    }
+
+    fn unsupported_parser_mode(&self, mode: ParserMode, range: Range<usize>) -> Diagnostic {
+        let name = if mode == ParserMode::Jsx {
+            "JSX"
+        } else {
+            "TypeScript"
+        };
+        Diagnostic::warning(
+            self.file_id,
+            "directives",
+            format!("the parser does not support {} yet", name),
+        )
+        .primary(
+            range,
+            if FileKind::of(&self.root_node) == FileKind::Module {
+                "this file is parsed as a module instead"
+            } else {
+                "this file is parsed as a script instead"
+            },
+        )
+    }
+
+    fn unknown_command(&self, text: &str, range: Range<usize>) -> Diagnostic {
+        const COMMANDS: [&str; 5] = ["ignore", "disable", "disable-next-line", "enable", "parser"];
+
+        let mut err = self
+            .err(format!("unknown directive command `{}`", text))
+            .primary(range, "");
+
+        if let Some(suggestion) = find_best_match_for_name(COMMANDS.iter().cloned(), text, None) {
+            err = err.footer_help(format!("did you mean `{}`", suggestion));
         }
+        err
     }
 }
 
@@ -416,7 +805,8 @@ impl<'src> Lexer<'src> {
         let mut tok = next;
 
         loop {
-            if self.peek().map(|tok| tok.kind) == Some(T![-]) {
+            // `-` separates words of a rule name and `/` separates a plugin namespace from the rule name
+            if matches!(self.peek().map(|tok| tok.kind), Some(T![-]) | Some(T![/])) {
                 tok = self.next().unwrap();
                 let kind = self.peek().map(|t| t.kind);
                 if kind == Some(T![ident]) || kind.map_or(false, |kind| kind.is_keyword()) {
@@ -439,6 +829,16 @@ impl<'src> Lexer<'src> {
         }
     }
 
+    /// A list of rules if the next word is a rule name.
+    pub fn optional_rule_list(&mut self) -> Result<Option<Vec<Token>>, Diagnostic> {
+        let kind = self.peek_no_whitespace().map(|t| t.kind);
+        if matches!(kind, Some(kind) if kind == T![ident] || kind.is_keyword()) {
+            self.rule_list().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn rule_list(&mut self) -> Result<Vec<Token>, Diagnostic> {
         let mut toks = vec![];
 
@@ -0,0 +1,184 @@
+//! Generation of user facing rule documentation from the metadata of rules.
+//!
+//! This works on any rule store, including rules registered by plugins, so documentation can be generated by
+//! any tool which uses the linter. `xtask docgen` renders the pages of the website with it as well, adding the
+//! docs of options and the examples of rule tests from the source of the builtin rules.
+
+use crate::{CstRule, CstRuleStore};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// The rendered documentation of a single rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleDocPage {
+    pub name: &'static str,
+    pub group: &'static str,
+    /// The docs of the rule, the body of the page.
+    pub docs: &'static str,
+    /// The path of the page relative to the docs root, e.g. `rules/errors/no-empty.md`.
+    pub path: PathBuf,
+    /// The options the rule accepts, sorted by name.
+    pub options: Vec<RuleOption>,
+    /// The code examples under the `Invalid Code Examples` (or `Incorrect Code Examples`) heading of the docs.
+    pub invalid_examples: Vec<String>,
+    /// The code examples under the `Correct Code Examples` heading of the docs.
+    pub valid_examples: Vec<String>,
+    /// The full markdown page.
+    pub markdown: String,
+}
+
+/// A single config option of a rule.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleOption {
+    /// The name of the option as used in the config, e.g. `splitDeclarations`.
+    pub name: String,
+    /// The JSON type of the option, e.g. `boolean` or `string`.
+    pub ty: &'static str,
+    /// The default value of the option.
+    pub default: Value,
+    /// What the option does. Rules do not have docs for their options at runtime, so this is only set by
+    /// generators which read the source of rules such as `xtask docgen`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl RuleDocPage {
+    /// Render the markdown of the page, generators which change the page should render it again.
+    pub fn render(&self) -> String {
+        let mut markdown = format!(
+            "<!--\n generated docs file, do not edit by hand, see rslint_core::docgen \n-->\n# {}\n\n{}\n",
+            self.name,
+            self.docs.replace("```ignore", "```js")
+        );
+        if self.options.is_empty() {
+            return markdown;
+        }
+
+        let described = self
+            .options
+            .iter()
+            .any(|option| option.description.is_some());
+        markdown.push_str("\n## Config\n");
+        if described {
+            markdown.push_str("| Name | Type | Default | Description |\n");
+            markdown.push_str("| ---- | ---- | ------- | ----------- |\n");
+        } else {
+            markdown.push_str("| Name | Type | Default |\n");
+            markdown.push_str("| ---- | ---- | ------- |\n");
+        }
+        for option in &self.options {
+            markdown.push_str(&format!(
+                "| `{}` | {} | `{}` |",
+                option.name, option.ty, option.default
+            ));
+            if described {
+                let description = option.description.as_deref().unwrap_or_default();
+                markdown.push_str(&format!(" {} |", description.replace('\n', "<br>")));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
+
+/// Generate a documentation page for every rule in the store.
+///
+/// The options of a rule are found by serializing its current value, therefore the
+/// rules in the store should be in their default configuration.
+pub fn generate(store: &CstRuleStore) -> Vec<RuleDocPage> {
+    store.rules.iter().map(|rule| page(&**rule)).collect()
+}
+
+fn page(rule: &dyn CstRule) -> RuleDocPage {
+    let docs = rule.docs().trim();
+    let options = options(rule);
+    let (invalid_examples, valid_examples) = examples(docs);
+
+    let mut page = RuleDocPage {
+        name: rule.name(),
+        group: rule.group(),
+        docs,
+        path: PathBuf::from("rules")
+            .join(rule.group())
+            .join(rule.name())
+            .with_extension("md"),
+        options,
+        invalid_examples,
+        valid_examples,
+        markdown: String::new(),
+    };
+    page.markdown = page.render();
+    page
+}
+
+pub(crate) fn options(rule: &dyn CstRule) -> Vec<RuleOption> {
+    // rules are serialized by typetag as `{ "RuleName": { ...options } }`
+    let fields = match serde_json::to_value(rule) {
+        Ok(Value::Object(map)) => map.into_iter().next().map(|(_, fields)| fields),
+        _ => None,
+    };
+    match fields {
+        Some(Value::Object(fields)) => fields
+            .into_iter()
+            .map(|(name, default)| RuleOption {
+                name,
+                ty: json_type(&default),
+                default,
+                description: None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Collect the code blocks under the invalid and valid example headings of rule docs.
+pub(crate) fn examples(docs: &str) -> (Vec<String>, Vec<String>) {
+    let (mut invalid, mut valid) = (vec![], vec![]);
+    let mut section: Option<bool> = None;
+    let mut block: Option<String> = None;
+    // block comment docs are not unindented, so code is unindented relative to its fence
+    let mut indent = 0;
+
+    for line in docs.lines() {
+        let trimmed = line.trim();
+        if let Some(code) = block.as_mut() {
+            if trimmed == "```" {
+                let code = block.take().unwrap().trim_end().to_string();
+                match section {
+                    Some(true) => invalid.push(code),
+                    Some(false) => valid.push(code),
+                    None => {}
+                }
+            } else {
+                let line_indent = line.len() - line.trim_start().len();
+                code.push_str(&line[line_indent.min(indent)..]);
+                code.push('\n');
+            }
+        } else if trimmed.starts_with("```") {
+            indent = line.len() - line.trim_start().len();
+            block = Some(String::new());
+        } else if trimmed.starts_with('#') {
+            let heading = trimmed.to_ascii_lowercase();
+            section = if heading.contains("incorrect") || heading.contains("invalid") {
+                Some(true)
+            } else if heading.contains("correct") || heading.contains("valid") {
+                Some(false)
+            } else {
+                None
+            };
+        }
+    }
+    (invalid, valid)
+}
@@ -0,0 +1,144 @@
+//! Inference of the environment a file runs in from its path, its source, and the closest `package.json`.
+//!
+//! The [`Environment`] of a file decides whether it is parsed as a module or a script and which globals it
+//! has on top of the globals configured by the host:
+//!
+//! - `.mjs` files are ES modules and `.cjs` files are CommonJS scripts, both are run by Node.js.
+//! - Other files are ES modules if the closest `package.json` has `"type": "module"`, and scripts otherwise.
+//! - Files starting with a shebang which runs `node`, such as `#!/usr/bin/env node`, are run by Node.js.
+//!
+//! Files run by Node.js get the [`NODE`] globals, and ES modules never get the [`COMMONJS`] globals such as
+//! `require` since they are not defined in them. Files without any of these hints (which includes browser
+//! code) only have the globals of the host.
+//!
+//! ```
+//! use rslint_core::environment::{Environment, PackageType};
+//! use rslint_core::FileKind;
+//!
+//! let env = Environment::resolve("src/cli.js", "#!/usr/bin/env node\n", Some(PackageType::Module));
+//! assert_eq!(env.kind, FileKind::Module);
+//! assert!(env.node);
+//!
+//! let globals = env.globals(&Default::default());
+//! assert!(globals.contains("process"));
+//! assert!(!globals.contains("require"));
+//! ```
+
+use crate::util::globals::{Globals, COMMONJS, NODE};
+use crate::FileKind;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The module system of a package, declared by the `type` field of its `package.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageType {
+    CommonJs,
+    Module,
+}
+
+impl PackageType {
+    /// The type declared by the source of a `package.json`, a missing or unknown `type` is `CommonJs` like
+    /// in Node.js. `None` if the source is not a JSON object.
+    pub fn from_manifest(src: &str) -> Option<Self> {
+        let manifest = serde_json::from_str::<serde_json::Value>(src).ok()?;
+        let ty = manifest.as_object()?.get("type").and_then(|ty| ty.as_str());
+        Some(if ty == Some("module") {
+            PackageType::Module
+        } else {
+            PackageType::CommonJs
+        })
+    }
+
+    /// The type of the package closest to the file at `path`, `None` if there is no `package.json` in the
+    /// directory of the file or any of its ancestors.
+    pub fn find(path: impl AsRef<Path>) -> Option<Self> {
+        PackageTypes::default().of(path)
+    }
+}
+
+/// A cache of the package types of directories for looking up the packages of many files.
+#[derive(Debug, Clone, Default)]
+pub struct PackageTypes {
+    dirs: HashMap<PathBuf, Option<PackageType>>,
+}
+
+impl PackageTypes {
+    /// The type of the package closest to the file at `path`, see [`PackageType::find`].
+    ///
+    /// Like in Node.js the closest `package.json` is used even if it is not valid.
+    pub fn of(&mut self, path: impl AsRef<Path>) -> Option<PackageType> {
+        let dir = match path.as_ref().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_owned());
+
+        let mut visited = vec![];
+        let mut ty = None;
+        for ancestor in dir.ancestors() {
+            if let Some(cached) = self.dirs.get(ancestor) {
+                ty = *cached;
+                break;
+            }
+            visited.push(ancestor.to_owned());
+            if let Ok(src) = fs::read_to_string(ancestor.join("package.json")) {
+                ty = Some(PackageType::from_manifest(&src).unwrap_or(PackageType::CommonJs));
+                break;
+            }
+        }
+        for dir in visited {
+            self.dirs.insert(dir, ty);
+        }
+        ty
+    }
+}
+
+/// The environment a file runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Environment {
+    /// Whether the file is parsed as a module or a script.
+    pub kind: FileKind,
+    /// Whether the file is run by Node.js.
+    pub node: bool,
+}
+
+impl Environment {
+    /// Infer the environment of a file from its path, its source, and the type of its package.
+    pub fn resolve(path: impl AsRef<Path>, src: &str, package: Option<PackageType>) -> Self {
+        let ext = path.as_ref().extension().and_then(|ext| ext.to_str());
+        let kind = match ext {
+            Some("mjs") => FileKind::Module,
+            Some("cjs") => FileKind::Script,
+            _ if package == Some(PackageType::Module) => FileKind::Module,
+            _ => FileKind::Script,
+        };
+        let node = matches!(ext, Some("mjs") | Some("cjs")) || runs_node(src);
+        Self { kind, node }
+    }
+
+    /// The globals of a file in this environment, given the globals configured by the host.
+    pub fn globals(&self, globals: &Globals) -> Globals {
+        let mut globals = globals.clone();
+        if self.node {
+            globals.extend(NODE);
+        }
+        if self.kind == FileKind::Module {
+            for name in COMMONJS.iter() {
+                globals.remove(name);
+            }
+        }
+        globals
+    }
+}
+
+/// Whether the shebang of a file runs it with Node.js, such as `#!/usr/bin/env node`.
+fn runs_node(src: &str) -> bool {
+    let line = match src.strip_prefix("#!") {
+        Some(rest) => rest.lines().next().unwrap_or_default(),
+        None => return false,
+    };
+    line.split_whitespace()
+        .map(|word| word.rsplit('/').next().unwrap_or(word))
+        .any(|program| matches!(program, "node" | "nodejs"))
+}
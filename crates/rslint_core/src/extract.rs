@@ -0,0 +1,450 @@
+//! Extraction of JavaScript embedded in other files, such as the scripts of HTML pages and Vue components and
+//! the code blocks of Markdown documents.
+//!
+//! An [`Extractor`] splits one physical file into [`ExtractedFile`]s, which are linted as separate virtual files
+//! with their own [`FileKind`]. For example a Vue component is split into its `<script>`, its `<script setup>`, and
+//! each expression interpolated in its template. The [`ProjectLinter`](crate::project::ProjectLinter) lints the
+//! extracted files and maps their diagnostics back to the physical file with [`shift_diagnostic`].
+//!
+//! YAML front matter at the start of a file (as used by static site generators) is skipped, so scripts
+//! mentioned in it are not extracted.
+
+use crate::{Diagnostic, FileKind, LintResult};
+use rslint_parser::TextSize;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::path::Path;
+
+/// JavaScript source code extracted from a physical file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedFile {
+    pub source: String,
+    pub kind: FileKind,
+    /// The byte offset of the source in the physical file.
+    pub offset: usize,
+    /// Which part of the physical file this is, such as `script setup` or `template`.
+    pub part: &'static str,
+}
+
+/// Extracts the JavaScript embedded in files which are not JavaScript files.
+pub trait Extractor: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    /// The extensions of the files this extractor handles, without a leading dot.
+    fn extensions(&self) -> &'static [&'static str];
+    /// Extract the virtual files of a file, in the order they appear in it.
+    fn extract(&self, src: &str) -> Vec<ExtractedFile>;
+}
+
+/// Every built in extractor.
+pub fn extractors() -> Vec<Box<dyn Extractor>> {
+    vec![Box::new(Html), Box::new(Vue), Box::new(Markdown)]
+}
+
+/// The built in extractor for a path by its extension, `None` for JavaScript files and unknown files.
+pub fn extractor_for_path(path: impl AsRef<Path>) -> Option<Box<dyn Extractor>> {
+    let ext = path.as_ref().extension()?.to_str()?;
+    extractors()
+        .into_iter()
+        .find(|extractor| extractor.extensions().contains(&ext))
+}
+
+/// Extracts the bodies of the `<script>` tags of HTML files, inline scripts with `type="module"` are modules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Html;
+
+impl Extractor for Html {
+    fn name(&self) -> &'static str {
+        "html"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["html", "htm"]
+    }
+
+    fn extract(&self, src: &str) -> Vec<ExtractedFile> {
+        script_tags(src)
+            .into_iter()
+            .filter(|tag| tag.is_javascript() && tag.attr("src").is_none())
+            .map(|tag| {
+                let kind = if tag.attr("type") == Some(Some("module")) {
+                    FileKind::Module
+                } else {
+                    FileKind::Script
+                };
+                tag.extracted(src, kind, "script")
+            })
+            .collect()
+    }
+}
+
+/// Extracts the `<script>` and `<script setup>` blocks of Vue single file components as modules, and
+/// the expressions interpolated with `{{ }}` in the `<template>` block as scripts.
+///
+/// Blocks in other languages, such as `<script lang="ts">`, are skipped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Vue;
+
+impl Extractor for Vue {
+    fn name(&self) -> &'static str {
+        "vue"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["vue"]
+    }
+
+    fn extract(&self, src: &str) -> Vec<ExtractedFile> {
+        let mut files = script_tags(src)
+            .into_iter()
+            .filter(ScriptTag::is_javascript)
+            .map(|tag| {
+                let part = if tag.attr("setup").is_some() {
+                    "script setup"
+                } else {
+                    "script"
+                };
+                tag.extracted(src, FileKind::Module, part)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(template) = template_block(src) {
+            let mut pos = template.start;
+            while let Some(start) = src[pos..template.end].find("{{") {
+                let start = pos + start + 2;
+                let end = match src[start..template.end].find("}}") {
+                    Some(end) => start + end,
+                    None => break,
+                };
+                files.push(ExtractedFile {
+                    source: src[start..end].to_string(),
+                    kind: FileKind::Script,
+                    offset: start,
+                    part: "template",
+                });
+                pos = end + 2;
+            }
+        }
+        files.sort_by_key(|file| file.offset);
+        files
+    }
+}
+
+/// Extracts the fenced code blocks of Markdown files whose language is JavaScript, such as ` ```js `.
+///
+/// Blocks tagged `js`, `javascript`, or `mjs` are modules and blocks tagged `cjs` are scripts. Blocks in other
+/// languages, including `jsx` and `ts` which the parser does not support, are skipped, and so are indented code
+/// blocks since they have no language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Markdown;
+
+impl Extractor for Markdown {
+    fn name(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["md", "markdown"]
+    }
+
+    fn extract(&self, src: &str) -> Vec<ExtractedFile> {
+        let mut files = vec![];
+        let mut pos = front_matter(src).map_or(0, |range| range.end);
+        // the fence character, the length of the fence, the kind of the block if it is JavaScript, and where its body starts
+        let mut open: Option<(char, usize, Option<FileKind>, usize)> = None;
+        for line in src[pos..].split_inclusive('\n') {
+            let start = pos;
+            pos += line.len();
+            let fence = match code_fence(line) {
+                Some(fence) => fence,
+                None => continue,
+            };
+            match open {
+                Some((ch, len, kind, body_start)) => {
+                    if fence.0 == ch && fence.1 >= len && fence.2.is_empty() {
+                        if let Some(kind) = kind {
+                            files.push(ExtractedFile {
+                                source: src[body_start..start].to_string(),
+                                kind,
+                                offset: body_start,
+                                part: "code block",
+                            });
+                        }
+                        open = None;
+                    }
+                }
+                None => {
+                    let lang = fence.2.split_whitespace().next().unwrap_or_default();
+                    let kind = match lang.to_ascii_lowercase().as_str() {
+                        "js" | "javascript" | "mjs" => Some(FileKind::Module),
+                        "cjs" => Some(FileKind::Script),
+                        _ => None,
+                    };
+                    open = Some((fence.0, fence.1, kind, pos));
+                }
+            }
+        }
+        // like in CommonMark a block which is never closed ends at the end of the file
+        if let Some((_, _, Some(kind), body_start)) = open {
+            files.push(ExtractedFile {
+                source: src[body_start..].to_string(),
+                kind,
+                offset: body_start,
+                part: "code block",
+            });
+        }
+        files
+    }
+}
+
+/// The character and length of the code fence a Markdown line starts with, and the info string after it.
+fn code_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.len() - trimmed.trim_start_matches(ch).len();
+    let info = trimmed[len..].trim();
+    if len < 3 || (ch == '`' && info.contains('`')) {
+        return None;
+    }
+    Some((ch, len, info))
+}
+
+/// The range of the YAML front matter at the start of a file, including its `---` delimiters.
+pub fn front_matter(src: &str) -> Option<Range<usize>> {
+    let rest = src
+        .strip_prefix("---\r\n")
+        .or_else(|| src.strip_prefix("---\n"))?;
+    let body_start = src.len() - rest.len();
+    let mut pos = body_start;
+    for line in rest.split_inclusive('\n') {
+        pos += line.len();
+        if line.trim_end() == "---" {
+            return Some(0..pos);
+        }
+    }
+    None
+}
+
+/// Move every span of a diagnostic by `offset` bytes, for mapping the diagnostics of an extracted file to
+/// the physical file it was extracted from.
+pub fn shift_diagnostic(diagnostic: &mut Diagnostic, offset: usize) {
+    let shift = |range: &mut Range<usize>| *range = range.start + offset..range.end + offset;
+    if let Some(primary) = diagnostic.primary.as_mut() {
+        shift(&mut primary.span.range);
+    }
+    for child in diagnostic.children.iter_mut() {
+        shift(&mut child.span.range);
+    }
+    for suggestion in diagnostic.suggestions.iter_mut() {
+        shift(&mut suggestion.span.range);
+        suggestion.labels.iter_mut().for_each(shift);
+        if let rslint_errors::SuggestionChange::Indels(indels) = &mut suggestion.substitution {
+            for indel in indels.iter_mut() {
+                let offset = TextSize::from(offset as u32);
+                indel.delete = rslint_parser::TextRange::new(
+                    indel.delete.start() + offset,
+                    indel.delete.end() + offset,
+                );
+            }
+        }
+    }
+    diagnostic.captures.values_mut().for_each(shift);
+    diagnostic.suppressed_by.iter_mut().for_each(shift);
+}
+
+/// Move every diagnostic and directive of a lint result by `offset` bytes, see [`shift_diagnostic`].
+pub fn shift_result(result: &mut LintResult, offset: usize) {
+    for diagnostic in result
+        .parser_diagnostics
+        .iter_mut()
+        .chain(result.directive_diagnostics.iter_mut())
+        .chain(
+            result
+                .rule_results
+                .values_mut()
+                .flat_map(|res| res.diagnostics.iter_mut().chain(res.suppressed.iter_mut())),
+        )
+    {
+        shift_diagnostic(diagnostic, offset);
+    }
+    let offset = TextSize::from(offset as u32);
+    for range in result.suppressions.directives.iter_mut() {
+        *range += offset;
+    }
+}
+
+/// A `<script>` tag and the range of its body.
+#[derive(Debug, Clone)]
+struct ScriptTag<'a> {
+    attrs: Vec<Attr<'a>>,
+    body: Range<usize>,
+}
+
+impl<'a> ScriptTag<'a> {
+    /// The value of an attribute, `Some(None)` if the attribute has no value.
+    fn attr(&self, name: &str) -> Option<Option<&'a str>> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    fn is_javascript(&self) -> bool {
+        let lang = matches!(
+            self.attr("lang"),
+            None | Some(Some("js")) | Some(Some("javascript"))
+        );
+        let ty = match self.attr("type").flatten() {
+            None => true,
+            Some(ty) => matches!(
+                ty.to_ascii_lowercase().as_str(),
+                "module" | "text/javascript" | "application/javascript"
+            ),
+        };
+        lang && ty
+    }
+
+    fn extracted(&self, src: &str, kind: FileKind, part: &'static str) -> ExtractedFile {
+        ExtractedFile {
+            source: src[self.body.clone()].to_string(),
+            kind,
+            offset: self.body.start,
+            part,
+        }
+    }
+}
+
+/// Find the position of `pat` in `src` after `from`, ignoring ASCII case.
+fn find_ignore_case(src: &str, from: usize, pat: &str) -> Option<usize> {
+    src.as_bytes()[from..]
+        .windows(pat.len())
+        .position(|window| window.eq_ignore_ascii_case(pat.as_bytes()))
+        .map(|idx| from + idx)
+}
+
+/// The name and value of an attribute, the value is `None` for attributes like `setup` which have no value.
+type Attr<'a> = (&'a str, Option<&'a str>);
+
+/// The position after the end of a tag starting at `start`, and its attributes.
+fn parse_tag(src: &str, start: usize) -> Option<(usize, Vec<Attr<'_>>)> {
+    let bytes = src.as_bytes();
+    let mut pos = start + 1;
+    while pos < bytes.len() && bytes[pos].is_ascii_alphanumeric() {
+        pos += 1;
+    }
+    let mut attrs = vec![];
+    loop {
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'/') {
+            pos += 1;
+        }
+        match bytes.get(pos)? {
+            b'>' => return Some((pos + 1, attrs)),
+            _ => {
+                let name_start = pos;
+                while pos < bytes.len()
+                    && !bytes[pos].is_ascii_whitespace()
+                    && !matches!(bytes[pos], b'=' | b'>' | b'/')
+                {
+                    pos += 1;
+                }
+                let name = &src[name_start..pos];
+                if bytes.get(pos) != Some(&b'=') {
+                    attrs.push((name, None));
+                    continue;
+                }
+                pos += 1;
+                let value = match bytes.get(pos)? {
+                    quote @ b'"' | quote @ b'\'' => {
+                        let end = pos + 1 + src[pos + 1..].find(*quote as char)?;
+                        let value = &src[pos + 1..end];
+                        pos = end + 1;
+                        value
+                    }
+                    _ => {
+                        let value_start = pos;
+                        while pos < bytes.len()
+                            && !bytes[pos].is_ascii_whitespace()
+                            && bytes[pos] != b'>'
+                        {
+                            pos += 1;
+                        }
+                        &src[value_start..pos]
+                    }
+                };
+                attrs.push((name, Some(value)));
+            }
+        }
+    }
+}
+
+/// Whether a tag named `name` starts at `pos`, and not a tag whose name starts with `name`.
+fn is_tag(src: &str, pos: usize, name: &str) -> bool {
+    let matches_name = match src.get(pos..pos + name.len()) {
+        Some(tag) => tag.eq_ignore_ascii_case(name),
+        None => false,
+    };
+    matches_name
+        && matches!(src.as_bytes().get(pos + name.len()), Some(c) if c.is_ascii_whitespace() || matches!(c, b'>' | b'/'))
+}
+
+/// Every `<script>` tag of a file outside of comments and the front matter.
+fn script_tags(src: &str) -> Vec<ScriptTag<'_>> {
+    let mut tags = vec![];
+    let mut pos = front_matter(src).map_or(0, |range| range.end);
+    while let Some(idx) = src[pos..].find('<') {
+        let start = pos + idx;
+        if src[start..].starts_with("<!--") {
+            match src[start..].find("-->") {
+                Some(end) => pos = start + end + 3,
+                None => break,
+            }
+        } else if is_tag(src, start, "<script") {
+            let (body_start, attrs) = match parse_tag(src, start) {
+                Some(tag) => tag,
+                None => break,
+            };
+            let body_end = find_ignore_case(src, body_start, "</script").unwrap_or(src.len());
+            tags.push(ScriptTag {
+                attrs,
+                body: body_start..body_end,
+            });
+            pos = body_end;
+        } else {
+            pos = start + 1;
+        }
+    }
+    tags
+}
+
+/// The range of the body of the top level `<template>` block of a Vue component.
+fn template_block(src: &str) -> Option<Range<usize>> {
+    let mut pos = front_matter(src).map_or(0, |range| range.end);
+    let mut depth = 0;
+    let mut body_start = None;
+    while let Some(idx) = src[pos..].find('<') {
+        let start = pos + idx;
+        if src[start..].starts_with("<!--") {
+            pos = start + src[start..].find("-->")? + 3;
+        } else if is_tag(src, start, "<template") {
+            let (end, _) = parse_tag(src, start)?;
+            depth += 1;
+            body_start.get_or_insert(end);
+            pos = end;
+        } else if depth > 0 && is_tag(src, start, "</template") {
+            depth -= 1;
+            if depth == 0 {
+                return Some(body_start?..start);
+            }
+            pos = start + 1;
+        } else if depth == 0 && is_tag(src, start, "<script") {
+            // the template can not contain top level blocks, skip scripts so `<template>` in strings is ignored
+            let (body_start, _) = parse_tag(src, start)?;
+            pos = find_ignore_case(src, body_start, "</script")?;
+        } else {
+            pos = start + 1;
+        }
+    }
+    None
+}
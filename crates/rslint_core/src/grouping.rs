@@ -0,0 +1,115 @@
+//! Grouping of the diagnostics of a file which report the same problem.
+//!
+//! Rules run on the tree the parser recovered from syntax errors, so one mistake can be reported several times:
+//! by the parser and by rules looking at the recovered code, or by several rules flagging the same span such as
+//! `eqeqeq` and `no-compare-neg-zero` on the `==` of `a == -0`. [`group_diagnostics`] folds these into a single
+//! [`DiagnosticGroup`] each, so hosts can show every problem once. See [`LintResult::grouped_diagnostics`].
+//!
+//! [`LintResult::grouped_diagnostics`]: crate::LintResult::grouped_diagnostics
+
+use crate::Diagnostic;
+use rslint_errors::Severity;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Diagnostics which report the same problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticGroup<'a> {
+    /// The diagnostic shown for the group, the first diagnostic reported on the span.
+    pub primary: &'a Diagnostic,
+    /// The other diagnostics of the group in the order they were reported.
+    pub duplicates: Vec<&'a Diagnostic>,
+}
+
+impl<'a> DiagnosticGroup<'a> {
+    fn new(primary: &'a Diagnostic) -> Self {
+        Self {
+            primary,
+            duplicates: vec![],
+        }
+    }
+
+    /// Every diagnostic of the group, starting with the primary diagnostic.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Diagnostic> + '_ {
+        std::iter::once(self.primary).chain(self.duplicates.iter().copied())
+    }
+
+    /// Whether the group is a single diagnostic without any duplicates.
+    pub fn is_single(&self) -> bool {
+        self.duplicates.is_empty()
+    }
+}
+
+/// Group the diagnostics of the parser and the other diagnostics of a file, such as the diagnostics of rules.
+///
+/// Diagnostics whose primary label has the same range are grouped, and diagnostics which are not from the parser
+/// are grouped with a parser error if their primary label overlaps the error since they are usually caused by it.
+/// Diagnostics without a primary label are never grouped. Groups are in the order of their first diagnostic, with
+/// the parser diagnostics first.
+pub fn group_diagnostics<'a>(
+    parser: &'a [Diagnostic],
+    others: impl IntoIterator<Item = &'a Diagnostic>,
+) -> Vec<DiagnosticGroup<'a>> {
+    let mut groups: Vec<DiagnosticGroup> = vec![];
+    let mut by_range: HashMap<Range<usize>, usize> = HashMap::new();
+    let mut errors = vec![];
+
+    for diagnostic in parser {
+        let range = match primary_range(diagnostic) {
+            Some(range) => range,
+            None => {
+                groups.push(DiagnosticGroup::new(diagnostic));
+                continue;
+            }
+        };
+        if let Some(&idx) = by_range.get(&range) {
+            groups[idx].duplicates.push(diagnostic);
+            continue;
+        }
+        if diagnostic.severity == Severity::Error {
+            errors.push((range.clone(), groups.len()));
+        }
+        by_range.insert(range, groups.len());
+        groups.push(DiagnosticGroup::new(diagnostic));
+    }
+
+    for diagnostic in others {
+        let range = match primary_range(diagnostic) {
+            Some(range) => range,
+            None => {
+                groups.push(DiagnosticGroup::new(diagnostic));
+                continue;
+            }
+        };
+        let group = by_range.get(&range).copied().or_else(|| {
+            errors
+                .iter()
+                .find(|(error, _)| overlaps(error, &range))
+                .map(|(_, idx)| *idx)
+        });
+        match group {
+            Some(idx) => groups[idx].duplicates.push(diagnostic),
+            None => {
+                by_range.insert(range, groups.len());
+                groups.push(DiagnosticGroup::new(diagnostic));
+            }
+        }
+    }
+    groups
+}
+
+fn primary_range(diagnostic: &Diagnostic) -> Option<Range<usize>> {
+    diagnostic
+        .primary
+        .as_ref()
+        .map(|primary| primary.span.range.clone())
+}
+
+/// Whether two ranges share a character, empty ranges overlap the ranges they touch.
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    if a.is_empty() || b.is_empty() {
+        a.start <= b.end && b.start <= a.end
+    } else {
+        a.start < b.end && b.start < a.end
+    }
+}
@@ -32,4 +32,12 @@ group! {
     valid_typeof::ValidTypeof,
     no_extra_boolean_cast::NoExtraBooleanCast,
     no_confusing_arrow::NoConfusingArrow,
+    no_fallthrough::NoFallthrough,
+    no_unreachable::NoUnreachable,
+    no_impossible_comparison::NoImpossibleComparison,
+    no_redeclare::NoRedeclare,
+    no_use_before_define::NoUseBeforeDefine,
+    no_dupe_args::NoDupeArgs,
+    no_template_curly_in_string::NoTemplateCurlyInString,
+    no_global_assign::NoGlobalAssign,
 }
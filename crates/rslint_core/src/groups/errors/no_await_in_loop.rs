@@ -1,4 +1,5 @@
 use crate::rule_prelude::*;
+use util::scope::is_function_scope;
 use SyntaxKind::*;
 
 declare_lint! {
@@ -11,7 +12,13 @@ declare_lint! {
     Generally it is recommended that you create all promises, then use `Promise.all` for them. This way your async
     operations will be performed concurrently.
 
-    ## Incorrect Code Exapmles
+    Awaits in the body, the condition, and the update of a loop are reported. Awaits in the initializer of a
+    `for` loop and in the iterated expression of a `for...in` or `for...of` loop only run once and are allowed.
+    Awaiting in the body of a `for await...of` loop is allowed since that loop already waits for each item, but
+    a `for await...of` loop inside of another loop is reported. Awaits inside of functions declared in a loop
+    are not reported.
+
+    ## Incorrect Code Examples
 
     ```js
     async function foo(xs) {
@@ -24,6 +31,12 @@ declare_lint! {
     }
     ```
 
+    ```js
+    async function foo() {
+        while (!(await isReady())) {}
+    }
+    ```
+
     ## Correct Code Examples
 
     ```js
@@ -37,6 +50,14 @@ declare_lint! {
         return baz(await Promise.all(results));
     }
     ```
+
+    ```js
+    async function foo(stream) {
+        for await (const chunk of stream) {
+            await write(chunk);
+        }
+    }
+    ```
     */
     #[derive(Default)]
     NoAwaitInLoop,
@@ -47,31 +68,63 @@ declare_lint! {
 #[typetag::serde]
 impl CstRule for NoAwaitInLoop {
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
-        if let Some(err_node) = node.children().find(|node| node.kind() == AWAIT_EXPR) {
-            for ancestor in node.ancestors() {
-                match ancestor.kind() {
-                    FN_DECL | FN_EXPR | ARROW_EXPR => return None,
-                    FOR_OF_STMT if ancestor.to::<ast::ForOfStmt>().await_token().is_some() => {
-                        return None
-                    }
-                    _ => {}
-                }
-
-                if ancestor.is_loop() {
-                    let err = ctx.err(self.name(), "Unexpected `await` in loop")
-                        .primary(err_node, "this expression causes the loop to wait for the promise to resolve before continuing")
-                        .footer_note("the promises are resolved one after the other, not at the same time")
-                        .footer_help(format!("try adding the promises to an array, then resolving them all outside the loop using `{}`", color("Promise.all(/* promises */)")));
+        let for_await = is_for_await(node);
+        if node.kind() != AWAIT_EXPR && !for_await {
+            return None;
+        }
 
-                    ctx.add_err(err);
+        let mut child = node.clone();
+        for ancestor in node.ancestors().skip(1) {
+            if is_function_scope(&ancestor) {
+                return None;
+            }
+            if ancestor.is_loop() && is_looped(&ancestor, &child) {
+                // the body of a `for await` loop already waits for each item
+                if is_for_await(&ancestor) {
                     return None;
                 }
+
+                let label = if for_await {
+                    "this loop causes the outer loop to wait for every item before continuing"
+                } else {
+                    "this expression causes the loop to wait for the promise to resolve before continuing"
+                };
+                let err = ctx.err(self.name(), "Unexpected `await` in loop")
+                    .primary(node, label)
+                    .secondary(ancestor.first_token()?, "the loop starts here")
+                    .footer_note("the promises are resolved one after the other, not at the same time")
+                    .footer_help(format!("try adding the promises to an array, then resolving them all outside the loop using `{}`", color("Promise.all(/* promises */)")));
+
+                ctx.add_err(err);
+                return None;
             }
+            child = ancestor;
         }
         None
     }
 }
 
+fn is_for_await(node: &SyntaxNode) -> bool {
+    node.try_to::<ast::ForOfStmt>()
+        .and_then(|stmt| stmt.await_token())
+        .is_some()
+}
+
+/// Whether `child` of the loop `parent` is evaluated in every iteration.
+fn is_looped(parent: &SyntaxNode, child: &SyntaxNode) -> bool {
+    match parent.kind() {
+        FOR_STMT => child.kind() != FOR_STMT_INIT,
+        // only the body of a `for...in` or `for...of` loop, which is after the closing paren of its head
+        FOR_IN_STMT | FOR_OF_STMT => parent
+            .children_with_tokens()
+            .filter_map(|elem| elem.into_token())
+            .find(|token| token.kind() == T![')'])
+            .filter(|paren| paren.text_range().end() <= child.text_range().start())
+            .is_some(),
+        _ => true,
+    }
+}
+
 rule_tests! {
     NoAwaitInLoop::default(),
     err: {
@@ -89,7 +142,28 @@ rule_tests! {
                 await i;
             }
         }
-        "
+        ",
+        "async function foo() { for (const x in xs) { await x; } }",
+        "async function foo() { for (let i = 0; await check(i); i++) {} }",
+        "async function foo() { for (let i = 0; i < 5; await next()) {} }",
+        "async function foo() { while (await bar()) {} }",
+        "async function foo() { do { await bar(); } while (a); }",
+        "async function foo() { do {} while (await bar()); }",
+        "async function foo() { for (const x of xs) { if (x) { const y = await x; } } }",
+        "async function foo() { for (const x of xs) for (const y of ys) await y; }",
+        "async function foo() { while (a) { for await (const x of xs) {} } }",
+        "async function foo() { for (const x of xs) { for (const y of await x) {} } }"
     },
-    ok: {}
+    ok: {
+        "async function foo() { await bar(); }",
+        "async function foo() { for (const x of await xs) {} }",
+        "async function foo() { for (const x in await xs) {} }",
+        "async function foo() { for (let i = await start(); i < 5; i++) {} }",
+        "async function foo() { for await (const x of xs) { await x; } }",
+        "async function foo() { for await (const x of xs) { for await (const y of x) {} } }",
+        "async function foo() { for (const x of xs) { async function bar() { await x; } } }",
+        "async function foo() { while (a) { xs.map(async x => await x); } }",
+        "async function foo() { while (a) { const bar = async function () { await x; }; } }",
+        "async function foo() { for (const x of xs) { class A { async bar() { await x; } } } }"
+    }
 }
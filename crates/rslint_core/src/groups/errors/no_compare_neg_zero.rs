@@ -38,6 +38,10 @@ declare_lint! {
 
 #[typetag::serde]
 impl CstRule for NoCompareNegZero {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if node.try_to::<ast::BinExpr>()?.comparison() {
             let bin = node.to::<ast::BinExpr>();
@@ -71,9 +75,11 @@ fn issue_err(expr: ast::Expr, ctx: &mut RuleCtx, op: SyntaxToken, parent: &Synta
             ),
         )
         .primary(
-            op,
+            op.clone(),
             "...because this comparison passes for both `-0` and `+0`",
         )
+        .capture("operator", op)
+        .capture("argument", expr.syntax().trimmed_range())
         .suggestion(
             parent,
             "try using `Object.is` instead",
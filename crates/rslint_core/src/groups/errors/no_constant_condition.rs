@@ -1,4 +1,5 @@
 use crate::rule_prelude::*;
+use crate::util::const_eval::truthiness;
 use ast::*;
 use SyntaxKind::*;
 
@@ -11,6 +12,11 @@ declare_lint! {
     This rule is aimed at catching those conditions in `if`, `do while`, `while`, and `for` statements, as well as
     conditional expressions.
 
+    Conditions are evaluated as constant expressions, so conditions made of literals such
+    as `"a" + "b"` or `1 > 2`, assignments of constants like `x = 1`, and logical expressions which short circuit
+    like `foo || true` are constant too. Loops can be excluded with `checkLoops` for code which uses
+    `while (true)` as an infinite loop.
+
     ## Incorrect Code Examples
 
     ```js
@@ -28,6 +34,19 @@ declare_lint! {
     }
     ```
 
+    ```js
+    // this assigns `1` to `x` instead of comparing it
+    if (x = 1) {
+
+    }
+    ```
+
+    ```js
+    while ("a" + "b") {
+
+    }
+    ```
+
     ## Correct Code Examples
 
     ```js
@@ -35,50 +54,52 @@ declare_lint! {
         /* */
     }
     ```
+
+    ```js
+    if (x === 1) {
+
+    }
+    ```
     */
-    #[derive(Default)]
+    #[serde(default)]
     NoConstantCondition,
     errors,
-    "no-constant-condition"
+    "no-constant-condition",
+    /// Whether to check the conditions of loops, `true` by default.
+    pub check_loops: bool
+}
+
+impl Default for NoConstantCondition {
+    fn default() -> Self {
+        Self { check_loops: true }
+    }
 }
 
 #[typetag::serde]
 impl CstRule for NoConstantCondition {
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
-        let mut notes = vec![];
         let cond = match node.kind() {
-            IF_STMT | DO_WHILE_STMT | WHILE_STMT => {
-                if let Some(cond) = node.children().find_map(|node| node.try_to::<Condition>()) {
-                    if !util::is_const(cond.condition()?, true, &mut notes) {
-                        return None;
-                    }
-                    cond.condition().unwrap()
-                } else {
-                    return None;
-                }
-            }
-            COND_EXPR => {
-                let cond = node.to::<CondExpr>().test()?;
-                if !util::is_const(cond.clone(), true, &mut notes) {
-                    return None;
-                }
-                cond
-            }
-            FOR_STMT => {
-                let cond = node.to::<ForStmt>().test()?.expr()?;
-                if !util::is_const(cond.clone(), true, &mut notes) {
-                    return None;
-                }
-                cond
-            }
+            DO_WHILE_STMT | WHILE_STMT if !self.check_loops => return None,
+            IF_STMT | DO_WHILE_STMT | WHILE_STMT => node
+                .children()
+                .find_map(|node| node.try_to::<Condition>())?
+                .condition()?,
+            COND_EXPR => node.to::<CondExpr>().test()?,
+            FOR_STMT if self.check_loops => node.to::<ForStmt>().test()?.expr()?,
             _ => return None,
         };
+        let condition_value = truthiness(cond.clone())?;
 
         let mut err = ctx.err(self.name(), "unexpected constant condition");
-        if let Some(condition_value) = util::simple_bool_coerce(cond.clone()) {
-            err = util::simple_const_condition_context(node.clone(), condition_value, err);
-        } else {
-            err = err.primary(cond.syntax(), "this condition always yields one result")
+        err = util::simple_const_condition_context(node.clone(), condition_value, err);
+        if err.primary.is_none() {
+            err = err.primary(cond.syntax(), "this condition always yields one result");
+        }
+        if let Some(lhs) = assignment(cond).and_then(|assign| assign.lhs()) {
+            err = err.footer_help(format!(
+                "this assigns to `{}`, did you mean to compare it with `===`?",
+                lhs.syntax().trimmed_text()
+            ));
         }
         ctx.add_err(err);
 
@@ -86,6 +107,15 @@ impl CstRule for NoConstantCondition {
     }
 }
 
+/// The assignment a condition is made of, if it is an assignment which is not wrapped in parentheses.
+fn assignment(cond: Expr) -> Option<AssignExpr> {
+    match cond {
+        Expr::AssignExpr(assign) if assign.op()? == AssignOp::Assign => Some(assign),
+        Expr::SequenceExpr(seq) => assignment(seq.exprs().last()?),
+        _ => None,
+    }
+}
+
 rule_tests! {
     NoConstantCondition::default(),
     err: {
@@ -97,13 +127,44 @@ rule_tests! {
         "false ? false : false ? false : false",
         "while (true) {}",
         "do { /* */ } while (NaN ? NaN : true)",
-        "do { } while (NaN ? Infinity : true)"
+        "do { } while (NaN ? Infinity : true)",
+        "if (x = 1) {}",
+        "while (\"a\" + \"b\") {}",
+        "if (1 > 2) {}",
+        "if (1n < 2) {}",
+        "if (`foo${1 + 1}`) {}",
+        "if (foo || true) {}",
+        "if (foo && void bar) {}",
+        "if (typeof foo) {}",
+        "if (!(2 ** 3 === 8)) {}",
+        "for (;\"\";) {}"
     },
     ok: {
         "if (foo) {}",
         "if (false > foo) {} else {}",
         "if (foo ? NaN : Infinity) {}",
         "do {} while (foo + 6)",
-        "for(var i = 5; foo; i++) {}"
+        "for(var i = 5; foo; i++) {}",
+        "if (x == 1) {}",
+        "if (x = foo) {}",
+        "if (foo + bar) {}",
+        "while (`${foo}`) {}",
+        "if (foo || false) {}",
+        "if ([] < 1) {}"
+    }
+}
+
+rule_tests! {
+    check_loops_valid,
+    check_loops_invalid,
+    NoConstantCondition { check_loops: false },
+    err: {
+        "if (true) {}",
+        "true ? foo : bar"
+    },
+    ok: {
+        "while (true) {}",
+        "do {} while (1)",
+        "for (;true;) {}"
     }
 }
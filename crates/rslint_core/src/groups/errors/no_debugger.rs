@@ -1,4 +1,5 @@
 use crate::rule_prelude::*;
+use SyntaxKind::*;
 
 declare_lint! {
     /**
@@ -8,6 +9,9 @@ declare_lint! {
     debugger. These statements are rendered useless by modern IDEs which have built in breakpoint support.
     Having them in production code is erroneous as it will tell the browser to stop running and open a debugger.
 
+    The fix removes the statement, unless it is the body of a statement such as `if (a) debugger;` where
+    removing it would make the next statement the body.
+
     ## Invalid Code Examples
 
     ```js
@@ -25,14 +29,28 @@ declare_lint! {
 
 #[typetag::serde]
 impl CstRule for NoDebugger {
-    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
-        if node.kind() == SyntaxKind::DEBUGGER_STMT {
-            let err = ctx
-                .err(self.name(), "debugger statements are not allowed")
-                .primary(node, "");
+    fn fixable(&self) -> bool {
+        true
+    }
 
-            ctx.add_err(err);
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if node.kind() != DEBUGGER_STMT {
+            return None;
+        }
+        let in_stmt_list = node
+            .parent()
+            .filter(|parent| {
+                matches!(
+                    parent.kind(),
+                    SCRIPT | MODULE | BLOCK_STMT | CASE_CLAUSE | DEFAULT_CLAUSE
+                )
+            })
+            .is_some();
+        if in_stmt_list {
+            ctx.fix().delete(node).eat_leading_whitespace(node);
         }
+        ctx.diagnostic(node, "debugger statements are not allowed")
+            .emit();
         None
     }
 }
@@ -41,7 +59,13 @@ rule_tests! {
     NoDebugger::default(),
     err: {
         "debugger",
-        "debugger;"
+        "debugger;",
+        "if (a) debugger;",
+        "function foo() { debugger; }",
+        "switch (a) { case 1: debugger; }"
     },
-    ok: {}
+    ok: {
+        "let debugger_ = 5;",
+        "foo.debugger();"
+    }
 }
@@ -0,0 +1,78 @@
+use crate::rule_prelude::*;
+use util::scope::{is_function_scope, parameter_bindings};
+
+declare_lint! {
+    /**
+    Disallow functions with duplicate parameter names.
+
+    Functions outside of strict mode may declare the same parameter name multiple times, the last parameter
+    then shadows the earlier ones, which is almost always a typo. Duplicate parameters are a syntax error in
+    strict mode code, arrow functions, and functions with default, rest, or destructured parameters, they are
+    reported there as well since the parser does not reject them.
+
+    ## Invalid Code Examples
+
+    ```js
+    function foo(a, b, a) {
+        return a + b;
+    }
+    ```
+
+    ```js
+    const foo = function(a, { b, a }) {};
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    function foo(a, b, c) {
+        return a + b + c;
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoDupeArgs,
+    errors,
+    "no-dupe-args"
+}
+
+#[typetag::serde]
+impl CstRule for NoDupeArgs {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if !is_function_scope(node) {
+            return None;
+        }
+        let params = parameter_bindings(node);
+        for (idx, param) in params.iter().enumerate() {
+            let text = param.text();
+            if let Some(first) = params[..idx].iter().find(|other| other.text() == text) {
+                ctx.diagnostic(param.range(), format!("duplicate parameter `{}`", text))
+                    .primary_label(format!("`{}` is then declared again here", text))
+                    .secondary(first.range(), format!("`{}` is first declared here", text))
+                    .emit();
+            }
+        }
+        None
+    }
+}
+
+rule_tests! {
+    NoDupeArgs::default(),
+    err: {
+        "function foo(a, b, a) {}",
+        "function foo(a, a, a) {}",
+        "var foo = function(a, b, b) {}",
+        "var foo = (a, a) => {}",
+        "function foo(a, { b: a }) {}",
+        "function foo(a, [b, ...a]) {}",
+        "var foo = { bar(a, a) {} }",
+        "var foo = { set bar(a) {} }; function baz(c, c) {}"
+    },
+    ok: {
+        "function foo(a, b, c) {}",
+        "var foo = function(a, { a: b }) {}",
+        "function foo(a) { function bar(a) {} }",
+        "var foo = a => a;",
+        "function foo(a = a) {}"
+    }
+}
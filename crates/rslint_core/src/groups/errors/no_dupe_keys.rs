@@ -1,6 +1,5 @@
 use crate::rule_prelude::*;
 use ast::*;
-use rslint_parser::{NodeOrToken, SyntaxToken};
 
 declare_lint! {
     /**
@@ -9,6 +8,9 @@ declare_lint! {
     Object literals allow keys to be declared multiple times, however this causes unwanted
     behavior by shadowing the first declaration.
 
+    Keys are compared by the name of the property they define, so number keys are compared by their
+    value and computed keys made only of literals are evaluated.
+
     ## Invalid Code Examples
 
     ```js
@@ -18,6 +20,25 @@ declare_lint! {
         bar: 3
     }
     ```
+
+    ```js
+    let foo = {
+        1: "a",
+        0x1: "b",
+        ["1"]: "c"
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    let foo = {
+        bar: 1,
+        [bar]: 2,
+        "1": 3,
+        "01": 4
+    }
+    ```
     */
     #[derive(Default)]
     NoDupeKeys,
@@ -25,45 +46,24 @@ declare_lint! {
     "no-dupe-keys"
 }
 
-// FIXME: this should consider the value of a number key, aka 1 and 0x1
 #[typetag::serde]
 impl CstRule for NoDupeKeys {
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if let Expr::ObjectExpr(obj) = node.try_to()? {
-            // String based equality is evil! using tokens is the correct way,
-            // because it counts `foo . bar` and `foo.bar` as the same expr, string eq does not.
-            let mut declared: Vec<(Vec<SyntaxToken>, std::string::String)> = vec![];
-
-            for prop in obj.props().filter_map(|prop| prop.key_element()) {
-                let tokens = match prop.clone() {
-                    NodeOrToken::Node(node) => node.lossy_tokens(),
-                    NodeOrToken::Token(tok) => vec![tok],
-                };
-
-                let text = match prop {
-                    NodeOrToken::Node(node) => node.trimmed_text().to_string(),
-                    NodeOrToken::Token(tok) => tok.text().to_string(),
-                };
-                if let Some((found, text)) = declared
-                    .iter()
-                    .find(|(decl_tokens, _)| util::string_token_eq(decl_tokens, &tokens))
-                {
-                    let range = util::token_list_range(found);
-
-                    let err = ctx
-                        .err(
-                            self.name(),
-                            format!("duplicate property definition `{}`", text),
-                        )
-                        .secondary(range, format!("`{}` is first declared here", text))
-                        .primary(
-                            util::token_list_range(tokens),
-                            format!("`{}` is then redeclared here", text),
-                        );
-
-                    ctx.add_err(err);
+            let mut declared: Vec<(std::string::String, SyntaxNode)> = vec![];
+
+            for (key, key_node) in obj.props().filter_map(|prop| static_key(&prop)) {
+                if let Some((_, first)) = declared.iter().find(|(other, _)| *other == key) {
+                    let text = first.trimmed_text();
+                    ctx.diagnostic(
+                        key_node.trimmed_range(),
+                        format!("duplicate property definition `{}`", text),
+                    )
+                    .primary_label(format!("`{}` is then redeclared here", text))
+                    .secondary(first, format!("`{}` is first declared here", text))
+                    .emit();
                 } else {
-                    declared.push((tokens, text));
+                    declared.push((key, key_node));
                 }
             }
         }
@@ -71,6 +71,32 @@ impl CstRule for NoDupeKeys {
     }
 }
 
+/// The name of the property a property of an object literal defines, and the node of its key.
+///
+/// Literal keys and computed keys are folded with [`const_eval`](util::const_eval), so `1`, `0x1`, `"1"`,
+/// and `[0 + 1]` are all the key `1`. Computed keys which are not constant are not known.
+fn static_key(prop: &ObjectProp) -> Option<(std::string::String, SyntaxNode)> {
+    let name = match prop {
+        ObjectProp::IdentProp(prop) => return name_key(prop.name()?),
+        ObjectProp::InitializedProp(prop) => return name_key(prop.key()?),
+        ObjectProp::LiteralProp(prop) => prop.key()?,
+        ObjectProp::Getter(getter) => getter.key()?,
+        ObjectProp::Setter(setter) => setter.key()?,
+        ObjectProp::Method(method) => method.name()?,
+        ObjectProp::SpreadProp(_) => return None,
+    };
+    let value = match &name {
+        PropName::Ident(name) => return name_key(name.clone()),
+        PropName::Literal(lit) => util::const_eval::eval(Expr::Literal(lit.clone()))?,
+        PropName::Computed(computed) => util::const_eval::eval(computed.prop()?)?,
+    };
+    Some((value.to_js_string()?, name.syntax().clone()))
+}
+
+fn name_key(name: Name) -> Option<(std::string::String, SyntaxNode)> {
+    Some((name.text(), name.syntax().clone()))
+}
+
 rule_tests! {
     NoDupeKeys::default(),
     err: {
@@ -92,7 +118,15 @@ rule_tests! {
 
             }
         }
-        "
+        ",
+        "let foo = { a: 1, 'a': 2 }",
+        "let foo = { 1: 1, 0x1: 2 }",
+        "let foo = { 1: 1, 1.0: 2 }",
+        "let foo = { a: 1, ['a']: 2 }",
+        "let foo = { a: 1, ['a' + '']: 2 }",
+        "let foo = { 10: 1, [5 * 2]: 2 }",
+        "let foo = { a, a() {} }",
+        "let foo = { null: 1, [null]: 2 }"
     },
     ok: {
         "
@@ -103,6 +137,11 @@ rule_tests! {
             },
             baz: {}
         }
-        "
+        ",
+        "let foo = { a: 1, [a]: 2 }",
+        "let foo = { 1: 1, '01': 2 }",
+        "let foo = { a: 1, ...a }",
+        "let foo = { [a()]: 1, [a()]: 2 }",
+        "let foo = { a: 1, b: { a: 2 } }"
     }
 }
@@ -1,5 +1,7 @@
 use crate::rule_prelude::*;
 use ast::SwitchStmt;
+use std::collections::HashSet;
+use util::expr_eq::ExprKey;
 
 declare_lint! {
     /**
@@ -32,6 +34,17 @@ declare_lint! {
             break;
     }
     ```
+
+    ## Correct Code Examples
+
+    ```js
+    switch (a) {
+        case (b + c) * d:
+            break;
+        case b + c * d:
+            break;
+    }
+    ```
     */
     #[derive(Default)]
     NoDuplicateCases,
@@ -43,30 +56,25 @@ declare_lint! {
 impl CstRule for NoDuplicateCases {
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if let Some(switch) = node.try_to::<SwitchStmt>() {
-            let mut seen: Vec<SyntaxNode> = vec![];
+            let mut seen = HashSet::new();
             for case in switch.cases().filter_map(|case| case.into_case()) {
                 if let Some(expr) = case.test() {
-                    if let Some(old) = seen.iter().find(|clause| clause.lexical_eq(expr.syntax())) {
-                        let err = ctx
-                            .err(
-                                self.name(),
-                                format!("duplicate switch statement test `{}`", old.trimmed_text()),
-                            )
-                            .secondary(
-                                old,
-                                format!("`{}` is first tested for here", old.trimmed_text()),
-                            )
-                            .primary(
-                                expr.syntax(),
-                                format!(
-                                    "`{}` is then tested for again here",
-                                    expr.syntax().trimmed_text()
-                                ),
-                            );
-
-                        ctx.add_err(err)
+                    if let Some(ExprKey(old)) = seen.get(&ExprKey(expr.syntax().clone())) {
+                        ctx.diagnostic(
+                            expr.syntax(),
+                            format!("duplicate switch statement test `{}`", old.trimmed_text()),
+                        )
+                        .primary_label(format!(
+                            "`{}` is then tested for again here",
+                            expr.syntax().trimmed_text()
+                        ))
+                        .secondary(
+                            old,
+                            format!("`{}` is first tested for here", old.trimmed_text()),
+                        )
+                        .emit();
                     } else {
-                        seen.push(expr.syntax().clone());
+                        seen.insert(ExprKey(expr.syntax().clone()));
                     }
                 }
             }
@@ -98,7 +106,14 @@ rule_tests! {
             case 5:
             break;
         }
-        "
+        ",
+        "switch (a) { case (b): break; case b: break; }",
+        "switch (a) { case 'b': break; case \"b\": break; }",
+        "switch (a) { case b /* c */ + d: break; case b + (d): break; }"
     },
-    ok: {}
+    ok: {
+        "switch (a) { case b: break; case c: break; }",
+        "switch (a) { case (b + c) * d: break; case b + c * d: break; }",
+        "switch (a) { case 'b': break; case 'c': break; }"
+    }
 }
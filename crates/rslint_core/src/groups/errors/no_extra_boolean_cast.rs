@@ -9,6 +9,9 @@ declare_lint! {
     In contexts where expression will be coerced to a `Boolean` (e.g. `if`),
     casting to a boolean (using `!!` or `Boolean(expr)`) is unnecessary.
 
+    The cast is automatically removed unless it contains comments, `Boolean(expr)` is replaced with `expr`
+    wrapped in parentheses if it is needed.
+
     ## Invalid Code Examples
 
     ```js
@@ -41,6 +44,10 @@ enum Reason {
 
 #[typetag::serde]
 impl CstRule for NoExtraBooleanCast {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         match node.kind() {
             UNARY_EXPR => {
@@ -50,16 +57,27 @@ impl CstRule for NoExtraBooleanCast {
                     .try_to::<Expr>()?;
 
                 if expr.op()? != op![!]
-                    || !matches!(child, Expr::UnaryExpr(expr) if expr.op()? == op![!])
+                    || !matches!(&child, Expr::UnaryExpr(expr) if expr.op()? == op![!])
                 {
                     return None;
                 }
 
                 if let Some(reason) = in_bool_ctx(node, self.enforce_for_logical_operands) {
-                    let err = ctx.err(self.name(), "redundant double negation").primary(
+                    let mut err = ctx.err(self.name(), "redundant double negation").primary(
                         expr.op_token().unwrap().text_range(),
                         "this operator is redundant...",
                     );
+                    let operand = child.syntax().to::<UnaryExpr>().expr();
+                    if let Some(operand) = operand.filter(|_| !has_comments(node)) {
+                        let text = operand.syntax().trimmed_text().to_string();
+                        err = err.suggestion(
+                            node.trimmed_range(),
+                            "remove the double negation",
+                            text.clone(),
+                            Applicability::Always,
+                        );
+                        ctx.fix().replace(node.trimmed_range(), text);
+                    }
                     ctx.add_err(reason_labels(err, reason));
                 }
             }
@@ -69,10 +87,20 @@ impl CstRule for NoExtraBooleanCast {
                 }
 
                 if let Some(reason) = in_bool_ctx(node, self.enforce_for_logical_operands) {
-                    let err = ctx.err(self.name(), "redundant `Boolean` call").primary(
+                    let mut err = ctx.err(self.name(), "redundant `Boolean` call").primary(
                         node.trimmed_range(),
                         "this call to `Boolean` is redundant...",
                     );
+                    if let Some(text) = call_replacement(node) {
+                        // `Boolean` may be shadowed
+                        err = err.suggestion(
+                            node.trimmed_range(),
+                            "remove the call to `Boolean`",
+                            text.clone(),
+                            Applicability::MaybeIncorrect,
+                        );
+                        ctx.fix().replace(node.trimmed_range(), text);
+                    }
                     ctx.add_err(reason_labels(err, reason));
                 }
             }
@@ -82,6 +110,57 @@ impl CstRule for NoExtraBooleanCast {
     }
 }
 
+/// Expressions which do not need to be wrapped in parentheses to be used as the operand of `!` or as the
+/// test of a conditional expression.
+const TIGHT_KINDS: [SyntaxKind; 13] = [
+    NAME_REF,
+    LITERAL,
+    THIS_EXPR,
+    GROUPING_EXPR,
+    CALL_EXPR,
+    NEW_EXPR,
+    DOT_EXPR,
+    BRACKET_EXPR,
+    UNARY_EXPR,
+    ARRAY_EXPR,
+    OBJECT_EXPR,
+    TEMPLATE,
+    SUPER_CALL,
+];
+
+/// What a redundant `Boolean` call is replaced with, `None` if it has comments, more than one argument,
+/// or a spread argument.
+fn call_replacement(node: &SyntaxNode) -> Option<std::string::String> {
+    if has_comments(node) {
+        return None;
+    }
+    let mut args = node.child_with_kind(ARG_LIST)?.children();
+    let arg = match (args.next(), args.next()) {
+        (None, _) => return Some("false".to_string()),
+        (Some(arg), None) if arg.kind() != SPREAD_ELEMENT => arg,
+        _ => return None,
+    };
+
+    let text = arg.trimmed_text().to_string();
+    let enclosed = matches!(
+        node.parent().map(|parent| parent.kind()),
+        Some(CONDITION) | Some(FOR_STMT_TEST) | Some(ARG_LIST) | Some(GROUPING_EXPR)
+    );
+    if enclosed || TIGHT_KINDS.contains(&arg.kind()) {
+        Some(text)
+    } else {
+        Some(format!("({})", text))
+    }
+}
+
+/// Whether there are comments in a node, leading comments are not included.
+fn has_comments(node: &SyntaxNode) -> bool {
+    let range = node.trimmed_range();
+    node.descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .any(|token| token.kind() == COMMENT && range.contains_range(token.text_range()))
+}
+
 fn reason_labels(builder: Diagnostic, reason: Reason) -> Diagnostic {
     match reason {
         Reason::ExplicitBoolean(node) => builder.secondary(
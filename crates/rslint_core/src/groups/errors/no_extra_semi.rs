@@ -1,4 +1,6 @@
 use crate::rule_prelude::*;
+use util::asi::{continues_statement, is_terminator};
+use util::tokens::{next_non_trivia, prev_non_trivia};
 use SyntaxKind::*;
 
 declare_lint! {
@@ -7,6 +9,7 @@ declare_lint! {
 
     Unneeded semicolons are often caused by typing mistakes, while this is not an error, it
     can cause confusion when reading the code. This rule disallows empty statements (extra semicolons).
+    Semicolons which keep the next statement from continuing the statement before it are not reported.
 
     ## Invalid Code Examples
 
@@ -41,19 +44,37 @@ const ALLOWED: [SyntaxKind; 8] = [
 
 #[typetag::serde]
 impl CstRule for NoExtraSemi {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if node.kind() == SyntaxKind::EMPTY_STMT
             && node
                 .parent()
                 .map_or(true, |parent| !ALLOWED.contains(&parent.kind()))
         {
-            ctx.fix().delete(node);
-            let err = ctx
-                .err(self.name(), "unnecessary semicolon")
-                .primary(node, "")
-                .suggestion(node, "delete the semicolon", "", Applicability::Always);
+            // the semicolon is needed if it is the only thing ending the statement before it and the next
+            // statement starts with a token like `(` which would continue that statement without it
+            if is_terminator(node) {
+                return None;
+            }
+            let semicolon = node.first_token()?;
+            let ends_statement = prev_non_trivia(&semicolon)
+                .filter(|prev| !matches!(prev.kind(), SEMICOLON | L_CURLY | R_CURLY))
+                .is_some();
+            if ends_statement
+                && next_non_trivia(&semicolon)
+                    .filter(continues_statement)
+                    .is_some()
+            {
+                return None;
+            }
 
-            ctx.add_err(err);
+            ctx.fix().delete(node);
+            ctx.diagnostic(node, "unnecessary semicolon")
+                .suggestion(node, "delete the semicolon", "", Applicability::Always)
+                .emit();
         }
         None
     }
@@ -81,6 +102,8 @@ rule_tests! {
   ok: {
     "
       class Foo {}
-      "
+      ",
+    "export { foo };",
+    "export { foo } from 'foo';"
   }
 }
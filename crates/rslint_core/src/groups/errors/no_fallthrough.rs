@@ -0,0 +1,250 @@
+use crate::rule_prelude::*;
+use crate::util::cfg::{case_stmts, ControlFlowGraph, EdgeKind};
+use ast::SwitchStmt;
+use regex::{Regex, RegexBuilder};
+use rslint_parser::TextRange;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow cases of switch statements which fall through to the next case.
+
+    When the statements of a case do not end in a `break`, `return`, `throw`, or `continue`, execution
+    continues with the statements of the next case. This is easy to do by accident by forgetting a `break`.
+    Cases without any statements are allowed since they are an intentional way of grouping cases.
+
+    Intentional fallthrough can be marked with a comment matching `commentPattern` at the end of the case,
+    by default any comment containing `falls through`, `fall through`, or `fallthrough`.
+
+    ## Invalid Code Examples
+
+    ```js
+    switch (foo) {
+        case 1:
+            doSomething();
+        case 2:
+            doSomethingElse();
+    }
+    ```
+
+    ```js
+    switch (foo) {
+        case 1:
+            if (bar) {
+                break;
+            }
+        default:
+            doSomethingElse();
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    switch (foo) {
+        case 1:
+        case 2:
+            doSomething();
+            break;
+        case 3:
+            doSomethingElse();
+            // falls through
+        default:
+            doSomethingElse();
+    }
+    ```
+    */
+    #[serde(default)]
+    NoFallthrough,
+    errors,
+    "no-fallthrough",
+    /// A case insensitive regular expression which comments marking intentional fallthrough must match,
+    /// `falls?\s?through` by default.
+    pub comment_pattern: String
+}
+
+impl Default for NoFallthrough {
+    fn default() -> Self {
+        Self {
+            comment_pattern: "falls?\\s?through".to_string(),
+        }
+    }
+}
+
+#[typetag::serde]
+impl CstRule for NoFallthrough {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let switch = node.try_to::<SwitchStmt>()?;
+        let pattern = RegexBuilder::new(&self.comment_pattern)
+            .case_insensitive(true)
+            .build();
+        let pattern = match pattern {
+            Ok(pattern) => pattern,
+            Err(err) => {
+                let err = ctx
+                    .err(self.name(), "invalid `commentPattern` config")
+                    .primary(switch.switch_token()?, "")
+                    .footer_note(err.to_string());
+                ctx.add_err(err);
+                return None;
+            }
+        };
+
+        let graph = ControlFlowGraph::new(node);
+        for (from, edge) in graph.edges() {
+            if edge.kind != EdgeKind::Fallthrough || !graph.is_reachable(from) {
+                continue;
+            }
+            let next = graph.block(edge.to).nodes.first()?;
+            let case = next.prev_sibling()?;
+            let last = match case_stmts(&case).last() {
+                Some(last) => last,
+                None => continue,
+            };
+            let gap = TextRange::new(last.trimmed_range().end(), next.trimmed_range().start());
+            if has_fallthrough_comment(node, gap, &pattern) {
+                continue;
+            }
+
+            let err = ctx
+                .err(self.name(), "this case falls through to the next case")
+                .secondary(
+                    case.first_token()?,
+                    "the statements of this case do not always break, return, throw, or continue...",
+                )
+                .primary(
+                    next.first_token()?,
+                    "...so execution continues in this case",
+                )
+                .footer_help(
+                    "add a `break` or a `// falls through` comment if the fallthrough is intentional",
+                );
+            ctx.add_err(err);
+        }
+        None
+    }
+}
+
+fn has_fallthrough_comment(switch: &SyntaxNode, range: TextRange, pattern: &Regex) -> bool {
+    switch
+        .descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .filter(|token| token.kind() == COMMENT && range.contains_range(token.text_range()))
+        .any(|token| pattern.is_match(token.text()))
+}
+
+rule_tests! {
+    NoFallthrough::default(),
+    err: {
+        "
+        switch (foo) {
+            case 1:
+                doSomething();
+            case 2:
+                doSomethingElse();
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                if (bar) {
+                    break;
+                }
+            default:
+                doSomethingElse();
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                try {
+                    return a;
+                } catch (e) {
+                    log(e);
+                }
+            case 2:
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                for (const a of b) {
+                    break;
+                }
+            case 2:
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                a();
+                // some comment
+            case 2:
+        }
+        "
+    },
+    ok: {
+        "
+        switch (foo) {
+            case 1:
+            case 2:
+                doSomething();
+                break;
+            default:
+                doSomethingElse();
+        }
+        ",
+        "
+        function foo(a) {
+            switch (a) {
+                case 1:
+                    return 1;
+                case 2:
+                    throw new Error();
+                case 3:
+                    if (bar) {
+                        return 2;
+                    } else {
+                        return 3;
+                    }
+                case 4: {
+                    return 4;
+                }
+                default:
+                    return 5;
+            }
+        }
+        ",
+        "
+        for (const a of b) {
+            switch (a) {
+                case 1:
+                    continue;
+                case 2:
+                    try {
+                        a();
+                    } finally {
+                        break;
+                    }
+                case 3:
+                    a();
+            }
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                a();
+                // falls through
+            case 2:
+                b();
+                /* Fall through */
+            case 3:
+                c();
+            // fallthrough
+            default:
+                d();
+        }
+        "
+    }
+}
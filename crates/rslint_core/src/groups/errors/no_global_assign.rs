@@ -0,0 +1,122 @@
+use crate::rule_prelude::*;
+use util::scope::{is_global_reference, reference_kind, ReferenceKind};
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow assignments to built-in globals.
+
+    Globals such as `Object`, `undefined`, or `process` are provided by the environment, assigning to them
+    either throws an error in strict mode, silently does nothing, or replaces the global for every script
+    which uses it. It is almost always a mistake, such as a missing declaration of a local variable with the
+    same name.
+
+    The read-only globals of the file are checked, which are the globals of ES2021 and Node.js by default and
+    the globals declared with `/* global name */` comments. Names can be allowed with the `exceptions` option or
+    declared writable with `/* global name: writable */`. Assignments to local variables which shadow a global
+    are not reported.
+
+    ## Invalid Code Examples
+
+    ```js
+    Object = null;
+    undefined = 1;
+    ```
+
+    ```js
+    function setup() {
+        Promise = require("bluebird");
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    let Promise = require("bluebird");
+    Promise = Promise.config({});
+    ```
+
+    ```js
+    a = 1;
+    window.Object = null;
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    NoGlobalAssign,
+    errors,
+    "no-global-assign",
+    /// Globals which may be assigned to, such as `["Promise"]`. The default is an empty list.
+    pub exceptions: Vec<String>
+}
+
+#[typetag::serde]
+impl CstRule for NoGlobalAssign {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if !matches!(node.kind(), NAME_REF | NAME) {
+            return None;
+        }
+        let name = node.text().to_string();
+        if !ctx.globals.is_readonly(&name) || self.exceptions.contains(&name) {
+            return None;
+        }
+        if reference_kind(node)? == ReferenceKind::Read || !is_global_reference(node, &name) {
+            return None;
+        }
+
+        ctx.diagnostic(
+            node,
+            format!("cannot assign to the read-only global `{}`", name),
+        )
+        .primary_label(format!("`{}` is a built-in global", name))
+        .footer_help("declare a local variable with this name, or use a different name")
+        .emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoGlobalAssign::default(),
+    err: {
+        "Object = null;",
+        "undefined = 1;",
+        "NaN++;",
+        "Math += 1;",
+        "[Map] = a;",
+        "({ Set } = a);",
+        "({ a: Symbol } = b);",
+        "for (Array in a) {}",
+        "function foo() { Promise = require('bluebird'); }",
+        "process = null;",
+        "function foo(Object) {} Object = null;",
+        "/* global jQuery */ jQuery = null;"
+    },
+    ok: {
+        "a = 1;",
+        "window.Object = null;",
+        "let Promise = a; Promise = b;",
+        "var undefined_ = 1;",
+        "var Map = a; Map = b;",
+        "function foo(Object) { Object = null; }",
+        "function Symbol() {} Symbol = null;",
+        "exports = {};",
+        "let a = Object;",
+        "({ Object } = a) => Object;",
+        "/* global Promise: writable */ Promise = require('bluebird');",
+        "/* global jQuery */ { let jQuery = a; jQuery = b; }"
+    }
+}
+
+rule_tests! {
+    exceptions_valid,
+    exceptions_invalid,
+    NoGlobalAssign {
+        exceptions: vec!["Promise".to_string()],
+    },
+    err: {
+        "Object = null;"
+    },
+    ok: {
+        "Promise = require('bluebird');"
+    }
+}
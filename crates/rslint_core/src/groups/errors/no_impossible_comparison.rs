@@ -0,0 +1,100 @@
+use crate::rule_prelude::*;
+use crate::util::domain::Domain;
+use ast::{BinExpr, BinOp, Expr};
+
+declare_lint! {
+    /**
+    Disallow comparisons which can never be true.
+
+    The values an expression can have are often known without running the code, `typeof` only results in a
+    handful of strings and the `length` of a string or array is never negative. Comparing against a value
+    outside of these is always false, which is usually a typo or a misunderstanding of the values involved.
+
+    ## Invalid Code Examples
+
+    ```js
+    if (typeof foo === "nul") {}
+    ```
+
+    ```js
+    if (items.length < 0) {}
+    ```
+
+    ```js
+    if (typeof 5 === "string") {}
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    if (typeof foo === "undefined") {}
+
+    if (items.length <= 0) {}
+    ```
+    */
+    #[derive(Default)]
+    NoImpossibleComparison,
+    errors,
+    "no-impossible-comparison"
+}
+
+#[typetag::serde]
+impl CstRule for NoImpossibleComparison {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let expr = node.try_to::<BinExpr>()?;
+        let (lhs, rhs) = (expr.lhs()?, expr.rhs()?);
+        let (left, right) = (Domain::of(lhs.clone()), Domain::of(rhs.clone()));
+        let possible = match expr.op()? {
+            BinOp::StrictEquality => left.can_equal(&right, true),
+            BinOp::Equality => left.can_equal(&right, false),
+            BinOp::LessThan => left.can_be_less(&right, false),
+            BinOp::LessThanOrEqual => left.can_be_less(&right, true),
+            BinOp::GreaterThan => right.can_be_less(&left, false),
+            BinOp::GreaterThanOrEqual => right.can_be_less(&left, true),
+            _ => return None,
+        };
+        if possible {
+            return None;
+        }
+
+        let mut err = ctx
+            .err(self.name(), "this comparison can never be true")
+            .primary(expr.op_token()?, "");
+        for (operand, domain) in [(lhs, left), (rhs, right)].iter() {
+            // the values of literals are obvious
+            if !matches!(operand, Expr::Literal(_)) {
+                err = err.secondary(operand.syntax(), format!("this is always {}", domain));
+            }
+        }
+        ctx.add_err(err);
+        None
+    }
+}
+
+rule_tests! {
+    NoImpossibleComparison::default(),
+    err: {
+        "typeof foo === \"nul\"",
+        "typeof foo == \"strnig\"",
+        "foo.length < 0",
+        "0 > foo.length",
+        "foo.length === -1",
+        "typeof 5 === \"string\"",
+        "typeof foo === undefined",
+        "void foo === null",
+        "(a ? 1 : 2) === 3"
+    },
+    ok: {
+        "typeof foo === \"undefined\"",
+        "typeof foo !== \"nul\"",
+        "foo.length <= 0",
+        "foo.length < 1",
+        "foo == null",
+        "foo === 5",
+        "typeof foo == bar",
+        "typeof foo === \"function\"",
+        "(a ? 1 : 2) === 2",
+        "-foo.length < 0",
+        "foo + 1 < 0"
+    }
+}
@@ -163,6 +163,10 @@ fn spanned_byte_matches(bytes: &[u8]) -> Vec<usize> {
 
 #[typetag::serde]
 impl CstRule for NoIrregularWhitespace {
+    fn tolerates_parse_errors(&self) -> bool {
+        true
+    }
+
     fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         let string = root.text().to_string();
         let bytes = string.as_bytes();
@@ -30,14 +30,20 @@ declare_lint! {
 
 #[typetag::serde]
 impl CstRule for NoNewSymbol {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if node.kind() == NEW_EXPR {
             let new_expr = node.to::<NewExpr>();
 
-            if new_expr.object()?.syntax().text() == "Symbol" {
+            let callee = new_expr.object()?;
+            if callee.syntax().text() == "Symbol" {
                 let err = ctx
                     .err(self.name(), "`Symbol` cannot be called as a constructor.")
                     .primary(node, "")
+                    .capture("callee", callee.syntax().trimmed_range())
                     .suggestion(
                         node,
                         "help: call it as a function instead",
@@ -0,0 +1,135 @@
+use crate::rule_prelude::*;
+use ast::{Name, VarDecl};
+use std::collections::HashMap;
+use util::scope::*;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow declaring the same variable more than once in a scope.
+
+    `var` and function declarations can be repeated, the later declarations silently reuse the variable of the
+    first one, which is usually a mistake such as a copy pasted declaration. Declaring a name which is already
+    declared with `let`, `const`, or `class` is a syntax error at runtime.
+
+    ## Invalid Code Examples
+
+    ```js
+    var a = 3;
+    var a = 10;
+    ```
+
+    ```js
+    function foo(bar) {
+        var bar = 5;
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    var a = 3;
+    a = 10;
+    ```
+
+    ```js
+    let a = 1;
+    {
+        let a = 2;
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoRedeclare,
+    errors,
+    "no-redeclare"
+}
+
+#[typetag::serde]
+impl CstRule for NoRedeclare {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        // the declarations of a function body are checked with the declarations of its function
+        let is_fn_body =
+            node.kind() == BLOCK_STMT && node.parent().is_some_and(|p| is_function_scope(&p));
+        if is_block_scope(node) && !is_fn_body {
+            self.check_scope(node, ctx);
+        }
+        None
+    }
+
+    fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        self.check_scope(root, ctx);
+        None
+    }
+}
+
+impl NoRedeclare {
+    fn check_scope(&self, scope: &SyntaxNode, ctx: &mut RuleCtx) {
+        let mut declared: HashMap<std::string::String, Name> = HashMap::new();
+        for name in declarations(scope) {
+            let first = match declared.get(&name.text()) {
+                Some(first) => first,
+                None => {
+                    declared.insert(name.text(), name);
+                    continue;
+                }
+            };
+            let lexical = is_lexical(first) || is_lexical(&name);
+            let mut err = ctx
+                .diagnostic(
+                    name.range(),
+                    format!("`{}` is already declared in this scope", name.text()),
+                )
+                .primary_label("redeclared here")
+                .secondary(first.range(), "first declared here");
+            if lexical {
+                err = err.footer_note("redeclaring a `let`, `const`, or `class` is a syntax error");
+            }
+            err.emit();
+        }
+    }
+}
+
+/// Whether a declared name is declared with `let`, `const`, or `class`.
+fn is_lexical(name: &Name) -> bool {
+    let decl = name.syntax().ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            VAR_DECL | CLASS_DECL | FN_DECL | PARAMETER_LIST
+        )
+    });
+    match decl {
+        Some(decl) if decl.kind() == VAR_DECL => !decl.to::<VarDecl>().is_var(),
+        Some(decl) => decl.kind() == CLASS_DECL,
+        None => false,
+    }
+}
+
+rule_tests! {
+    NoRedeclare::default(),
+    err: {
+        "var a = 3; var a = 10;",
+        "var a; var a; var a;",
+        "var { a, b: [a] } = c;",
+        "function foo() {} function foo() {}",
+        "function foo() {} var foo;",
+        "function foo(bar) { var bar = 5; }",
+        "function foo(bar, bar) {}",
+        "let a = 1; let a = 2;",
+        "class A {} var A;",
+        "if (b) { var a; } var a;",
+        "{ const a = 1; let a = 2; }",
+        "switch (a) { case 1: let b; break; case 2: let b; }",
+        "for (let i = 0, i = 1;;) {}",
+        "function foo() { let a; let a; }"
+    },
+    ok: {
+        "var a = 3; a = 10;",
+        "let a = 1; { let a = 2; }",
+        "var a; function foo() { var a; }",
+        "function foo(bar) { function baz(bar) {} }",
+        "try {} catch (e) { var e; }",
+        "let a; for (let a of b) {}",
+        "var a = function a() {};"
+    }
+}
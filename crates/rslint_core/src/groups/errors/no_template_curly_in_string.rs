@@ -0,0 +1,114 @@
+use crate::rule_prelude::*;
+use ast::Literal;
+
+declare_lint! {
+    /**
+    Disallow template literal placeholders in regular strings.
+
+    `${expr}` is only replaced with the value of `expr` inside of a template literal, in a string with single or
+    double quotes it is kept as it is. Such a string is almost always a template literal which was written
+    with the wrong quotes.
+
+    ## Invalid Code Examples
+
+    ```js
+    const greeting = "Hello, ${name}!";
+    ```
+
+    ```js
+    console.log('took ${end - start}ms');
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const greeting = `Hello, ${name}!`;
+    ```
+
+    ```js
+    const placeholder = "Hello, $name!";
+    ```
+    */
+    #[derive(Default)]
+    NoTemplateCurlyInString,
+    errors,
+    "no-template-curly-in-string"
+}
+
+#[typetag::serde]
+impl CstRule for NoTemplateCurlyInString {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let literal = node.try_to::<Literal>()?;
+        let text = literal.inner_string_text()?.to_string();
+        let placeholder = find_placeholder(&text)?;
+        // the text of the string starts after the opening quote
+        let start = usize::from(literal.token().text_range().start()) + 1;
+
+        ctx.diagnostic(node, "unexpected template literal placeholder in a string")
+            .secondary(
+                start + placeholder.start..start + placeholder.end,
+                "this is not replaced because the string is not a template literal",
+            )
+            .suggestion(
+                node,
+                "did you mean to use a template literal?",
+                to_template(&text),
+                Applicability::MaybeIncorrect,
+            )
+            .emit();
+        None
+    }
+}
+
+/// The range of the first `${...}` placeholder with at least one character inside of it.
+fn find_placeholder(text: &str) -> Option<std::ops::Range<usize>> {
+    let mut from = 0;
+    while let Some(idx) = text[from..].find("${") {
+        let start = from + idx;
+        match text[start + 2..].find('}') {
+            Some(0) => from = start + 2,
+            Some(len) => return Some(start..start + len + 3),
+            None => return None,
+        }
+    }
+    None
+}
+
+/// Turn the text of a string into a template literal, escaping backticks which would end it.
+fn to_template(text: &str) -> String {
+    let mut template = String::from("`");
+    let mut escaped = false;
+    for c in text.chars() {
+        if c == '`' && !escaped {
+            template.push('\\');
+        }
+        escaped = c == '\\' && !escaped;
+        template.push(c);
+    }
+    template.push('`');
+    template
+}
+
+rule_tests! {
+    NoTemplateCurlyInString::default(),
+    err: {
+        "'Hello, ${name}'",
+        "\"Hello, ${name}\"",
+        "'${greeting}, ${name}'",
+        "'took ${end - start}ms'",
+        "'${}${a}'",
+        "foo('${a.b}')",
+        "'`${code}`'"
+    },
+    ok: {
+        "`Hello, ${name}`",
+        "'Hello, $name'",
+        "'${}'",
+        "'${'",
+        "'{name}'",
+        "'$ {name}'",
+        "'}${a'",
+        "foo(`${a}`, '$')",
+        "/${a}/"
+    }
+}
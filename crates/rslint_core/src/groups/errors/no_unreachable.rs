@@ -0,0 +1,240 @@
+use crate::rule_prelude::*;
+use crate::util::cfg::ControlFlowGraph;
+use ast::VarDecl;
+use rslint_parser::TextRange;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow code which can never be executed.
+
+    Statements after a `return`, `throw`, `break`, or `continue`, and after loops which never end, can never run.
+    Such code is usually left over from refactoring or a sign of a misplaced jump.
+
+    Function declarations and `var` declarations without an initializer are allowed after a jump since they are
+    hoisted to the top of their function, which makes them usable from reachable code.
+
+    ## Invalid Code Examples
+
+    ```js
+    function foo() {
+        return 5;
+        console.log("done");
+    }
+    ```
+
+    ```js
+    while (true) {
+        doSomething();
+    }
+    cleanUp();
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    function foo() {
+        return bar();
+
+        function bar() {
+            return 5;
+        }
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoUnreachable,
+    errors,
+    "no-unreachable"
+}
+
+#[typetag::serde]
+impl CstRule for NoUnreachable {
+    fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        self.check_body(root, ctx);
+        None
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if matches!(
+            node.kind(),
+            FN_DECL | FN_EXPR | ARROW_EXPR | METHOD | GETTER | SETTER
+        ) {
+            // arrow functions with an expression body have no statements
+            self.check_body(&node.child_with_kind(BLOCK_STMT)?, ctx);
+        }
+        None
+    }
+}
+
+impl NoUnreachable {
+    fn check_body(&self, body: &SyntaxNode, ctx: &mut RuleCtx) {
+        let unreachable = ControlFlowGraph::new(body)
+            .unreachable_nodes()
+            .into_iter()
+            .filter(|node| !is_hoisted(node))
+            .collect::<Vec<_>>();
+
+        // consecutive statements are reported together
+        let mut groups: Vec<Vec<SyntaxNode>> = vec![];
+        for node in unreachable {
+            match groups.last_mut() {
+                Some(group)
+                    if group.last().and_then(|last| last.next_sibling()) == Some(node.clone()) =>
+                {
+                    group.push(node)
+                }
+                _ => groups.push(vec![node]),
+            }
+        }
+
+        for group in groups {
+            let (first, last) = (&group[0], &group[group.len() - 1]);
+            let range = TextRange::new(first.trimmed_range().start(), last.trimmed_range().end());
+            let mut err = ctx
+                .err(self.name(), "unreachable code")
+                .primary(range, "this code can never be executed...");
+
+            if let Some(cause) = first.prev_sibling() {
+                let label = match cause.kind() {
+                    RETURN_STMT | THROW_STMT | BREAK_STMT | CONTINUE_STMT => {
+                        format!(
+                            "...because of this `{}`",
+                            cause.first_token().unwrap().text()
+                        )
+                    }
+                    WHILE_STMT | DO_WHILE_STMT | FOR_STMT => {
+                        "...because this loop never ends".to_string()
+                    }
+                    _ => String::new(),
+                };
+                if !label.is_empty() {
+                    err = err.secondary(cause, label);
+                }
+            }
+            ctx.add_err(err);
+        }
+    }
+}
+
+/// Whether a statement is hoisted to the top of its function, which makes it usable even if it is unreachable.
+fn is_hoisted(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        FN_DECL => true,
+        VAR_DECL => {
+            let decl = node.to::<VarDecl>();
+            decl.is_var()
+                && decl
+                    .declared()
+                    .all(|declarator| declarator.value().is_none())
+        }
+        _ => false,
+    }
+}
+
+rule_tests! {
+    NoUnreachable::default(),
+    err: {
+        "
+        function foo() {
+            return 5;
+            a();
+        }
+        ",
+        "
+        function foo() {
+            throw new Error();
+            var a = 5;
+        }
+        ",
+        "
+        while (true) {
+            a();
+        }
+        b();
+        ",
+        "
+        for (const a of b) {
+            continue;
+            c();
+        }
+        ",
+        "
+        const foo = () => {
+            if (a) {
+                return 1;
+            } else {
+                return 2;
+            }
+            b();
+        }
+        ",
+        "
+        switch (foo) {
+            case 1:
+                break;
+                a();
+        }
+        ",
+        "
+        function foo() {
+            try {
+                return 1;
+            } finally {
+                a();
+            }
+            b();
+        }
+        "
+    },
+    ok: {
+        "
+        function foo() {
+            return bar();
+
+            function bar() {
+                return 5;
+            }
+        }
+        ",
+        "
+        function foo() {
+            return;
+            var a;
+        }
+        ",
+        "
+        while (true) {
+            if (a) {
+                break;
+            }
+        }
+        b();
+        ",
+        "
+        function foo() {
+            try {
+                throw new Error();
+            } catch (e) {
+                a();
+            }
+            b();
+        }
+        ",
+        "
+        outer: for (;;) {
+            for (;;) {
+                break outer;
+            }
+        }
+        a();
+        ",
+        "
+        function foo() {
+            return () => {
+                a();
+            };
+        }
+        "
+    }
+}
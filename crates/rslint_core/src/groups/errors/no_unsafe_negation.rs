@@ -34,6 +34,10 @@ declare_lint! {
 
 #[typetag::serde]
 impl CstRule for NoUnsafeNegation {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         if node.kind() == BIN_EXPR
             && matches!(node.to::<BinExpr>().op()?, BinOp::Instanceof | BinOp::In)
@@ -64,6 +68,8 @@ impl CstRule for NoUnsafeNegation {
                             ),
                         )
                         .secondary(rest_range, "`!` is not negating this expression")
+                        .capture("negation", unary.syntax().trimmed_range())
+                        .capture("argument", unary_node.trimmed_range())
                         .suggestion_with_labels(
                             expr.range(),
                             "wrap the instanceof check in parentheses",
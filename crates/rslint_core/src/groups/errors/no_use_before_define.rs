@@ -0,0 +1,221 @@
+use crate::rule_prelude::*;
+use ast::{ClassDecl, FnDecl, Name, VarDecl};
+use rslint_parser::TextSize;
+use util::scope::*;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow using variables, functions, and classes before they are defined.
+
+    Using a `let`, `const`, or `class` binding before its declaration throws a `ReferenceError` since the
+    binding is in its temporal dead zone. `var` and function declarations are hoisted so using them early
+    does not throw, however a `var` is still `undefined` until its declaration runs, which makes such code
+    confusing to read.
+
+    References inside of functions which are declared before the binding are reported as well, since whether
+    they are safe depends on when the function is called. They can be allowed with the `functions`, `classes`,
+    and `variables` options.
+
+    ## Invalid Code Examples
+
+    ```js
+    console.log(a);
+    let a = 5;
+    ```
+
+    ```js
+    new Foo();
+    class Foo {}
+    ```
+
+    ```js
+    const a = a + 1;
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    let a = 5;
+    console.log(a);
+    ```
+
+    ```js
+    const bar = 5;
+    function foo() {
+        return bar;
+    }
+    ```
+    */
+    #[serde(default)]
+    NoUseBeforeDefine,
+    errors,
+    "no-use-before-define",
+    /// Whether to check references to function declarations, `true` by default. Function declarations are
+    /// hoisted so using them before their declaration is always safe.
+    pub functions: bool,
+    /// Whether to check references to classes from inside of functions, `true` by default. References in the
+    /// same function as the class are always checked.
+    pub classes: bool,
+    /// Whether to check references to variables from inside of functions, `true` by default. References in the
+    /// same function as the variable are always checked.
+    pub variables: bool
+}
+
+impl Default for NoUseBeforeDefine {
+    fn default() -> Self {
+        Self {
+            functions: true,
+            classes: true,
+            variables: true,
+        }
+    }
+}
+
+#[typetag::serde]
+impl CstRule for NoUseBeforeDefine {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        match node.kind() {
+            VAR_DECL => {
+                let decl = node.to::<VarDecl>();
+                let scope = if decl.is_var() {
+                    enclosing_function_scope(node)
+                } else {
+                    enclosing_block_scope(node)
+                };
+                for name in var_decl_bindings(&decl) {
+                    // repeated `var` declarations are only checked once
+                    if decl.is_var()
+                        && var_declarations(&scope, &name.text())
+                            .first()
+                            .map(|first| first.range())
+                            != Some(name.range())
+                    {
+                        continue;
+                    }
+                    self.check(
+                        &scope,
+                        &name,
+                        "variable",
+                        !decl.is_var(),
+                        self.variables,
+                        ctx,
+                    );
+                }
+            }
+            FN_DECL if self.functions => {
+                let name = node.to::<FnDecl>().name()?;
+                self.check(
+                    &enclosing_block_scope(node),
+                    &name,
+                    "function",
+                    false,
+                    true,
+                    ctx,
+                );
+            }
+            CLASS_DECL => {
+                let name = node.to::<ClassDecl>().name()?;
+                self.check(
+                    &enclosing_block_scope(node),
+                    &name,
+                    "class",
+                    false,
+                    self.classes,
+                    ctx,
+                );
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+impl NoUseBeforeDefine {
+    /// Report the references to `name` before it is defined. If `in_initializer` is set, references in the
+    /// rest of the declaration of the name are reported too, and if `nested` is not set, references inside of
+    /// functions other than the function of the declaration are allowed.
+    fn check(
+        &self,
+        scope: &SyntaxNode,
+        name: &Name,
+        kind: &str,
+        in_initializer: bool,
+        nested: bool,
+        ctx: &mut RuleCtx,
+    ) {
+        let text = name.text();
+        let defined = if in_initializer {
+            declaration_end(name.syntax())
+        } else {
+            name.range().start()
+        };
+        let decl_fn = enclosing_function_scope(name.syntax());
+        for reference in references(scope, &text) {
+            let range = reference.range();
+            if range.start() >= defined || range == name.range() {
+                continue;
+            }
+            if !nested && enclosing_function_scope(&reference.node) != decl_fn {
+                continue;
+            }
+            ctx.diagnostic(range, format!("`{}` was used before it was defined", text))
+                .secondary(name.range(), format!("the {} is defined here", kind))
+                .emit();
+        }
+    }
+}
+
+/// The end of the declarator a name is declared in, the name is not initialized until then.
+fn declaration_end(name: &SyntaxNode) -> TextSize {
+    name.ancestors()
+        .find(|node| node.kind() == DECLARATOR)
+        .map(|declarator| declarator.text_range().end())
+        .unwrap_or_else(|| name.text_range().start())
+}
+
+rule_tests! {
+    NoUseBeforeDefine::default(),
+    err: {
+        "console.log(a); let a = 5;",
+        "console.log(a); var a = 5;",
+        "a = 1; var a;",
+        "typeof a; const a = 1;",
+        "const a = a + 1;",
+        "let [a, b = a] = c; let { d = d } = e;",
+        "foo(); function foo() {}",
+        "new Foo(); class Foo {}",
+        "function foo() { return bar; } const bar = 5;",
+        "if (a) { b; let b; }",
+        "let a; { a; let a = 5; }",
+        "for (let i = i; i < 5; i++) {}"
+    },
+    ok: {
+        "let a = 5; console.log(a);",
+        "function foo() {} foo();",
+        "class Foo {} new Foo();",
+        "var a = 1; var a = a + 1;",
+        "var a = function a() { return a; };",
+        "let a; { let a = 5; a; } a;",
+        "b; { let b; }",
+        "function foo(a) { return a; } let a;",
+        "class Foo { bar() { return Foo; } }",
+        "A: for (;;) { break A; } let A;"
+    }
+}
+
+rule_tests! {
+    options_valid,
+    options_invalid,
+    NoUseBeforeDefine { functions: false, classes: false, variables: false },
+    err: {
+        "a; let a;",
+        "new Foo(); class Foo {}",
+        "function foo() { a; let a; }"
+    },
+    ok: {
+        "foo(); function foo() {}",
+        "function foo() { return new Bar(); } class Bar {}",
+        "function foo() { return bar; } const bar = 5;"
+    }
+}
@@ -1,5 +1,7 @@
 use crate::rule_prelude::*;
-use ast::{BinExpr, Expr, Literal, UnaryExpr};
+use crate::util::const_eval::{eval, ConstValue};
+use ast::{BinExpr, Expr, UnaryExpr};
+use rslint_parser::TextRange;
 
 declare_lint! {
     /**
@@ -11,12 +13,18 @@ declare_lint! {
     will always return `false`.
     This behaviour will be denied by this rule.
 
+    Comparison values which only differ from a valid type by their casing or surrounding
+    whitespace (e.g. `"String"` or `" number"`) are automatically fixed if they are a string literal.
+    Other typos, and values of constant expressions such as `"str" + "nig"`, get a suggestion
+    for the closest valid type.
+
     ## Invalid Code Examples
     ```js
     typeof foo === "strnig"
     typeof foo == "undefimed"
     typeof bar != "nunber"
     typeof bar !== "fucntion"
+    typeof baz === "Object"
     ```
     */
     #[derive(Default)]
@@ -61,6 +69,10 @@ impl ValidTypeof {
 
 #[typetag::serde]
 impl CstRule for ValidTypeof {
+    fn fixable(&self) -> bool {
+        true
+    }
+
     fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         let expr = node.try_to::<BinExpr>()?;
         if !expr.comparison() {
@@ -76,53 +88,82 @@ impl CstRule for ValidTypeof {
             return None;
         };
 
-        let str_literal = cmp_value
-            .syntax()
-            .try_to::<Literal>()
-            .and_then(|lit| Some((lit.inner_string_text()?, lit.range())));
-
-        let (literal, literal_range) = if self.require_string_literals {
-            if let Some(lit) = str_literal {
-                lit
-            } else if is_typeof_expr(&cmp_value) {
-                return None;
-            } else {
-                let repr = cmp_value.text();
-                let err = self
-                    .error_from_value(repr, ctx)
-                    .primary(cmp_value.range(), "");
-
-                ctx.add_err(err);
+        // only string literals are fixed, the quote of other values is unknown
+        let (value, range, quote) = match string_value(&cmp_value) {
+            Some((value, range, quote)) => (value, range, Some(quote)),
+            None if self.require_string_literals => {
+                if !is_typeof_expr(&cmp_value) {
+                    let err = self
+                        .error_from_value(cmp_value.text(), ctx)
+                        .primary(cmp_value.range(), "");
+                    ctx.add_err(err);
+                }
                 return None;
             }
-        } else {
-            str_literal?
+            None => match eval(cmp_value.clone())? {
+                ConstValue::String(value) => (value, cmp_value.range(), None),
+                _ => return None,
+            },
         };
 
-        if !VALID_TYPES.iter().any(|ty| *ty == literal) {
-            let literal = String::from(literal);
-            let suggestion =
-                util::find_best_match_for_name(VALID_TYPES.iter().copied(), &literal, None);
+        if VALID_TYPES.contains(&value.as_str()) {
+            return None;
+        }
 
-            let err = self
-                .error_from_value(literal, ctx)
-                .primary(literal_range, "");
+        let normalized = value.trim().to_ascii_lowercase();
+        let err = self.error_from_value(&value, ctx).primary(range, "");
+        let quoted = |value: &str| {
+            let quote = quote.unwrap_or('"');
+            format!("{}{}{}", quote, value, quote)
+        };
 
-            let err = if let Some(suggestion) = suggestion {
-                err.footer_help(format!(
-                    "a type with a similair name exists: `{}`",
-                    suggestion
-                ))
+        if VALID_TYPES.contains(&normalized.as_str()) {
+            let replacement = quoted(&normalized);
+            let applicability = if quote.is_some() {
+                ctx.fix().replace(range, replacement.clone());
+                Applicability::Always
             } else {
-                err
+                Applicability::MaybeIncorrect
             };
-
+            let err = err.suggestion_inline(
+                range,
+                "typeof always returns a lowercase type name, try using",
+                replacement,
+                applicability,
+            );
+            ctx.add_err(err);
+        } else if let Some(suggestion) =
+            util::find_best_match_for_name(VALID_TYPES.iter().copied(), &normalized, None)
+        {
+            let err = err.suggestion_inline(
+                range,
+                "a type with a similar name exists",
+                quoted(suggestion),
+                Applicability::MaybeIncorrect,
+            );
+            ctx.add_err(err);
+        } else {
             ctx.add_err(err);
         }
         None
     }
 }
 
+/// Get the value, range, and quote char of a string literal or a template without substitutions.
+fn string_value(expr: &Expr) -> Option<(String, TextRange, char)> {
+    match expr {
+        Expr::Literal(lit) => {
+            let quote = lit.token().text().chars().next()?;
+            Some((lit.inner_string_text()?.to_string(), lit.range(), quote))
+        }
+        Expr::Template(tpl) if tpl.tag().is_none() && tpl.elements().next().is_none() => {
+            let value = tpl.quasis().map(|t| t.text().to_string()).collect();
+            Some((value, tpl.template_range()?, '`'))
+        }
+        _ => None,
+    }
+}
+
 fn is_typeof_expr(expr: &Expr) -> bool {
     expr.syntax()
         .try_to::<UnaryExpr>()
@@ -136,13 +177,23 @@ rule_tests! {
         r#"typeof foo === "strnig""#,
         r#"typeof foo == "undefimed""#,
         r#"typeof bar != "nunber""#,
-        r#"typeof bar !== "fucntion""#
+        r#"typeof bar !== "fucntion""#,
+        r#"typeof foo === "String""#,
+        r#"typeof foo === " number""#,
+        "typeof foo === `strnig`",
+        r#""undefimed" === typeof foo"#,
+        r#"typeof foo === "str" + "nig""#,
+        r#"typeof foo === ("Num" + "ber")"#
     },
     ok: {
         r#"typeof foo === "string""#,
         r#"typeof bar == "undefined""#,
         "typeof foo === baz",
         "typeof foo === 4",
-        "typeof bar === typeof qux"
+        "typeof bar === typeof qux",
+        "typeof foo === `bigint`",
+        "typeof foo === `${bar}`",
+        r#"typeof foo === "str" + "ing""#,
+        r#"typeof foo === "num" + bar"#
     }
 }
@@ -1,8 +1,16 @@
 //! All of the groups of built in rules in the linter.
 
 pub mod errors;
+pub mod node;
+pub mod promises;
+pub mod regex;
+pub mod style;
 
 pub use errors::errors;
+pub use node::node;
+pub use promises::promises;
+pub use regex::regex;
+pub use style::style;
 
 /// Macro for easily making a rule group hashmap.
 /// This will call `::new()` on each rule.  
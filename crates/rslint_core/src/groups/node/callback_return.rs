@@ -0,0 +1,157 @@
+use crate::rule_prelude::*;
+use ast::CallExpr;
+use util::{expr_eq::strip_parens, scope::is_function_scope};
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Enforce returning after calling a callback.
+
+    Node.js style asynchronous functions report their result by calling a callback, the function usually has
+    nothing left to do afterwards. Forgetting to return after calling the callback, often in an error branch,
+    keeps the function running and commonly calls the callback a second time.
+
+    Calls to callbacks are allowed in a `return` statement, as the body of an arrow function, as the last
+    statement of a function, and right before a `return` statement. Callbacks are found by their name, which
+    makes this rule a heuristic.
+
+    ## Invalid Code Examples
+
+    ```js
+    function readConfig(path, callback) {
+        fs.readFile(path, (err, data) => {
+            if (err) {
+                callback(err);
+            }
+            callback(null, JSON.parse(data));
+        });
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    function readConfig(path, callback) {
+        fs.readFile(path, (err, data) => {
+            if (err) {
+                return callback(err);
+            }
+            callback(null, JSON.parse(data));
+        });
+    }
+    ```
+    */
+    #[serde(default)]
+    CallbackReturn,
+    node,
+    "callback-return",
+    /// The names of callbacks, which can include member accesses such as `this.callback`. The default is
+    /// `["callback", "cb", "next"]`.
+    pub callbacks: Vec<String>
+}
+
+impl Default for CallbackReturn {
+    fn default() -> Self {
+        Self {
+            callbacks: vec!["callback".to_string(), "cb".to_string(), "next".to_string()],
+        }
+    }
+}
+
+#[typetag::serde]
+impl CstRule for CallbackReturn {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let callee = node.try_to::<CallExpr>()?.callee()?;
+        if !matches!(callee.syntax().kind(), NAME_REF | DOT_EXPR) {
+            return None;
+        }
+        let name = callee
+            .syntax()
+            .text()
+            .to_string()
+            .split_whitespace()
+            .collect::<std::string::String>();
+        if !self.callbacks.contains(&name) {
+            return None;
+        }
+
+        let block = node
+            .ancestors()
+            .find(|ancestor| matches!(ancestor.kind(), BLOCK_STMT | RETURN_STMT | ARROW_EXPR))?;
+        if block.kind() != BLOCK_STMT {
+            return None;
+        }
+        let function_body = block.parent().filter(is_function_scope).is_some();
+        match block.children().collect::<Vec<_>>().as_slice() {
+            [.., last] if function_body && calls(last, node) => return None,
+            [.., second_last, last] if last.kind() == RETURN_STMT && calls(second_last, node) => {
+                return None
+            }
+            _ => {}
+        }
+        if !node
+            .ancestors()
+            .any(|ancestor| is_function_scope(&ancestor))
+        {
+            return None;
+        }
+
+        ctx.diagnostic(
+            node,
+            format!("expected a `return` with the call to `{}`", name),
+        )
+        .primary_label("the function keeps running after calling the callback")
+        .footer_help(format!(
+            "return the result of the callback with `return {}(...)`",
+            name
+        ))
+        .emit();
+        None
+    }
+}
+
+/// Whether `stmt` is an expression statement of only `call`.
+fn calls(stmt: &SyntaxNode, call: &SyntaxNode) -> bool {
+    stmt.kind() == EXPR_STMT
+        && stmt.first_child().map(|expr| strip_parens(&expr)).as_ref() == Some(call)
+}
+
+rule_tests! {
+    CallbackReturn::default(),
+    err: {
+        "function foo(err, callback) { if (err) { callback(err); } callback(); }",
+        "function foo(callback) { if (a) callback(); else cb(); bar(); }",
+        "function foo(callback) { callback(); bar(); }",
+        "var foo = function(next) { if (a) { next(new Error()); } next(); }",
+        "var foo = (err, cb) => { if (err) { cb(err); } else { bar(); } baz(); }",
+        "function foo(callback) { bar(callback()); }",
+        "class Foo { load(callback) { while (a) { callback(); } } }"
+    },
+    ok: {
+        "function foo(err, callback) { if (err) { return callback(err); } callback(); }",
+        "function foo(callback) { callback(); }",
+        "function foo(callback) { if (a) { callback(); return; } bar(); }",
+        "var foo = (err, cb) => cb(err);",
+        "function foo(callback) { bar(); callback(); }",
+        "function foo(callback) { setTimeout(() => callback(), 0); }",
+        "callback();",
+        "function foo(done) { if (a) { done(); } bar(); }",
+        "function foo(callback) { callback.call(this); bar(); }"
+    }
+}
+
+rule_tests! {
+    custom_callbacks_valid,
+    custom_callbacks_invalid,
+    CallbackReturn {
+        callbacks: vec!["done".to_string(), "this.callback".to_string()],
+    },
+    err: {
+        "function foo() { if (a) { done(); } bar(); }",
+        "function foo() { if (a) { this.callback(); } bar(); }"
+    },
+    ok: {
+        "function foo(callback) { if (a) { callback(); } bar(); }",
+        "function foo() { if (a) { return this.callback(); } bar(); }"
+    }
+}
@@ -0,0 +1,11 @@
+//! Rules for code which is run by Node.js, the CLI only runs these if the `node` environment is enabled.
+
+use crate::group;
+
+group! {
+    /// Rules for code which is run by Node.js, the CLI only runs these if the `node` environment is enabled.
+    node,
+    no_process_exit::NoProcessExit,
+    no_sync::NoSync,
+    callback_return::CallbackReturn,
+}
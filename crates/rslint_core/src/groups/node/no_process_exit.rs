@@ -0,0 +1,85 @@
+use crate::rule_prelude::*;
+use ast::{CallExpr, Expr};
+
+declare_lint! {
+    /**
+    Disallow `process.exit()`.
+
+    `process.exit()` ends the process immediately, even if there is still pending I/O such as writes to a file
+    or to stdout, and it cannot be caught by the caller. Throwing an error, or setting `process.exitCode` and
+    letting the process finish, lets the program clean up and lets callers handle the failure.
+
+    ## Invalid Code Examples
+
+    ```js
+    if (!config) {
+        console.error("no config found");
+        process.exit(1);
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    if (!config) {
+        throw new Error("no config found");
+    }
+    ```
+
+    ```js
+    if (!config) {
+        console.error("no config found");
+        process.exitCode = 1;
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoProcessExit,
+    node,
+    "no-process-exit"
+}
+
+#[typetag::serde]
+impl CstRule for NoProcessExit {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let call = node.try_to::<CallExpr>()?;
+        let dot = match call.callee()? {
+            Expr::DotExpr(dot) => dot,
+            _ => return None,
+        };
+        match dot.object()? {
+            Expr::NameRef(name) if name.text() == "process" => {}
+            _ => return None,
+        }
+        if dot.prop()?.text() != "exit" {
+            return None;
+        }
+
+        ctx.diagnostic(node, "unexpected use of `process.exit()`")
+            .primary_label("this ends the process immediately")
+            .footer_help(
+                "throw an error or set `process.exitCode` instead, so pending I/O can finish",
+            )
+            .emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoProcessExit::default(),
+    err: {
+        "process.exit(0);",
+        "process.exit(1);",
+        "process.exit();",
+        "function foo() { if (err) { process.exit(1) } }",
+        "var a = () => process.exit(2);"
+    },
+    ok: {
+        "process.exitCode = 1;",
+        "process.kill(pid);",
+        "exit(1);",
+        "foo.process.exit(1);",
+        "app.exit();",
+        "process.exit;"
+    }
+}
@@ -0,0 +1,100 @@
+use crate::rule_prelude::*;
+use ast::CallExpr;
+use util::{callee_name, scope::is_function_scope};
+
+declare_lint! {
+    /**
+    Disallow synchronous methods such as `fs.readFileSync`.
+
+    Node.js APIs ending with `Sync`, such as most of the `fs` module, block the event loop until they finish,
+    so a server cannot handle any other request in the meantime. Their asynchronous versions should be used
+    instead. Synchronous calls are usually fine in scripts and while a program starts up, `allowAtRootLevel`
+    allows them outside of functions.
+
+    ## Invalid Code Examples
+
+    ```js
+    app.get("/", (req, res) => {
+        res.send(fs.readFileSync("index.html"));
+    });
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    app.get("/", async (req, res) => {
+        res.send(await fs.promises.readFile("index.html"));
+    });
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    NoSync,
+    node,
+    "no-sync",
+    /// Allow synchronous calls which are not inside of a function, such as reading a config file once when
+    /// a module is loaded.
+    pub allow_at_root_level: bool
+}
+
+#[typetag::serde]
+impl CstRule for NoSync {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let call = node.try_to::<CallExpr>()?;
+        let name = callee_name(&call)?;
+        let async_name = name
+            .text()
+            .strip_suffix("Sync")
+            .filter(|name| !name.is_empty())?;
+        if self.allow_at_root_level && !node.ancestors().any(|node| is_function_scope(&node)) {
+            return None;
+        }
+
+        ctx.diagnostic(
+            node,
+            format!("unexpected synchronous method `{}`", name.text()),
+        )
+        .primary_label("this blocks the event loop until it finishes")
+        .footer_help(format!(
+            "use an asynchronous method instead, such as `{}`",
+            async_name
+        ))
+        .emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoSync::default(),
+    err: {
+        "fs.readFileSync('foo.txt');",
+        "var data = fs.readFileSync(path, 'utf8');",
+        "existsSync(path);",
+        "function foo() { fs.writeFileSync(path, data); }",
+        "child_process.execSync('ls');"
+    },
+    ok: {
+        "fs.readFile('foo.txt', cb);",
+        "fs.promises.readFile('foo.txt');",
+        "Sync();",
+        "fs.readFileSync;",
+        "syncFiles();"
+    }
+}
+
+rule_tests! {
+    root_level_valid,
+    root_level_invalid,
+    NoSync {
+        allow_at_root_level: true,
+    },
+    err: {
+        "function foo() { fs.readFileSync(path); }",
+        "var foo = () => fs.statSync(path);",
+        "class Foo { load() { fs.readFileSync(path); } }"
+    },
+    ok: {
+        "var config = fs.readFileSync('config.json');",
+        "if (fs.existsSync(path)) {}"
+    }
+}
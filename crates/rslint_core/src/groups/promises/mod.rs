@@ -0,0 +1,10 @@
+//! Rules which catch misuse of promises, such as promises which are never handled.
+
+use crate::group;
+
+group! {
+    /// Rules which catch misuse of promises, such as promises which are never handled.
+    promises,
+    no_floating_promise::NoFloatingPromise,
+    require_atomic_updates::RequireAtomicUpdates,
+}
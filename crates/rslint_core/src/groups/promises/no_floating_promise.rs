@@ -0,0 +1,151 @@
+use crate::rule_prelude::*;
+use ast::{CallExpr, ExprStmt};
+use util::{callee_name, expr_eq::strip_parens};
+
+declare_lint! {
+    /**
+    Disallow calls which return promises from being used as statements without handling the promise.
+
+    A promise which is neither awaited nor given a rejection handler silently swallows errors, and the code
+    after the call runs before the promise settles, which is rarely what was intended. Without type information
+    the linter cannot know which functions return promises, so this rule uses the name of the called function,
+    by default `fetch` and functions ending with `Async`.
+
+    A floating promise can be awaited, handled with `.then` or `.catch`, or explicitly ignored with `void`.
+
+    ## Invalid Code Examples
+
+    ```js
+    fetch("/api/items");
+    ```
+
+    ```js
+    async function save() {
+        db.writeAsync(item);
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    async function load() {
+        const res = await fetch("/api/items");
+    }
+    ```
+
+    ```js
+    fetch("/api/items").then(render).catch(report);
+
+    void analytics.sendAsync(event);
+    ```
+    */
+    #[serde(default)]
+    NoFloatingPromise,
+    promises,
+    "no-floating-promise",
+    /// The names of functions which return promises, `*` matches any number of characters so `*Async` matches
+    /// every name ending with `Async`. Method calls such as `db.writeAsync()` are matched by the method name.
+    /// The default is `["fetch", "*Async"]`.
+    pub async_patterns: Vec<String>
+}
+
+impl Default for NoFloatingPromise {
+    fn default() -> Self {
+        Self {
+            async_patterns: vec!["fetch".to_string(), "*Async".to_string()],
+        }
+    }
+}
+
+#[typetag::serde]
+impl CstRule for NoFloatingPromise {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let expr = node.try_to::<ExprStmt>()?.expr()?;
+        let call = strip_parens(expr.syntax()).try_to::<CallExpr>()?;
+        let name = callee_name(&call)?;
+        if !self
+            .async_patterns
+            .iter()
+            .any(|pattern| matches_pattern(pattern, name.text()))
+        {
+            return None;
+        }
+
+        ctx.diagnostic(
+            call.syntax(),
+            format!("the promise returned by `{}` is not handled", name.text()),
+        )
+        .primary_label("this promise is never awaited or handled")
+        .footer_help(
+            "await the promise, handle it with `.then` or `.catch`, or ignore it explicitly with `void`",
+        )
+        .emit();
+        None
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` in the pattern matches any number of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let mut rest = match name.strip_prefix(parts.next().unwrap_or_default()) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let (last, middle) = match parts.split_last() {
+        Some(split) => split,
+        None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+rule_tests! {
+    NoFloatingPromise::default(),
+    err: {
+        "fetch('/api')",
+        "fetch(url, { method: 'POST' });",
+        "readFileAsync('foo.txt');",
+        "db.writeAsync(item);",
+        "this.client.fetch(url);",
+        "async function foo() { saveAsync(); }",
+        "(fetch(url));",
+        "api?.loadAsync();"
+    },
+    ok: {
+        "async function foo() { await fetch(url); }",
+        "void fetch(url);",
+        "fetch(url).then(render);",
+        "fetch(url).catch(report);",
+        "const res = fetch(url);",
+        "function foo() { return loadAsync(); }",
+        "foo();",
+        "fetcher();",
+        "isAsync;",
+        "promise.then(() => saveAsync());"
+    }
+}
+
+rule_tests! {
+    custom_patterns_valid,
+    custom_patterns_invalid,
+    NoFloatingPromise {
+        async_patterns: vec!["query".to_string(), "load*Data".to_string()],
+    },
+    err: {
+        "db.query('SELECT 1');",
+        "loadUserData(id);",
+        "loadData();"
+    },
+    ok: {
+        "fetch(url);",
+        "readFileAsync('foo.txt');",
+        "loadUser(id);",
+        "queryAll();"
+    }
+}
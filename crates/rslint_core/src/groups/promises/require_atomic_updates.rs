@@ -0,0 +1,210 @@
+use crate::rule_prelude::*;
+use ast::AssignExpr;
+use util::{
+    expr_eq::strip_parens,
+    scope::{declares, enclosing_function_scope, is_block_scope, is_function_scope, references},
+};
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow assignments which can lose updates because of an `await` or `yield`.
+
+    An async function pauses at every `await`, and other code can run and change variables in the meantime.
+    An assignment which reads a variable, then waits, and then writes the variable overwrites any change made
+    while the function was paused:
+
+    ```js
+    let total = 0;
+
+    async function add(item) {
+        total += await price(item);
+    }
+
+    Promise.all(items.map(add));
+    ```
+
+    Every call reads `total` as `0` before waiting for the price, so only the last price is kept. The same
+    applies to `yield` in generators.
+
+    Only variables which other code can change are reported, these are variables declared outside of the
+    function and variables which are used by a nested function. Variables which are local to the function
+    cannot change while it is paused.
+
+    ## Invalid Code Examples
+
+    ```js
+    let count = 0;
+
+    async function increment() {
+        count = Math.max(count, await getStep());
+    }
+    ```
+
+    ```js
+    async function foo() {
+        let result = "";
+        const reset = () => { result = ""; };
+        result += await read();
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    let count = 0;
+
+    async function increment() {
+        const step = await getStep();
+        count = Math.max(count, step);
+    }
+    ```
+
+    ```js
+    async function foo() {
+        let result = "";
+        result += await read();
+        return result;
+    }
+    ```
+    */
+    #[derive(Default)]
+    RequireAtomicUpdates,
+    promises,
+    "require-atomic-updates"
+}
+
+#[typetag::serde]
+impl CstRule for RequireAtomicUpdates {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let assign = node.try_to::<AssignExpr>()?;
+        let target = strip_parens(assign.lhs()?.syntax());
+        let name = match target.kind() {
+            NAME_REF => target.text().to_string(),
+            SINGLE_PATTERN => target.first_child()?.text().to_string(),
+            _ => return None,
+        };
+        let rhs = assign.rhs()?;
+        let pauses = pauses(node);
+
+        // a compound assignment reads the variable before evaluating the right hand side
+        let read = if assign.op()? != ast::AssignOp::Assign {
+            target.trimmed_range()
+        } else {
+            references(rhs.syntax(), &name)
+                .into_iter()
+                .filter(|reference| {
+                    reference.is_read()
+                        && !reference
+                            .node
+                            .ancestors()
+                            .take_while(|ancestor| ancestor != rhs.syntax())
+                            .any(|ancestor| is_function_scope(&ancestor))
+                })
+                .map(|reference| reference.range())
+                .find(|range| {
+                    pauses
+                        .iter()
+                        .any(|pause| range.start() < pause.text_range().end())
+                })?
+        };
+        let pause = pauses
+            .iter()
+            .find(|pause| read.start() < pause.text_range().end())?;
+        if !is_shared(node, &name) {
+            return None;
+        }
+
+        let keyword = if pause.kind() == AWAIT_EXPR {
+            "await"
+        } else {
+            "yield"
+        };
+        ctx.diagnostic(
+            node,
+            format!(
+                "possible race condition: `{}` might be assigned based on an outdated value of `{}`",
+                name, name
+            ),
+        )
+        .secondary(read, format!("`{}` is read here", name))
+        .secondary(
+            pause,
+            format!(
+                "the function pauses here, `{}` can be changed before it is assigned",
+                name
+            ),
+        )
+        .footer_help(format!(
+            "store the result of the `{}` in a variable, then update `{}` without pausing",
+            keyword, name
+        ))
+        .emit();
+        None
+    }
+}
+
+/// The `await` and `yield` expressions inside of a node in the order they appear, excluding the ones
+/// inside of nested functions.
+fn pauses(expr: &SyntaxNode) -> Vec<SyntaxNode> {
+    let mut pauses = vec![];
+    expr.descendants_with(&mut |node| {
+        if is_function_scope(node) {
+            return false;
+        }
+        if matches!(node.kind(), AWAIT_EXPR | YIELD_EXPR) {
+            pauses.push(node.clone());
+        }
+        true
+    });
+    pauses
+}
+
+/// Whether code outside of the function of `node` can change `name`, either because it is declared outside of
+/// the function or because a nested function uses it.
+fn is_shared(node: &SyntaxNode, name: &str) -> bool {
+    let func = enclosing_function_scope(node);
+    let scope = node
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor != &func)
+        .chain(Some(func.clone()))
+        .find(|ancestor| is_block_scope(ancestor) && declares(ancestor, name));
+    match scope {
+        Some(scope) if is_function_scope(&func) => references(&scope, name)
+            .iter()
+            .any(|reference| enclosing_function_scope(&reference.node) != func),
+        // the variable is declared outside of the function, or the assignment is not inside of a function
+        _ => true,
+    }
+}
+
+rule_tests! {
+    RequireAtomicUpdates::default(),
+    err: {
+        "let a = 0; async function foo() { a += await bar(); }",
+        "let a = 0; async function foo() { a = Math.max(a, await bar()); }",
+        "let a = 0; async function foo() { a = (a) + (await bar()); }",
+        "let a = 0; async function foo() { a = await bar(a); }",
+        "let a = 0; function* foo() { a += yield; }",
+        "let a = 0; function* foo() { a = a + (yield 5); }",
+        "async function foo() { a -= await bar(); }",
+        "async function foo() { let a = 0; const reset = () => { a = 0; }; a += await bar(); }",
+        "async function foo(a) { setTimeout(() => a++); a += await bar(); }",
+        "let a = 0; const foo = async () => { a *= await bar(); };",
+        "let a = 0; async function foo() { if (b) { a += await bar(); } }"
+    },
+    ok: {
+        "let a = 0; async function foo() { const b = await bar(); a += b; }",
+        "let a = 0; async function foo() { a = await bar(); }",
+        "let a = 0; async function foo() { a = (await bar()) + a; }",
+        "let a = 0; async function foo() { a += bar(); }",
+        "async function foo() { let a = 0; a += await bar(); return a; }",
+        "async function foo(a) { a += await bar(); return a; }",
+        "async function foo() { let a = 0; while (b) { a += await bar(); } }",
+        "let a = 0; async function foo() { let a = 0; a += await bar(); }",
+        "let a = 0; async function foo() { a = a + (async () => await bar()); }",
+        "let a = 0; async function foo() { a = (() => a)() + await bar(); }",
+        "let a = 0; async function foo() { a.b += await bar(); }"
+    }
+}
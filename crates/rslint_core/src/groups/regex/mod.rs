@@ -0,0 +1,12 @@
+//! Rules which check the patterns of regular expressions for errors and likely mistakes.
+
+use crate::group;
+
+group! {
+    /// Rules which check the patterns of regular expressions for errors and likely mistakes.
+    regex,
+    no_invalid_regexp::NoInvalidRegexp,
+    no_empty_character_class::NoEmptyCharacterClass,
+    no_regex_spaces::NoRegexSpaces,
+    no_control_regex::NoControlRegex,
+}
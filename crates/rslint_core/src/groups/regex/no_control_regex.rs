@@ -0,0 +1,96 @@
+use crate::rule_prelude::*;
+use util::regex::RegexSource;
+
+declare_lint! {
+    /**
+    Disallow control characters in regular expressions.
+
+    Control characters are the invisible characters with the codes 0 to 31, such as the NUL character. They
+    rarely appear in text which is matched by a regex, so a control character in a pattern is usually a mistake.
+    Control characters written as an escape such as `\x1f` or `\u001f` and raw control characters are reported,
+    common escapes such as `\t` and `\n` are allowed.
+
+    ## Invalid Code Examples
+
+    ```js
+    const unitSeparator = /\x1f/;
+    const formFeed = new RegExp("\x0c");
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const space = /\x20/;
+    const whitespace = /\t\n/;
+    ```
+    */
+    #[derive(Default)]
+    NoControlRegex,
+    regex,
+    "no-control-regex"
+}
+
+#[typetag::serde]
+impl CstRule for NoControlRegex {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let source = RegexSource::from_node(node)?;
+        let controls = source
+            .parse()
+            .ok()?
+            .chars()
+            .into_iter()
+            .filter(|c| {
+                let raw = &source.pattern[c.range()];
+                c.value < 0x20
+                    && (raw.starts_with("\\x") || raw.starts_with("\\u") || !raw.starts_with('\\'))
+            })
+            .collect::<Vec<_>>();
+        let first = controls.first()?;
+
+        let names = controls
+            .iter()
+            .map(|c| format!("`\\x{:02x}`", c.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut diagnostic = ctx
+            .diagnostic(
+                source.range(first.range()),
+                format!(
+                    "unexpected control characters in a regular expression: {}",
+                    names
+                ),
+            )
+            .primary_label("this is a control character");
+        if source.literal {
+            for c in &controls[1..] {
+                diagnostic =
+                    diagnostic.secondary(source.range(c.range()), "this is a control character");
+            }
+        }
+        diagnostic.emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoControlRegex::default(),
+    err: {
+        "/\\x00/",
+        "/\\x0C/",
+        "/\\x1F/",
+        "/\\u000C/",
+        "/\\u{1F}/u",
+        "/[\\x00-\\x1f]/",
+        "/a\\x1fb\\x0c/",
+        "new RegExp('\u{1f}')"
+    },
+    ok: {
+        "/x1f/",
+        "/\\x20/",
+        "/\\t\\n\\r/",
+        "/\\u{1F}/",
+        "/\\\\x1f/",
+        "new RegExp('\\\\x1f')",
+        "new RegExp(foo)"
+    }
+}
@@ -0,0 +1,70 @@
+use crate::rule_prelude::*;
+use util::regex::{RegexSource, TermKind};
+
+declare_lint! {
+    /**
+    Disallow empty character classes in regular expressions.
+
+    An empty character class `[]` does not match any character, so the regex can never match. It is usually a
+    typo or a leftover, `[^]` matches any character and is allowed.
+
+    ## Invalid Code Examples
+
+    ```js
+    /^abc[]/.test("abcdefg");
+    "abcdefg".match(/^abc[]/);
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    /^abc[a-z]/.test("abcdefg");
+    "abcdefg".match(/^abc[^]/);
+    ```
+    */
+    #[derive(Default)]
+    NoEmptyCharacterClass,
+    regex,
+    "no-empty-character-class"
+}
+
+#[typetag::serde]
+impl CstRule for NoEmptyCharacterClass {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let source = RegexSource::from_node(node).filter(|source| source.literal)?;
+        let mut empty = vec![];
+        source.parse().ok()?.walk(&mut |term| match &term.kind {
+            TermKind::Class(class) if !class.negated && class.items.is_empty() => {
+                empty.push(term.range.clone())
+            }
+            _ => {}
+        });
+
+        for range in empty {
+            ctx.diagnostic(source.range(range), "empty character class")
+                .primary_label("this class does not match any character")
+                .footer_help("remove the class, or use `[^]` to match any character")
+                .emit();
+        }
+        None
+    }
+}
+
+rule_tests! {
+    NoEmptyCharacterClass::default(),
+    err: {
+        "/^abc[]/",
+        "/foo[]bar/g",
+        "/(a|[])/",
+        "/[]*/",
+        "if (/[]/.test(a)) {}"
+    },
+    ok: {
+        "/^abc[a-z]/",
+        "/^abc[^]/",
+        "/[\\]]/",
+        "/\\[]/",
+        "/[[]/",
+        "new RegExp('[]')"
+    }
+}
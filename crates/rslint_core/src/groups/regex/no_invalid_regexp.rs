@@ -0,0 +1,131 @@
+use crate::rule_prelude::*;
+use util::regex::{parse, validate_flags, RegexSource};
+
+declare_lint! {
+    /**
+    Disallow invalid regular expressions.
+
+    The parser only finds where a regex literal ends, a pattern such as `/(/` or `/a{2,1}/` is accepted by the
+    parser but throws a `SyntaxError` when the script is loaded. Patterns and flags given to the `RegExp`
+    constructor as constant strings throw when the constructor is called.
+
+    ## Invalid Code Examples
+
+    ```js
+    const group = /(foo/;
+
+    const range = new RegExp("[z-a]");
+
+    const flags = RegExp("foo", "gg");
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const group = /(foo)/;
+
+    const range = new RegExp("[a-z]");
+
+    const dynamic = new RegExp(pattern);
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    NoInvalidRegexp,
+    regex,
+    "no-invalid-regexp",
+    /// Flags which are allowed in `RegExp` calls in addition to the standard flags, such as `["d"]`
+    /// for engines which support new flags.
+    pub allow_constructor_flags: Vec<char>
+}
+
+#[typetag::serde]
+impl CstRule for NoInvalidRegexp {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let source = RegexSource::from_node(node)?;
+
+        if let (false, Some(flags)) = (source.literal, &source.flags) {
+            if let Err(err) = validate_flags(flags, &self.allow_constructor_flags) {
+                ctx.diagnostic(node, err.message)
+                    .primary_label("the `RegExp` call throws a `SyntaxError`")
+                    .emit();
+                return None;
+            }
+        }
+
+        // without constant flags the pattern is only invalid if it is invalid with and without the `u` flag
+        let result = match &source.flags {
+            Some(_) => source.parse(),
+            None => parse(&source.pattern, "")
+                .or_else(|err| parse(&source.pattern, "u").map_err(|_| err)),
+        };
+        if let Err(err) = result {
+            let label = if source.literal {
+                "this regex throws a `SyntaxError` when the script is loaded"
+            } else {
+                "this pattern throws a `SyntaxError` when `RegExp` is called"
+            };
+            ctx.diagnostic(
+                source.range(err.range),
+                format!("invalid regular expression: {}", err.message),
+            )
+            .primary_label(label)
+            .emit();
+        }
+        None
+    }
+}
+
+rule_tests! {
+    NoInvalidRegexp::default(),
+    err: {
+        "/(foo/",
+        "/foo)/",
+        "/a{2,1}/",
+        "/a|*b/",
+        "/[z-a]/",
+        "/\\u{110000}/u",
+        "/\\k<foo>(?<bar>a)/",
+        "/(?<a>x)(?<a>y)/",
+        "/a]/u",
+        "/\\-/u",
+        "/(?<=a)+/",
+        "new RegExp('[')",
+        "RegExp('a', 'gg')",
+        "new RegExp('a', 'z')",
+        "new RegExp('(?<1a>x)')"
+    },
+    ok: {
+        "/(foo)/",
+        "/a]{/",
+        "/a{2,}b{1,3}/",
+        "/[a-z\\d-]/",
+        "/\\1(a)/",
+        "/\\8/",
+        "/(?<year>\\d{4})-\\k<year>/",
+        "/\\p{Letter}/u",
+        "/\\u{1F600}/u",
+        "/(?=a)*/",
+        "/[\\b\\c_]/",
+        "new RegExp('[a-z]', 'gi')",
+        "RegExp(pattern)",
+        "new RegExp('\\\\')",
+        "new RegExp('{', flags)",
+        "new Foo('(')"
+    }
+}
+
+rule_tests! {
+    allowed_flags_valid,
+    allowed_flags_invalid,
+    NoInvalidRegexp {
+        allow_constructor_flags: vec!['d'],
+    },
+    err: {
+        "new RegExp('a', 'dd')",
+        "new RegExp('a', 'v')"
+    },
+    ok: {
+        "new RegExp('a', 'dg')"
+    }
+}
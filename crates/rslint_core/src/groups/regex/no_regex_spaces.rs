@@ -0,0 +1,93 @@
+use crate::rule_prelude::*;
+use util::regex::{RegexSource, TermKind};
+
+declare_lint! {
+    /**
+    Disallow multiple spaces in regular expressions.
+
+    Spaces in a pattern are matched literally, several of them in a row are hard to count and the number
+    of spaces is easy to get wrong when editing. A quantifier such as ` {3}` states the number of spaces.
+    Spaces inside of character classes are allowed.
+
+    ## Invalid Code Examples
+
+    ```js
+    const literal = /foo   bar/;
+    const constructed = new RegExp("foo   bar");
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const literal = /foo {3}bar/;
+    const constructed = new RegExp("foo {3}bar");
+    ```
+    */
+    #[derive(Default)]
+    NoRegexSpaces,
+    regex,
+    "no-regex-spaces"
+}
+
+#[typetag::serde]
+impl CstRule for NoRegexSpaces {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let source = RegexSource::from_node(node)?;
+        let pattern = source.parse().ok()?;
+
+        // runs of unquantified spaces, a quantified space after a run is not part of it
+        let mut runs = vec![];
+        for alternative in pattern.all_alternatives() {
+            let mut run: Option<std::ops::Range<usize>> = None;
+            for term in &alternative.terms {
+                let is_space = matches!(term.kind, TermKind::Char(c) if c.value == ' ' as u32)
+                    && &source.pattern[term.range.clone()] == " ";
+                match (&mut run, is_space) {
+                    (Some(run), true) => run.end = term.range.end,
+                    (None, true) => run = Some(term.range.clone()),
+                    (_, false) => runs.extend(run.take()),
+                }
+            }
+            runs.extend(run);
+        }
+
+        runs.retain(|run| run.len() > 1);
+        for run in &runs {
+            ctx.diagnostic(
+                source.range(run.clone()),
+                "multiple spaces in a regular expression",
+            )
+            .primary_label(format!("{} spaces are hard to count", run.len()))
+            .footer_help(format!("use `{{{}}}` instead", run.len()))
+            .emit();
+        }
+        if source.literal && !runs.is_empty() {
+            let fixer = ctx.fix();
+            for run in runs {
+                fixer.replace(source.range(run.clone()), format!(" {{{}}}", run.len()));
+            }
+        }
+        None
+    }
+}
+
+rule_tests! {
+    NoRegexSpaces::default(),
+    err: {
+        "/foo  bar/",
+        "/foo   bar/g",
+        "/a   +/",
+        "/(a|  b)/",
+        "new RegExp('foo  bar')",
+        "RegExp('  ')"
+    },
+    ok: {
+        "/foo bar/",
+        "/foo {3}bar/",
+        "/[  ]/",
+        "/a +/",
+        "/\\x20\\x20/",
+        "new RegExp('foo bar')",
+        "new RegExp(foo + '  ')"
+    }
+}
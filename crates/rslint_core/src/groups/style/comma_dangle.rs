@@ -0,0 +1,242 @@
+use crate::rule_prelude::*;
+use util::tokens::{on_same_line, prev_non_trivia};
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Require or disallow trailing commas in lists.
+
+    Trailing commas make diffs cleaner since adding an item to the end of a multiline list does not change
+    the line before it. This rule enforces a consistent style for the trailing commas of arrays, objects,
+    imports, exports, and function parameters and arguments, each of which can be configured on its own.
+
+    Each option takes one of the following modes:
+
+    - `"never"` (the default) disallows trailing commas.
+    - `"always-multiline"` requires a trailing comma if the last item and the closing bracket are on different
+    lines, and disallows it if they are on the same line.
+    - `"only-multiline"` allows a trailing comma if the last item and the closing bracket are on different lines,
+    and disallows it if they are on the same line.
+
+    A comma is never required after a rest element such as `...rest` since that is a syntax error, and the
+    comma of a hole at the end of an array such as `[a, ,]` is never reported since removing it would change
+    the length of the array.
+
+    ## Invalid Code Examples
+
+    ```js
+    // with the default `"never"` mode
+    let a = [1, 2,];
+    foo(a, b,);
+    ```
+
+    ```js
+    // with `"always-multiline"` for objects
+    let a = {
+        b: 1,
+        c: 2
+    };
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    // with the default `"never"` mode
+    let a = [1, 2];
+    foo(a, b);
+    ```
+
+    ```js
+    // with `"always-multiline"` for objects
+    let a = {
+        b: 1,
+        c: 2,
+    };
+    let d = { e: 1 };
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    CommaDangle,
+    style,
+    "comma-dangle",
+    /// The mode for array literals and array destructuring patterns.
+    pub arrays: CommaDangleMode,
+    /// The mode for object literals and object destructuring patterns.
+    pub objects: CommaDangleMode,
+    /// The mode for named imports such as `import { a, b } from "c"`.
+    pub imports: CommaDangleMode,
+    /// The mode for named exports such as `export { a, b }`.
+    pub exports: CommaDangleMode,
+    /// The mode for function parameters and call arguments.
+    pub functions: CommaDangleMode
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommaDangleMode {
+    Never,
+    AlwaysMultiline,
+    OnlyMultiline,
+}
+
+impl Default for CommaDangleMode {
+    fn default() -> Self {
+        CommaDangleMode::Never
+    }
+}
+
+impl CommaDangle {
+    fn mode(&self, kind: SyntaxKind) -> Option<CommaDangleMode> {
+        Some(match kind {
+            ARRAY_EXPR | ARRAY_PATTERN => self.arrays,
+            OBJECT_EXPR | OBJECT_PATTERN => self.objects,
+            NAMED_IMPORTS => self.imports,
+            EXPORT_NAMED => self.exports,
+            PARAMETER_LIST | ARG_LIST => self.functions,
+            _ => return None,
+        })
+    }
+}
+
+#[typetag::serde]
+impl CstRule for CommaDangle {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let mode = self.mode(node.kind())?;
+        let close = node
+            .children_with_tokens()
+            .filter_map(|elem| elem.into_token())
+            .find(|token| matches!(token.kind(), T![']'] | T!['}'] | T![')']))?;
+        let last = prev_non_trivia(&close)?;
+        let multiline = !on_same_line(&last, &close);
+
+        match last.kind() {
+            T!['['] | T!['{'] | T!['('] => {}
+            T![,] => {
+                let before = prev_non_trivia(&last)?;
+                // the comma of a hole such as `[a, ,]` is part of the array
+                let is_hole = matches!(before.kind(), T![,] | T!['[']) && before.parent() == *node;
+                if is_hole || (multiline && mode != CommaDangleMode::Never) {
+                    return None;
+                }
+                ctx.fix().delete(last.text_range());
+                ctx.diagnostic(last.text_range(), "unexpected trailing comma")
+                    .primary_label("remove this comma")
+                    .emit();
+            }
+            _ => {
+                if !multiline
+                    || mode != CommaDangleMode::AlwaysMultiline
+                    || ends_with_rest(node, &last)
+                {
+                    return None;
+                }
+                ctx.fix().insert_after(last.text_range(), ",");
+                ctx.diagnostic(last.text_range(), "missing trailing comma")
+                    .primary_label("add a comma after this")
+                    .emit();
+            }
+        }
+        None
+    }
+}
+
+/// Whether the last item of a list is a rest element, which cannot be followed by a comma.
+fn ends_with_rest(list: &SyntaxNode, last: &SyntaxToken) -> bool {
+    last.parent()
+        .ancestors()
+        .find(|node| node.parent().as_ref() == Some(list))
+        .filter(|item| item.kind() == REST_PATTERN)
+        .is_some()
+}
+
+rule_tests! {
+    CommaDangle::default(),
+    err: {
+        "let a = [1, 2,];",
+        "let a = { b: 1, };",
+        "let [a,] = b;",
+        "let { a, } = b;",
+        "import { a, } from 'a';",
+        "export { a, };",
+        "export { a, } from 'a';",
+        "function foo(a, b,) {}",
+        "foo(a,);",
+        "new Foo(a,);",
+        "(a,) => a;",
+        "class A { foo(a,) {} }",
+        "let a = [\n    1,\n];"
+    },
+    ok: {
+        "let a = [1, 2];",
+        "let a = [];",
+        "let a = [,];",
+        "let a = [1, ,];",
+        "let a = { b: 1 };",
+        "let a = {};",
+        "import { a } from 'a';",
+        "export { a };",
+        "function foo(a, b) {}",
+        "foo();",
+        "foo(a);",
+        "a => a;"
+    }
+}
+
+rule_tests! {
+    always_multiline_valid,
+    always_multiline_invalid,
+    CommaDangle {
+        arrays: CommaDangleMode::AlwaysMultiline,
+        objects: CommaDangleMode::AlwaysMultiline,
+        imports: CommaDangleMode::AlwaysMultiline,
+        exports: CommaDangleMode::AlwaysMultiline,
+        functions: CommaDangleMode::AlwaysMultiline,
+    },
+    err: {
+        "let a = [\n    1,\n    2\n];",
+        "let a = {\n    b: 1\n};",
+        "let a = { b: 1, };",
+        "import {\n    a\n} from 'a';",
+        "export {\n    a\n};",
+        "foo(\n    a\n);",
+        "function foo(\n    a\n) {}",
+        "let a = [\n    1 // one\n];"
+    },
+    ok: {
+        "let a = [\n    1,\n    2,\n];",
+        "let a = [1, 2];",
+        "let a = { b: 1 };",
+        "let a = {\n    b: 1,\n};",
+        "let a = [\n];",
+        "let a = [1,\n    2];",
+        "let {\n    a,\n    ...b\n} = c;",
+        "function foo(\n    a,\n    ...b\n) {}",
+        "foo(\n    a,\n);"
+    }
+}
+
+rule_tests! {
+    only_multiline_valid,
+    only_multiline_invalid,
+    CommaDangle {
+        arrays: CommaDangleMode::OnlyMultiline,
+        functions: CommaDangleMode::OnlyMultiline,
+        ..CommaDangle::default()
+    },
+    err: {
+        "let a = [1, 2,];",
+        "foo(a,);",
+        "let a = {\n    b: 1,\n};"
+    },
+    ok: {
+        "let a = [\n    1,\n    2,\n];",
+        "let a = [\n    1,\n    2\n];",
+        "foo(\n    a\n);",
+        "foo(\n    a,\n);"
+    }
+}
@@ -0,0 +1,258 @@
+use crate::rule_prelude::*;
+use crate::util::cfg::ControlFlowGraph;
+use ast::{Expr, ReturnStmt, UnaryOp};
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Require functions to either always or never return a value.
+
+    A function which returns a value on some paths and nothing on others is hard to use, since callers have to
+    handle `undefined` even though it looks like the function always returns something. Running off the end of
+    a function also returns nothing, so functions which return values have to return on every path.
+
+    By default `return undefined` and `return void 0` count as returning a value, so functions which return
+    values can use them to return `undefined` explicitly instead of `return;`.
+
+    ## Invalid Code Examples
+
+    ```js
+    function foo(a) {
+        if (a) {
+            return true;
+        } else {
+            return;
+        }
+    }
+    ```
+
+    ```js
+    function foo(a) {
+        if (a) {
+            return true;
+        }
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    function foo(a) {
+        if (a) {
+            return true;
+        }
+        return undefined;
+    }
+    ```
+
+    ```js
+    function foo(a) {
+        if (a) {
+            return;
+        }
+        bar();
+    }
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    ConsistentReturn,
+    style,
+    "consistent-return",
+    /// Whether `return undefined` and `return void 0` count as returning nothing, which disallows them in
+    /// functions which return values (`false` by default).
+    pub treat_undefined_as_unspecified: bool
+}
+
+#[typetag::serde]
+impl CstRule for ConsistentReturn {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if !matches!(
+            node.kind(),
+            FN_DECL | FN_EXPR | ARROW_EXPR | METHOD | GETTER | SETTER
+        ) {
+            return None;
+        }
+        // arrow functions with an expression body always return their expression
+        let body = node.child_with_kind(BLOCK_STMT)?;
+        let graph = ControlFlowGraph::new(&body);
+        let returns = graph
+            .returns()
+            .into_iter()
+            .map(|node| {
+                let returns_value = self.returns_value(&node.to::<ReturnStmt>());
+                (node, returns_value)
+            })
+            .collect::<Vec<_>>();
+
+        let (first, first_value) = returns.first()?.clone();
+        for (node, returns_value) in &returns[1..] {
+            if *returns_value == first_value {
+                continue;
+            }
+            let (msg, label) = if first_value {
+                (
+                    "expected a return value",
+                    "...but this return statement does not return a value",
+                )
+            } else {
+                (
+                    "unexpected return value",
+                    "...but this return statement returns a value",
+                )
+            };
+            let err = ctx
+                .err(self.name(), msg)
+                .secondary(&first, returned(first_value))
+                .primary(node, label);
+            ctx.add_err(err);
+        }
+
+        if first_value && graph.completes_normally() {
+            let err = ctx
+                .err(
+                    self.name(),
+                    "expected a return value at the end of the function",
+                )
+                .secondary(&first, returned(true))
+                .primary(
+                    body.last_token()?,
+                    "...but the end of the function can be reached, which returns nothing",
+                );
+            ctx.add_err(err);
+        }
+        None
+    }
+}
+
+impl ConsistentReturn {
+    fn returns_value(&self, stmt: &ReturnStmt) -> bool {
+        match stmt.value() {
+            Some(value) => !(self.treat_undefined_as_unspecified && is_undefined(&value)),
+            None => false,
+        }
+    }
+}
+
+fn returned(value: bool) -> &'static str {
+    if value {
+        "this function returns a value here..."
+    } else {
+        "this function returns nothing here..."
+    }
+}
+
+fn is_undefined(expr: &Expr) -> bool {
+    match expr {
+        Expr::NameRef(name) => name.syntax().text() == "undefined",
+        Expr::UnaryExpr(unary) => unary.op() == Some(UnaryOp::Void),
+        Expr::GroupingExpr(group) => match group.inner() {
+            Some(inner) => is_undefined(&inner),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+rule_tests! {
+    ConsistentReturn::default(),
+    err: {
+        "
+        function foo(a) {
+            if (a) {
+                return true;
+            } else {
+                return;
+            }
+        }
+        ",
+        "
+        function foo(a) {
+            if (a) {
+                return;
+            }
+            return 5;
+        }
+        ",
+        "
+        function foo(a) {
+            if (a) {
+                return true;
+            }
+        }
+        ",
+        "
+        const foo = (a) => {
+            for (const b of a) {
+                return b;
+            }
+        }
+        ",
+        "
+        class Foo {
+            bar(a) {
+                switch (a) {
+                    case 1:
+                        return 1;
+                    default:
+                        break;
+                }
+            }
+        }
+        "
+    },
+    ok: {
+        "
+        function foo(a) {
+            if (a) {
+                return true;
+            }
+            return undefined;
+        }
+        ",
+        "
+        function foo(a) {
+            if (a) {
+                return;
+            }
+            bar();
+        }
+        ",
+        "
+        function foo(a) {
+            if (a) {
+                return 1;
+            } else {
+                return 2;
+            }
+        }
+        ",
+        "
+        function foo(a) {
+            while (true) {
+                if (a) {
+                    return 1;
+                }
+            }
+        }
+        ",
+        "
+        function foo() {
+            function bar() {
+                return 1;
+            }
+            return;
+        }
+        ",
+        "
+        function foo(a) {
+            try {
+                return a();
+            } catch (e) {
+                throw e;
+            }
+        }
+        ",
+        "const foo = a => a ? 1 : undefined;"
+    }
+}
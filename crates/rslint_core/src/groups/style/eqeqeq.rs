@@ -0,0 +1,258 @@
+use crate::rule_prelude::*;
+use crate::util::const_eval::{eval, ConstValue};
+use ast::{BinExpr, BinOp, Expr, LiteralKind, UnaryOp};
+
+declare_lint! {
+    /**
+    Require the use of `===` and `!==` over `==` and `!=`.
+
+    The loose equality operators (`==` and `!=`) perform type coercion before comparing
+    their operands, which follows a set of rules that are obscure and often surprising:
+
+    ```js
+    [] == false // true
+    "0" == false // true
+    null == undefined // true
+    ```
+
+    Strict equality operators do not coerce their operands, so they should be preferred.
+    When both sides of a comparison are guaranteed to be of the same type (for example
+    `typeof foo == "string"` or `"id-" + id == "id-1"`), the comparison is automatically fixed to use the strict
+    operator, otherwise a suggestion is issued because the fix may change the behavior of the code.
+
+    ## Invalid Code Examples
+
+    ```js
+    if (foo == bar) {}
+    if (typeof foo != "number") {}
+    if (foo == null) {}
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    if (foo === bar) {}
+    if (typeof foo !== "number") {}
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    Eqeqeq,
+    style,
+    "eqeqeq",
+    /// The mode used for checking comparisons, either `"always"` (the default) which always requires
+    /// strict operators, `"allow-null"` which allows loose comparisons against `null`, or `"smart"`
+    /// which allows comparisons of two literals, `typeof` comparisons, and comparisons against `null`.
+    pub mode: EqeqeqMode
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EqeqeqMode {
+    Always,
+    Smart,
+    AllowNull,
+}
+
+impl Default for EqeqeqMode {
+    fn default() -> Self {
+        EqeqeqMode::Always
+    }
+}
+
+#[typetag::serde]
+impl CstRule for Eqeqeq {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let expr = node.try_to::<BinExpr>()?;
+        let (op_token, op) = expr.op_details()?;
+        let strict_op = match op {
+            BinOp::Equality => "===",
+            BinOp::Inequality => "!==",
+            _ => return None,
+        };
+        let (lhs, rhs) = (expr.lhs()?, expr.rhs()?);
+
+        let null_cmp = is_null(&lhs) || is_null(&rhs);
+        let allowed = match self.mode {
+            EqeqeqMode::Always => false,
+            EqeqeqMode::AllowNull => null_cmp,
+            EqeqeqMode::Smart => {
+                null_cmp
+                    || is_typeof_expr(&lhs)
+                    || is_typeof_expr(&rhs)
+                    || (is_literal(&lhs) && is_literal(&rhs))
+            }
+        };
+        if allowed {
+            return None;
+        }
+
+        let err = ctx
+            .err(
+                self.name(),
+                format!("expected `{}` but found `{}`", strict_op, op_token.text()),
+            )
+            .primary(op_token.text_range(), "")
+            .capture("operator", op_token.text_range())
+            .capture("left", lhs.syntax().trimmed_range())
+            .capture("right", rhs.syntax().trimmed_range());
+
+        if same_type(&lhs, &rhs) {
+            ctx.fix().replace(op_token.text_range(), strict_op);
+            ctx.add_err(err.suggestion_inline(
+                op_token.text_range(),
+                "both sides are always the same type, use",
+                strict_op,
+                Applicability::Always,
+            ));
+        } else {
+            ctx.add_suggestion(
+                err,
+                op_token.text_range(),
+                "this may change the behavior of the comparison, but consider using",
+                strict_op,
+            );
+        }
+        None
+    }
+}
+
+/// The type an expression is guaranteed to evaluate to, if it can be statically known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StaticType {
+    String,
+    Number,
+    BigInt,
+    Boolean,
+    Null,
+    Undefined,
+}
+
+fn static_type(expr: &Expr) -> Option<StaticType> {
+    if let Some(value) = eval(expr.clone()) {
+        return Some(match value {
+            ConstValue::String(_) => StaticType::String,
+            ConstValue::Number(_) => StaticType::Number,
+            ConstValue::BigInt(_) => StaticType::BigInt,
+            ConstValue::Bool(_) => StaticType::Boolean,
+            ConstValue::Null => StaticType::Null,
+            ConstValue::Undefined => StaticType::Undefined,
+            ConstValue::Object { .. } => return None,
+        });
+    }
+    Some(match expr {
+        Expr::Literal(lit) => match lit.kind() {
+            LiteralKind::String => StaticType::String,
+            LiteralKind::Number(_) => StaticType::Number,
+            LiteralKind::BigInt(_) => StaticType::BigInt,
+            LiteralKind::Bool(_) => StaticType::Boolean,
+            LiteralKind::Null => StaticType::Null,
+            LiteralKind::Regex => return None,
+        },
+        Expr::Template(tpl) if tpl.tag().is_none() => StaticType::String,
+        Expr::GroupingExpr(group) => return static_type(&group.inner()?),
+        Expr::UnaryExpr(unexpr) => match unexpr.op()? {
+            UnaryOp::Typeof => StaticType::String,
+            UnaryOp::LogicalNot | UnaryOp::Delete => StaticType::Boolean,
+            UnaryOp::Plus => StaticType::Number,
+            UnaryOp::Void => StaticType::Undefined,
+            _ => return None,
+        },
+        Expr::BinExpr(binexpr) => match binexpr.op()? {
+            BinOp::LessThan
+            | BinOp::GreaterThan
+            | BinOp::LessThanOrEqual
+            | BinOp::GreaterThanOrEqual
+            | BinOp::Equality
+            | BinOp::StrictEquality
+            | BinOp::Inequality
+            | BinOp::StrictInequality
+            | BinOp::In
+            | BinOp::Instanceof => StaticType::Boolean,
+            // concatenating anything with a string always results in a string
+            BinOp::Plus
+                if [binexpr.lhs(), binexpr.rhs()]
+                    .iter()
+                    .flatten()
+                    .any(|expr| static_type(expr) == Some(StaticType::String)) =>
+            {
+                StaticType::String
+            }
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Whether the loose comparison of two expressions is guaranteed to behave like a strict comparison.
+fn same_type(lhs: &Expr, rhs: &Expr) -> bool {
+    matches!((static_type(lhs), static_type(rhs)), (Some(l), Some(r)) if l == r)
+}
+
+fn is_null(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal(lit) if lit.is_null())
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal(_))
+}
+
+fn is_typeof_expr(expr: &Expr) -> bool {
+    matches!(expr, Expr::UnaryExpr(unexpr) if unexpr.op() == Some(UnaryOp::Typeof))
+}
+
+rule_tests! {
+    Eqeqeq::default(),
+    err: {
+        "foo == bar",
+        "foo != bar",
+        "typeof foo == 'string'",
+        "foo == null",
+        "null != foo",
+        "'a' == 'b'",
+        "(a < b) == (c > d)"
+    },
+    ok: {
+        "foo === bar",
+        "foo !== bar",
+        "typeof foo === 'string'",
+        "foo === null"
+    }
+}
+
+rule_tests! {
+    smart_valid,
+    smart_invalid,
+    Eqeqeq { mode: EqeqeqMode::Smart },
+    err: {
+        "foo == bar",
+        "foo != true",
+        "foo.bar == baz"
+    },
+    ok: {
+        "typeof foo == 'undefined'",
+        "'hello' != 'world'",
+        "0 == 0",
+        "foo == null",
+        "null != foo"
+    }
+}
+
+rule_tests! {
+    allow_null_valid,
+    allow_null_invalid,
+    Eqeqeq { mode: EqeqeqMode::AllowNull },
+    err: {
+        "foo == bar",
+        "typeof foo == 'string'"
+    },
+    ok: {
+        "foo == null",
+        "null != foo",
+        "foo === bar"
+    }
+}
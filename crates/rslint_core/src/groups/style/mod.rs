@@ -0,0 +1,21 @@
+//! Rules which enforce a consistent code style or guard against patterns
+//! which are not always errors but frequently lead to bugs.
+
+use crate::group;
+
+group! {
+    /// Rules which enforce a consistent code style or guard against patterns
+    /// which are not always errors but frequently lead to bugs.
+    style,
+    eqeqeq::Eqeqeq,
+    no_var::NoVar,
+    prefer_const::PreferConst,
+    consistent_return::ConsistentReturn,
+    no_param_reassign::NoParamReassign,
+    no_unused_labels::NoUnusedLabels,
+    no_extra_label::NoExtraLabel,
+    quotes::Quotes,
+    semi::Semi,
+    comma_dangle::CommaDangle,
+    no_console::NoConsole,
+}
@@ -0,0 +1,134 @@
+use crate::rule_prelude::*;
+use ast::{BracketExpr, DotExpr, Expr};
+use util::scope::is_global_reference;
+
+declare_lint! {
+    /**
+    Disallow the use of `console`.
+
+    Calls to `console` are often left over from debugging, and in code which runs in the browser they
+    clutter the console of the user. Code which needs to log should usually use a logger which can be
+    configured or turned off.
+
+    Methods such as `warn` or `error` can be allowed with the `allow` option. References to a local
+    variable named `console` are not reported.
+
+    ## Invalid Code Examples
+
+    ```js
+    console.log("loaded");
+    ```
+
+    ```js
+    function save(item) {
+        console.info("saving", item);
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    // with `allow` set to `["warn", "error"]`
+    console.warn("deprecated");
+    console.error(err);
+    ```
+
+    ```js
+    const console = createLogger();
+    console.log("loaded");
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    NoConsole,
+    style,
+    "no-console",
+    /// The methods of `console` which may be used, such as `["warn", "error"]`. The default is an empty list.
+    pub allow: Vec<String>
+}
+
+#[typetag::serde]
+impl CstRule for NoConsole {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let (object, method) = if let Some(dot) = node.try_to::<DotExpr>() {
+            (dot.object()?, Some(dot.prop()?.text()))
+        } else {
+            let bracket = node.try_to::<BracketExpr>()?;
+            let method = match bracket.prop()? {
+                Expr::Literal(lit) => lit.inner_string_text().map(|text| text.to_string()),
+                _ => None,
+            };
+            (bracket.object()?, method)
+        };
+        match object {
+            Expr::NameRef(name) if name.text() == "console" => {}
+            _ => return None,
+        }
+        if method
+            .as_ref()
+            .filter(|method| self.allow.contains(method))
+            .is_some()
+            || !is_global_reference(node, "console")
+        {
+            return None;
+        }
+
+        let msg = match &method {
+            Some(method) => format!("unexpected use of `console.{}`", method),
+            None => "unexpected use of `console`".to_string(),
+        };
+        let mut diagnostic = ctx
+            .diagnostic(node, msg)
+            .primary_label("this was likely left over from debugging");
+        if !self.allow.is_empty() {
+            diagnostic = diagnostic.footer_note(format!(
+                "the allowed methods are {}",
+                self.allow
+                    .iter()
+                    .map(|method| format!("`{}`", method))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        diagnostic.emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoConsole::default(),
+    err: {
+        "console.log('foo');",
+        "console.warn(a);",
+        "console['error'](err);",
+        "console[method]();",
+        "function foo() { console.info(a); }",
+        "const log = console.log;",
+        "function foo(console) {} console.log(a);"
+    },
+    ok: {
+        "log('foo');",
+        "foo.console.log();",
+        "const console = logger; console.log(a);",
+        "var console = logger; console.log(a);",
+        "function foo(console) { console.log(a); }",
+        "{ let console = logger; console.log(a); }"
+    }
+}
+
+rule_tests! {
+    allow_valid,
+    allow_invalid,
+    NoConsole {
+        allow: vec!["warn".to_string(), "error".to_string()],
+    },
+    err: {
+        "console.log('foo');",
+        "console['info'](a);"
+    },
+    ok: {
+        "console.warn('foo');",
+        "console.error(err);",
+        "console['error'](err);"
+    }
+}
@@ -0,0 +1,133 @@
+use crate::rule_prelude::*;
+use ast::{BreakStmt, ContinueStmt, LabelledStmt};
+use rslint_parser::TextRange;
+use util::scope::is_function_scope;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow labels on `break` and `continue` statements which target the loop or switch they would target
+    without a label.
+
+    A labelled `break` or `continue` suggests that it jumps out of an outer loop, a label which targets the
+    innermost loop anyway is unneeded and misleading.
+
+    ## Invalid Code Examples
+
+    ```js
+    A: while (foo) {
+        break A;
+    }
+    ```
+
+    ```js
+    A: for (const item of items) {
+        if (item.skip) {
+            continue A;
+        }
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    A: while (foo) {
+        while (bar) {
+            break A;
+        }
+    }
+    ```
+
+    ```js
+    A: {
+        if (foo) {
+            break A;
+        }
+        bar();
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoExtraLabel,
+    style,
+    "no-extra-label"
+}
+
+const LOOPS: [SyntaxKind; 5] = [
+    FOR_STMT,
+    FOR_IN_STMT,
+    FOR_OF_STMT,
+    WHILE_STMT,
+    DO_WHILE_STMT,
+];
+
+#[typetag::serde]
+impl CstRule for NoExtraLabel {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let keyword = match node.kind() {
+            BREAK_STMT => node.to::<BreakStmt>().break_token()?,
+            CONTINUE_STMT => node.to::<ContinueStmt>().continue_token()?,
+            _ => return None,
+        };
+        let label = node.child_with_kind(NAME_REF)?;
+
+        // the statement an unlabelled `break` or `continue` would jump out of
+        let innermost = node
+            .ancestors()
+            .skip(1)
+            .take_while(|ancestor| !is_function_scope(ancestor))
+            .find(|ancestor| {
+                LOOPS.contains(&ancestor.kind())
+                    || (node.kind() == BREAK_STMT && ancestor.kind() == SWITCH_STMT)
+            })?;
+        let labelled = innermost.parent()?.try_to::<LabelledStmt>()?;
+        if label.text() != labelled.label()?.text().as_str() {
+            return None;
+        }
+
+        let removed = TextRange::new(keyword.text_range().end(), label.trimmed_range().end());
+        let target = labelled.label()?.range();
+        ctx.fix().delete(removed);
+        ctx.diagnostic(
+            label.trimmed_range(),
+            format!("the label `{}` is unnecessary", label.text()),
+        )
+        .secondary(
+            target,
+            format!(
+                "the `{}` targets this statement without a label",
+                keyword.text()
+            ),
+        )
+        .suggestion(removed, "remove the label", "", Applicability::Always)
+        .emit();
+        None
+    }
+}
+
+rule_tests! {
+    NoExtraLabel::default(),
+    err: {
+        "A: while (a) { break A; }",
+        "A: while (a) { if (b) { continue A; } }",
+        "A: for (;;) { break A; }",
+        "A: for (a in b) { continue A; }",
+        "A: for (a of b) { break A; }",
+        "A: do { break A; } while (a);",
+        "A: switch (a) { case 0: break A; }",
+        "A: while (a) { B: { break A; } }",
+        "A: while (a) { switch (b) { case 0: continue A; } }"
+    },
+    ok: {
+        "A: while (a) { while (b) { break A; } }",
+        "A: while (a) { switch (b) { case 0: break A; } }",
+        "A: { if (a) { break A; } b(); }",
+        "A: if (a) { break A; }",
+        "while (a) { break; }",
+        "A: while (a) { function foo() { while (b) { break; } } }"
+    }
+}
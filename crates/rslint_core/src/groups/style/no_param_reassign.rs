@@ -0,0 +1,175 @@
+use crate::rule_prelude::*;
+use ast::{UnaryExpr, UnaryOp};
+use util::scope::*;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow reassigning function parameters.
+
+    Assigning to a parameter hides the value the function was called with, which makes the function harder
+    to follow, and in sloppy mode functions it also changes the `arguments` object. Declare a new variable
+    instead of reusing the parameter.
+
+    With `props` enabled, modifying the properties of parameters is disallowed as well, since it mutates the
+    objects of the caller.
+
+    ## Invalid Code Examples
+
+    ```js
+    function foo(bar) {
+        bar = 13;
+    }
+    ```
+
+    ```js
+    function foo({ bar }) {
+        bar++;
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    function foo(bar) {
+        const baz = bar + 1;
+    }
+    ```
+
+    ```js
+    function foo(bar) {
+        bar.baz = 1;
+    }
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    NoParamReassign,
+    style,
+    "no-param-reassign",
+    /// Whether to disallow modifying the properties of parameters, such as `bar.baz = 1` or `delete bar.baz`.
+    pub props: bool,
+    /// Parameters whose properties may be modified even if `props` is enabled, such as `req` and `res` in
+    /// express middleware.
+    pub ignore_property_modifications_for: Vec<String>
+}
+
+#[typetag::serde]
+impl CstRule for NoParamReassign {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if !is_function_scope(node) {
+            return None;
+        }
+        for param in parameter_bindings(node) {
+            let name = param.text();
+            for reference in references(node, &name) {
+                if reference.is_write() {
+                    ctx.diagnostic(
+                        reference.range(),
+                        format!("assignment to function parameter `{}`", name),
+                    )
+                    .secondary(param.range(), "the parameter is declared here")
+                    .emit();
+                } else if self.props && !self.ignore_property_modifications_for.contains(&name) {
+                    if let Some(target) = modified_property(&reference.node) {
+                        ctx.diagnostic(
+                            target,
+                            format!("assignment to a property of function parameter `{}`", name),
+                        )
+                        .secondary(param.range(), "the parameter is declared here")
+                        .emit();
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The member expression which is modified if a reference is the object of a property which is assigned,
+/// updated, or deleted, for example `a.b` in `a.b = 5` or `a.b.c++`.
+fn modified_property(reference: &SyntaxNode) -> Option<SyntaxNode> {
+    let mut target = reference.clone();
+    let mut is_member = false;
+    while let Some(parent) = target.parent() {
+        match parent.kind() {
+            DOT_EXPR | BRACKET_EXPR if parent.first_child().as_ref() == Some(&target) => {
+                is_member = true;
+            }
+            GROUPING_EXPR => {}
+            _ => break,
+        }
+        target = parent;
+    }
+    if !is_member {
+        return None;
+    }
+
+    let parent = target.parent()?;
+    let modified = match parent.kind() {
+        ASSIGN_EXPR => parent.first_child().as_ref() == Some(&target),
+        UNARY_EXPR => matches!(
+            parent.to::<UnaryExpr>().op(),
+            Some(UnaryOp::Increment) | Some(UnaryOp::Decrement) | Some(UnaryOp::Delete)
+        ),
+        FOR_STMT_INIT => matches!(
+            parent.parent().map(|p| p.kind()),
+            Some(FOR_IN_STMT) | Some(FOR_OF_STMT)
+        ),
+        _ => false,
+    };
+    if modified {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+rule_tests! {
+    NoParamReassign::default(),
+    err: {
+        "function foo(bar) { bar = 13; }",
+        "function foo(bar) { bar += 13; }",
+        "function foo(bar) { (bar) = 13; }",
+        "function foo(bar) { bar++; }",
+        "function foo({ bar }) { bar = 13; }",
+        "function foo([, bar]) { bar--; }",
+        "function foo(...bar) { [bar] = baz; }",
+        "function foo(bar) { ({ bar } = baz); }",
+        "function foo(bar) { for (bar in baz) {} }",
+        "const foo = bar => { bar = 1; };",
+        "class Foo { set bar(value) { value = 1; } }",
+        "function foo(bar) { return () => { bar = 1; }; }"
+    },
+    ok: {
+        "function foo(bar) { const baz = bar; }",
+        "function foo(bar) { bar.baz = 1; }",
+        "function foo(bar) { delete bar.baz; }",
+        "function foo(bar) { function baz() { let bar = 1; bar = 2; } }",
+        "function foo(bar) { { let bar; bar = 1; } }",
+        "function foo() { bar = 13; }",
+        "let bar; function foo() { bar = 13; }"
+    }
+}
+
+rule_tests! {
+    props_valid,
+    props_invalid,
+    NoParamReassign { props: true, ignore_property_modifications_for: vec!["res".to_string()] },
+    err: {
+        "function foo(bar) { bar.baz = 1; }",
+        "function foo(bar) { bar[baz] += 1; }",
+        "function foo(bar) { bar.baz.qux = 1; }",
+        "function foo(bar) { (bar).baz++; }",
+        "function foo(bar) { delete bar.baz; }",
+        "function foo(bar) { for (bar.baz of qux) {} }",
+        "function foo({ bar }) { bar.baz = 1; }"
+    },
+    ok: {
+        "function foo(bar) { bar.baz(); }",
+        "function foo(bar) { qux = bar.baz; }",
+        "function foo(bar) { qux.bar = 1; }",
+        "function foo(bar) { baz[bar] = 1; }",
+        "function foo(req, res) { res.status = 404; }"
+    }
+}
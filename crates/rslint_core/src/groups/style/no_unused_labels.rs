@@ -0,0 +1,126 @@
+use crate::rule_prelude::*;
+use ast::LabelledStmt;
+use rslint_parser::TextRange;
+use util::scope::is_function_scope;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow labels which are never used.
+
+    A label which is not targeted by any `break` or `continue` statement does nothing, it is usually left over
+    from a refactor or a typo of an object literal.
+
+    ## Invalid Code Examples
+
+    ```js
+    A: var foo = 0;
+    ```
+
+    ```js
+    A: {
+        foo();
+        bar();
+    }
+    ```
+
+    ```js
+    A: for (let i = 0; i < 10; i++) {
+        if (i === 5) {
+            break;
+        }
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    A: for (let i = 0; i < 10; i++) {
+        for (let j = 0; j < 10; j++) {
+            if (i === j) {
+                continue A;
+            }
+        }
+    }
+    ```
+    */
+    #[derive(Default)]
+    NoUnusedLabels,
+    style,
+    "no-unused-labels"
+}
+
+#[typetag::serde]
+impl CstRule for NoUnusedLabels {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let stmt = node.try_to::<LabelledStmt>()?;
+        let label = stmt.label()?;
+        if is_label_used(&stmt, &label.text()) {
+            return None;
+        }
+
+        // the label is only removed if there are no comments between it and its statement
+        let body = stmt.stmt()?;
+        let removed = TextRange::new(label.range().start(), body.syntax().text_range().start());
+        let has_comments = node
+            .children_with_tokens()
+            .filter_map(|elem| elem.into_token())
+            .any(|token| token.kind() == COMMENT);
+        if has_comments {
+            ctx.diagnostic(
+                label.range(),
+                format!("`{}` is defined but never used", label.text()),
+            )
+            .emit();
+        } else {
+            ctx.fix().delete(removed);
+            ctx.diagnostic(
+                label.range(),
+                format!("`{}` is defined but never used", label.text()),
+            )
+            .suggestion(removed, "remove the label", "", Applicability::Always)
+            .emit();
+        }
+        None
+    }
+}
+
+/// Whether any `break` or `continue` inside of the labelled statement targets its label.
+fn is_label_used(stmt: &LabelledStmt, label: &str) -> bool {
+    let mut used = false;
+    stmt.syntax().descendants_with(&mut |node| {
+        // labels can not be targeted from inside of functions
+        if used || is_function_scope(node) {
+            return false;
+        }
+        if matches!(node.kind(), BREAK_STMT | CONTINUE_STMT) {
+            used = node
+                .child_with_kind(NAME_REF)
+                .is_some_and(|target| target.text() == label);
+        }
+        true
+    });
+    used
+}
+
+rule_tests! {
+    NoUnusedLabels::default(),
+    err: {
+        "A: var foo = 0;",
+        "A: { foo(); bar(); }",
+        "A: for (var i = 0; i < 10; i++) { foo(); if (i > 5) break; }",
+        "A: for (;;) { B: for (;;) { break A; } }",
+        "A: for (;;) { function foo() { A: while (bar) { break A; } } }",
+        "A: /* comment */ { foo(); }"
+    },
+    ok: {
+        "A: { if (foo()) { break A; } bar(); }",
+        "A: for (var i = 0; i < 10; ++i) { foo(); if (i > 5) break A; }",
+        "A: for (;;) { B: for (;;) { if (a) { continue A; } break B; } }",
+        "A: while (foo) { switch (bar) { case 1: break A; } }"
+    }
+}
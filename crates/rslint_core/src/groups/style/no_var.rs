@@ -0,0 +1,199 @@
+use crate::rule_prelude::*;
+use ast::{Name, VarDecl};
+use rslint_parser::TextRange;
+use util::scope::*;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Disallow `var` declarations in favor of `let` and `const`.
+
+    `var` declarations are scoped to the enclosing function rather than the enclosing block and
+    they are hoisted, which means they can be used before they are declared. `let` and `const` are
+    block scoped and cannot be used before their declaration, which makes code easier to reason about.
+
+    This rule automatically converts `var` to `const` if none of the declared variables are reassigned,
+    and to `let` otherwise. The fix is only applied if it does not change the behavior of the code,
+    for example, a variable used before it is declared or used outside of the block it is declared in
+    will only be reported with a suggestion.
+
+    ## Invalid Code Examples
+
+    ```js
+    var foo = 5;
+    ```
+
+    ```js
+    var bar = 1;
+    bar += 1;
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const foo = 5;
+    let bar = 1;
+    bar += 1;
+    ```
+    */
+    #[derive(Default)]
+    NoVar,
+    style,
+    "no-var"
+}
+
+#[typetag::serde]
+impl CstRule for NoVar {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let decl = node.try_to::<VarDecl>()?;
+        let var_token = decl.var_token()?;
+        let bindings = var_decl_bindings(&decl);
+        let fn_scope = enclosing_function_scope(node);
+
+        let keyword = if can_be_const(&decl) { "const" } else { "let" };
+
+        let err = ctx
+            .err(self.name(), "`var` declarations are not allowed")
+            .primary(var_token.text_range(), "");
+
+        if let Some((name, reason, range)) = conversion_hazard(&decl, &bindings, &fn_scope) {
+            let err = match range {
+                Some(range) => err.secondary(range, format!("`{}` {}", name, reason)),
+                None => err.footer_note(format!("`{}` {}", name, reason)),
+            };
+            ctx.add_suggestion(
+                err,
+                var_token.text_range(),
+                "converting this declaration may change the behavior of the code, but consider using",
+                keyword,
+            );
+        } else {
+            ctx.fix().replace(var_token.text_range(), keyword);
+            ctx.add_err(err.suggestion_inline(
+                var_token.text_range(),
+                "try using",
+                keyword,
+                Applicability::Always,
+            ));
+        }
+        None
+    }
+}
+
+/// Whether a declaration can be converted to `const`, that is, all of its declarators are initialized
+/// and none of the bindings are written to after the declaration.
+fn can_be_const(decl: &VarDecl) -> bool {
+    let declarators = never_reassigned_bindings(decl);
+    !declarators.is_empty()
+        && declarators
+            .iter()
+            .all(DeclaratorBindings::all_never_reassigned)
+}
+
+/// Check if converting a `var` declaration would change the behavior of the code, returns the
+/// offending name, the reason, and an optional range of a reference causing the hazard.
+fn conversion_hazard(
+    decl: &VarDecl,
+    bindings: &[Name],
+    fn_scope: &SyntaxNode,
+) -> Option<(String, &'static str, Option<TextRange>)> {
+    let node = decl.syntax();
+    let block = enclosing_block_scope(node);
+    let in_loop = node
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor != fn_scope)
+        .any(|ancestor| ancestor.is_loop());
+    // lexical declarations can not be the body of a statement such as `if (a) var b = 1;`
+    let in_statement_list = matches!(
+        node.parent().map(|parent| parent.kind()),
+        Some(BLOCK_STMT)
+            | Some(SCRIPT)
+            | Some(MODULE)
+            | Some(CASE_CLAUSE)
+            | Some(DEFAULT_CLAUSE)
+            | Some(FOR_STMT_INIT)
+            | Some(EXPORT_DECL)
+    );
+
+    for name in bindings {
+        let text = name.text();
+        let hazard = |reason, range| Some((text.clone(), reason, range));
+
+        if !in_statement_list {
+            return hazard(
+                "is declared as the body of a statement, where only `var` is allowed",
+                None,
+            );
+        }
+        if block.kind() == SWITCH_STMT {
+            return hazard("is declared in a switch case", None);
+        }
+        if block.kind() == SCRIPT {
+            return hazard("is a global variable", None);
+        }
+        if var_declarations(fn_scope, &text).len() > 1 {
+            return hazard("is declared multiple times", None);
+        }
+        if in_loop
+            && !matches!(node.parent().map(|p| p.kind()), Some(FOR_STMT_INIT))
+            && decl
+                .declared()
+                .any(|declarator| declarator.value().is_none())
+        {
+            return hazard(
+                "is not initialized and would be reset on every loop iteration",
+                None,
+            );
+        }
+
+        if let Some(reference) = use_outside_block(fn_scope, &block, &text) {
+            return hazard(
+                "is used outside of the block it is declared in",
+                Some(reference.range()),
+            );
+        }
+        if let Some(reference) = use_before_declaration(fn_scope, node, &text) {
+            return hazard("is used before it is declared", Some(reference.range()));
+        }
+        if in_loop || block.is_loop() {
+            let captured = references(fn_scope, &text)
+                .into_iter()
+                .find(|reference| &enclosing_function_scope(&reference.node) != fn_scope);
+            if let Some(reference) = captured {
+                return hazard(
+                    "is captured by a closure inside of a loop",
+                    Some(reference.range()),
+                );
+            }
+        }
+    }
+    None
+}
+
+rule_tests! {
+    NoVar::default(),
+    err: {
+        "var foo = 5;",
+        "var foo = 5, bar = 6; bar += 1;",
+        "function foo() { var { a, b: [c] } = d; }",
+        "function foo() { var a; a = 5; }",
+        "function foo() { a = 5; var a; }",
+        "function foo() { if (b) { var a = 5; } return a; }",
+        "for (var i = 0; i < 5; i++) { fns.push(() => i); }",
+        "function foo() { switch (a) { case 1: var b = 5; } }",
+        "function foo() { var a = 1; var a = 2; }",
+        "function foo() { if (x) var a = 1; }",
+        "function foo() { while (x) var a = 1; }"
+    },
+    ok: {
+        "let foo = 5;",
+        "const foo = 5;",
+        "for (let i = 0; i < 5; i++) {}",
+        "for (const a of b) {}"
+    }
+}
@@ -0,0 +1,183 @@
+use crate::rule_prelude::*;
+use ast::VarDecl;
+use util::scope::*;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Require `const` declarations for variables which are never reassigned.
+
+    If a variable declared with `let` is never reassigned after its declaration, using `const`
+    makes it clear to readers that the value will not change, and prevents accidental reassignment.
+
+    Declarations in which every variable can be `const` are automatically fixed. If only some of the
+    declarators in a declaration qualify, the declaration can be split into multiple declarations by
+    enabling `splitDeclarations`.
+
+    ## Invalid Code Examples
+
+    ```js
+    let a = 5;
+    console.log(a);
+    ```
+
+    ```js
+    for (let item of items) {
+        console.log(item);
+    }
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    const a = 5;
+    let b = 6;
+    b += a;
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    PreferConst,
+    style,
+    "prefer-const",
+    /// How destructuring patterns are treated, `"any"` (the default) reports any variable in a destructuring
+    /// pattern which could be `const` even if other variables in the pattern are reassigned, while `"all"` only
+    /// reports variables in a pattern if every variable in the pattern could be `const`.
+    pub destructuring: Destructuring,
+    /// Split declarations with multiple declarators if only some of them can be `const`, for example
+    /// `let a = 1, b = 2; b++;` is fixed to `const a = 1; let b = 2; b++;`.
+    pub split_declarations: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Destructuring {
+    Any,
+    All,
+}
+
+impl Default for Destructuring {
+    fn default() -> Self {
+        Destructuring::Any
+    }
+}
+
+#[typetag::serde]
+impl CstRule for PreferConst {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let decl = node.try_to::<VarDecl>()?;
+        let let_token = decl.let_token()?;
+        let declarators = never_reassigned_bindings(&decl);
+
+        let mut reported = false;
+        for declarator in &declarators {
+            if self.destructuring == Destructuring::All && !declarator.all_never_reassigned() {
+                continue;
+            }
+            for name in &declarator.never_reassigned {
+                let err = ctx
+                    .err(
+                        self.name(),
+                        format!("`{}` is never reassigned, use `const` instead", name.text()),
+                    )
+                    .primary(name.range(), "")
+                    .secondary(let_token.text_range(), "declared with `let` here");
+                ctx.add_err(err);
+                reported = true;
+            }
+        }
+        if !reported {
+            return None;
+        }
+
+        if declarators
+            .iter()
+            .all(DeclaratorBindings::all_never_reassigned)
+        {
+            ctx.fix().replace(let_token.text_range(), "const");
+        } else if self.split_declarations
+            && declarators
+                .iter()
+                .any(DeclaratorBindings::all_never_reassigned)
+            && matches!(
+                node.parent().map(|p| p.kind()),
+                Some(BLOCK_STMT)
+                    | Some(SCRIPT)
+                    | Some(MODULE)
+                    | Some(CASE_CLAUSE)
+                    | Some(DEFAULT_CLAUSE)
+            )
+        {
+            // group consecutive declarators so the order of evaluation is preserved
+            let mut groups: Vec<(bool, Vec<String>)> = vec![];
+            for declarator in &declarators {
+                let is_const = declarator.all_never_reassigned();
+                let text = declarator.declarator.syntax().trimmed_text().to_string();
+                match groups.last_mut() {
+                    Some((last, texts)) if *last == is_const => texts.push(text),
+                    _ => groups.push((is_const, vec![text])),
+                }
+            }
+            let new_text = groups
+                .into_iter()
+                .map(|(is_const, texts)| {
+                    format!(
+                        "{} {};",
+                        if is_const { "const" } else { "let" },
+                        texts.join(", ")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            ctx.fix().replace(node.trimmed_range(), new_text);
+        }
+        None
+    }
+}
+
+rule_tests! {
+    PreferConst::default(),
+    err: {
+        "let a = 5;",
+        "let a = 5, b = 6; b++;",
+        "let { a, b } = c; a = 5;",
+        "let { a, b: [c] } = d; a = 5;",
+        "for (let a of b) { console.log(a); }",
+        "for (let a in b) {}",
+        "function foo() { let a = 5; return () => a; }",
+        "let a = 1; function foo() { let a; a = 5; }",
+        "let a = 1; { let a; a = 5; }"
+    },
+    ok: {
+        "let a;",
+        "let a = 5; a = 6;",
+        "let a = 5; a += 6;",
+        "let a = 5; a++;",
+        "let [a] = b; [a] = c;",
+        "let { a } = b; ({ a } = c);",
+        "let a = 5; (a) = 6;",
+        "let a = 5; (a)--;",
+        "for (let i = 0; i < 5; i++) {}",
+        "for (let a of b) { a = 5; }",
+        "let a = 1; function foo() { a = 5; }",
+        "const a = 5;"
+    }
+}
+
+rule_tests! {
+    destructuring_all_valid,
+    destructuring_all_invalid,
+    PreferConst { destructuring: Destructuring::All, split_declarations: false },
+    err: {
+        "let { a, b } = c;",
+        "let a = 5, { b, c } = d; c = 6;"
+    },
+    ok: {
+        "let { a, b } = c; a = 5;",
+        "let [a, ...b] = c; b = [];"
+    }
+}
@@ -0,0 +1,302 @@
+use crate::rule_prelude::*;
+use ast::Template;
+use rslint_parser::TextRange;
+use SyntaxKind::*;
+
+declare_lint! {
+    /**
+    Enforce the consistent use of either double quotes, single quotes, or backticks for strings.
+
+    Strings which use the wrong quotes are fixed by changing their quotes, escaping any quotes inside of the
+    string which would end it and removing escapes which are no longer needed.
+
+    Template literals without placeholders or line breaks are reported unless backticks are preferred
+    or `allowTemplateLiterals` is enabled. Tagged templates are never reported. When backticks are preferred,
+    strings are allowed where template literals cannot be used: directives such as `"use strict"`, property
+    names, and the sources of imports and exports.
+
+    ## Invalid Code Examples
+
+    ```js
+    // with the default `"double"` style
+    let a = 'foo';
+    let b = `bar`;
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    // with the default `"double"` style
+    let a = "foo";
+    let b = `bar ${baz}`;
+    let c = tag`qux`;
+    ```
+
+    ```js
+    // with `avoidEscape` enabled
+    let a = 'a "quoted" word';
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    Quotes,
+    style,
+    "quotes",
+    /// The quotes strings should use, `"double"` (the default), `"single"`, or `"backtick"`.
+    pub style: QuoteStyle,
+    /// Allow strings to use other quotes if they contain the preferred quote, so it does not have to be
+    /// escaped, for example `'a "quoted" word'` with the `"double"` style.
+    pub avoid_escape: bool,
+    /// Allow template literals without placeholders even if backticks are not preferred.
+    pub allow_template_literals: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuoteStyle {
+    Double,
+    Single,
+    Backtick,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        QuoteStyle::Double
+    }
+}
+
+impl QuoteStyle {
+    fn quote(self) -> char {
+        match self {
+            QuoteStyle::Double => '"',
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Backtick => '`',
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            QuoteStyle::Double => "double quotes",
+            QuoteStyle::Single => "single quotes",
+            QuoteStyle::Backtick => "backticks",
+        }
+    }
+}
+
+#[typetag::serde]
+impl CstRule for Quotes {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_token(&self, token: &SyntaxToken, ctx: &mut RuleCtx) -> Option<()> {
+        if token.kind() != STRING {
+            return None;
+        }
+        let text = token.text().to_string();
+        let quote = text.chars().next()?;
+        if quote == self.style.quote() || text.len() < 2 {
+            return None;
+        }
+        if self.style == QuoteStyle::Backtick && !allows_template(token) {
+            return None;
+        }
+        self.report(ctx, token.text_range(), &text[1..text.len() - 1], quote)
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let template = node.try_to::<Template>()?;
+        if self.style == QuoteStyle::Backtick
+            || self.allow_template_literals
+            || template.tag().is_some()
+            || template.elements().next().is_some()
+        {
+            return None;
+        }
+        let range = template.template_range()?;
+        let text = template
+            .syntax()
+            .text()
+            .slice(range - node.text_range().start())
+            .to_string();
+        // unterminated templates have no closing backtick
+        if text.len() < 2 || !text.ends_with('`') || text.contains(&['\n', '\r'][..]) {
+            return None;
+        }
+        self.report(ctx, range, &text[1..text.len() - 1], '`')
+    }
+}
+
+impl Quotes {
+    fn report(
+        &self,
+        ctx: &mut RuleCtx,
+        range: TextRange,
+        content: &str,
+        quote: char,
+    ) -> Option<()> {
+        let preferred = self.style.quote();
+        if self.avoid_escape && content.contains(preferred) {
+            return None;
+        }
+
+        let requoted = requote(content, quote, preferred);
+        if let Some(requoted) = &requoted {
+            ctx.fix().replace(range, requoted);
+        }
+        let diagnostic = ctx
+            .diagnostic(
+                range,
+                format!("strings must use {}", self.style.description()),
+            )
+            .primary_label(format!("this should use {}", self.style.description()));
+        if requoted.is_none() {
+            diagnostic
+                .footer_note("this string cannot be fixed because it has escapes which are not allowed in template literals")
+                .emit();
+        } else {
+            diagnostic.emit();
+        }
+        None
+    }
+}
+
+/// Whether a string token could be a template literal, strings which are directives, property names, or
+/// module sources cannot be.
+fn allows_template(token: &SyntaxToken) -> bool {
+    let literal = match token.parent() {
+        literal if literal.kind() == LITERAL => literal,
+        _ => return false,
+    };
+    match literal.parent() {
+        Some(parent) => match parent.kind() {
+            EXPR_STMT => false,
+            LITERAL_PROP | METHOD | GETTER | SETTER => {
+                parent.first_child().as_ref() != Some(&literal)
+            }
+            _ => true,
+        },
+        None => true,
+    }
+}
+
+/// The content of a string with the quote `from` changed to the quote `to`, returns `None` if the string
+/// has octal escapes which template literals do not allow.
+fn requote(content: &str, from: char, to: char) -> Option<String> {
+    let mut requoted = String::with_capacity(content.len() + 2);
+    requoted.push(to);
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars.next()?;
+                let is_octal = escaped.is_ascii_digit()
+                    && (escaped != '0' || matches!(chars.peek(), Some(c) if c.is_ascii_digit()));
+                if to == '`' && is_octal {
+                    return None;
+                }
+                // escapes of the old quote, or of `$` in templates, are not needed anymore
+                if escaped == from || (from == '`' && escaped == '$') {
+                    if escaped == to {
+                        requoted.push('\\');
+                    }
+                    requoted.push(escaped);
+                } else {
+                    requoted.push('\\');
+                    requoted.push(escaped);
+                }
+            }
+            '$' if to == '`' && chars.peek() == Some(&'{') => requoted.push_str("\\$"),
+            c if c == to => {
+                requoted.push('\\');
+                requoted.push(c);
+            }
+            c => requoted.push(c),
+        }
+    }
+    requoted.push(to);
+    Some(requoted)
+}
+
+rule_tests! {
+    Quotes::default(),
+    err: {
+        "let a = 'foo';",
+        "let a = `foo`;",
+        "let a = 'a \"b\" c';",
+        "import a from 'a';",
+        "export * from 'a';",
+        "({ 'a': 1 });",
+        "'use strict';",
+        "foo('bar', `baz`);"
+    },
+    ok: {
+        "let a = \"foo\";",
+        "let a = `foo ${bar}`;",
+        "let a = tag`foo`;",
+        "let a = `foo\nbar`;",
+        "import a from \"a\";",
+        "let a = 5;"
+    }
+}
+
+rule_tests! {
+    single_valid,
+    single_invalid,
+    Quotes {
+        style: QuoteStyle::Single,
+        avoid_escape: true,
+        allow_template_literals: false,
+    },
+    err: {
+        "let a = \"foo\";",
+        "let a = `foo`;"
+    },
+    ok: {
+        "let a = 'foo';",
+        "let a = \"it's\";",
+        "let a = `it's`;",
+        "let a = `${a}`;"
+    }
+}
+
+rule_tests! {
+    backtick_valid,
+    backtick_invalid,
+    Quotes {
+        style: QuoteStyle::Backtick,
+        avoid_escape: false,
+        allow_template_literals: false,
+    },
+    err: {
+        "let a = 'foo';",
+        "let a = \"foo\";",
+        "foo(\"a\", 'b');",
+        "({ ['a']: 1 });"
+    },
+    ok: {
+        "let a = `foo`;",
+        "'use strict';",
+        "import a from 'a';",
+        "export { a } from \"a\";",
+        "({ 'a': 1, \"b\"() {} });",
+        "class A { 'a'() {} }"
+    }
+}
+
+rule_tests! {
+    templates_valid,
+    templates_invalid,
+    Quotes {
+        style: QuoteStyle::Double,
+        avoid_escape: false,
+        allow_template_literals: true,
+    },
+    err: {
+        "let a = 'foo';"
+    },
+    ok: {
+        "let a = `foo`;",
+        "let a = \"foo\";"
+    }
+}
@@ -0,0 +1,155 @@
+use crate::rule_prelude::*;
+use util::asi::{can_remove_semicolon, needs_semicolon, terminator};
+use util::tokens::last_non_trivia;
+
+declare_lint! {
+    /**
+    Require or disallow semicolons at the end of statements.
+
+    JavaScript inserts semicolons at the end of lines which end a statement, this is called automatic
+    semicolon insertion (ASI). Some projects always write semicolons, others only write them where ASI would
+    not insert one. This rule enforces one of the two styles.
+
+    With the `"never"` mode, semicolons are only reported if removing them is safe: the next statement must
+    start on another line, and must not start with a token such as `(`, `[`, or a template literal, which would
+    make it part of the previous statement.
+
+    ## Invalid Code Examples
+
+    ```js
+    // with the default `"always"` mode
+    let a = 5
+    foo()
+    ```
+
+    ```js
+    // with the `"never"` mode
+    let a = 5;
+    foo();
+    ```
+
+    ## Correct Code Examples
+
+    ```js
+    // with the default `"always"` mode
+    let a = 5;
+    foo();
+    ```
+
+    ```js
+    // with the `"never"` mode
+    let a = 5
+    foo();
+    [1, 2].forEach(bar)
+    ```
+    */
+    #[derive(Default)]
+    #[serde(default)]
+    Semi,
+    style,
+    "semi",
+    /// Either `"always"` (the default) which requires semicolons at the end of statements, or `"never"`
+    /// which disallows semicolons which are not needed.
+    pub mode: SemiMode
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SemiMode {
+    Always,
+    Never,
+}
+
+impl Default for SemiMode {
+    fn default() -> Self {
+        SemiMode::Always
+    }
+}
+
+#[typetag::serde]
+impl CstRule for Semi {
+    fn fixable(&self) -> bool {
+        true
+    }
+
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if !needs_semicolon(node) {
+            return None;
+        }
+        let semicolon = terminator(node);
+
+        match (self.mode, semicolon) {
+            (SemiMode::Always, None) => {
+                let last = last_non_trivia(node)?;
+                ctx.fix().insert_after(last.text_range(), ";");
+                ctx.diagnostic(last.text_range(), "missing semicolon")
+                    .primary_label("add a semicolon after this")
+                    .emit();
+            }
+            (SemiMode::Never, Some(semicolon)) if can_remove_semicolon(&semicolon) => {
+                ctx.fix().delete(semicolon.text_range());
+                ctx.diagnostic(semicolon.text_range(), "unnecessary semicolon")
+                    .primary_label("this semicolon can be removed")
+                    .emit();
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+rule_tests! {
+    Semi::default(),
+    err: {
+        "let a = 5",
+        "foo()",
+        "function foo() { return }",
+        "throw err",
+        "for (;;) { break }",
+        "for (;;) { continue }",
+        "debugger",
+        "do {} while (a)",
+        "let a = 5\nfoo();",
+        "a = b // comment",
+        "export { a }",
+        "export * from 'a'"
+    },
+    ok: {
+        "let a = 5;",
+        "foo();",
+        "for (let i = 0; i < 5; i++) {}",
+        "for (const a of b) {}",
+        "function foo() {}",
+        "class A {}",
+        "if (a) {}",
+        "do {} while (a);",
+        "export { a };",
+        "export { a } from 'a';"
+    }
+}
+
+rule_tests! {
+    never_valid,
+    never_invalid,
+    Semi { mode: SemiMode::Never },
+    err: {
+        "let a = 5;",
+        "foo();\nbar()",
+        "function foo() { return; }",
+        "foo(); // comment\nbar()",
+        "do {} while (a);",
+        "export { a };\nfoo()"
+    },
+    ok: {
+        "let a = 5",
+        "foo()\n;[1, 2].forEach(bar)",
+        "foo();\n[1, 2].forEach(bar)",
+        "foo();\n(a || b).c()",
+        "foo();\n`a`.length",
+        "foo();\n/a/.test(b)",
+        "foo();\n-a",
+        "foo(); bar()",
+        "if (a) b; else c",
+        "for (let i = 0; i < 5; i++) {}"
+    }
+}
@@ -0,0 +1,76 @@
+//! A static interval tree for looking up the diagnostics which overlap an offset or range.
+//!
+//! Entries are sorted by their start and the tree is implicit, the root of a slice of entries is its middle
+//! entry and every entry stores the largest end of its subtree. Queries skip every subtree which ends before
+//! the queried range, which makes them logarithmic in the number of entries plus the number of results.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+struct Entry<K> {
+    start: usize,
+    end: usize,
+    max_end: usize,
+    key: K,
+}
+
+/// An index of keys by the range they cover. Ranges are inclusive on both ends so a range touching
+/// the queried range is included, this is what editors expect for a cursor at the end of a diagnostic.
+#[derive(Debug, Clone)]
+pub(crate) struct IntervalIndex<K> {
+    entries: Vec<Entry<K>>,
+}
+
+impl<K: Copy> IntervalIndex<K> {
+    pub fn new(ranges: impl IntoIterator<Item = (Range<usize>, K)>) -> Self {
+        let mut entries = ranges
+            .into_iter()
+            .map(|(range, key)| Entry {
+                start: range.start,
+                end: range.end,
+                max_end: range.end,
+                key,
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| (entry.start, entry.end));
+        fill_max_end(&mut entries);
+        Self { entries }
+    }
+
+    /// The keys of every range overlapping `range`, sorted by the start of their range.
+    pub fn overlapping(&self, range: Range<usize>) -> Vec<K> {
+        let mut out = vec![];
+        query(&self.entries, &range, &mut out);
+        out
+    }
+}
+
+fn fill_max_end<K>(entries: &mut [Entry<K>]) -> usize {
+    if entries.is_empty() {
+        return 0;
+    }
+    let mid = entries.len() / 2;
+    let (left, rest) = entries.split_at_mut(mid);
+    let (root, right) = rest.split_first_mut().unwrap();
+    root.max_end = root.end.max(fill_max_end(left)).max(fill_max_end(right));
+    root.max_end
+}
+
+fn query<K: Copy>(entries: &[Entry<K>], range: &Range<usize>, out: &mut Vec<K>) {
+    if entries.is_empty() {
+        return;
+    }
+    let mid = entries.len() / 2;
+    let root = &entries[mid];
+    if root.max_end < range.start {
+        return;
+    }
+    query(&entries[..mid], range, out);
+    // everything to the right starts after the root, so it can only overlap if the root starts in the range
+    if root.start <= range.end {
+        if root.end >= range.start {
+            out.push(root.key);
+        }
+        query(&entries[mid + 1..], range, out);
+    }
+}
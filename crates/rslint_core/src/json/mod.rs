@@ -0,0 +1,196 @@
+//! Linting of JSON and JSONC files such as `package.json` and `tsconfig.json`.
+//!
+//! JSON files are parsed by a small parser into a [`JsonValue`] tree and checked by [`JsonRule`]s, which are
+//! separate from [`CstRule`](crate::CstRule)s since they do not run on a JavaScript syntax tree. The diagnostics
+//! of [`lint_json`] are regular [`Diagnostic`]s, so they can be reported with any formatter.
+//!
+//! Projects can validate their config files against a schema by implementing [`SchemaValidator`] and adding it
+//! to the store with [`JsonRuleStore::schema`].
+
+mod parser;
+mod rules;
+
+pub use rules::{
+    DuplicateKeys, Schema, SchemaValidator, SchemaViolation, TrailingCommas, TrailingCommasPolicy,
+};
+
+use crate::Diagnostic;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::path::Path;
+
+/// The dialect of a JSON file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonKind {
+    /// Strict JSON, comments are syntax errors.
+    Json,
+    /// JSON with comments, as used by editors and `tsconfig.json`.
+    Jsonc,
+}
+
+impl JsonKind {
+    /// The dialect of a file by its extension, `None` if it is not a JSON file.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(JsonKind::Json),
+            "jsonc" => Some(JsonKind::Jsonc),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed JSON value and its range in the source code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonValue {
+    pub kind: JsonValueKind,
+    pub range: Range<usize>,
+    /// The range of the comma after the last element if this is an array or object with a trailing comma.
+    pub trailing_comma: Option<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueKind {
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// An unescaped string.
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<JsonMember>),
+}
+
+/// A key and value of an object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonMember {
+    /// The unescaped key.
+    pub key: String,
+    /// The range of the key including its quotes.
+    pub key_range: Range<usize>,
+    pub value: JsonValue,
+}
+
+impl JsonValue {
+    /// This value and every value nested in it, parents before their children.
+    pub fn descendants(&self) -> Vec<&JsonValue> {
+        let mut values = vec![self];
+        let mut idx = 0;
+        while let Some(value) = values.get(idx) {
+            match &value.kind {
+                JsonValueKind::Array(elements) => values.extend(elements.iter()),
+                JsonValueKind::Object(members) => {
+                    values.extend(members.iter().map(|member| &member.value))
+                }
+                _ => {}
+            }
+            idx += 1;
+        }
+        values
+    }
+
+    /// The value of a key if this is an object, the last value is used if the key is duplicated.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match &self.kind {
+            JsonValueKind::Object(members) => members
+                .iter()
+                .rev()
+                .find(|member| member.key == key)
+                .map(|member| &member.value),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed JSON file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDocument<'a> {
+    pub file_id: usize,
+    pub src: &'a str,
+    pub kind: JsonKind,
+    /// The value of the document, `None` if the document has a syntax error.
+    pub value: Option<JsonValue>,
+    /// The ranges of the comments in the document, comments are only allowed in JSONC.
+    pub comments: Vec<Range<usize>>,
+}
+
+/// A rule which checks a JSON document.
+///
+/// The names of JSON rules start with `json/` so they can not be confused with the names of JavaScript rules.
+pub trait JsonRule: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Check a document which was parsed without syntax errors.
+    fn check(&self, doc: &JsonDocument, value: &JsonValue, diagnostics: &mut Vec<Diagnostic>);
+}
+
+/// The JSON rules to run on JSON files.
+#[derive(Debug, Default)]
+pub struct JsonRuleStore {
+    pub rules: Vec<Box<dyn JsonRule>>,
+}
+
+impl JsonRuleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every built in JSON rule with its default config.
+    pub fn builtins(mut self) -> Self {
+        self.rules.push(Box::new(DuplicateKeys));
+        self.rules.push(Box::new(TrailingCommas::default()));
+        self
+    }
+
+    /// Add a rule which validates documents against a schema.
+    pub fn schema(mut self, validator: impl SchemaValidator + 'static) -> Self {
+        self.rules.push(Box::new(Schema::new(validator)));
+        self
+    }
+
+    /// Replace a rule which has the same name as `rule`, or add it if there is no such rule.
+    pub fn set(&mut self, rule: Box<dyn JsonRule>) {
+        self.rules.retain(|existing| existing.name() != rule.name());
+        self.rules.push(rule);
+    }
+}
+
+/// Parse a JSON document without linting it.
+pub fn parse_json(
+    file_id: usize,
+    src: &str,
+    kind: JsonKind,
+) -> (JsonDocument<'_>, Vec<Diagnostic>) {
+    let mut parser = parser::Parser::new(src, file_id, kind);
+    let value = parser.parse();
+    let doc = JsonDocument {
+        file_id,
+        src,
+        kind,
+        value,
+        comments: parser.comments,
+    };
+    (doc, parser.diagnostics)
+}
+
+/// Parse and lint a JSON document, returning the syntax errors or the diagnostics of the rules.
+///
+/// # Examples
+/// ```
+/// use rslint_core::json::{lint_json, JsonKind, JsonRuleStore};
+///
+/// let store = JsonRuleStore::new().builtins();
+/// let diagnostics = lint_json(0, r#"{ "a": 1, "a": 2 }"#, JsonKind::Json, &store);
+/// assert_eq!(diagnostics[0].code.as_deref(), Some("json/duplicate-keys"));
+/// ```
+pub fn lint_json(
+    file_id: usize,
+    src: &str,
+    kind: JsonKind,
+    store: &JsonRuleStore,
+) -> Vec<Diagnostic> {
+    let (doc, mut diagnostics) = parse_json(file_id, src, kind);
+    if let Some(value) = &doc.value {
+        for rule in &store.rules {
+            rule.check(&doc, value, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
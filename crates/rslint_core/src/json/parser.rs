@@ -0,0 +1,345 @@
+//! A small recursive descent parser for JSON and JSONC.
+//!
+//! The parser stops at the first syntax error, config files are small and usually only have one error at a time.
+//! Trailing commas are always accepted by the parser and recorded so the `trailing-commas` rule can report them
+//! according to its policy.
+
+use super::{JsonKind, JsonMember, JsonValue, JsonValueKind};
+use crate::Diagnostic;
+use std::ops::Range;
+
+pub(super) struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    file_id: usize,
+    kind: JsonKind,
+    pub(super) comments: Vec<Range<usize>>,
+    pub(super) diagnostics: Vec<Diagnostic>,
+}
+
+/// A syntax error, the diagnostic is already recorded when this is returned.
+struct Error;
+
+type Result<T> = std::result::Result<T, Error>;
+
+impl<'a> Parser<'a> {
+    pub(super) fn new(src: &'a str, file_id: usize, kind: JsonKind) -> Self {
+        Self {
+            src,
+            pos: 0,
+            file_id,
+            kind,
+            comments: vec![],
+            diagnostics: vec![],
+        }
+    }
+
+    /// Parse the document, which is a single value.
+    pub(super) fn parse(&mut self) -> Option<JsonValue> {
+        // a byte order mark is allowed by editors and node's `require`
+        if self.src.starts_with('\u{feff}') {
+            self.pos = '\u{feff}'.len_utf8();
+        }
+        let value = self.value().ok()?;
+        self.trivia().ok()?;
+        if self.pos < self.src.len() {
+            self.err(
+                self.pos..self.src.len(),
+                "expected the end of the file",
+                "the document ends after the first value",
+            );
+            return None;
+        }
+        Some(value)
+    }
+
+    fn err(&mut self, range: Range<usize>, title: &str, label: &str) -> Error {
+        let diagnostic =
+            Diagnostic::error(self.file_id, "SyntaxError", title).primary(range, label);
+        self.diagnostics.push(diagnostic);
+        Error
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn unexpected(&mut self, expected: &str) -> Error {
+        match self.peek() {
+            Some(c) => {
+                let range = self.pos..self.pos + c.len_utf8();
+                self.err(
+                    range,
+                    &format!("expected {} but found `{}`", expected, c),
+                    "",
+                )
+            }
+            None => self.err(
+                self.src.len()..self.src.len(),
+                &format!("expected {} but the file ended", expected),
+                "",
+            ),
+        }
+    }
+
+    /// Skip whitespace and comments.
+    fn trivia(&mut self) -> Result<()> {
+        loop {
+            let rest = &self.src[self.pos..];
+            let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+            self.pos += rest.len() - trimmed.len();
+
+            let start = self.pos;
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                match comment.find("*/") {
+                    Some(end) => self.pos += end + 4,
+                    None => {
+                        return Err(self.err(
+                            start..self.src.len(),
+                            "unterminated block comment",
+                            "this comment is never closed",
+                        ))
+                    }
+                }
+            } else {
+                return Ok(());
+            }
+            if self.kind == JsonKind::Json {
+                return Err(self.err(start..self.pos, "comments are not allowed in JSON", ""));
+            }
+            self.comments.push(start..self.pos);
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue> {
+        self.trivia()?;
+        let start = self.pos;
+        let kind = match self.peek() {
+            Some('{') => return self.object(),
+            Some('[') => return self.array(),
+            Some('"') => JsonValueKind::String(self.string()?),
+            Some('-') | Some('0'..='9') => JsonValueKind::Number(self.number()?),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let word = self.src[start..]
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .next()
+                    .unwrap_or_default();
+                let kind = match word {
+                    "true" => JsonValueKind::Bool(true),
+                    "false" => JsonValueKind::Bool(false),
+                    "null" => JsonValueKind::Null,
+                    _ => {
+                        return Err(self.err(
+                            start..start + word.len(),
+                            &format!("expected a value but found `{}`", word),
+                            "",
+                        ))
+                    }
+                };
+                self.pos += word.len();
+                kind
+            }
+            _ => return Err(self.unexpected("a value")),
+        };
+        Ok(JsonValue {
+            kind,
+            range: start..self.pos,
+            trailing_comma: None,
+        })
+    }
+
+    fn object(&mut self) -> Result<JsonValue> {
+        let start = self.pos;
+        self.eat('{');
+        let mut members = vec![];
+        let mut trailing_comma = None;
+        loop {
+            self.trivia()?;
+            if self.eat('}') {
+                break;
+            }
+            if self.peek() != Some('"') {
+                return Err(self.unexpected("a string key or `}`"));
+            }
+            let key_start = self.pos;
+            let key = self.string()?;
+            let key_range = key_start..self.pos;
+            self.trivia()?;
+            if !self.eat(':') {
+                return Err(self.unexpected("`:`"));
+            }
+            let value = self.value()?;
+            members.push(JsonMember {
+                key,
+                key_range,
+                value,
+            });
+
+            self.trivia()?;
+            let comma = self.pos;
+            if self.eat(',') {
+                self.trivia()?;
+                if self.peek() == Some('}') {
+                    trailing_comma = Some(comma..comma + 1);
+                }
+            } else if self.eat('}') {
+                break;
+            } else {
+                return Err(self.unexpected("`,` or `}`"));
+            }
+        }
+        Ok(JsonValue {
+            kind: JsonValueKind::Object(members),
+            range: start..self.pos,
+            trailing_comma,
+        })
+    }
+
+    fn array(&mut self) -> Result<JsonValue> {
+        let start = self.pos;
+        self.eat('[');
+        let mut elements = vec![];
+        let mut trailing_comma = None;
+        loop {
+            self.trivia()?;
+            if self.eat(']') {
+                break;
+            }
+            elements.push(self.value()?);
+
+            self.trivia()?;
+            let comma = self.pos;
+            if self.eat(',') {
+                self.trivia()?;
+                if self.peek() == Some(']') {
+                    trailing_comma = Some(comma..comma + 1);
+                }
+            } else if self.eat(']') {
+                break;
+            } else {
+                return Err(self.unexpected("`,` or `]`"));
+            }
+        }
+        Ok(JsonValue {
+            kind: JsonValueKind::Array(elements),
+            range: start..self.pos,
+            trailing_comma,
+        })
+    }
+
+    /// Parse a string and unescape it.
+    fn string(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.eat('"');
+        let mut string = String::new();
+        loop {
+            let c = match self.peek() {
+                Some(c) if c != '\n' => c,
+                _ => {
+                    return Err(self.err(
+                        start..self.pos,
+                        "unterminated string",
+                        "this string is never closed",
+                    ))
+                }
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escape_start = self.pos - 1;
+                    let unescaped = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let hex = self.src.get(self.pos + 1..self.pos + 5).unwrap_or("");
+                            match u32::from_str_radix(hex, 16) {
+                                Ok(code) if hex.len() == 4 => {
+                                    self.pos += 4;
+                                    // surrogates are kept as the replacement character, keys are only compared
+                                    std::char::from_u32(code).unwrap_or('\u{fffd}')
+                                }
+                                _ => {
+                                    return Err(self.err(
+                                        escape_start..self.pos + 1,
+                                        "invalid unicode escape",
+                                        "expected 4 hex digits",
+                                    ))
+                                }
+                            }
+                        }
+                        _ => {
+                            let end = self.pos + self.peek().map_or(0, char::len_utf8);
+                            return Err(self.err(escape_start..end, "invalid escape sequence", ""));
+                        }
+                    };
+                    self.pos += 1;
+                    string.push(unescaped);
+                }
+                c if (c as u32) < 0x20 => {
+                    return Err(self.err(
+                        self.pos - 1..self.pos,
+                        "control characters must be escaped in strings",
+                        "",
+                    ))
+                }
+                c => string.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        let start = self.pos;
+        let bytes = self.src.as_bytes();
+        let digits = |pos: &mut usize| {
+            let digits_start = *pos;
+            while *pos < bytes.len() && bytes[*pos].is_ascii_digit() {
+                *pos += 1;
+            }
+            *pos - digits_start
+        };
+
+        let mut pos = self.pos;
+        if bytes[pos] == b'-' {
+            pos += 1;
+        }
+        let int_start = pos;
+        let int_digits = digits(&mut pos);
+        let mut valid = int_digits > 0 && !(int_digits > 1 && bytes[int_start] == b'0');
+        if pos < bytes.len() && bytes[pos] == b'.' {
+            pos += 1;
+            valid &= digits(&mut pos) > 0;
+        }
+        if pos < bytes.len() && matches!(bytes[pos], b'e' | b'E') {
+            pos += 1;
+            if pos < bytes.len() && matches!(bytes[pos], b'+' | b'-') {
+                pos += 1;
+            }
+            valid &= digits(&mut pos) > 0;
+        }
+        self.pos = pos;
+
+        let text = &self.src[start..pos];
+        match text.parse() {
+            Ok(num) if valid => Ok(num),
+            _ => Err(self.err(start..pos, &format!("invalid number `{}`", text), "")),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! The built in JSON rules.
+
+use super::{JsonDocument, JsonKind, JsonRule, JsonValue, JsonValueKind};
+use crate::Diagnostic;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// Disallow duplicate keys in objects, only the last value of a duplicated key is used by most parsers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DuplicateKeys;
+
+impl JsonRule for DuplicateKeys {
+    fn name(&self) -> &'static str {
+        "json/duplicate-keys"
+    }
+
+    fn check(&self, doc: &JsonDocument, value: &JsonValue, diagnostics: &mut Vec<Diagnostic>) {
+        for value in value.descendants() {
+            let members = match &value.kind {
+                JsonValueKind::Object(members) => members,
+                _ => continue,
+            };
+            let mut seen = HashMap::new();
+            for member in members {
+                match seen.get(member.key.as_str()) {
+                    Some(first) => {
+                        let err = Diagnostic::error(
+                            doc.file_id,
+                            self.name(),
+                            format!("duplicate key `{}`", member.key),
+                        )
+                        .secondary(Range::clone(first), "this key is first defined here...")
+                        .primary(member.key_range.clone(), "...and redefined here")
+                        .footer_note("only the value of the last key is used");
+                        diagnostics.push(err);
+                    }
+                    None => {
+                        seen.insert(member.key.as_str(), member.key_range.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// When trailing commas are allowed in arrays and objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrailingCommasPolicy {
+    /// Never allow trailing commas.
+    #[default]
+    Never,
+    /// Require trailing commas in arrays and objects which span multiple lines and disallow them otherwise.
+    /// Strict JSON never allows trailing commas, so this only applies to JSONC files.
+    AlwaysMultiline,
+}
+
+/// Enforce a policy for trailing commas in arrays and objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TrailingCommas {
+    pub policy: TrailingCommasPolicy,
+}
+
+impl JsonRule for TrailingCommas {
+    fn name(&self) -> &'static str {
+        "json/trailing-commas"
+    }
+
+    fn check(&self, doc: &JsonDocument, value: &JsonValue, diagnostics: &mut Vec<Diagnostic>) {
+        let policy = match doc.kind {
+            JsonKind::Json => TrailingCommasPolicy::Never,
+            JsonKind::Jsonc => self.policy,
+        };
+        for value in value.descendants() {
+            let last = match &value.kind {
+                JsonValueKind::Array(elements) => elements.last().map(|elem| elem.range.end),
+                JsonValueKind::Object(members) => {
+                    members.last().map(|member| member.value.range.end)
+                }
+                _ => continue,
+            };
+            let multiline = doc.src[value.range.clone()].contains('\n');
+            match (&value.trailing_comma, last) {
+                (Some(comma), _) if policy == TrailingCommasPolicy::Never || !multiline => {
+                    let err =
+                        Diagnostic::error(doc.file_id, self.name(), "unexpected trailing comma")
+                            .primary(comma.clone(), "");
+                    let err = if doc.kind == JsonKind::Json {
+                        err.footer_note("trailing commas are not allowed in JSON")
+                    } else {
+                        err
+                    };
+                    diagnostics.push(err);
+                }
+                (None, Some(last))
+                    if policy == TrailingCommasPolicy::AlwaysMultiline && multiline =>
+                {
+                    let err = Diagnostic::error(doc.file_id, self.name(), "missing trailing comma")
+                        .primary(last..last, "expected a `,` after the last element");
+                    diagnostics.push(err);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A value which does not match a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// The range of the value or key which does not match.
+    pub range: Range<usize>,
+    pub message: String,
+}
+
+/// A hook for validating JSON documents against a schema, for example by dispatching on the
+/// name of the file to the schema of `package.json` or `tsconfig.json`.
+pub trait SchemaValidator: Send + Sync {
+    fn validate(&self, doc: &JsonDocument, value: &JsonValue) -> Vec<SchemaViolation>;
+}
+
+/// Report the violations of a [`SchemaValidator`].
+pub struct Schema {
+    validator: Box<dyn SchemaValidator>,
+}
+
+impl Schema {
+    pub fn new(validator: impl SchemaValidator + 'static) -> Self {
+        Self {
+            validator: Box::new(validator),
+        }
+    }
+}
+
+impl fmt::Debug for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Schema").finish()
+    }
+}
+
+impl JsonRule for Schema {
+    fn name(&self) -> &'static str {
+        "json/schema"
+    }
+
+    fn check(&self, doc: &JsonDocument, value: &JsonValue, diagnostics: &mut Vec<Diagnostic>) {
+        for violation in self.validator.validate(doc, value) {
+            let err = Diagnostic::error(doc.file_id, self.name(), violation.message)
+                .primary(violation.range, "");
+            diagnostics.push(err);
+        }
+    }
+}
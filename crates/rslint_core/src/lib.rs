@@ -26,33 +26,147 @@
 //! ⚠️ note however that many rules rely on checking tokens or the root and running on single nodes
 //! may yield incorrect results, you should only do this if you know about the rule's implementation.
 
+mod index;
+mod results;
 mod rule;
 mod store;
 mod testing;
 
 pub mod autofix;
+pub mod baseline;
+pub mod cache;
+pub mod code_climate;
+pub mod coverage;
+pub mod daemon;
+pub mod degraded;
+pub mod determinism;
 pub mod directives;
+pub mod docgen;
+pub mod environment;
+pub mod extract;
+pub mod grouping;
 pub mod groups;
+pub mod json;
+pub mod meta;
+pub mod plugin;
+pub mod preset;
+pub mod profile;
+pub mod project;
+pub mod project_rules;
+pub mod remap;
+pub mod rename;
 pub mod rule_prelude;
+#[cfg(all(test, target_os = "linux"))]
+pub(crate) mod sandbox;
+pub mod sarif;
+pub mod session;
+pub mod trend;
 pub mod util;
 
 pub use self::{
-    rule::{CstRule, Outcome, Rule, RuleCtx, RuleLevel, RuleResult},
+    meta::RuleMeta,
+    results::RuleResults,
+    rule::{
+        CstRule, FailurePolicy, Outcome, OutcomePolicy, OutcomeSummary, ProjectRule, Rule, RuleCtx,
+        RuleDiagnostic, RuleLevel, RuleResult,
+    },
     store::CstRuleStore,
 };
 pub use rslint_errors::{Diagnostic, Severity, Span};
 
-use crate::directives::skip_node;
+use crate::autofix::FixPolicy;
 #[doc(inline)]
-pub use crate::directives::{apply_top_level_directives, Directive, DirectiveParser};
+pub use crate::determinism::lint_deterministic;
+#[doc(inline)]
+pub use crate::directives::{apply_top_level_directives, Directive, DirectiveParser, ParserMode};
+use crate::directives::{FileDirectives, LazyDirectives, SuppressionIndex, SuppressionStats};
+use crate::environment::{Environment, PackageType};
+use crate::grouping::{group_diagnostics, DiagnosticGroup};
+use crate::index::IntervalIndex;
+#[doc(hidden)]
+pub use crate::testing::run_rule_test as __run_rule_test;
+use crate::util::globals::Globals;
 use dyn_clone::clone_box;
+#[doc(hidden)]
+pub use inventory as __inventory;
 use rayon::prelude::*;
-use rslint_parser::{parse_module, parse_text, util::SyntaxNodeExt, SyntaxKind, SyntaxNode};
-use std::collections::HashMap;
-use std::sync::Arc;
+use rayon::ThreadPoolBuilder;
+use rslint_parser::{
+    parse_module, parse_text, util::SyntaxNodeExt, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Options which configure a single linting run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinterOptions {
+    /// Whether to include potentially spammy details in rule diagnostics.
+    pub verbose: bool,
+    /// A debugging option which shuffles the order rules are run in and the order top level
+    /// nodes are visited in using this seed.
+    ///
+    /// Rules must never depend on other rules or on the order nodes are visited in, running with
+    /// multiple seeds and comparing the diagnostics is used to catch violations of this.
+    pub shuffle_seed: Option<u64>,
+    /// Overrides for the severity of parser diagnostics by their code, the parser uses `SyntaxError`
+    /// for errors and `ParserWarning` for warnings. A severity of `None` removes the diagnostics.
+    ///
+    /// Overrides are applied before the outcome of the file is computed, so escalating warnings to
+    /// errors makes the file fail.
+    pub parser_severities: HashMap<String, Option<Severity>>,
+    /// Which fixes are applied when fixing the result, every fix is applied by default.
+    pub fix_policy: FixPolicy,
+    /// Add a `disabled-rules` note to files on which some rules of the store were not run, listing the
+    /// rules and why they were not run. Rules are not run on files they do not [apply to](CstRule::applies_to)
+    /// and on files linted in [degraded](degraded) mode.
+    pub report_disabled_rules: bool,
+    /// Keep the diagnostics suppressed by directives in [`RuleResult::suppressed`] instead of throwing
+    /// them out, for auditing what directives hide.
+    ///
+    /// Rules are then run on nodes which are suppressed by node level directives, and the results of rules
+    /// ignored in the whole file are kept with every diagnostic suppressed.
+    pub record_suppressed: bool,
+    /// The global variables of the file, the `/* global name */` comments of the file add to these.
+    pub globals: Globals,
+    /// The number of threads rules and files are linted on, `None` uses the current rayon pool.
+    ///
+    /// `Some(1)` lints on the calling thread without rayon, which makes the order rules run in deterministic and
+    /// works where threads can not be spawned, such as WASM. Other values run on a pool with that many threads
+    /// unless the current pool is already small enough, so hosts linting many files should lint them through
+    /// [`ProjectLinter`](project::ProjectLinter) or [`lint_files`](project::lint_files) which create one pool.
+    pub threads: Option<usize>,
+}
+
+/// The kind of a JavaScript file, which determines how it is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    Script,
+    Module,
+}
+
+impl FileKind {
+    /// The kind of a parsed file by the kind of its root.
+    pub fn of(root: &SyntaxNode) -> Self {
+        if root.kind() == SyntaxKind::MODULE {
+            FileKind::Module
+        } else {
+            FileKind::Script
+        }
+    }
+
+    /// Infer the kind of a file from its extension, `.mjs` files are modules and anything else is a script.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("mjs") => FileKind::Module,
+            _ => FileKind::Script,
+        }
+    }
+}
 
 /// The result of linting a file.
-// TODO: A lot of this stuff can be shoved behind a "linter options" struct
 #[derive(Debug, Clone)]
 pub struct LintResult<'s> {
     /// Any diagnostics (errors, warnings, etc) emitted from the parser
@@ -60,18 +174,39 @@ pub struct LintResult<'s> {
     /// The store used for the lint run
     pub store: &'s CstRuleStore,
     /// The diagnostics emitted by each rule run
-    pub rule_results: HashMap<&'static str, RuleResult>,
+    pub rule_results: RuleResults,
     /// Any warnings or errors emitted by the directive parser
     pub directive_diagnostics: Vec<Diagnostic>,
+    /// Counts of the directives in the file which suppress rules.
+    pub suppressions: SuppressionStats,
     pub parsed: SyntaxNode,
     pub file_id: usize,
-    pub verbose: bool,
+    /// The options used for the lint run
+    pub options: LinterOptions,
     pub fixed_code: Option<String>,
+    /// The top level declarations found by scanning tokens if the file had so many syntax errors that
+    /// it was linted in degraded mode, see the [`degraded`] module.
+    pub degraded_declarations: Option<Vec<degraded::TopLevelDeclaration>>,
+    /// The index used by [`diagnostics_in`](Self::diagnostics_in), built the first time it is needed.
+    pub(crate) diagnostic_index: OnceLock<IntervalIndex<DiagnosticKey>>,
+}
+
+/// Where a diagnostic of a [`LintResult`] is stored.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DiagnosticKey {
+    Parser(usize),
+    Rule(&'static str, usize),
+    Directive(usize),
 }
 
 impl LintResult<'_> {
+    /// The result of a rule by its name, `None` if the rule was not run on the file.
+    pub fn for_rule(&self, name: &str) -> Option<&RuleResult> {
+        self.rule_results.get(name)
+    }
+
     /// Get all of the diagnostics thrown during linting, in the order of parser diagnostics, then
-    /// the diagnostics of each rule sequentially.
+    /// the diagnostics of each rule in the order the rules were registered, then directive diagnostics.
     pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
         self.parser_diagnostics
             .iter()
@@ -84,11 +219,99 @@ impl LintResult<'_> {
             .chain(self.directive_diagnostics.iter())
     }
 
+    /// The diagnostics of [`diagnostics`](Self::diagnostics) grouped by the problem they report, so a syntax error
+    /// which rules also report or a span flagged by several rules is only shown once, see the [`grouping`] module.
+    pub fn grouped_diagnostics(&self) -> Vec<DiagnosticGroup<'_>> {
+        group_diagnostics(
+            &self.parser_diagnostics,
+            self.rule_results
+                .values()
+                .flat_map(|result| result.diagnostics.iter())
+                .chain(self.directive_diagnostics.iter()),
+        )
+    }
+
+    /// The diagnostics of each rule which were suppressed by directives, these are only kept if
+    /// [`LinterOptions::record_suppressed`] is set.
+    pub fn suppressed_diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.rule_results
+            .values()
+            .flat_map(|result| result.suppressed.iter())
+    }
+
+    /// The diagnostics whose primary label or suggestions touch `offset`, such as the diagnostics an editor
+    /// shows when hovering over a position.
+    pub fn diagnostics_at(&self, offset: usize) -> Vec<&Diagnostic> {
+        self.diagnostics_in(offset..offset)
+    }
+
+    /// The diagnostics whose primary label or suggestions overlap or touch `range`, sorted by their start.
+    ///
+    /// The first call builds an interval tree of the diagnostics, so lookups do not scan every diagnostic.
+    /// If diagnostics are added or removed through the fields of the result afterwards,
+    /// [`invalidate_index`](Self::invalidate_index) must be called.
+    pub fn diagnostics_in(&self, range: Range<usize>) -> Vec<&Diagnostic> {
+        let index = self.diagnostic_index.get_or_init(|| {
+            let parser = self
+                .parser_diagnostics
+                .iter()
+                .enumerate()
+                .map(|(idx, d)| (d, DiagnosticKey::Parser(idx)));
+            let rules = self.rule_results.iter().flat_map(|(name, result)| {
+                result
+                    .diagnostics
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx, d)| (d, DiagnosticKey::Rule(name, idx)))
+            });
+            let directives = self
+                .directive_diagnostics
+                .iter()
+                .enumerate()
+                .map(|(idx, d)| (d, DiagnosticKey::Directive(idx)));
+            IntervalIndex::new(
+                parser
+                    .chain(rules)
+                    .chain(directives)
+                    .filter_map(|(d, key)| Some((diagnostic_range(d)?, key))),
+            )
+        });
+        index
+            .overlapping(range)
+            .into_iter()
+            .filter_map(|key| match key {
+                DiagnosticKey::Parser(idx) => self.parser_diagnostics.get(idx),
+                DiagnosticKey::Rule(name, idx) => self.rule_results.get(name)?.diagnostics.get(idx),
+                DiagnosticKey::Directive(idx) => self.directive_diagnostics.get(idx),
+            })
+            .collect()
+    }
+
+    /// Drop the index used by [`diagnostics_in`](Self::diagnostics_in) so it is built again on the next lookup.
+    pub fn invalidate_index(&mut self) {
+        self.diagnostic_index = OnceLock::new();
+    }
+
+    /// Whether the file had so many syntax errors that it was linted in degraded mode.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded_declarations.is_some()
+    }
+
     /// The overall outcome of linting this file (failure, warning, success, etc)
     pub fn outcome(&self) -> Outcome {
         self.diagnostics().into()
     }
 
+    /// The number of diagnostics of each severity in this file.
+    pub fn summary(&self) -> OutcomeSummary {
+        self.diagnostics().into()
+    }
+
+    /// The outcome of linting this file under a policy, such as one which fails on warnings.
+    pub fn outcome_with(&self, policy: &OutcomePolicy) -> Outcome {
+        policy.outcome(self.diagnostics())
+    }
+
     /// Attempt to automatically fix any fixable issues and return the fixed code.
     ///
     /// This will not run if there are syntax errors unless `dirty` is set to true.
@@ -114,20 +337,72 @@ pub fn lint_file(
     store: &CstRuleStore,
     verbose: bool,
 ) -> Result<LintResult, Diagnostic> {
+    let options = LinterOptions {
+        verbose,
+        ..LinterOptions::default()
+    };
+    lint_file_with_options(file_id, file_source, module, store, options)
+}
+
+/// Lint a file with a specific rule store and linter options.
+///
+/// The file is parsed as a module if `module` is set, a `rslint-parser` directive at the top of the file
+/// overrides this.
+pub fn lint_file_with_options(
+    file_id: usize,
+    file_source: impl AsRef<str>,
+    module: bool,
+    store: &CstRuleStore,
+    options: LinterOptions,
+) -> Result<LintResult, Diagnostic> {
+    let (parser_diagnostics, root) = parse_file(file_id, file_source.as_ref(), module);
+    lint_file_inner(root, parser_diagnostics, file_id, store, options)
+}
+
+/// Parse a file as a module or a script, unless a `rslint-parser` directive at the top of the file
+/// asks for the other mode.
+pub(crate) fn parse_file(file_id: usize, src: &str, module: bool) -> (Vec<Diagnostic>, SyntaxNode) {
+    let _phase = profile::start_phase(profile::Phase::Parse);
+    let (parser_diagnostics, root) = parse_as(file_id, src, module);
+    let store = CstRuleStore::new();
+    let mode = DirectiveParser::new(root.clone(), file_id, &store).parser_mode();
+    match mode.and_then(ParserMode::kind) {
+        Some(kind) if kind != FileKind::of(&root) => {
+            parse_as(file_id, src, kind == FileKind::Module)
+        }
+        _ => (parser_diagnostics, root),
+    }
+}
+
+fn parse_as(file_id: usize, src: &str, module: bool) -> (Vec<Diagnostic>, SyntaxNode) {
     let (parser_diagnostics, green) = if module {
-        let parse = parse_module(file_source.as_ref(), file_id);
+        let parse = parse_module(src, file_id);
         (parse.errors().to_owned(), parse.green())
     } else {
-        let parse = parse_text(file_source.as_ref(), file_id);
+        let parse = parse_text(src, file_id);
         (parse.errors().to_owned(), parse.green())
     };
-    lint_file_inner(
-        SyntaxNode::new_root(green),
-        parser_diagnostics,
-        file_id,
-        store,
-        verbose,
-    )
+    (parser_diagnostics, SyntaxNode::new_root(green))
+}
+
+/// Lint source code read from a reader (such as stdin) as if it was the file at `pseudo_path`.
+///
+/// The kind and the globals of the file are inferred from `pseudo_path`, the source, and the `package.json`
+/// closest to `pseudo_path` (see [`environment`]). The file id of the result is `0`.
+pub fn lint_stdin(
+    mut reader: impl Read,
+    pseudo_path: impl AsRef<Path>,
+    store: &CstRuleStore,
+    mut options: LinterOptions,
+) -> Result<LintResult, Diagnostic> {
+    let mut src = String::new();
+    reader.read_to_string(&mut src).map_err(|err| {
+        Diagnostic::error(0, "io", format!("failed to read source code: {}", err))
+    })?;
+    let path = pseudo_path.as_ref();
+    let env = Environment::resolve(path, &src, PackageType::find(path));
+    options.globals = env.globals(&options.globals);
+    lint_file_with_options(0, src, env.kind == FileKind::Module, store, options)
 }
 
 /// used by lint_file and incrementally_relint to not duplicate code
@@ -136,58 +411,235 @@ pub(crate) fn lint_file_inner(
     parser_diagnostics: Vec<Diagnostic>,
     file_id: usize,
     store: &CstRuleStore,
-    verbose: bool,
+    options: LinterOptions,
 ) -> Result<LintResult, Diagnostic> {
+    lint_rules_inner(node, parser_diagnostics, file_id, store, options, None)
+}
+
+/// Like [`lint_file_inner`] but only runs the rules of the store named in `only` if it is given,
+/// directives are still resolved with the whole store.
+pub(crate) fn lint_rules_inner<'s>(
+    node: SyntaxNode,
+    parser_diagnostics: Vec<Diagnostic>,
+    file_id: usize,
+    store: &'s CstRuleStore,
+    options: LinterOptions,
+    only: Option<&HashSet<&str>>,
+) -> Result<LintResult<'s>, Diagnostic> {
+    let kind = FileKind::of(&node);
     let mut new_store = store.clone();
-    let results = DirectiveParser::new(node.clone(), file_id, store).get_file_directives()?;
-    let mut directive_diagnostics = vec![];
+    new_store.rules.retain(|rule| rule.applies_to(kind));
+    if let Some(only) = only {
+        new_store.rules.retain(|rule| only.contains(rule.name()));
+    }
+    // whether the file is too broken to lint depends on the real errors and not on the overridden severities
+    let error_count = parser_diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let mut parser_diagnostics = apply_parser_severities(parser_diagnostics, &options);
+    let directives = LazyDirectives::new(node.clone(), file_id, store);
 
-    let directives = results
-        .into_iter()
-        .map(|res| {
-            directive_diagnostics.extend(res.diagnostics);
-            res.directive
+    let degraded_declarations = if degraded::catastrophic(&node, error_count) {
+        new_store.rules.retain(|rule| rule.tolerates_parse_errors());
+        parser_diagnostics.push(Diagnostic::note(
+            file_id,
+            "degraded",
+            "this file has too many syntax errors to be linted fully, only some rules were run",
+        ));
+        Some(degraded::scan_top_level_declarations(&node))
+    } else {
+        None
+    };
+    if options.report_disabled_rules {
+        parser_diagnostics.extend(disabled_rules_note(
+            store,
+            file_id,
+            kind,
+            degraded_declarations.is_some(),
+        ));
+    }
+
+    if let Some(seed) = options.shuffle_seed {
+        util::shuffle(&mut new_store.rules, seed);
+    }
+
+    let file = FileInput {
+        file_id,
+        src: Arc::new(node.to_string()),
+        globals: file_globals(&node, file_id, store, &options),
+    };
+    let run = |rule: &Arc<dyn CstRule>| {
+        (
+            store
+                .rule_id(rule.name())
+                .expect("rule is not in the store"),
+            rule.name(),
+            run_rule_inner(
+                &**rule,
+                node.clone(),
+                &options,
+                &|node, rule| {
+                    !options.record_suppressed && directives.suppressed(node, rule.name())
+                },
+                file.clone(),
+                degraded_declarations.is_some(),
+            ),
+        )
+    };
+    let mut results = if options.threads == Some(1) {
+        let _phase = profile::start_phase(profile::Phase::Rules);
+        new_store.rules.iter().map(run).collect::<RuleResults>()
+    } else {
+        // rules run while directives are parsed, top level directives are applied once both are done
+        with_threads(options.threads, || {
+            rayon::join(
+                || directives.get(),
+                || {
+                    let _phase = profile::start_phase(profile::Phase::Rules);
+                    new_store
+                        .rules
+                        .par_iter()
+                        .map(run)
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                        .collect::<RuleResults>()
+                },
+            )
+            .1
         })
-        .collect::<Vec<_>>();
+    };
 
+    let file_directives = directives.into_inner().map_err(|err| *err)?;
+    if options.record_suppressed {
+        for (name, result) in results.iter_mut() {
+            file_directives.record_suppressed(&node, name, result);
+        }
+    }
+    let FileDirectives {
+        directives,
+        diagnostics: mut directive_diagnostics,
+        disabled,
+        ..
+    } = file_directives;
+    let suppressions = SuppressionStats::new(&directives);
     apply_top_level_directives(
         directives.as_slice(),
         &mut new_store,
         &mut directive_diagnostics,
         file_id,
     );
-
-    let src = Arc::new(node.to_string());
-    let results = new_store
-        .rules
-        .par_iter()
-        .map(|rule| {
-            (
-                rule.name(),
-                run_rule(
-                    &**rule,
-                    file_id,
-                    node.clone(),
-                    verbose,
-                    &directives,
-                    src.clone(),
-                ),
-            )
-        })
-        .collect();
+    if !options.record_suppressed {
+        results.retain(|name, _| new_store.rules.iter().any(|rule| rule.name() == name));
+    }
+    for (name, result) in results.iter_mut() {
+        disabled.apply(name, result);
+    }
 
     Ok(LintResult {
         parser_diagnostics,
         store,
         rule_results: results,
         directive_diagnostics,
+        suppressions,
         parsed: node,
         file_id,
-        verbose,
+        options,
         fixed_code: None,
+        degraded_declarations,
+        diagnostic_index: OnceLock::new(),
     })
 }
 
+/// Run `op` with at most `threads` rayon threads, a pool is only created if the current pool has more threads.
+/// `op` has to run on the calling thread without rayon itself if `threads` is 1.
+pub(crate) fn with_threads<R: Send>(threads: Option<usize>, op: impl FnOnce() -> R + Send) -> R {
+    let pool = threads
+        .filter(|threads| *threads > 1 && *threads < rayon::current_num_threads())
+        .and_then(|threads| ThreadPoolBuilder::new().num_threads(threads).build().ok());
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+/// A note listing the rules of the store which are not run on a file and why, `None` if every rule is run.
+///
+/// This uses the whole store and not only the rules being rerun, so the note stays the same when a file is
+/// partially relinted.
+fn disabled_rules_note(
+    store: &CstRuleStore,
+    file_id: usize,
+    kind: FileKind,
+    degraded: bool,
+) -> Option<Diagnostic> {
+    let kind_name = match kind {
+        FileKind::Script => "scripts",
+        FileKind::Module => "modules",
+    };
+    let reasons = store
+        .rules
+        .iter()
+        .filter_map(|rule| {
+            if !rule.applies_to(kind) {
+                Some(format!("`{}` does not apply to {}", rule.name(), kind_name))
+            } else if degraded && !rule.tolerates_parse_errors() {
+                Some(format!(
+                    "`{}` does not run on files with too many syntax errors",
+                    rule.name()
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if reasons.is_empty() {
+        return None;
+    }
+
+    let title = if reasons.len() == 1 {
+        "1 rule was not run on this file".to_string()
+    } else {
+        format!("{} rules were not run on this file", reasons.len())
+    };
+    Some(reasons.into_iter().fold(
+        Diagnostic::note(file_id, "disabled-rules", title),
+        |note, reason| note.footer_note(reason),
+    ))
+}
+
+/// The range covered by the primary label and the suggestions of a diagnostic.
+fn diagnostic_range(diagnostic: &Diagnostic) -> Option<Range<usize>> {
+    diagnostic
+        .primary
+        .iter()
+        .map(|primary| &primary.span)
+        .chain(diagnostic.suggestions.iter().map(|s| &s.span))
+        .filter(|span| span.file == diagnostic.file_id)
+        .map(|span| span.range.clone())
+        .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+}
+
+/// Override the severity of parser diagnostics or remove them using [`LinterOptions::parser_severities`].
+pub(crate) fn apply_parser_severities(
+    diagnostics: Vec<Diagnostic>,
+    options: &LinterOptions,
+) -> Vec<Diagnostic> {
+    if options.parser_severities.is_empty() {
+        return diagnostics;
+    }
+    diagnostics
+        .into_iter()
+        .filter_map(|mut diagnostic| {
+            let code = diagnostic.code.as_deref().unwrap_or_default();
+            if let Some(severity) = options.parser_severities.get(code) {
+                diagnostic.severity = (*severity)?;
+            }
+            Some(diagnostic)
+        })
+        .collect()
+}
+
 /// Run a single run on an entire parsed file.
 ///
 /// # Panics
@@ -199,22 +651,86 @@ pub fn run_rule(
     verbose: bool,
     directives: &[Directive],
     src: Arc<String>,
+) -> RuleResult {
+    let options = LinterOptions {
+        verbose,
+        ..LinterOptions::default()
+    };
+    let index = SuppressionIndex::new(directives);
+    let file = FileInput {
+        file_id,
+        src,
+        globals: file_globals(&root, file_id, &CstRuleStore::new(), &options),
+    };
+    run_rule_inner(
+        rule,
+        root,
+        &options,
+        &|node, rule| index.suppressed(node, rule.name()),
+        file,
+        false,
+    )
+}
+
+/// The globals of the options extended by the globals declared by the comments of a file.
+fn file_globals(
+    root: &SyntaxNode,
+    file_id: usize,
+    store: &CstRuleStore,
+    options: &LinterOptions,
+) -> Arc<Globals> {
+    let mut globals = options.globals.clone();
+    for declaration in DirectiveParser::new(root.clone(), file_id, store).global_declarations() {
+        globals.add(declaration.name, declaration.writable);
+    }
+    Arc::new(globals)
+}
+
+/// The parts of a [`RuleCtx`] which are the same for every rule run on a file.
+#[derive(Clone)]
+struct FileInput {
+    file_id: usize,
+    src: Arc<String>,
+    globals: Arc<Globals>,
+}
+
+fn run_rule_inner(
+    rule: &dyn CstRule,
+    root: SyntaxNode,
+    options: &LinterOptions,
+    suppressed: &(dyn Fn(&SyntaxNode, &dyn CstRule) -> bool + Sync),
+    file: FileInput,
+    degraded: bool,
 ) -> RuleResult {
     assert!(root.kind() == SyntaxKind::SCRIPT || root.kind() == SyntaxKind::MODULE);
+    let _run = profile::start(rule.name());
     let mut ctx = RuleCtx {
-        file_id,
-        verbose,
+        file_id: file.file_id,
+        rule: rule.name(),
+        verbose: options.verbose,
         diagnostics: vec![],
         fixer: None,
-        src,
+        src: file.src,
+        globals: file.globals,
     };
 
     rule.check_root(&root, &mut ctx);
 
-    root.descendants_with_tokens_with(&mut |elem| {
+    if degraded {
+        // the tree is mostly errors, so nodes are meaningless but tokens are not
+        for tok in root
+            .descendants_with_tokens()
+            .filter_map(|elem| elem.into_token())
+        {
+            let _ = rule.check_token(&tok, &mut ctx);
+        }
+        return RuleResult::new(ctx.diagnostics, ctx.fixer);
+    }
+
+    let mut visit = |elem: &SyntaxElement| {
         match elem {
             rslint_parser::NodeOrToken::Node(node) => {
-                if skip_node(directives, &node, rule) || node.kind() == SyntaxKind::ERROR {
+                if suppressed(&node, rule) || node.kind() == SyntaxKind::ERROR {
                     return false;
                 }
                 rule.check_node(&node, &mut ctx);
@@ -224,28 +740,47 @@ pub fn run_rule(
             }
         };
         true
-    });
+    };
+
+    if let Some(seed) = options.shuffle_seed {
+        let mut children = root.children_with_tokens().collect::<Vec<_>>();
+        util::shuffle(&mut children, seed);
+        for child in children {
+            if visit(&child) {
+                if let Some(node) = child.as_node() {
+                    node.descendants_with_tokens_with(&mut visit);
+                }
+            }
+        }
+    } else {
+        root.descendants_with_tokens_with(&mut visit);
+    }
     RuleResult::new(ctx.diagnostics, ctx.fixer)
 }
 
-/// Get a rule by its kebab-case name.
+/// Get a rule by its kebab-case name, this includes rules of registered plugins.
 pub fn get_rule_by_name(name: &str) -> Option<Box<dyn CstRule>> {
     CstRuleStore::new()
         .builtins()
+        .external()
         .rules
         .iter()
         .find(|rule| rule.name() == name)
         .map(|rule| clone_box(&**rule))
 }
 
-/// Get a group's rules by the group name.
+/// Get a group's rules by the group name, the name of a registered plugin is also a group of its rules.
 // TODO: there should be a good way to not have to hardcode all of this
 pub fn get_group_rules_by_name(group_name: &str) -> Option<Vec<Box<dyn CstRule>>> {
     use groups::*;
 
     Some(match group_name {
         "errors" => errors(),
-        _ => return None,
+        "style" => style(),
+        "promises" => promises(),
+        "node" => node(),
+        "regex" => regex(),
+        _ => (plugin::get_plugin(group_name)?.rules)(),
     })
 }
 
@@ -253,6 +788,7 @@ pub fn get_group_rules_by_name(group_name: &str) -> Option<Vec<Box<dyn CstRule>>
 pub fn get_rule_suggestion(incorrect_rule_name: &str) -> Option<&str> {
     let rules = CstRuleStore::new()
         .builtins()
+        .external()
         .rules
         .into_iter()
         .map(|rule| rule.name());
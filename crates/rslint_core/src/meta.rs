@@ -0,0 +1,106 @@
+//! Machine readable metadata of rules, for tools such as docs generators and editors which enumerate rules.
+
+use crate::docgen::{examples, options, RuleOption};
+use crate::preset::Preset;
+use crate::CstRule;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+const RULE_DOCS_URI: &str = "https://rdambrosio016.github.io/RSLint/rules";
+
+/// The metadata of a rule.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleMeta {
+    pub name: &'static str,
+    pub group: &'static str,
+    /// The first line of the rule's docs.
+    pub description: &'static str,
+    /// The full markdown docs of the rule.
+    pub docs: &'static str,
+    /// Whether the rule is in the [recommended](Preset::recommended) preset.
+    pub recommended: bool,
+    /// Whether the rule can fix some of the problems it reports.
+    pub fixable: bool,
+    /// The code examples under the `Invalid Code Examples` (or `Incorrect Code Examples`) heading of the docs.
+    pub invalid_examples: Vec<String>,
+    /// The code examples under the `Correct Code Examples` heading of the docs.
+    pub valid_examples: Vec<String>,
+    /// The config options of the rule and their defaults, sorted by name.
+    pub options: Vec<RuleOption>,
+    /// The URL of the rule's documentation, external rules are not documented on the website.
+    pub docs_url: Option<String>,
+}
+
+impl dyn CstRule {
+    /// The metadata of the rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use rslint_core::CstRuleStore;
+    ///
+    /// let meta = CstRuleStore::new().builtins().get("no-extra-semi").unwrap().meta();
+    /// assert_eq!(meta.group, "errors");
+    /// assert!(meta.recommended && meta.fixable);
+    /// ```
+    pub fn meta(&self) -> RuleMeta {
+        let docs = self.docs().trim();
+        let description = docs.lines().next().unwrap_or("").trim();
+        let (invalid_examples, valid_examples) = examples(docs);
+        let options = match default_config(self) {
+            Some(rule) => options(&*rule),
+            None => options(self),
+        };
+
+        RuleMeta {
+            name: self.name(),
+            group: self.group(),
+            description,
+            docs,
+            invalid_examples,
+            valid_examples,
+            recommended: recommended().contains(self.name()),
+            fixable: self.fixable(),
+            options,
+            docs_url: docs_url(self),
+        }
+    }
+}
+
+/// The rule with its default config, `None` if the rule can not be deserialized from an empty config.
+fn default_config(rule: &dyn CstRule) -> Option<Box<dyn CstRule>> {
+    // rules are serialized by typetag as `{ "RuleName": { ...options } }`
+    let tag = match serde_json::to_value(rule).ok()? {
+        Value::Object(map) => map.into_iter().next()?.0,
+        _ => return None,
+    };
+    let mut empty = Map::new();
+    empty.insert(tag, Value::Object(Map::new()));
+    serde_json::from_value(Value::Object(empty)).ok()
+}
+
+fn recommended() -> &'static HashSet<&'static str> {
+    static RECOMMENDED: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    RECOMMENDED.get_or_init(|| {
+        Preset::recommended()
+            .rules()
+            .iter()
+            .map(|rule| rule.name())
+            .collect()
+    })
+}
+
+/// The URL of the documentation of a builtin rule.
+pub(crate) fn docs_url(rule: &dyn CstRule) -> Option<String> {
+    if rule.name().contains('/') {
+        None
+    } else {
+        Some(format!(
+            "{}/{}/{}.html",
+            RULE_DOCS_URI,
+            rule.group(),
+            rule.name()
+        ))
+    }
+}
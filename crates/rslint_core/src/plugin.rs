@@ -0,0 +1,62 @@
+//! Support for rules shipped by third party crates.
+//!
+//! External rules are declared like any builtin rule using [`declare_lint`](crate::declare_lint), however, their name
+//! must be namespaced with the name of the plugin which ships them, e.g. `my-plugin/no-foo`. This keeps
+//! them from clashing with builtin rules or rules of other plugins in diagnostics, configs, and directives.
+//!
+//! Plugins register their rules with [`register_plugin`](crate::register_plugin), which collects them when the crate is linked.
+//! Linking the plugin crate is then enough for the rules to be discovered through [`plugins`] and
+//! loaded with [`CstRuleStore::external`](crate::CstRuleStore::external).
+//!
+//! ```ignore
+//! rslint_core::register_plugin!("my-plugin", NoFoo, NoBar);
+//! ```
+
+use crate::CstRule;
+
+/// A plugin and its rules registered with [`register_plugin`](crate::register_plugin).
+pub struct Plugin {
+    /// The name of the plugin, which is the namespace of all of its rules.
+    pub name: &'static str,
+    /// Make new instances of all of the rules of the plugin.
+    pub rules: fn() -> Vec<Box<dyn CstRule>>,
+}
+
+inventory::collect!(Plugin);
+
+/// Register the rules of a plugin so they can be discovered at runtime.
+///
+/// Each rule must have a `new` function (which [`declare_lint`](crate::declare_lint) generates) and
+/// its name must be namespaced with the name of the plugin.
+#[macro_export]
+macro_rules! register_plugin {
+    ($name:literal, $($rule:path),* $(,)?) => {
+        $crate::__inventory::submit! {
+            $crate::plugin::Plugin {
+                name: $name,
+                rules: || vec![$(Box::new(<$rule>::new()) as Box<dyn $crate::CstRule>),*],
+            }
+        }
+    };
+}
+
+/// All plugins which were registered by linked crates.
+pub fn plugins() -> impl Iterator<Item = &'static Plugin> {
+    inventory::iter::<Plugin>.into_iter()
+}
+
+/// Get a registered plugin by its name.
+pub fn get_plugin(name: &str) -> Option<&'static Plugin> {
+    plugins().find(|plugin| plugin.name == name)
+}
+
+/// The rules of every registered plugin.
+pub fn external_rules() -> Vec<Box<dyn CstRule>> {
+    plugins().flat_map(|plugin| (plugin.rules)()).collect()
+}
+
+/// Get the plugin namespace of a rule name, e.g. `my-plugin` for `my-plugin/no-foo`.
+pub fn rule_namespace(rule_name: &str) -> Option<&str> {
+    let idx = rule_name.find('/')?;
+    Some(&rule_name[..idx]).filter(|namespace| !namespace.is_empty())
+}
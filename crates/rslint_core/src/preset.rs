@@ -0,0 +1,131 @@
+//! Named sets of configured rules which can be composed and loaded into a store.
+//!
+//! The built in presets are [`Preset::errors`], [`Preset::style`], [`Preset::promises`], [`Preset::node`],
+//! [`Preset::regex`], [`Preset::recommended`], and [`Preset::all`].
+//! Presets can also be defined in code by extending other presets and adding or removing rules:
+//!
+//! ```
+//! use rslint_core::{groups::errors::NoEmpty, preset::Preset, CstRuleStore};
+//!
+//! let strict = Preset::new("strict")
+//!     .extend(&Preset::recommended())
+//!     .with_rules(vec![Box::new(NoEmpty {
+//!         disallow_empty_functions: true,
+//!         ..NoEmpty::default()
+//!     }) as _])
+//!     .without(&["no-await-in-loop"]);
+//!
+//! let store = CstRuleStore::new().preset(&strict);
+//! assert!(store.get("no-empty").is_some());
+//! assert!(store.get("no-await-in-loop").is_none());
+//! ```
+
+use crate::groups::{errors, node, promises, regex, style};
+use crate::CstRule;
+
+/// A named set of configured rules.
+#[derive(Debug, Clone)]
+pub struct Preset {
+    name: String,
+    rules: Vec<Box<dyn CstRule>>,
+}
+
+impl Preset {
+    /// An empty preset.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rules: vec![],
+        }
+    }
+
+    /// The rules of the `errors` group.
+    pub fn errors() -> Self {
+        Self::new("errors").with_rules(errors())
+    }
+
+    /// The rules of the `style` group.
+    pub fn style() -> Self {
+        Self::new("style").with_rules(style())
+    }
+
+    /// The rules of the `promises` group.
+    pub fn promises() -> Self {
+        Self::new("promises").with_rules(promises())
+    }
+
+    /// The rules of the `node` group.
+    pub fn node() -> Self {
+        Self::new("node").with_rules(node())
+    }
+
+    /// The rules of the `regex` group.
+    pub fn regex() -> Self {
+        Self::new("regex").with_rules(regex())
+    }
+
+    /// The rules which are recommended for every project, these are the rules of the `errors` group.
+    pub fn recommended() -> Self {
+        Self::new("recommended").extend(&Self::errors())
+    }
+
+    /// Every built in rule.
+    pub fn all() -> Self {
+        Self::new("all")
+            .extend(&Self::errors())
+            .extend(&Self::style())
+            .extend(&Self::promises())
+            .extend(&Self::node())
+            .extend(&Self::regex())
+    }
+
+    /// Get a built in preset by its name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        Some(match name {
+            "errors" => Self::errors(),
+            "style" => Self::style(),
+            "promises" => Self::promises(),
+            "node" => Self::node(),
+            "regex" => Self::regex(),
+            "recommended" => Self::recommended(),
+            "all" => Self::all(),
+            _ => return None,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The rules of the preset in the order they were added.
+    pub fn rules(&self) -> &[Box<dyn CstRule>] {
+        &self.rules
+    }
+
+    /// Add rules to the preset, rules with the same name as a rule already in the preset replace it.
+    pub fn with_rules(mut self, rules: impl IntoIterator<Item = Box<dyn CstRule>>) -> Self {
+        for rule in rules {
+            match self
+                .rules
+                .iter_mut()
+                .find(|prev| prev.name() == rule.name())
+            {
+                Some(prev) => *prev = rule,
+                None => self.rules.push(rule),
+            }
+        }
+        self
+    }
+
+    /// Add the rules of another preset, its rules replace rules with the same name in this preset.
+    pub fn extend(self, other: &Preset) -> Self {
+        self.with_rules(other.rules.iter().cloned())
+    }
+
+    /// Remove rules from the preset by name.
+    pub fn without(mut self, names: &[impl AsRef<str>]) -> Self {
+        self.rules
+            .retain(|rule| !names.iter().any(|name| name.as_ref() == rule.name()));
+        self
+    }
+}
@@ -0,0 +1,252 @@
+//! Per rule profiling of the time rules take and the memory they allocate.
+//!
+//! Profiling is disabled by default and enabled for the whole process with [`enable`]. While enabled, every
+//! run of a rule on a file adds to the totals of the rule, which are collected with [`report`].
+//!
+//! Allocations are counted by the [`CountingAllocator`], which is only available with the `alloc-profiling`
+//! feature and has to be installed as the global allocator of the binary, rslint_core never installs a global
+//! allocator itself:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: rslint_core::profile::CountingAllocator = rslint_core::profile::CountingAllocator;
+//! ```
+//!
+//! Allocations are attributed to the rule running on the thread which makes them, so allocations of the
+//! runner itself, such as parsing, are never attributed to a rule. Without the allocator the allocation
+//! counts are always zero.
+//!
+//! Besides the rules, the time of each [`Phase`] of the pipeline is recorded and collected with [`phases`].
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PROFILES: Mutex<Option<HashMap<&'static str, RuleProfile>>> = Mutex::new(None);
+static PHASES: Mutex<PhaseTimings> = Mutex::new(PhaseTimings::ZERO);
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The totals of a rule over every file it ran on while profiling was enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleProfile {
+    pub name: &'static str,
+    /// The number of times the rule ran, which is the number of files it ran on.
+    pub runs: u64,
+    pub time: Duration,
+    /// The number of allocations and reallocations made by the rule.
+    pub allocations: u64,
+    /// The total bytes requested by the allocations of the rule, reallocations add the size they grew by.
+    /// Memory which was freed is not subtracted.
+    pub bytes: u64,
+}
+
+/// A phase of the lint pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Parsing files, reparsing a file after applying fixes is part of [`Phase::Fix`].
+    Parse,
+    /// Resolving references to names with [`util::scope`](crate::util::scope). Scope resolution happens
+    /// while rules run, so this time is also part of [`Phase::Rules`].
+    Scope,
+    /// Running the rules of a file, relinting the code after applying fixes is also part of [`Phase::Fix`].
+    Rules,
+    /// Applying autofixes and relinting the fixed code.
+    Fix,
+}
+
+/// The total time spent in each phase of the lint pipeline.
+///
+/// Rules and scope resolution run in parallel, the time of every thread is added up so the totals can be
+/// larger than the wall time of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PhaseTimings {
+    pub parse: Duration,
+    pub scope: Duration,
+    pub rules: Duration,
+    pub fix: Duration,
+}
+
+impl PhaseTimings {
+    pub const ZERO: PhaseTimings = PhaseTimings {
+        parse: Duration::ZERO,
+        scope: Duration::ZERO,
+        rules: Duration::ZERO,
+        fix: Duration::ZERO,
+    };
+
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Parse => self.parse,
+            Phase::Scope => self.scope,
+            Phase::Rules => self.rules,
+            Phase::Fix => self.fix,
+        }
+    }
+
+    pub fn add(&mut self, phase: Phase, time: Duration) {
+        match phase {
+            Phase::Parse => self.parse += time,
+            Phase::Scope => self.scope += time,
+            Phase::Rules => self.rules += time,
+            Phase::Fix => self.fix += time,
+        }
+    }
+
+    /// Add the timings of another run to these timings.
+    pub fn merge(&mut self, other: &PhaseTimings) {
+        self.parse += other.parse;
+        self.scope += other.scope;
+        self.rules += other.rules;
+        self.fix += other.fix;
+    }
+}
+
+/// Enable profiling for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether allocations are counted, this is the case if rslint_core was built with the `alloc-profiling`
+/// feature. Allocations are still only counted if the binary installed the [`CountingAllocator`].
+pub fn counts_allocations() -> bool {
+    cfg!(feature = "alloc-profiling")
+}
+
+/// The profiles of every rule which ran since profiling was enabled or [`reset`], sorted by allocated bytes
+/// and then time, descending.
+pub fn report() -> Vec<RuleProfile> {
+    let profiles = PROFILES.lock().unwrap();
+    let mut profiles = profiles
+        .iter()
+        .flat_map(|map| map.values().copied())
+        .collect::<Vec<_>>();
+    profiles.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(b.time.cmp(&a.time)));
+    profiles
+}
+
+/// The time spent in each phase since profiling was enabled or [`reset`].
+pub fn phases() -> PhaseTimings {
+    *PHASES.lock().unwrap()
+}
+
+/// Clear the profiles of every rule and the phase timings.
+pub fn reset() {
+    *PROFILES.lock().unwrap() = None;
+    *PHASES.lock().unwrap() = PhaseTimings::ZERO;
+}
+
+/// Start profiling a run of a rule, the run is recorded when the returned guard is dropped.
+pub(crate) fn start(rule: &'static str) -> Option<Run> {
+    if !is_enabled() {
+        return None;
+    }
+    Some(Run {
+        rule,
+        start: Instant::now(),
+        allocations: ALLOCATIONS.with(Cell::get),
+        bytes: BYTES.with(Cell::get),
+    })
+}
+
+pub(crate) struct Run {
+    rule: &'static str,
+    start: Instant,
+    allocations: u64,
+    bytes: u64,
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        // read the counters before anything else allocates, recording the run allocates but is not part of it
+        let allocations = ALLOCATIONS.with(Cell::get) - self.allocations;
+        let bytes = BYTES.with(Cell::get) - self.bytes;
+        let time = self.start.elapsed();
+
+        let mut profiles = PROFILES.lock().unwrap();
+        let profile = profiles
+            .get_or_insert_with(HashMap::new)
+            .entry(self.rule)
+            .or_insert(RuleProfile {
+                name: self.rule,
+                runs: 0,
+                time: Duration::default(),
+                allocations: 0,
+                bytes: 0,
+            });
+        profile.runs += 1;
+        profile.time += time;
+        profile.allocations += allocations;
+        profile.bytes += bytes;
+    }
+}
+
+/// Start timing a phase, the time is recorded when the returned guard is dropped.
+pub(crate) fn start_phase(phase: Phase) -> Option<PhaseRun> {
+    if !is_enabled() {
+        return None;
+    }
+    Some(PhaseRun {
+        phase,
+        start: Instant::now(),
+    })
+}
+
+pub(crate) struct PhaseRun {
+    phase: Phase,
+    start: Instant,
+}
+
+impl Drop for PhaseRun {
+    fn drop(&mut self) {
+        let time = self.start.elapsed();
+        PHASES.lock().unwrap().add(self.phase, time);
+    }
+}
+
+/// A global allocator which counts the allocations of each thread and otherwise defers to the
+/// [system allocator](std::alloc::System).
+#[cfg(feature = "alloc-profiling")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-profiling")]
+impl CountingAllocator {
+    fn count(size: usize) {
+        // the counters have no destructors, but the thread may be torn down while it still allocates
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+    }
+}
+
+#[cfg(feature = "alloc-profiling")]
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        Self::count(layout.size());
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        Self::count(layout.size());
+        std::alloc::System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+        Self::count(new_size.saturating_sub(layout.size()));
+        std::alloc::System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
@@ -0,0 +1,819 @@
+//! Linting of multiple files which import and export from each other.
+//!
+//! [`lint_file`](crate::lint_file) only ever looks at a single file, the [`ProjectLinter`] instead takes
+//! every file of a project, builds a [`ModuleGraph`] from their imports and exports, and runs
+//! [`ProjectRule`]s on the whole graph after running the CST rules on each file.
+//!
+//! Imports are resolved purely from the paths of the files given to the linter, the file system is never
+//! touched. Relative specifiers such as `./foo` are resolved to `foo`, `foo.js`, `foo.mjs`, `foo/index.js`,
+//! or `foo/index.mjs` in that order, any other specifier (e.g. packages) is left unresolved.
+//!
+//! Files such as Vue components can be added with an [`Extractor`], each file extracted from them is linted on its own
+//! and their results are mapped back to the physical file, see [`ProjectLinter::add_extracted_file`].
+
+use crate::directives::SuppressionStats;
+use crate::extract::{shift_diagnostic, shift_result, Extractor};
+use crate::util::scope::var_decl_bindings;
+use crate::{
+    lint_file_inner, parse_file, with_threads, CstRuleStore, FileKind, LintResult, LinterOptions,
+    ProjectRule,
+};
+use rayon::prelude::*;
+use rslint_errors::{file::FileId, Diagnostic};
+use rslint_parser::{
+    ast::*, AstNode, GreenNode, SyntaxKind, SyntaxNode, SyntaxNodeExt, TextRange, TextSize,
+};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, TryRecvError, TrySendError};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+const RESOLVED_EXTENSIONS: [&str; 2] = ["js", "mjs"];
+
+/// A single file given to the [`ProjectLinter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProjectFile {
+    file_id: FileId,
+    path: PathBuf,
+    source: std::string::String,
+    module: bool,
+    /// The offset of the source in the physical file if it was extracted from another file.
+    offset: usize,
+}
+
+/// A linter which lints many files at once and runs rules which need information from multiple files.
+#[derive(Debug, Clone)]
+pub struct ProjectLinter<'s> {
+    store: &'s CstRuleStore,
+    project_rules: Vec<Box<dyn ProjectRule>>,
+    files: Vec<ProjectFile>,
+    options: LinterOptions,
+}
+
+impl<'s> ProjectLinter<'s> {
+    /// Create a new linter which will run the rules of `store` on every file.
+    pub fn new(store: &'s CstRuleStore) -> Self {
+        Self {
+            store,
+            project_rules: vec![],
+            files: vec![],
+            options: LinterOptions::default(),
+        }
+    }
+
+    /// Set the options used for linting every file.
+    pub fn with_options(mut self, options: LinterOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add a file to the project, the path is used for resolving imports to this file
+    /// and `module` dictates whether the file is parsed as a module or a script.
+    pub fn add_file(
+        &mut self,
+        file_id: FileId,
+        path: impl AsRef<Path>,
+        source: impl Into<std::string::String>,
+        module: bool,
+    ) -> &mut Self {
+        self.files.push(ProjectFile {
+            file_id,
+            path: normalize(path.as_ref()),
+            source: source.into(),
+            module,
+            offset: 0,
+        });
+        self
+    }
+
+    /// Add a file which is not a JavaScript file, such as a Vue component, to the project. The files extracted
+    /// from it by `extractor` are linted separately, but their results have the id of the physical file and their
+    /// diagnostics point into its source. Their modules are merged into a single module in the module graph.
+    ///
+    /// Results of extracted files can not be fixed, since they are linted on the extracted source.
+    pub fn add_extracted_file(
+        &mut self,
+        file_id: FileId,
+        path: impl AsRef<Path>,
+        source: &str,
+        extractor: &dyn Extractor,
+    ) -> &mut Self {
+        let path = normalize(path.as_ref());
+        for extracted in extractor.extract(source) {
+            self.files.push(ProjectFile {
+                file_id,
+                path: path.clone(),
+                source: extracted.source,
+                module: extracted.kind == FileKind::Module,
+                offset: extracted.offset,
+            });
+        }
+        self
+    }
+
+    /// Add a rule which is run once on the module graph of the whole project.
+    pub fn add_project_rule(&mut self, rule: Box<dyn ProjectRule>) -> &mut Self {
+        self.project_rules.push(rule);
+        self
+    }
+
+    /// Lint every file with the store's rules on the threads given by [`LinterOptions::threads`], then run the
+    /// project rules on the module graph.
+    pub fn lint(&self) -> ProjectLintResult<'s> {
+        let linted = lint_all(&self.files, self.options.threads, |file| {
+            self.lint_file(file)
+        });
+        let mut result = collect_linted(self.files.iter(), linted);
+        result.project_diagnostics = self.run_project_rules(&result.graph);
+        result
+    }
+
+    /// Lint the files on multiple threads and give the diagnostics of each file to `sink` as soon as the file
+    /// is linted, then run the project rules on the module graph. Files are given to `sink` in the order they
+    /// finish, files added with an extractor give a report for every extracted file.
+    ///
+    /// At most `options.capacity` reports are queued for `sink`. If `sink` can not keep up, such as when the
+    /// reports are written to a slow formatter or a network connection, fewer files are linted at once so
+    /// linted files do not pile up in memory, and more are linted again once `sink` catches up.
+    pub fn lint_streaming(
+        &self,
+        options: &StreamOptions,
+        mut sink: impl FnMut(FileReport),
+    ) -> ProjectStreamResult {
+        let throttle = Throttle::new(options);
+        let next = AtomicUsize::new(0);
+        let (tx, rx) = sync_channel::<StreamedFile>(options.capacity.max(1));
+        let mut parsed = vec![None; self.files.len()];
+
+        thread::scope(|scope| {
+            for worker in 0..throttle.max {
+                let tx = tx.clone();
+                let (throttle, next) = (&throttle, &next);
+                scope.spawn(move || {
+                    while throttle.wait_for_turn(worker) {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        let file = match self.files.get(index) {
+                            Some(file) => file,
+                            None => {
+                                throttle.finish();
+                                return;
+                            }
+                        };
+                        let (root, result) = self.lint_file(file);
+                        let streamed = StreamedFile {
+                            index,
+                            green: root.green().to_owned(),
+                            suppressions: result
+                                .as_ref()
+                                .map(|res| res.suppressions.clone())
+                                .unwrap_or_default(),
+                            report: FileReport {
+                                file_id: file.file_id,
+                                result: result
+                                    .map(|res| res.diagnostics().cloned().collect())
+                                    .map_err(Box::new),
+                            },
+                        };
+                        let sent = match tx.try_send(streamed) {
+                            Ok(()) => Ok(()),
+                            Err(TrySendError::Full(streamed)) => {
+                                throttle.slow_down();
+                                tx.send(streamed).map_err(|_| ())
+                            }
+                            Err(TrySendError::Disconnected(_)) => Err(()),
+                        };
+                        if sent.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            loop {
+                let streamed = match rx.try_recv() {
+                    Ok(streamed) => streamed,
+                    Err(TryRecvError::Empty) => {
+                        throttle.speed_up();
+                        match rx.recv() {
+                            Ok(streamed) => streamed,
+                            Err(_) => break,
+                        }
+                    }
+                    Err(TryRecvError::Disconnected) => break,
+                };
+                parsed[streamed.index] = Some((streamed.green, streamed.suppressions));
+                sink(streamed.report);
+            }
+        });
+
+        let mut modules: Vec<ModuleInfo> = vec![];
+        for (file, parsed) in self.files.iter().zip(parsed) {
+            if let Some((green, suppressions)) = parsed {
+                add_module(
+                    &mut modules,
+                    file,
+                    SyntaxNode::new_root(green),
+                    &suppressions,
+                );
+            }
+        }
+        let graph = ModuleGraph::new(modules);
+        ProjectStreamResult {
+            project_diagnostics: self.run_project_rules(&graph),
+            graph,
+            throttled: throttle.throttled.load(Ordering::SeqCst),
+        }
+    }
+
+    fn lint_file(&self, file: &ProjectFile) -> Linted<'s> {
+        lint_project_file(file, self.store, self.options.clone())
+    }
+
+    fn run_project_rules(&self, graph: &ModuleGraph) -> HashMap<&'static str, Vec<Diagnostic>> {
+        self.project_rules
+            .iter()
+            .map(|rule| {
+                let mut diagnostics = vec![];
+                rule.check_project(graph, &mut diagnostics);
+                (rule.name(), diagnostics)
+            })
+            .collect()
+    }
+}
+
+/// The root of a linted file and its result, or the error which prevented it from being linted.
+type Linted<'s> = (SyntaxNode, Result<LintResult<'s>, Diagnostic>);
+
+/// Parse and lint a single file, moving the results of extracted files to their offset in the physical file.
+fn lint_project_file<'s>(
+    file: &ProjectFile,
+    store: &'s CstRuleStore,
+    options: LinterOptions,
+) -> Linted<'s> {
+    let (parser_diagnostics, root) = parse_file(file.file_id, &file.source, file.module);
+    let result = lint_file_inner(
+        root.clone(),
+        parser_diagnostics,
+        file.file_id,
+        store,
+        options,
+    );
+    match result {
+        Ok(mut res) => {
+            shift_result(&mut res, file.offset);
+            (root, Ok(res))
+        }
+        Err(mut diagnostic) => {
+            shift_diagnostic(&mut diagnostic, file.offset);
+            (root, Err(diagnostic))
+        }
+    }
+}
+
+/// Collect the results of linted files and build their module graph, the project rules are not run.
+fn collect_linted<'a, 's>(
+    files: impl Iterator<Item = &'a ProjectFile>,
+    linted: Vec<Linted<'s>>,
+) -> ProjectLintResult<'s> {
+    let mut results = vec![];
+    let mut errors = vec![];
+    let mut modules: Vec<ModuleInfo> = vec![];
+
+    for (file, (root, result)) in files.zip(linted) {
+        let suppressions = result
+            .as_ref()
+            .map(|res| res.suppressions.clone())
+            .unwrap_or_default();
+        add_module(&mut modules, file, root, &suppressions);
+        match result {
+            Ok(res) => results.push(res),
+            Err(diagnostic) => errors.push(diagnostic),
+        }
+    }
+    ProjectLintResult {
+        results,
+        errors,
+        project_diagnostics: HashMap::new(),
+        graph: ModuleGraph::new(modules),
+    }
+}
+
+/// A file given to [`lint_files`], each file has its own store and options so files can be linted with the
+/// config of their directory.
+#[derive(Debug, Clone)]
+pub struct LintInput<'s> {
+    pub file_id: FileId,
+    /// The path of the file, used for resolving imports in the module graph.
+    pub path: PathBuf,
+    pub source: std::string::String,
+    pub kind: FileKind,
+    /// The store the file is linted with, files with the same config should share a store since stores are only
+    /// borrowed.
+    pub store: &'s CstRuleStore,
+    pub options: LinterOptions,
+}
+
+/// Lint many files in parallel and build their module graph, like the CLI does.
+///
+/// Files are linted on the threads given by `threads` like [`LinterOptions::threads`], which replaces the
+/// `threads` of the options of every file. The results and errors are in the order of `files` regardless of the
+/// order files finish in. No project rules are run, use a [`ProjectLinter`] for those.
+pub fn lint_files<'s>(files: Vec<LintInput<'s>>, threads: Option<usize>) -> ProjectLintResult<'s> {
+    let files = files
+        .into_iter()
+        .map(|input| {
+            let file = ProjectFile {
+                file_id: input.file_id,
+                path: normalize(&input.path),
+                source: input.source,
+                module: input.kind == FileKind::Module,
+                offset: 0,
+            };
+            let options = LinterOptions {
+                threads,
+                ..input.options
+            };
+            (file, input.store, options)
+        })
+        .collect::<Vec<_>>();
+
+    let linted = lint_all(&files, threads, |(file, store, options)| {
+        lint_project_file(file, store, options.clone())
+    });
+    collect_linted(files.iter().map(|(file, ..)| file), linted)
+}
+
+/// Lint files on the threads given by `threads`, see [`LinterOptions::threads`]. The results are in the order of
+/// `files`.
+fn lint_all<'s, T: Sync>(
+    files: &[T],
+    threads: Option<usize>,
+    lint: impl Fn(&T) -> Linted<'s> + Sync + Send,
+) -> Vec<Linted<'s>> {
+    if threads == Some(1) {
+        files.iter().map(lint).collect()
+    } else {
+        with_threads(threads, || files.par_iter().map(lint).collect())
+    }
+}
+
+/// Add the module of a file to the modules of a project, files extracted from the same physical file are
+/// merged into one module.
+fn add_module(
+    modules: &mut Vec<ModuleInfo>,
+    file: &ProjectFile,
+    root: SyntaxNode,
+    suppressions: &SuppressionStats,
+) {
+    let mut module = ModuleInfo::new(file, root);
+    module.suppressions.merge(suppressions);
+    match modules
+        .iter_mut()
+        .find(|existing| existing.file_id == file.file_id)
+    {
+        Some(existing) => existing.merge(module),
+        None => modules.push(module),
+    }
+}
+
+/// Options for [`ProjectLinter::lint_streaming`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// The number of reports which can be queued before linting waits for the consumer, at least 1.
+    pub capacity: usize,
+    /// The number of files linted at once while the consumer keeps up.
+    pub max_workers: usize,
+    /// The number of files linted at once while the consumer lags behind, at least 1.
+    pub min_workers: usize,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 16,
+            max_workers: rayon::current_num_threads(),
+            min_workers: 1,
+        }
+    }
+}
+
+/// The diagnostics of a single file linted by [`ProjectLinter::lint_streaming`].
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub file_id: FileId,
+    /// Every diagnostic of the file in the order of [`LintResult::diagnostics`], or the error which prevented
+    /// the file from being linted, such as an invalid directive.
+    pub result: Result<Vec<Diagnostic>, Box<Diagnostic>>,
+}
+
+/// The result of [`ProjectLinter::lint_streaming`], the diagnostics of each file were already given to the sink.
+#[derive(Debug, Clone)]
+pub struct ProjectStreamResult {
+    /// The diagnostics emitted by each project rule.
+    pub project_diagnostics: HashMap<&'static str, Vec<Diagnostic>>,
+    /// The module graph of the project.
+    pub graph: ModuleGraph,
+    /// How many times fewer files were linted at once because the consumer could not keep up.
+    pub throttled: usize,
+}
+
+/// A linted file sent from a worker to the consumer, syntax nodes can not be sent between threads so the
+/// tree is sent as a green node.
+struct StreamedFile {
+    index: usize,
+    green: GreenNode,
+    suppressions: SuppressionStats,
+    report: FileReport,
+}
+
+/// The number of workers allowed to lint files, lowered when the consumer lags and raised when it waits for files.
+struct Throttle {
+    allowed: Mutex<usize>,
+    changed: Condvar,
+    min: usize,
+    max: usize,
+    finished: AtomicBool,
+    throttled: AtomicUsize,
+}
+
+impl Throttle {
+    fn new(options: &StreamOptions) -> Self {
+        let max = options.max_workers.max(1);
+        Self {
+            allowed: Mutex::new(max),
+            changed: Condvar::new(),
+            min: options.min_workers.clamp(1, max),
+            max,
+            finished: AtomicBool::new(false),
+            throttled: AtomicUsize::new(0),
+        }
+    }
+
+    /// Wait until a worker may lint another file, returns `false` if every file was taken.
+    fn wait_for_turn(&self, worker: usize) -> bool {
+        let mut allowed = self.allowed.lock().unwrap();
+        while *allowed <= worker && !self.finished.load(Ordering::SeqCst) {
+            allowed = self.changed.wait(allowed).unwrap();
+        }
+        !self.finished.load(Ordering::SeqCst)
+    }
+
+    fn finish(&self) {
+        let _allowed = self.allowed.lock().unwrap();
+        self.finished.store(true, Ordering::SeqCst);
+        self.changed.notify_all();
+    }
+
+    fn slow_down(&self) {
+        let mut allowed = self.allowed.lock().unwrap();
+        if *allowed > self.min {
+            *allowed -= 1;
+            self.throttled.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn speed_up(&self) {
+        let mut allowed = self.allowed.lock().unwrap();
+        if *allowed < self.max {
+            *allowed += 1;
+            self.changed.notify_all();
+        }
+    }
+}
+
+/// The result of linting a whole project.
+#[derive(Debug, Clone)]
+pub struct ProjectLintResult<'s> {
+    /// The results of linting each file which could be linted, files added with an extractor have a result
+    /// for every extracted file.
+    pub results: Vec<LintResult<'s>>,
+    /// Errors which prevented a file from being linted, such as invalid directives.
+    pub errors: Vec<Diagnostic>,
+    /// The diagnostics emitted by each project rule.
+    pub project_diagnostics: HashMap<&'static str, Vec<Diagnostic>>,
+    /// The module graph of the project.
+    pub graph: ModuleGraph,
+}
+
+impl ProjectLintResult<'_> {
+    /// Get every diagnostic of every file, then any errors, then the diagnostics of project rules.
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.results
+            .iter()
+            .flat_map(|res| res.diagnostics())
+            .chain(self.errors.iter())
+            .chain(self.project_diagnostics.values().flatten())
+    }
+
+    /// Get every diagnostic of a single physical file, including the diagnostics of every file extracted from it.
+    pub fn file_diagnostics(&self, file_id: FileId) -> impl Iterator<Item = &Diagnostic> {
+        self.results
+            .iter()
+            .filter(move |res| res.file_id == file_id)
+            .flat_map(|res| res.diagnostics())
+            .chain(self.errors.iter().filter(move |d| d.file_id == file_id))
+            .chain(
+                self.project_diagnostics
+                    .values()
+                    .flatten()
+                    .filter(move |d| d.file_id == file_id),
+            )
+    }
+}
+
+/// An import of another file, this includes re-exports such as `export * from "foo"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// The specifier of the import, e.g. `./foo` for `import a from "./foo"`.
+    pub specifier: std::string::String,
+    /// The file the specifier refers to, if it is a file in the project.
+    pub resolved: Option<FileId>,
+    /// The names imported from the file, `default` for default imports and `*` for namespace imports and
+    /// wildcard re-exports. Empty for side effect imports such as `import "foo"`.
+    pub names: Vec<std::string::String>,
+    /// The range of the import declaration or re-export.
+    pub range: TextRange,
+}
+
+/// A name exported from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    /// The exported name, `default` for default exports and `*` for wildcard re-exports.
+    pub name: std::string::String,
+    /// The specifier this name is re-exported from, if any.
+    pub from: Option<std::string::String>,
+    /// The range of the node which declares the export.
+    pub range: TextRange,
+}
+
+/// A single file in the module graph.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+    pub file_id: FileId,
+    pub path: PathBuf,
+    pub parsed: SyntaxNode,
+    pub imports: Vec<Import>,
+    pub exports: Vec<Export>,
+    /// Counts of the directives of the file which suppress rules, empty if the file could not be linted.
+    pub suppressions: SuppressionStats,
+}
+
+impl ModuleInfo {
+    fn new(file: &ProjectFile, parsed: SyntaxNode) -> Self {
+        let mut imports = vec![];
+        let mut exports = vec![];
+
+        for item in parsed.children() {
+            if let Some(decl) = item.try_to::<ImportDecl>() {
+                imports.extend(import(&decl));
+            } else if let Some(named) = item.try_to::<ExportNamed>() {
+                let pairs = named
+                    .specifiers()
+                    .filter_map(|spec| specifier_names(&spec))
+                    .collect::<Vec<_>>();
+                let from = string_child(named.syntax());
+                for (_, exported) in &pairs {
+                    exports.push(Export {
+                        name: exported.clone(),
+                        from: from.clone(),
+                        range: named.range(),
+                    });
+                }
+                if let Some(specifier) = from {
+                    imports.push(Import {
+                        specifier,
+                        resolved: None,
+                        names: pairs.into_iter().map(|(local, _)| local).collect(),
+                        range: named.range(),
+                    });
+                }
+            } else if let Some(wildcard) = item.try_to::<ExportWildcard>() {
+                if let Some(specifier) = string_child(wildcard.syntax()) {
+                    exports.push(Export {
+                        name: "*".to_string(),
+                        from: Some(specifier.clone()),
+                        range: wildcard.range(),
+                    });
+                    imports.push(Import {
+                        specifier,
+                        resolved: None,
+                        names: vec!["*".to_string()],
+                        range: wildcard.range(),
+                    });
+                }
+            } else if item.kind() == SyntaxKind::EXPORT_DEFAULT_DECL
+                || item.kind() == SyntaxKind::EXPORT_DEFAULT_EXPR
+            {
+                exports.push(Export {
+                    name: "default".to_string(),
+                    from: None,
+                    range: item.text_range(),
+                });
+            } else if let Some(decl) = item.try_to::<ExportDecl>() {
+                exports.extend(declared_names(&decl).into_iter().map(|name| Export {
+                    name,
+                    from: None,
+                    range: decl.range(),
+                }));
+            }
+        }
+
+        let offset = TextSize::from(file.offset as u32);
+        for import in imports.iter_mut() {
+            import.range += offset;
+        }
+        for export in exports.iter_mut() {
+            export.range += offset;
+        }
+        Self {
+            file_id: file.file_id,
+            path: file.path.clone(),
+            parsed,
+            imports,
+            exports,
+            suppressions: SuppressionStats::default(),
+        }
+    }
+
+    /// Merge the module of another file extracted from the same physical file into this module.
+    fn merge(&mut self, other: ModuleInfo) {
+        self.imports.extend(other.imports);
+        self.exports.extend(other.exports);
+        self.suppressions.merge(&other.suppressions);
+    }
+
+    /// Whether this module exports a name, not including names which may be exported through wildcard re-exports.
+    pub fn exports_name(&self, name: &str) -> bool {
+        self.exports.iter().any(|export| export.name == name)
+    }
+}
+
+/// The imports and exports of every file in a project.
+#[derive(Debug, Clone)]
+pub struct ModuleGraph {
+    modules: Vec<ModuleInfo>,
+}
+
+impl ModuleGraph {
+    fn new(mut modules: Vec<ModuleInfo>) -> Self {
+        let paths = modules
+            .iter()
+            .map(|module| (module.path.clone(), module.file_id))
+            .collect::<HashMap<_, _>>();
+
+        for module in modules.iter_mut() {
+            let dir = module.path.parent().unwrap_or_else(|| Path::new(""));
+            for import in module.imports.iter_mut() {
+                import.resolved = resolve(dir, &import.specifier, &paths);
+            }
+        }
+        Self { modules }
+    }
+
+    /// All of the modules in the graph, in the order the files were added.
+    pub fn modules(&self) -> impl Iterator<Item = &ModuleInfo> {
+        self.modules.iter()
+    }
+
+    /// Get a module by its file id.
+    pub fn module(&self, file_id: FileId) -> Option<&ModuleInfo> {
+        self.modules.iter().find(|module| module.file_id == file_id)
+    }
+
+    /// The files a file imports from, without duplicates.
+    pub fn dependencies(&self, file_id: FileId) -> Vec<FileId> {
+        let mut deps = vec![];
+        for resolved in self
+            .module(file_id)
+            .into_iter()
+            .flat_map(|module| module.imports.iter())
+            .filter_map(|import| import.resolved)
+        {
+            if !deps.contains(&resolved) {
+                deps.push(resolved);
+            }
+        }
+        deps
+    }
+
+    /// The files which import from a file.
+    pub fn dependents(&self, file_id: FileId) -> Vec<FileId> {
+        self.modules
+            .iter()
+            .filter(|module| {
+                module
+                    .imports
+                    .iter()
+                    .any(|import| import.resolved == Some(file_id))
+            })
+            .map(|module| module.file_id)
+            .collect()
+    }
+
+    /// Every import of a file by other files, along with the file which imports it.
+    pub fn imports_of(&self, file_id: FileId) -> impl Iterator<Item = (FileId, &Import)> {
+        self.modules.iter().flat_map(move |module| {
+            module
+                .imports
+                .iter()
+                .filter(move |import| import.resolved == Some(file_id))
+                .map(move |import| (module.file_id, import))
+        })
+    }
+}
+
+fn import(decl: &ImportDecl) -> Option<Import> {
+    let specifier = string_child(decl.syntax())?;
+    let mut names = vec![];
+    for clause in decl.imports() {
+        match clause {
+            ImportClause::Name(_) => names.push("default".to_string()),
+            ImportClause::WildcardImport(_) => names.push("*".to_string()),
+            ImportClause::NamedImports(named) => names.extend(
+                named
+                    .specifiers()
+                    .filter_map(|spec| specifier_names(&spec))
+                    .map(|(imported, _)| imported),
+            ),
+        }
+    }
+    Some(Import {
+        specifier,
+        resolved: None,
+        names,
+        range: decl.range(),
+    })
+}
+
+/// The name on the left and the name on the right of `as` of a specifier, e.g. `a` and `b` for `a as b`.
+fn specifier_names(spec: &Specifier) -> Option<(std::string::String, std::string::String)> {
+    let left = spec.name()?.text().to_string();
+    let right = spec
+        .alias()
+        .map(|alias| alias.text())
+        .unwrap_or_else(|| left.clone());
+    Some((left, right))
+}
+
+/// The unquoted value of the first string token which is a direct child of a node.
+fn string_child(node: &SyntaxNode) -> Option<std::string::String> {
+    let token = node
+        .children_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .find(|token| token.kind() == SyntaxKind::STRING)?;
+    let text = token.text();
+    Some(text.get(1..text.len().saturating_sub(1))?.to_string())
+}
+
+fn declared_names(decl: &ExportDecl) -> Vec<std::string::String> {
+    match decl.decl() {
+        Some(Decl::FnDecl(func)) => func.name().map(|name| name.text()).into_iter().collect(),
+        Some(Decl::ClassDecl(class)) => class.name().map(|name| name.text()).into_iter().collect(),
+        Some(Decl::VarDecl(var)) => var_decl_bindings(&var)
+            .into_iter()
+            .map(|name| name.text())
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Resolve a relative specifier to a file in the project.
+fn resolve(dir: &Path, specifier: &str, paths: &HashMap<PathBuf, FileId>) -> Option<FileId> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+    let base = normalize(&dir.join(specifier));
+    let mut candidates = vec![base.clone()];
+    for ext in RESOLVED_EXTENSIONS.iter() {
+        let mut with_ext = base.clone().into_os_string();
+        with_ext.push(".");
+        with_ext.push(ext);
+        candidates.push(with_ext.into());
+    }
+    for ext in RESOLVED_EXTENSIONS.iter() {
+        candidates.push(base.join("index").with_extension(ext));
+    }
+    candidates
+        .into_iter()
+        .find_map(|candidate| paths.get(&candidate).copied())
+}
+
+/// Lexically remove `.` and `..` components from a path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !res.pop() {
+                    res.push("..");
+                }
+            }
+            other => res.push(other.as_os_str()),
+        }
+    }
+    res
+}
@@ -0,0 +1,75 @@
+//! Builtin [`ProjectRule`]s which can be added to a [`ProjectLinter`](crate::project::ProjectLinter).
+
+use crate::project::ModuleGraph;
+use crate::{Diagnostic, ProjectRule, Rule};
+
+/// Limit how many directives which suppress rules a single file may have, so suppressions can not pile
+/// up unnoticed.
+///
+/// Every directive counts once no matter how many rules it suppresses, `rslint-enable` directives are not
+/// counted. Files which could not be linted, for example because of an invalid directive, are not checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxDirectives {
+    /// The number of directives a file may have.
+    pub max: usize,
+}
+
+impl MaxDirectives {
+    pub fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl Default for MaxDirectives {
+    fn default() -> Self {
+        Self { max: 5 }
+    }
+}
+
+impl Rule for MaxDirectives {
+    fn name(&self) -> &'static str {
+        "max-directives"
+    }
+
+    fn group(&self) -> &'static str {
+        "project"
+    }
+}
+
+impl ProjectRule for MaxDirectives {
+    fn check_project(&self, graph: &ModuleGraph, diagnostics: &mut Vec<Diagnostic>) {
+        for module in graph.modules() {
+            let stats = &module.suppressions;
+            if stats.total() <= self.max {
+                continue;
+            }
+            let mut err = Diagnostic::error(
+                module.file_id,
+                self.name(),
+                format!(
+                    "this file has {} directives which suppress rules, but only {} are allowed",
+                    stats.total(),
+                    self.max
+                ),
+            )
+            .primary(
+                stats.directives[self.max],
+                "this directive is over the limit",
+            );
+            for range in &stats.directives[self.max + 1..] {
+                err = err.secondary(*range, "");
+            }
+            let most_suppressed = stats
+                .rules
+                .iter()
+                .max_by(|(a_name, a), (b_name, b)| a.cmp(b).then(b_name.cmp(a_name)));
+            if let Some((name, count)) = most_suppressed {
+                err = err.footer_note(format!(
+                    "`{}` is suppressed by {} of the directives",
+                    name, count
+                ));
+            }
+            diagnostics.push(err);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Remapping of diagnostics across edits of their file.
+//!
+//! Editors keep showing the diagnostics of the last lint while the user types and a new lint is running. With
+//! [`remap_diagnostics`] the old diagnostics can be moved to where their code is after an edit, diagnostics
+//! whose code was edited are dropped since their message may no longer be true.
+//!
+//! # Examples
+//! ```
+//! use rslint_core::{lint_file, remap::remap_diagnostics, CstRuleStore};
+//! use rslint_text_edit::TextEdit;
+//!
+//! let store = CstRuleStore::new().builtins();
+//! let result = lint_file(0, "debugger;", false, &store, false).unwrap();
+//! // the user typed a statement before the `debugger`
+//! let edit = TextEdit::insert(0.into(), "foo();\n".to_string());
+//! let remapped = remap_diagnostics(result.diagnostics(), &edit);
+//! assert_eq!(remapped[0].primary.as_ref().unwrap().span.range, 7..16);
+//! ```
+
+use crate::Diagnostic;
+use rslint_errors::SuggestionChange;
+use rslint_parser::{TextRange, TextSize};
+use rslint_text_edit::TextEdit;
+use std::convert::TryFrom;
+use std::ops::Range;
+
+/// The range of `range` after an edit, `None` if the edit changed text inside of the range.
+///
+/// Insertions at the start of a range move it and insertions at its end do not change it, text which is
+/// inserted inside of it or deletions which overlap it invalidate it.
+pub fn remap_range(range: Range<usize>, edit: &TextEdit) -> Option<Range<usize>> {
+    let mut delta: isize = 0;
+    for indel in edit.iter() {
+        let start = usize::from(indel.delete.start());
+        let end = usize::from(indel.delete.end());
+        if end <= range.start {
+            delta += indel.insert.len() as isize - (end - start) as isize;
+        } else if start >= range.end {
+            break;
+        } else {
+            return None;
+        }
+    }
+    let shift = |offset: usize| usize::try_from(offset as isize + delta).ok();
+    Some(shift(range.start)?..shift(range.end)?)
+}
+
+/// A diagnostic after an edit of its file, `None` if the edit invalidated its primary label.
+///
+/// Secondary labels, suggestions, and captures which were invalidated are removed from the diagnostic.
+/// Spans in other files are not changed.
+pub fn remap_diagnostic(diagnostic: &Diagnostic, edit: &TextEdit) -> Option<Diagnostic> {
+    let file_id = diagnostic.file_id;
+    let mut diagnostic = diagnostic.clone();
+    if let Some(primary) = diagnostic.primary.as_mut() {
+        if primary.span.file == file_id {
+            primary.span.range = remap_range(primary.span.range.clone(), edit)?;
+        }
+    }
+
+    diagnostic.children.retain_mut(|child| {
+        if child.span.file != file_id {
+            return true;
+        }
+        match remap_range(child.span.range.clone(), edit) {
+            Some(range) => {
+                child.span.range = range;
+                true
+            }
+            None => false,
+        }
+    });
+    diagnostic.suggestions.retain_mut(|suggestion| {
+        if suggestion.span.file != file_id {
+            return true;
+        }
+        remap_suggestion(suggestion, edit).is_some()
+    });
+    diagnostic
+        .captures
+        .retain(|_, range| match remap_range(range.clone(), edit) {
+            Some(remapped) => {
+                *range = remapped;
+                true
+            }
+            None => false,
+        });
+    Some(diagnostic)
+}
+
+fn remap_suggestion(suggestion: &mut rslint_errors::CodeSuggestion, edit: &TextEdit) -> Option<()> {
+    suggestion.span.range = remap_range(suggestion.span.range.clone(), edit)?;
+    for label in suggestion.labels.iter_mut() {
+        *label = remap_range(label.clone(), edit)?;
+    }
+    if let SuggestionChange::Indels(indels) = &mut suggestion.substitution {
+        for indel in indels.iter_mut() {
+            let range = remap_range(indel.delete.into(), edit)?;
+            indel.delete = TextRange::new(
+                TextSize::from(range.start as u32),
+                TextSize::from(range.end as u32),
+            );
+        }
+    }
+    Some(())
+}
+
+/// Remap diagnostics across an edit of their file with [`remap_diagnostic`], dropping the invalidated ones.
+pub fn remap_diagnostics<'a>(
+    diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+    edit: &TextEdit,
+) -> Vec<Diagnostic> {
+    diagnostics
+        .into_iter()
+        .filter_map(|diagnostic| remap_diagnostic(diagnostic, edit))
+        .collect()
+}
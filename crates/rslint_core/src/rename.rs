@@ -0,0 +1,275 @@
+//! Renaming a rule across a project.
+//!
+//! When a rule is renamed every config and every directive which names it has to be updated, a [`RuleRename`]
+//! collects a fix for each file which mentions the old name and then writes all of them at once:
+//!
+//! ```
+//! use rslint_core::rename::RuleRename;
+//!
+//! let mut rename = RuleRename::new("no-extra-semi", "no-extra-semicolon");
+//! rename
+//!     .add_file("foo.js", "// rslint-ignore no-extra-semi\nfoo();;\n", false)
+//!     .unwrap();
+//! rename.add_config("rslintrc.toml", "[rules.errors]\nno-extra-semi = {}\n");
+//!
+//! let fixed = rename.edits().iter().map(|edit| edit.fixed()).collect::<Vec<_>>();
+//! assert_eq!(fixed[0], "// rslint-ignore no-extra-semicolon\nfoo();;\n");
+//! assert_eq!(fixed[1], "[rules.errors]\nno-extra-semicolon = {}\n");
+//! ```
+//!
+//! The edits are then written with [`RuleRename::commit`].
+//!
+//! Configs are not deserialized, rule names are found in the `rules` tables of the TOML source so comments and
+//! formatting are kept. Names in configs can be in any case (`noEmpty`, `no_empty`), they are replaced with the
+//! new name as it is given.
+
+use crate::{autofix::Fixer, CstRuleStore, Diagnostic, DirectiveParser};
+use rslint_parser::{parse_module, parse_text};
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The fix of a single file.
+#[derive(Debug, Clone)]
+pub struct RenameEdit {
+    pub path: PathBuf,
+    pub fixer: Fixer,
+}
+
+impl RenameEdit {
+    /// The source code of the file after the rename.
+    pub fn fixed(&self) -> String {
+        self.fixer.apply()
+    }
+}
+
+/// A rename of a rule in the configs and directives of a project.
+#[derive(Debug, Clone)]
+pub struct RuleRename {
+    pub old: String,
+    pub new: String,
+    edits: Vec<RenameEdit>,
+}
+
+impl RuleRename {
+    pub fn new(old: impl Into<String>, new: impl Into<String>) -> Self {
+        Self {
+            old: old.into(),
+            new: new.into(),
+            edits: vec![],
+        }
+    }
+
+    /// Rename the rule in the directives of a JavaScript file.
+    ///
+    /// Directive names must match the old name exactly, a malformed directive makes the whole file fail
+    /// since its rule names cannot be told apart.
+    pub fn add_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        src: &str,
+        module: bool,
+    ) -> Result<&mut Self, Diagnostic> {
+        let fixer = self.directives_fix(0, src, module)?;
+        Ok(self.push(path.as_ref(), fixer))
+    }
+
+    /// Rename the rule in a `rslintrc.toml` config.
+    pub fn add_config(&mut self, path: impl AsRef<Path>, src: &str) -> &mut Self {
+        let fixer = self.config_fix(src);
+        self.push(path.as_ref(), fixer)
+    }
+
+    fn push(&mut self, path: &Path, fixer: Fixer) -> &mut Self {
+        if !fixer.indels.is_empty() {
+            self.edits.push(RenameEdit {
+                path: path.to_owned(),
+                fixer,
+            });
+        }
+        self
+    }
+
+    /// The fixes of every added file which mentions the old name.
+    pub fn edits(&self) -> &[RenameEdit] {
+        &self.edits
+    }
+
+    /// The fix which renames the rule in the directives of a JavaScript file.
+    pub fn directives_fix(
+        &self,
+        file_id: usize,
+        src: &str,
+        module: bool,
+    ) -> Result<Fixer, Diagnostic> {
+        let root = if module {
+            parse_module(src, file_id).syntax()
+        } else {
+            parse_text(src, file_id).syntax()
+        };
+        let store = CstRuleStore::new();
+        let mut fixer = Fixer::new(Arc::new(src.to_string()));
+        for (name, range) in DirectiveParser::new(root, file_id, &store).rule_names()? {
+            if name == self.old {
+                fixer.replace(range, &self.new);
+            }
+        }
+        Ok(fixer)
+    }
+
+    /// The fix which renames the rule in the `rules.errors`, `rules.warnings`, and `rules.allowed` tables of
+    /// a `rslintrc.toml` config.
+    pub fn config_fix(&self, src: &str) -> Fixer {
+        let mut fixer = Fixer::new(Arc::new(src.to_string()));
+        let mut table: Vec<String> = vec![];
+        // whether the previous line started an array of allowed rules which was not closed yet
+        let mut in_allowed = false;
+        let mut offset = 0;
+
+        for line in src.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let line = &line[..find_unquoted(line, '#').unwrap_or(line.len())];
+            if in_allowed {
+                in_allowed = self.rename_strings(&mut fixer, line, start);
+                continue;
+            }
+
+            let trimmed = line.trim_start();
+            let start = start + line.len() - trimmed.len();
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let keys = toml_keys(header, start + 1);
+                self.rename_key(&mut fixer, &[], &keys);
+                table = keys.into_iter().map(|(key, _)| key).collect();
+            } else if let Some(eq) = find_unquoted(trimmed, '=') {
+                let keys = toml_keys(&trimmed[..eq], start);
+                self.rename_key(&mut fixer, &table, &keys);
+                let path = table.iter().chain(keys.iter().map(|(key, _)| key));
+                let value = &trimmed[eq + 1..];
+                if path
+                    .map(String::as_str)
+                    .eq(["rules", "allowed"].iter().copied())
+                    && value.trim_start().starts_with('[')
+                {
+                    in_allowed = self.rename_strings(&mut fixer, value, start + eq + 1);
+                }
+            }
+        }
+        fixer
+    }
+
+    /// Rename the rule if one of the keys of a line under `table` is the name of a configured rule.
+    fn rename_key(&self, fixer: &mut Fixer, table: &[String], keys: &[(String, Range<usize>)]) {
+        let mut path = table.iter().chain(keys.iter().map(|(key, _)| key));
+        let level = match (path.next(), path.next()) {
+            (Some(rules), Some(level)) if rules == "rules" => level,
+            _ => return,
+        };
+        if level != "errors" && level != "warnings" {
+            return;
+        }
+        // the rule name is the third key of the path, which may be part of the table header
+        if table.len() > 2 {
+            return;
+        }
+        if let Some((name, range)) = keys.get(2 - table.len()) {
+            if same_rule(name, &self.old) {
+                fixer.replace(range.clone(), &self.new);
+            }
+        }
+    }
+
+    /// Rename the rule in the strings of an array, returns whether the array continues on the next line.
+    fn rename_strings(&self, fixer: &mut Fixer, text: &str, offset: usize) -> bool {
+        let mut idx = 0;
+        while let Some(c) = text[idx..].chars().next() {
+            match c {
+                '"' | '\'' => {
+                    let end = text[idx + 1..]
+                        .find(c)
+                        .map_or(text.len(), |end| idx + 1 + end);
+                    if same_rule(&text[idx + 1..end], &self.old) {
+                        fixer.replace(offset + idx + 1..offset + end, &self.new);
+                    }
+                    idx = (end + 1).min(text.len());
+                }
+                ']' => return false,
+                _ => idx += c.len_utf8(),
+            }
+        }
+        true
+    }
+
+    /// Write every edit, if writing any file fails the files which were already written are restored.
+    pub fn commit(&self) -> io::Result<()> {
+        for (idx, edit) in self.edits.iter().enumerate() {
+            if let Err(err) = fs::write(&edit.path, edit.fixed()) {
+                for written in &self.edits[..idx] {
+                    let _ = fs::write(&written.path, written.fixer.src.as_bytes());
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a rule name in a config is the name of `rule`, config names can be in any case.
+fn same_rule(name: &str, rule: &str) -> bool {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    normalize(name) == normalize(rule)
+}
+
+/// The index of the first `pat` which is not inside of a string.
+fn find_unquoted(text: &str, pat: char) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, c) if c == pat => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The keys of a dotted TOML key such as `rules.errors."no-empty"` with the range of each key,
+/// quoted keys are unquoted. The key ends at `=` or `]`.
+fn toml_keys(text: &str, offset: usize) -> Vec<(String, Range<usize>)> {
+    let is_bare = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let mut keys = vec![];
+    let mut idx = 0;
+
+    while let Some(c) = text[idx..].chars().next() {
+        match c {
+            '"' | '\'' => {
+                let end = text[idx + 1..]
+                    .find(c)
+                    .map_or(text.len(), |end| idx + 1 + end);
+                keys.push((
+                    text[idx + 1..end].to_string(),
+                    offset + idx + 1..offset + end,
+                ));
+                idx = (end + 1).min(text.len());
+            }
+            c if is_bare(c) => {
+                let end = text[idx..]
+                    .find(|c| !is_bare(c))
+                    .map_or(text.len(), |end| idx + end);
+                keys.push((text[idx..end].to_string(), offset + idx..offset + end));
+                idx = end;
+            }
+            ']' | '=' => break,
+            _ => idx += c.len_utf8(),
+        }
+    }
+    keys
+}
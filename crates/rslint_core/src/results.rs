@@ -0,0 +1,185 @@
+//! The results of every rule run on a file, indexed by rule.
+
+use crate::RuleResult;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use std::ops::Index;
+
+/// The position of a rule in the [`CstRuleStore`](crate::CstRuleStore) it was run from, which is the order
+/// rules were registered in. Ids are only comparable between results linted with the same store, so they are not
+/// exported and results which are kept when the store changes get new ids with [`RuleResults::reassign_ids`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RuleId(u32);
+
+impl RuleId {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index as u32)
+    }
+}
+
+type Entry = (RuleId, &'static str, RuleResult);
+
+/// The results of the rules run on a file.
+///
+/// Results are looked up by rule name in constant time and iterated in the order the rules were registered
+/// in the store, regardless of the order the rules finished running in.
+#[derive(Debug, Clone, Default)]
+pub struct RuleResults {
+    entries: Vec<Entry>,
+    positions: HashMap<&'static str, usize>,
+}
+
+impl RuleResults {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the result of a rule, replacing and returning the previous result of the rule if there was one.
+    pub(crate) fn insert(
+        &mut self,
+        id: RuleId,
+        name: &'static str,
+        result: RuleResult,
+    ) -> Option<RuleResult> {
+        if let Some(&pos) = self.positions.get(name) {
+            if self.entries[pos].0 == id {
+                return Some(std::mem::replace(&mut self.entries[pos].2, result));
+            }
+            let old = self.remove(name);
+            self.insert(id, name, result);
+            return old;
+        }
+        let pos = self.entries.partition_point(|(other, ..)| *other <= id);
+        self.entries.insert(pos, (id, name, result));
+        self.reindex(pos);
+        None
+    }
+
+    /// Remove the result of a rule.
+    pub fn remove(&mut self, name: &str) -> Option<RuleResult> {
+        let pos = self.positions.remove(name)?;
+        let (_, _, result) = self.entries.remove(pos);
+        self.reindex(pos);
+        Some(result)
+    }
+
+    /// Add the results of `other`, replacing the results of rules which are in both.
+    pub fn extend(&mut self, other: RuleResults) {
+        for (id, name, result) in other.entries {
+            self.insert(id, name, result);
+        }
+    }
+
+    /// Give every result the id of its rule in a new store, which has to contain the rules of every result.
+    pub(crate) fn reassign_ids(&mut self, ids: &HashMap<&'static str, RuleId>) {
+        for (id, name, _) in &mut self.entries {
+            *id = ids[name];
+        }
+        self.entries.sort_by_key(|(id, ..)| *id);
+        self.positions.clear();
+        self.reindex(0);
+    }
+
+    /// Keep only the results for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&'static str, &mut RuleResult) -> bool) {
+        self.entries
+            .retain_mut(|(_, name, result)| keep(name, result));
+        self.positions.clear();
+        self.reindex(0);
+    }
+
+    fn reindex(&mut self, from: usize) {
+        for (pos, (_, name, _)) in self.entries.iter().enumerate().skip(from) {
+            self.positions.insert(name, pos);
+        }
+    }
+
+    /// The result of a rule by its name.
+    pub fn get(&self, name: &str) -> Option<&RuleResult> {
+        self.positions.get(name).map(|&pos| &self.entries[pos].2)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut RuleResult> {
+        let pos = *self.positions.get(name)?;
+        Some(&mut self.entries[pos].2)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.positions.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The names of the rules, in registration order.
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|(_, name, _)| *name)
+    }
+
+    /// The name and result of every rule, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &RuleResult)> {
+        self.entries.iter().map(|(_, name, result)| (*name, result))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut RuleResult)> {
+        self.entries
+            .iter_mut()
+            .map(|(_, name, result)| (*name, result))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &RuleResult> {
+        self.entries.iter().map(|(.., result)| result)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut RuleResult> {
+        self.entries.iter_mut().map(|(.., result)| result)
+    }
+
+    pub fn into_values(self) -> impl Iterator<Item = RuleResult> {
+        self.entries.into_iter().map(|(.., result)| result)
+    }
+}
+
+impl Index<&str> for RuleResults {
+    type Output = RuleResult;
+
+    fn index(&self, name: &str) -> &RuleResult {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no result for the rule `{}`", name))
+    }
+}
+
+impl IntoIterator for RuleResults {
+    type Item = (&'static str, RuleResult);
+    type IntoIter = std::iter::Map<std::vec::IntoIter<Entry>, fn(Entry) -> Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries
+            .into_iter()
+            .map(|(_, name, result)| (name, result))
+    }
+}
+
+impl<'a> IntoIterator for &'a RuleResults {
+    type Item = (&'static str, &'a RuleResult);
+    type IntoIter = std::iter::Map<std::slice::Iter<'a, Entry>, fn(&'a Entry) -> Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(_, name, result)| (*name, result))
+    }
+}
+
+impl FromIterator<Entry> for RuleResults {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        let mut results = Self::new();
+        for (id, name, result) in iter {
+            results.insert(id, name, result);
+        }
+        results
+    }
+}
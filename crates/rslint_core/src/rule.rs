@@ -4,13 +4,16 @@
 #![allow(unused_variables, unused_imports)]
 
 use crate::autofix::Fixer;
-use crate::Diagnostic;
+use crate::project::ModuleGraph;
+use crate::util::globals::Globals;
+use crate::{Diagnostic, FileKind};
 use dyn_clone::DynClone;
-use rslint_errors::Severity;
+use rslint_errors::{Applicability, Severity, Span};
 use rslint_parser::{SyntaxNode, SyntaxNodeExt, SyntaxToken};
 use rslint_text_edit::apply_indels;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::{Send, Sync};
 use std::ops::{Deref, DerefMut, Drop};
@@ -70,6 +73,43 @@ pub trait CstRule: Rule {
     fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
         None
     }
+
+    /// Whether the rule still gives useful results on files which are mostly syntax errors.
+    /// Such files are linted in a degraded mode where only these rules are run, and only
+    /// `check_root` and `check_token` are called, tokens inside of `ERROR` nodes included.
+    /// See the [`degraded`](crate::degraded) module for more info.
+    /// Defaults to `false`.
+    #[inline]
+    fn tolerates_parse_errors(&self) -> bool {
+        false
+    }
+
+    /// Whether the rule applies to files of a kind, for example rules about imports only apply to modules.
+    /// The runner does not run rules on files they do not apply to, these rules are listed in a note if
+    /// [`LinterOptions::report_disabled_rules`](crate::LinterOptions::report_disabled_rules) is set.
+    /// Defaults to `true`.
+    #[inline]
+    fn applies_to(&self, kind: FileKind) -> bool {
+        true
+    }
+
+    /// Whether the rule can fix some of the problems it reports through [`RuleCtx::fix`].
+    /// This is only used for metadata, see [`RuleMeta`](crate::RuleMeta).
+    /// Defaults to `false`.
+    #[inline]
+    fn fixable(&self) -> bool {
+        false
+    }
+}
+
+/// A rule which runs once on every file of a project at once, such as rules which check for import cycles
+/// or unused exports.
+///
+/// Project rules are run by the [`ProjectLinter`](crate::project::ProjectLinter) after every file has been linted,
+/// unlike [`CstRule`]s they are not affected by directives.
+pub trait ProjectRule: Rule {
+    /// Check the module graph of the project, adding any diagnostics to `diagnostics`.
+    fn check_project(&self, graph: &ModuleGraph, diagnostics: &mut Vec<Diagnostic>);
 }
 
 /// A generic trait which describes things common to a rule regardless on what they run on.
@@ -90,6 +130,7 @@ pub trait Rule: Debug + DynClone + Send + Sync {
 
 dyn_clone::clone_trait_object!(Rule);
 dyn_clone::clone_trait_object!(CstRule);
+dyn_clone::clone_trait_object!(ProjectRule);
 
 /// The level configured for a rule.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -106,6 +147,8 @@ pub enum RuleLevel {
 pub struct RuleCtx {
     /// The file id of the file being linted.
     pub file_id: usize,
+    /// The name of the rule being run, used as the code of diagnostics made with [`diagnostic`](Self::diagnostic).
+    pub rule: &'static str,
     /// Whether the linter is run with the `--verbose` option.
     /// Which dictates whether the linter should include more (potentially spammy) context in diagnostics.
     pub verbose: bool,
@@ -113,10 +156,26 @@ pub struct RuleCtx {
     pub diagnostics: Vec<Diagnostic>,
     pub fixer: Option<Fixer>,
     pub src: Arc<String>,
+    /// The global variables of the file, from [`LinterOptions::globals`](crate::LinterOptions::globals) and
+    /// the `/* global name */` comments of the file.
+    pub globals: Arc<Globals>,
 }
 
 impl RuleCtx {
-    /// Make a new diagnostic builder.
+    /// Make a new diagnostic for the rule being run, with a primary label at `span` and the name of the rule
+    /// as its code. The diagnostic is added to the context with [`RuleDiagnostic::emit`].
+    ///
+    /// Unlike diagnostics made with [`err`](Self::err), the diagnostic always has exactly one primary label
+    /// and every span of it is checked to be a valid range of the file when it is emitted.
+    pub fn diagnostic(&mut self, span: impl Span, title: impl Into<String>) -> RuleDiagnostic<'_> {
+        let diagnostic = Diagnostic::error(self.file_id, self.rule, title).primary(span, "");
+        RuleDiagnostic {
+            ctx: self,
+            diagnostic,
+        }
+    }
+
+    /// Make a new diagnostic, prefer [`diagnostic`](Self::diagnostic) for new rules.
     pub fn err(&mut self, code: impl Into<String>, message: impl Into<String>) -> Diagnostic {
         Diagnostic::error(self.file_id, code.into(), message.into())
     }
@@ -125,6 +184,25 @@ impl RuleCtx {
         self.diagnostics.push(diagnostic)
     }
 
+    /// Add a diagnostic with a suggested change which is not safe to apply automatically, such as a change
+    /// which may alter the behavior of the code. The change is added to the diagnostic as a
+    /// [`MaybeIncorrect`](Applicability::MaybeIncorrect) suggestion, editors can offer it as a quick fix
+    /// but unlike changes made through [`fix`](Self::fix) it is never applied by autofix.
+    pub fn add_suggestion(
+        &mut self,
+        diagnostic: Diagnostic,
+        span: impl Span,
+        msg: &str,
+        replacement: impl Into<String>,
+    ) {
+        self.add_err(diagnostic.suggestion_inline(
+            span,
+            msg,
+            replacement,
+            Applicability::MaybeIncorrect,
+        ))
+    }
+
     /// Make a new fixer for this context and return a mutable reference to it
     pub fn fix(&mut self) -> &mut Fixer {
         let fixer = Fixer::new(self.src.clone());
@@ -133,11 +211,118 @@ impl RuleCtx {
     }
 }
 
+/// A diagnostic of a rule which is being built, made with [`RuleCtx::diagnostic`].
+#[must_use = "the diagnostic is only added to the context with `emit`"]
+pub struct RuleDiagnostic<'ctx> {
+    ctx: &'ctx mut RuleCtx,
+    diagnostic: Diagnostic,
+}
+
+impl RuleDiagnostic<'_> {
+    /// Move the primary label to another span.
+    pub fn primary(mut self, span: impl Span, msg: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.primary(span, msg);
+        self
+    }
+
+    /// Set the message of the primary label.
+    pub fn primary_label(mut self, msg: impl Into<String>) -> Self {
+        if let Some(primary) = self.diagnostic.primary.as_mut() {
+            primary.msg = msg.into();
+        }
+        self
+    }
+
+    /// Add a secondary label.
+    pub fn secondary(mut self, span: impl Span, msg: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.secondary(span, msg);
+        self
+    }
+
+    /// Add a note below the diagnostic.
+    pub fn footer_note(mut self, msg: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.footer_note(msg);
+        self
+    }
+
+    /// Add a help message below the diagnostic.
+    pub fn footer_help(mut self, msg: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.footer_help(msg);
+        self
+    }
+
+    /// Suggest replacing `span` with `replacement`, see [`Diagnostic::suggestion`].
+    pub fn suggestion(
+        mut self,
+        span: impl Span,
+        msg: &str,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        self.diagnostic = self
+            .diagnostic
+            .suggestion(span, msg, replacement, applicability);
+        self
+    }
+
+    /// Attach a named span for machine readable output, see [`Diagnostic::capture`].
+    pub fn capture(mut self, name: impl Into<String>, span: impl Span) -> Self {
+        self.diagnostic = self.diagnostic.capture(name, span);
+        self
+    }
+
+    /// Mark the code of the primary label as unnecessary, such as unreachable code.
+    pub fn unnecessary(mut self) -> Self {
+        self.diagnostic = self.diagnostic.unnecessary();
+        self
+    }
+
+    /// Add the diagnostic to the context.
+    ///
+    /// # Panics
+    /// Panics in debug builds if a span of the diagnostic ends before it starts or goes past the end of
+    /// the file. In release builds such spans are clamped to the file instead.
+    pub fn emit(self) {
+        let RuleDiagnostic {
+            ctx,
+            mut diagnostic,
+        } = self;
+        let len = ctx.src.len();
+        let file_id = ctx.file_id;
+        let mut spans = diagnostic
+            .primary
+            .iter_mut()
+            .chain(diagnostic.children.iter_mut())
+            .map(|label| &mut label.span)
+            .chain(diagnostic.suggestions.iter_mut().map(|s| &mut s.span))
+            .filter(|span| span.file == file_id)
+            .map(|span| &mut span.range)
+            .chain(diagnostic.captures.values_mut())
+            .collect::<Vec<_>>();
+        for range in spans.iter_mut() {
+            debug_assert!(
+                range.start <= range.end && range.end <= len,
+                "`{}` made a diagnostic with the span {:?} in a file of {} bytes",
+                ctx.rule,
+                range,
+                len
+            );
+            range.end = range.end.min(len);
+            range.start = range.start.min(range.end);
+        }
+        ctx.diagnostics.push(diagnostic);
+    }
+}
+
 /// The result of running a single rule on a syntax tree.
 #[derive(Debug, Clone)]
 pub struct RuleResult {
     pub diagnostics: Vec<Diagnostic>,
     pub fixer: Option<Fixer>,
+    /// The diagnostics which were suppressed by directives, each marked with the directive which
+    /// suppressed it. These are only kept if [`LinterOptions::record_suppressed`](crate::LinterOptions::record_suppressed)
+    /// is set, and do not count towards the outcome.
+    pub suppressed: Vec<Diagnostic>,
 }
 
 impl RuleResult {
@@ -146,6 +331,7 @@ impl RuleResult {
         Self {
             diagnostics,
             fixer: fixer.into(),
+            suppressed: vec![],
         }
     }
 
@@ -154,12 +340,18 @@ impl RuleResult {
         Outcome::from(&self.diagnostics)
     }
 
+    /// The number of diagnostics of each severity emitted by this rule.
+    pub fn summary(&self) -> OutcomeSummary {
+        OutcomeSummary::from(&self.diagnostics)
+    }
+
     /// Merge two results, this will join `self` and `other`'s diagnostics and take
     /// `self`'s fixer if available or otherwise take `other`'s fixer
     pub fn merge(self, other: RuleResult) -> RuleResult {
         RuleResult {
             diagnostics: [self.diagnostics, other.diagnostics].concat(),
             fixer: self.fixer.or(other.fixer),
+            suppressed: [self.suppressed, other.suppressed].concat(),
         }
     }
 
@@ -189,15 +381,13 @@ where
     T::Item: Borrow<Diagnostic>,
 {
     fn from(diagnostics: T) -> Self {
-        let mut outcome = Outcome::Success;
-        for diagnostic in diagnostics {
-            match diagnostic.borrow().severity {
-                Severity::Error => outcome = Outcome::Failure,
-                Severity::Warning if outcome != Outcome::Failure => outcome = Outcome::Warning,
-                _ => {}
-            }
-        }
-        outcome
+        OutcomeSummary::from(diagnostics).outcome()
+    }
+}
+
+impl From<OutcomeSummary> for Outcome {
+    fn from(summary: OutcomeSummary) -> Self {
+        summary.outcome()
     }
 }
 
@@ -215,6 +405,206 @@ impl Outcome {
     }
 }
 
+/// When a result counts as failing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FailurePolicy {
+    /// Fail if there are any errors.
+    #[default]
+    Errors,
+    /// Fail if there are any errors or warnings.
+    Warnings,
+    /// Fail if there are any errors or more than this many warnings.
+    MaxWarnings(usize),
+}
+
+/// How hosts decide the outcome of results, such as failing on warnings.
+///
+/// Severity floors only change how the diagnostics of a rule are counted, the diagnostics keep their severity.
+///
+/// ```
+/// use rslint_core::{Diagnostic, FailurePolicy, Outcome, OutcomePolicy, Severity};
+///
+/// let policy = OutcomePolicy::new(FailurePolicy::MaxWarnings(5)).floor("no-console", Severity::Error);
+/// let diagnostics = vec![Diagnostic::warning(0, "no-console", "")];
+/// assert_eq!(policy.outcome(&diagnostics), Outcome::Failure);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutcomePolicy {
+    /// When a result counts as failing, [`FailurePolicy::Warnings`] treats warnings as errors.
+    pub failure: FailurePolicy,
+    /// The lowest severity the diagnostics of a rule are counted as by the name of the rule, which is the code of
+    /// its diagnostics. Parser diagnostics can be raised by their `SyntaxError` or `ParserWarning` code.
+    pub severity_floors: HashMap<String, Severity>,
+}
+
+impl OutcomePolicy {
+    pub fn new(failure: FailurePolicy) -> Self {
+        Self {
+            failure,
+            severity_floors: HashMap::new(),
+        }
+    }
+
+    /// Count the diagnostics of a rule as at least `severity`.
+    pub fn floor(mut self, rule: impl Into<String>, severity: Severity) -> Self {
+        self.severity_floors.insert(rule.into(), severity);
+        self
+    }
+
+    /// The severity a diagnostic is counted as.
+    pub fn severity(&self, diagnostic: &Diagnostic) -> Severity {
+        let floor = diagnostic
+            .code
+            .as_ref()
+            .and_then(|code| self.severity_floors.get(code));
+        match floor {
+            Some(floor) if *floor > diagnostic.severity => *floor,
+            _ => diagnostic.severity,
+        }
+    }
+
+    /// The number of diagnostics of each severity with the severity floors applied.
+    pub fn summary<T>(&self, diagnostics: T) -> OutcomeSummary
+    where
+        T: IntoIterator,
+        T::Item: Borrow<Diagnostic>,
+    {
+        let mut summary = OutcomeSummary::default();
+        for diagnostic in diagnostics {
+            let diagnostic = diagnostic.borrow();
+            if diagnostic.suppressed_by.is_none() {
+                summary.count(self.severity(diagnostic));
+            }
+        }
+        summary
+    }
+
+    /// The outcome of diagnostics under this policy, see [`OutcomeSummary::outcome_with`].
+    ///
+    /// The outcome of many files is decided from the sum of their summaries, since the maximum number of warnings
+    /// applies to all of the files together.
+    pub fn outcome<T>(&self, diagnostics: T) -> Outcome
+    where
+        T: IntoIterator,
+        T::Item: Borrow<Diagnostic>,
+    {
+        self.summary(diagnostics).outcome_with(self.failure)
+    }
+}
+
+impl From<FailurePolicy> for OutcomePolicy {
+    fn from(failure: FailurePolicy) -> Self {
+        Self::new(failure)
+    }
+}
+
+/// The number of diagnostics of each severity in the result of running a rule, linting a file, or linting many files.
+///
+/// Summaries can be added together, and converted into an [`Outcome`]. Diagnostics which were
+/// [suppressed](Diagnostic::suppressed_by) by a directive are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OutcomeSummary {
+    pub bugs: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub notes: usize,
+    pub helps: usize,
+}
+
+impl OutcomeSummary {
+    /// The coarse outcome of the result, bugs count as errors.
+    pub fn outcome(&self) -> Outcome {
+        if self.bugs + self.errors > 0 {
+            Outcome::Failure
+        } else if self.warnings > 0 {
+            Outcome::Warning
+        } else {
+            Outcome::Success
+        }
+    }
+
+    /// Whether the result fails under a policy.
+    pub fn failing(&self, policy: FailurePolicy) -> bool {
+        let errors = self.bugs + self.errors;
+        match policy {
+            FailurePolicy::Errors => errors > 0,
+            FailurePolicy::Warnings => errors + self.warnings > 0,
+            FailurePolicy::MaxWarnings(max) => errors > 0 || self.warnings > max,
+        }
+    }
+
+    /// The outcome of the result under a policy, results which fail the policy are failures even if they only
+    /// have warnings.
+    pub fn outcome_with(&self, policy: FailurePolicy) -> Outcome {
+        if self.failing(policy) {
+            Outcome::Failure
+        } else if self.warnings > 0 {
+            Outcome::Warning
+        } else {
+            Outcome::Success
+        }
+    }
+
+    /// The number of errors and warnings, which are the diagnostics users have to act on.
+    pub fn problems(&self) -> usize {
+        self.bugs + self.errors + self.warnings
+    }
+
+    fn count(&mut self, severity: Severity) {
+        match severity {
+            Severity::Bug => self.bugs += 1,
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Note => self.notes += 1,
+            Severity::Help => self.helps += 1,
+        }
+    }
+}
+
+impl<T> From<T> for OutcomeSummary
+where
+    T: IntoIterator,
+    T::Item: Borrow<Diagnostic>,
+{
+    fn from(diagnostics: T) -> Self {
+        let mut summary = OutcomeSummary::default();
+        // suppressed diagnostics are only kept for auditing and never count
+        for diagnostic in diagnostics {
+            let diagnostic = diagnostic.borrow();
+            if diagnostic.suppressed_by.is_none() {
+                summary.count(diagnostic.severity);
+            }
+        }
+        summary
+    }
+}
+
+impl std::ops::Add for OutcomeSummary {
+    type Output = OutcomeSummary;
+
+    fn add(self, other: OutcomeSummary) -> OutcomeSummary {
+        OutcomeSummary {
+            bugs: self.bugs + other.bugs,
+            errors: self.errors + other.errors,
+            warnings: self.warnings + other.warnings,
+            notes: self.notes + other.notes,
+            helps: self.helps + other.helps,
+        }
+    }
+}
+
+impl std::ops::AddAssign for OutcomeSummary {
+    fn add_assign(&mut self, other: OutcomeSummary) {
+        *self = *self + other;
+    }
+}
+
+impl std::iter::Sum for OutcomeSummary {
+    fn sum<I: Iterator<Item = OutcomeSummary>>(iter: I) -> OutcomeSummary {
+        iter.fold(OutcomeSummary::default(), std::ops::Add::add)
+    }
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __pre_parse_docs_from_meta {
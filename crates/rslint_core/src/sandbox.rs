@@ -0,0 +1,111 @@
+//! A counter of the read syscalls made by rules, which [`rule_tests`](crate::rule_tests) uses to check that
+//! rules do not read files or make network requests while they are tested.
+//!
+//! This is only a debugging aid for the tests of this crate and not a sandbox:
+//! - Reads are counted through `/proc/thread-self/io`, therefore this module only exists on linux and rules are
+//!   not checked on other platforms.
+//! - Allocations are not tracked, rules which allocate a lot are not detected.
+//! - Only reads of the current thread are counted, reads on threads spawned by a rule are not detected.
+//! - Writes are not counted so rules can still be debugged with `dbg!`, which means rules which only write to
+//!   files or sockets are not detected either.
+
+/// The reads a closure run by [`count_reads`] made.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadReport {
+    /// The number of read syscalls, `None` if `/proc/thread-self/io` could not be read.
+    pub read_syscalls: Option<u64>,
+}
+
+impl ReadReport {
+    /// Panic if the rule read from a file, socket, or any other source.
+    pub fn assert_no_reads(&self, rule: &str) {
+        if let Some(reads) = self.read_syscalls.filter(|reads| *reads > 0) {
+            panic!(
+                "rule `{}` made {} read syscall(s) while linting, rules must never make file or network requests",
+                rule, reads
+            );
+        }
+    }
+}
+
+/// Run a closure and count the read syscalls it made on the current thread.
+pub fn count_reads<T>(f: impl FnOnce() -> T) -> (T, ReadReport) {
+    // reading the counter is a read syscall itself, so the cost of a measurement is subtracted
+    let overhead = read_syscalls()
+        .zip(read_syscalls())
+        .map(|(before, after)| after - before);
+    let before = read_syscalls();
+    let res = f();
+    let read_syscalls = before
+        .zip(read_syscalls())
+        .zip(overhead)
+        .map(|((before, after), overhead)| (after - before).saturating_sub(overhead));
+    (res, ReadReport { read_syscalls })
+}
+
+/// The number of read syscalls made by the current thread so far.
+fn read_syscalls() -> Option<u64> {
+    use std::io::Read;
+
+    // a single fixed size read keeps the number of syscalls of a measurement constant
+    let mut buf = [0; 512];
+    let len = std::fs::File::open("/proc/thread-self/io")
+        .and_then(|mut file| file.read(&mut buf))
+        .ok()?;
+    std::str::from_utf8(&buf[..len])
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("syscr:"))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{run_rule, CstRule, Rule, RuleCtx};
+    use rslint_parser::{parse_module, SyntaxNode};
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+
+    /// A rule which breaks the rules by reading a file.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct ReadsFiles;
+
+    impl Rule for ReadsFiles {
+        fn name(&self) -> &'static str {
+            "reads-files"
+        }
+
+        fn group(&self) -> &'static str {
+            "test"
+        }
+    }
+
+    #[typetag::serde]
+    impl CstRule for ReadsFiles {
+        fn check_root(&self, _: &SyntaxNode, _: &mut RuleCtx) -> Option<()> {
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+                .ok()
+                .map(drop)
+        }
+    }
+
+    fn run(rule: &dyn CstRule) -> ReadReport {
+        let src = "let a = 5;";
+        let root = parse_module(src, 0).syntax();
+        count_reads(|| run_rule(rule, 0, root, false, &[], Arc::new(src.to_string()))).1
+    }
+
+    #[test]
+    fn file_reads_are_counted() {
+        let report = run(&ReadsFiles);
+        assert!(report.read_syscalls.unwrap() > 0);
+        assert!(std::panic::catch_unwind(|| report.assert_no_reads("reads-files")).is_err());
+
+        let report = run(&*crate::get_rule_by_name("no-empty").unwrap());
+        assert_eq!(report.read_syscalls, Some(0));
+        report.assert_no_reads("no-empty");
+    }
+}
@@ -0,0 +1,339 @@
+//! Conversion of lint results to the [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format, which is used by tools such as GitHub code scanning.
+//!
+//! The log is built with [`sarif_log`] and can be serialized with any serde serializer which
+//! supports JSON, e.g. `serde_json`.
+
+use crate::meta::docs_url;
+use crate::{CstRule, LintResult};
+use rslint_errors::{
+    file::{FileId, Files, PathDisplay},
+    CodeSuggestion, Diagnostic, Severity,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+/// The schema of the emitted log.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// The SARIF version of the emitted log.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+const INFORMATION_URI: &str = "https://github.com/RDambrosio016/RSLint";
+
+/// The top level object of a SARIF file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<Run>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    pub artifacts: Vec<Artifact>,
+    pub results: Vec<SarifResult>,
+    /// How columns are counted, RSLint always counts unicode scalar values.
+    pub column_kind: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+/// The metadata of a single rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    pub properties: RuleProperties,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleProperties {
+    /// The group of the rule, or the plugin name for external rules.
+    pub group: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Artifact {
+    pub location: ArtifactLocation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+    pub index: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+    pub level: &'static str,
+    pub message: Message,
+    pub locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<ResultProperties>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResultProperties {
+    /// The named spans captured by the rule, see [`Diagnostic::capture`].
+    pub captures: BTreeMap<String, Region>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+/// A region of a file, lines and columns are one based and columns count unicode scalar values
+/// with tabs expanded to the [tab width](Files::tab_width) of the files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+    pub description: Message,
+    pub artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChange {
+    pub artifact_location: ArtifactLocation,
+    pub replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replacement {
+    pub deleted_region: Region,
+    pub inserted_content: Message,
+}
+
+/// Convert the diagnostics of a collection of lint results to a SARIF log with a single run.
+///
+/// Every rule of the stores used for linting is described in the log's tool metadata. Diagnostics which
+/// are not from a rule (e.g. parser errors) are still included but do not reference any rule metadata.
+/// Suggestions are included as fixes. `files` must contain the source code of every linted file.
+pub fn sarif_log(results: &[LintResult], files: &dyn Files) -> SarifLog {
+    let mut rules = vec![];
+    let mut rule_indices = HashMap::new();
+    for result in results {
+        for rule in &result.store.rules {
+            if !rule_indices.contains_key(rule.name()) {
+                rule_indices.insert(rule.name(), rules.len());
+                rules.push(descriptor(&**rule));
+            }
+        }
+    }
+
+    let mut artifacts: Vec<Artifact> = vec![];
+    let mut artifact_indices = HashMap::new();
+    let mut artifact_location = |file_id: FileId| {
+        let index = *artifact_indices.entry(file_id).or_insert_with(|| {
+            let uri = PathDisplay::default().display(files.name(file_id).unwrap_or_default());
+            artifacts.push(Artifact {
+                location: ArtifactLocation {
+                    uri,
+                    index: artifacts.len(),
+                },
+            });
+            artifacts.len() - 1
+        });
+        artifacts[index].location.clone()
+    };
+
+    let mut sarif_results = vec![];
+    for result in results {
+        for diagnostic in result.diagnostics() {
+            let artifact = artifact_location(diagnostic.file_id);
+            if let Some(res) = sarif_result(diagnostic, &artifact, &rule_indices, files) {
+                sarif_results.push(res);
+            }
+        }
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: ToolComponent {
+                    name: "rslint",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: INFORMATION_URI,
+                    rules,
+                },
+            },
+            artifacts,
+            results: sarif_results,
+            column_kind: "unicodeCodePoints",
+        }],
+    }
+}
+
+fn descriptor(rule: &dyn CstRule) -> ReportingDescriptor {
+    let docs = rule.docs().trim();
+    let short = docs.lines().next().map(|line| line.trim()).unwrap_or("");
+    let message = |text: &str| {
+        Some(Message {
+            text: text.to_string(),
+        })
+        .filter(|msg| !msg.text.is_empty())
+    };
+    ReportingDescriptor {
+        id: rule.name().to_string(),
+        short_description: message(short),
+        full_description: message(docs),
+        help_uri: docs_url(rule),
+        properties: RuleProperties {
+            group: rule.group(),
+        },
+    }
+}
+
+fn sarif_result(
+    diagnostic: &Diagnostic,
+    artifact: &ArtifactLocation,
+    rule_indices: &HashMap<&str, usize>,
+    files: &dyn Files,
+) -> Option<SarifResult> {
+    let primary = diagnostic.primary.as_ref()?;
+    let location = |range: Range<usize>, msg: &str| {
+        Some(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: artifact.clone(),
+                region: region(files, diagnostic.file_id, range)?,
+            },
+            message: Some(Message {
+                text: msg.to_string(),
+            })
+            .filter(|msg| !msg.text.is_empty()),
+        })
+    };
+    let rule_id = diagnostic.code.clone().unwrap_or_default();
+
+    Some(SarifResult {
+        rule_index: rule_indices.get(rule_id.as_str()).copied(),
+        rule_id,
+        level: level(diagnostic.severity),
+        message: Message {
+            text: diagnostic.title.clone(),
+        },
+        locations: vec![location(primary.span.range.clone(), &primary.msg)?],
+        related_locations: diagnostic
+            .children
+            .iter()
+            .filter_map(|child| location(child.span.range.clone(), &child.msg))
+            .collect(),
+        fixes: diagnostic
+            .suggestions
+            .iter()
+            .filter_map(|suggestion| fix(suggestion, artifact, files))
+            .collect(),
+        properties: Some(ResultProperties {
+            captures: diagnostic
+                .captures
+                .iter()
+                .filter_map(|(name, range)| {
+                    Some((
+                        name.clone(),
+                        region(files, diagnostic.file_id, range.clone())?,
+                    ))
+                })
+                .collect(),
+        })
+        .filter(|properties| !properties.captures.is_empty()),
+    })
+}
+
+fn fix(suggestion: &CodeSuggestion, artifact: &ArtifactLocation, files: &dyn Files) -> Option<Fix> {
+    let range = suggestion.span.range.clone();
+    let text = suggestion.replacement(files.source(suggestion.span.file)?)?;
+
+    Some(Fix {
+        description: Message {
+            text: suggestion.msg.clone(),
+        },
+        artifact_changes: vec![ArtifactChange {
+            artifact_location: artifact.clone(),
+            replacements: vec![Replacement {
+                deleted_region: region(files, suggestion.span.file, range)?,
+                inserted_content: Message { text },
+            }],
+        }],
+    })
+}
+
+fn region(files: &dyn Files, file_id: FileId, range: Range<usize>) -> Option<Region> {
+    let (start_line, start_column) = files.location(file_id, range.start)?;
+    let (end_line, end_column) = files.location(file_id, range.end)?;
+    Some(Region {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+        byte_offset: range.start,
+        byte_length: range.end - range.start,
+    })
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
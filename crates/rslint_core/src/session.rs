@@ -0,0 +1,280 @@
+//! Long running linting sessions which keep the results of files between runs.
+//!
+//! Hosts such as language servers lint the same files over and over. A [`LintSession`] caches the results of
+//! every rule for every file, so linting a file whose source did not change returns the cached results, and
+//! reloading the configuration with [`LintSession::reload_config`] only throws out the results of rules whose
+//! configuration changed. The next run on a file then only runs those rules.
+//!
+//! The session does not keep any scope information between runs. Rules resolve names on demand with
+//! [`util::scope`](crate::util::scope), which walks the current tree, so there are no facts which have to be
+//! updated after an edit and results of a relinted file never depend on an earlier tree.
+
+use crate::directives::{apply_top_level_directives, DirectiveParser, SuppressionStats};
+use crate::store::same_config;
+use crate::{degraded, lint_rules_inner, parse_file, CstRuleStore, LintResult, LinterOptions};
+use crate::{Diagnostic, RuleResults};
+use rslint_parser::{GreenNode, SyntaxNode};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// The cached results of a file.
+#[derive(Debug, Clone)]
+struct SessionFile {
+    source: String,
+    module: bool,
+    green: GreenNode,
+    /// The parser diagnostics before severity overrides are applied.
+    parser_diagnostics: Vec<Diagnostic>,
+    result: CachedResult,
+    /// Rules whose results were thrown out and have to be run again.
+    stale: HashSet<&'static str>,
+    /// Whether the file has to be linted again even if no rules are stale, because its directive
+    /// diagnostics depend on the rules of the store.
+    outdated: bool,
+    /// The rule names given to the directives of the file, including names of rules which do not exist.
+    directive_rules: HashSet<String>,
+}
+
+/// The parts of a [`LintResult`] which do not borrow the store.
+#[derive(Debug, Clone)]
+struct CachedResult {
+    parser_diagnostics: Vec<Diagnostic>,
+    rule_results: RuleResults,
+    directive_diagnostics: Vec<Diagnostic>,
+    suppressions: SuppressionStats,
+    degraded_declarations: Option<Vec<degraded::TopLevelDeclaration>>,
+}
+
+impl From<LintResult<'_>> for CachedResult {
+    fn from(result: LintResult) -> Self {
+        Self {
+            parser_diagnostics: result.parser_diagnostics,
+            rule_results: result.rule_results,
+            directive_diagnostics: result.directive_diagnostics,
+            suppressions: result.suppressions,
+            degraded_declarations: result.degraded_declarations,
+        }
+    }
+}
+
+/// How the rules of a session changed when its configuration was reloaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Rules which are only in the new store.
+    pub added: Vec<&'static str>,
+    /// Rules which are only in the old store.
+    pub removed: Vec<&'static str>,
+    /// Rules which are in both stores but are configured differently.
+    pub changed: Vec<&'static str>,
+    /// The files whose results are affected by the change and should be linted again, sorted by id.
+    pub affected_files: Vec<usize>,
+}
+
+impl ConfigChange {
+    /// Whether no rules were added, removed, or reconfigured.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A store and the cached lint results of files linted with it.
+#[derive(Debug, Clone)]
+pub struct LintSession {
+    store: CstRuleStore,
+    options: LinterOptions,
+    files: HashMap<usize, SessionFile>,
+}
+
+impl LintSession {
+    pub fn new(store: CstRuleStore, options: LinterOptions) -> Self {
+        Self {
+            store,
+            options,
+            files: HashMap::new(),
+        }
+    }
+
+    /// The rule store currently used for linting.
+    pub fn store(&self) -> &CstRuleStore {
+        &self.store
+    }
+
+    /// Lint a file, only running the rules whose results are not cached for this exact source code.
+    pub fn lint(
+        &mut self,
+        file_id: usize,
+        source: &str,
+        module: bool,
+    ) -> Result<LintResult<'_>, Diagnostic> {
+        let cached = self
+            .files
+            .get(&file_id)
+            .filter(|file| file.source == source && file.module == module);
+
+        match cached {
+            Some(file) if file.stale.is_empty() && !file.outdated => {}
+            Some(file) => {
+                let root = SyntaxNode::new_root(file.green.clone());
+                let only = file.stale.clone();
+                let result = lint_rules_inner(
+                    root,
+                    file.parser_diagnostics.clone(),
+                    file_id,
+                    &self.store,
+                    self.options.clone(),
+                    Some(&only),
+                )?;
+                let mut result = CachedResult::from(result);
+                let file = self.files.get_mut(&file_id).unwrap();
+                let mut rule_results = std::mem::take(&mut file.result.rule_results);
+                rule_results.extend(result.rule_results);
+                result.rule_results = rule_results;
+                file.result = result;
+                file.stale.clear();
+                file.outdated = false;
+            }
+            None => {
+                let (parser_diagnostics, root) = parse_file(file_id, source, module);
+                let green = root.green().clone();
+                let directive_rules = DirectiveParser::new(root.clone(), file_id, &self.store)
+                    .rule_names()
+                    .map(|names| names.into_iter().map(|(name, _)| name).collect())
+                    .unwrap_or_default();
+                let result = lint_rules_inner(
+                    root,
+                    parser_diagnostics.clone(),
+                    file_id,
+                    &self.store,
+                    self.options.clone(),
+                    None,
+                )?;
+                let file = SessionFile {
+                    source: source.to_string(),
+                    module,
+                    green,
+                    parser_diagnostics,
+                    result: result.into(),
+                    stale: HashSet::new(),
+                    outdated: false,
+                    directive_rules,
+                };
+                self.files.insert(file_id, file);
+            }
+        }
+        Ok(self.cached_result(file_id))
+    }
+
+    fn cached_result(&self, file_id: usize) -> LintResult<'_> {
+        let file = &self.files[&file_id];
+        LintResult {
+            parser_diagnostics: file.result.parser_diagnostics.clone(),
+            store: &self.store,
+            rule_results: file.result.rule_results.clone(),
+            directive_diagnostics: file.result.directive_diagnostics.clone(),
+            suppressions: file.result.suppressions.clone(),
+            parsed: SyntaxNode::new_root(file.green.clone()),
+            file_id,
+            options: self.options.clone(),
+            fixed_code: None,
+            degraded_declarations: file.result.degraded_declarations.clone(),
+            diagnostic_index: OnceLock::new(),
+        }
+    }
+
+    /// Forget the cached results of a file.
+    pub fn remove(&mut self, file_id: usize) {
+        self.files.remove(&file_id);
+    }
+
+    /// Replace the store of the session, throwing out only the cached results affected by the rules
+    /// which were added, removed, or reconfigured.
+    ///
+    /// A file is affected by a rule if the rule ran on it, or for new rules, if the rule is not ignored
+    /// by the file's top level directives. Files whose directives mention an added or removed rule are
+    /// also affected since their directive diagnostics may change.
+    pub fn reload_config(&mut self, store: CstRuleStore) -> ConfigChange {
+        let mut change = ConfigChange::default();
+        for rule in &store.rules {
+            match self.store.get_shared(rule.name()) {
+                None => change.added.push(rule.name()),
+                Some(old) if !same_config(&*old, &**rule) => change.changed.push(rule.name()),
+                _ => {}
+            }
+        }
+        change.removed = self
+            .store
+            .rules
+            .iter()
+            .map(|rule| rule.name())
+            .filter(|name| store.get_shared(name).is_none())
+            .collect();
+        self.store = store;
+        if change.is_empty() {
+            return change;
+        }
+
+        let mut added = self.store.clone();
+        added
+            .rules
+            .retain(|rule| change.added.contains(&rule.name()));
+        let ids = self.store.rule_ids();
+        for (file_id, file) in self.files.iter_mut() {
+            let mut affected = false;
+            for name in &change.removed {
+                affected |= file.result.rule_results.remove(name).is_some();
+                file.stale.remove(name);
+            }
+            // the results which are kept are ordered by the positions of their rules in the new store
+            file.result.rule_results.reassign_ids(&ids);
+            for name in &change.changed {
+                if file.result.rule_results.remove(name).is_some() || file.stale.contains(name) {
+                    file.stale.insert(name);
+                    affected = true;
+                }
+            }
+            if !added.rules.is_empty() {
+                let effective = effective_rules(file, *file_id, &added, &self.store);
+                affected |= !effective.is_empty();
+                file.stale.extend(effective);
+            }
+            if change
+                .added
+                .iter()
+                .chain(change.removed.iter())
+                .any(|name| file.directive_rules.contains(*name))
+            {
+                file.outdated = true;
+                affected = true;
+            }
+            if affected {
+                change.affected_files.push(*file_id);
+            }
+        }
+        change.affected_files.sort_unstable();
+        change
+    }
+}
+
+/// The rules of `rules` which would run on a file, which excludes rules ignored by top level directives
+/// and rules which are not run in degraded mode.
+fn effective_rules(
+    file: &SessionFile,
+    file_id: usize,
+    rules: &CstRuleStore,
+    store: &CstRuleStore,
+) -> Vec<&'static str> {
+    let mut rules = rules.clone();
+    if file.result.degraded_declarations.is_some() {
+        rules.rules.retain(|rule| rule.tolerates_parse_errors());
+    }
+    let root = SyntaxNode::new_root(file.green.clone());
+    // a file whose directives are invalid is linted again to report the error
+    if let Ok(results) = DirectiveParser::new(root, file_id, store).get_file_directives() {
+        let directives = results
+            .into_iter()
+            .map(|res| res.directive)
+            .collect::<Vec<_>>();
+        apply_top_level_directives(&directives, &mut rules, &mut vec![], file_id);
+    }
+    rules.rules.iter().map(|rule| rule.name()).collect()
+}
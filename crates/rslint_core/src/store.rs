@@ -1,12 +1,20 @@
 //! A rule store, which houses rule groups as well as individual rules.
 
 use crate::groups::*;
-use crate::CstRule;
+use crate::plugin::{external_rules, rule_namespace};
+use crate::preset::Preset;
+use crate::results::RuleId;
+use crate::{CstRule, RuleMeta};
+use dyn_clone::clone_box;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A utility structure for housing CST rules for a linting run.
+///
+/// Rules are shared between clones of a store, so cloning a store is cheap.
 #[derive(Debug, Default, Clone)]
 pub struct CstRuleStore {
-    pub rules: Vec<Box<dyn CstRule>>,
+    pub rules: Vec<Arc<dyn CstRule>>,
 }
 
 impl CstRuleStore {
@@ -16,13 +24,142 @@ impl CstRuleStore {
 
     /// All built in rules from every group.
     pub fn builtins(mut self) -> Self {
-        self.rules.extend(errors());
+        self.load_rules(errors());
+        self.load_rules(style());
+        self.load_rules(promises());
+        self.load_rules(node());
+        self.load_rules(regex());
         self
     }
 
+    /// The rules of the `errors` group.
+    pub fn errors(self) -> Self {
+        self.preset(&Preset::errors())
+    }
+
+    /// The rules of the `style` group.
+    pub fn style(self) -> Self {
+        self.preset(&Preset::style())
+    }
+
+    /// The rules of the `promises` group.
+    pub fn promises(self) -> Self {
+        self.preset(&Preset::promises())
+    }
+
+    /// The rules of the `node` group.
+    pub fn node(self) -> Self {
+        self.preset(&Preset::node())
+    }
+
+    /// The rules of the `regex` group.
+    pub fn regex(self) -> Self {
+        self.preset(&Preset::regex())
+    }
+
+    /// The recommended rules, see [`Preset::recommended`].
+    pub fn recommended(self) -> Self {
+        self.preset(&Preset::recommended())
+    }
+
+    /// Load the rules of a preset, rules with the same name as a rule already in the store replace it.
+    ///
+    /// # Examples
+    /// ```
+    /// use rslint_core::{preset::Preset, CstRuleStore};
+    ///
+    /// let store = CstRuleStore::new()
+    ///     .recommended()
+    ///     .preset(&Preset::style())
+    ///     .preset(&Preset::promises())
+    ///     .preset(&Preset::node())
+    ///     .preset(&Preset::regex());
+    /// assert_eq!(store.rules.len(), CstRuleStore::new().builtins().rules.len());
+    /// ```
+    pub fn preset(mut self, preset: &Preset) -> Self {
+        for rule in preset.rules() {
+            let rule: Arc<dyn CstRule> = Arc::from(clone_box(&**rule));
+            match self
+                .rules
+                .iter_mut()
+                .find(|prev| prev.name() == rule.name())
+            {
+                Some(prev) => *prev = rule,
+                None => self.rules.push(rule),
+            }
+        }
+        self
+    }
+
+    /// All rules of every registered plugin, see [`plugin`](crate::plugin).
+    pub fn external(mut self) -> Self {
+        for rule in external_rules() {
+            self.register_external(rule);
+        }
+        self
+    }
+
+    /// Register a rule from a third party crate in this store.
+    ///
+    /// # Panics
+    /// Panics if the rule's name is not namespaced with its plugin's name (e.g. `my-plugin/no-foo`),
+    /// or if a different rule with the same name is already in the store.
+    pub fn register_external(&mut self, rule: Box<dyn CstRule>) {
+        assert!(
+            rule_namespace(rule.name()).is_some(),
+            "external rule `{}` must be namespaced with its plugin name, e.g. `plugin/{}`",
+            rule.name(),
+            rule.name()
+        );
+        if self.rules.iter().any(|prev| prev.name() == rule.name()) {
+            panic!(
+                "external rule `{}` conflicts with a rule already in the store",
+                rule.name()
+            );
+        }
+        self.rules.push(rule.into());
+    }
+
     /// Load a list of rules into this store.
     pub fn load_rules(&mut self, rules: impl IntoIterator<Item = Box<dyn CstRule>>) {
-        self.rules.extend(rules);
+        self.rules.extend(rules.into_iter().map(Arc::from));
+    }
+
+    /// A copy of this store where rules are replaced by the rule with the same name in `overrides`.
+    ///
+    /// Rules which are not overridden, or whose override has the same configuration, are shared with
+    /// this store instead of being cloned. Overrides for rules which are not in the store are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use rslint_core::{groups::errors::NoEmpty, CstRule, CstRuleStore};
+    /// use std::sync::Arc;
+    ///
+    /// let store = CstRuleStore::new().builtins();
+    /// let no_empty = NoEmpty {
+    ///     allow_empty_catch: true,
+    ///     ..NoEmpty::default()
+    /// };
+    /// let overridden = store.with_overrides(vec![Box::new(no_empty) as Box<dyn CstRule>]);
+    ///
+    /// let shared = |name| Arc::ptr_eq(&store.get_shared(name).unwrap(), &overridden.get_shared(name).unwrap());
+    /// assert!(!shared("no-empty"));
+    /// assert!(shared("for-direction"));
+    /// ```
+    pub fn with_overrides(&self, overrides: impl IntoIterator<Item = Box<dyn CstRule>>) -> Self {
+        let mut overrides = overrides
+            .into_iter()
+            .map(|rule| (rule.name(), rule))
+            .collect::<HashMap<_, _>>();
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| match overrides.remove(rule.name()) {
+                Some(new) if !same_config(&*new, &**rule) => Arc::from(new),
+                _ => rule.clone(),
+            })
+            .collect();
+        Self { rules }
     }
 
     /// Get a rule using its rule name from this store.
@@ -34,9 +171,44 @@ impl CstRuleStore {
     /// assert!(CstRuleStore::new().builtins().get("no-empty").is_some())
     /// ```
     pub fn get(&self, rule_name: impl AsRef<str>) -> Option<Box<dyn CstRule>> {
+        self.get_shared(rule_name).map(|rule| clone_box(&*rule))
+    }
+
+    /// The metadata of every rule in the store.
+    pub fn metadata(&self) -> impl Iterator<Item = RuleMeta> + '_ {
+        self.rules.iter().map(|rule| rule.meta())
+    }
+
+    /// Get a rule using its rule name from this store without cloning it.
+    pub fn get_shared(&self, rule_name: impl AsRef<str>) -> Option<Arc<dyn CstRule>> {
         self.rules
             .iter()
             .find(|rule| rule.name() == rule_name.as_ref())
             .cloned()
     }
+
+    /// The id of a rule in this store, which orders the results of the rule in a [`LintResult`](crate::LintResult).
+    pub(crate) fn rule_id(&self, rule_name: impl AsRef<str>) -> Option<RuleId> {
+        self.rules
+            .iter()
+            .position(|rule| rule.name() == rule_name.as_ref())
+            .map(RuleId::new)
+    }
+
+    /// The id of every rule in this store by its name.
+    pub(crate) fn rule_ids(&self) -> HashMap<&'static str, RuleId> {
+        self.rules
+            .iter()
+            .enumerate()
+            .map(|(idx, rule)| (rule.name(), RuleId::new(idx)))
+            .collect()
+    }
+}
+
+/// Whether two rules are configured the same, rules which fail to serialize are always considered different.
+pub(crate) fn same_config(a: &dyn CstRule, b: &dyn CstRule) -> bool {
+    match (serde_json::to_string(a), serde_json::to_string(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
 }
@@ -1,5 +1,23 @@
 //! Macros for easily making rule tests which also generate documentation examples.
 
+use crate::{run_rule, CstRule, RuleResult};
+use rslint_parser::SyntaxNode;
+use std::sync::Arc;
+
+/// Run a rule on the code of a rule test, for the tests of this crate on linux the test fails if the rule reads
+/// files or makes network requests, see the `sandbox` module.
+pub fn run_rule_test(rule: &dyn CstRule, root: SyntaxNode, src: &str) -> RuleResult {
+    let run = || run_rule(rule, 0, root, true, &[], Arc::new(src.to_string()));
+    #[cfg(all(test, target_os = "linux"))]
+    {
+        let (result, report) = crate::sandbox::count_reads(run);
+        report.assert_no_reads(rule.name());
+        result
+    }
+    #[cfg(not(all(test, target_os = "linux")))]
+    run()
+}
+
 /// A macro for generating linter rule tests.
 ///
 /// The tests are also used to generate "more examples" sections
@@ -7,6 +25,9 @@
 /// on a code expr to make docgen ignore it for user facing docs.
 ///
 /// test code is run as modules, not scripts.
+///
+/// When the tests of this crate run on linux the read syscalls of rules are counted with the `sandbox` module, so
+/// tests fail if a rule reads files or makes network requests.
 #[macro_export]
 macro_rules! rule_tests {
     ($rule:expr,
@@ -52,7 +73,7 @@ macro_rules! rule_tests {
         fn $err_name() {
             $(
                 let res = rslint_parser::parse_module($code, 0);
-                let errs = $crate::run_rule(&$rule, 0, res.syntax(), true, &[], std::sync::Arc::new($code.to_string()));
+                let errs = $crate::__run_rule_test(&$rule, res.syntax(), $code);
                 if errs.diagnostics.is_empty() {
                     panic!("\nExpected:\n```\n{}\n```\nto fail linting, but instead it passed (with {} parsing errors)", $code, res.errors().len());
                 }
@@ -63,7 +84,7 @@ macro_rules! rule_tests {
         fn $ok_name() {
             $(
                 let res = rslint_parser::parse_module($ok_code, 0);
-                let errs = $crate::run_rule(&$rule, 0, res.syntax(), true, &[], std::sync::Arc::new($ok_code.to_string()));
+                let errs = $crate::__run_rule_test(&$rule, res.syntax(), $ok_code);
 
                 if !errs.diagnostics.is_empty() {
                     panic!("\nExpected:\n```\n{}\n```\nto pass linting, but instead it threw errors (along with {} parsing errors):\n\n", $ok_code, res.errors().len());
@@ -0,0 +1,137 @@
+//! Snapshots of the diagnostic counts of a run, for tracking how a project's diagnostics change over time.
+//!
+//! A [`TrendSnapshot`] counts the diagnostics of each rule by severity and can include the [`PhaseTimings`]
+//! of the run, so the performance of the linter can be tracked next to the diagnostics. Snapshots are
+//! serializable so CI can store one per run, and snapshots of separate runs, such as the shards of a
+//! parallel CI job, are combined with [`TrendSnapshot::merge`].
+//!
+//! # Examples
+//! ```
+//! use rslint_core::{lint_file, CstRuleStore, trend::TrendSnapshot};
+//!
+//! let store = CstRuleStore::new().builtins();
+//! let result = lint_file(0, "if (true) {}", false, &store, false).unwrap();
+//! let snapshot = TrendSnapshot::from_results(&[result]);
+//! assert_eq!(snapshot.rules["no-constant-condition"].errors, 1);
+//! ```
+
+use crate::profile::PhaseTimings;
+use crate::{Diagnostic, LintResult};
+use rslint_errors::Severity;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The number of diagnostics of each severity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SeverityCounts {
+    /// Errors and bugs.
+    pub errors: u64,
+    pub warnings: u64,
+    /// Notes and help messages.
+    pub notes: u64,
+}
+
+impl SeverityCounts {
+    pub fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Bug | Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Note | Severity::Help => self.notes += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &SeverityCounts) {
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+        self.notes += other.notes;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.errors + self.warnings + self.notes
+    }
+}
+
+/// The diagnostic counts of a run and optionally the time it spent in each phase.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TrendSnapshot {
+    /// The number of files which were linted.
+    pub files: u64,
+    /// The counts of each rule by its name, syntax errors are counted under their codes.
+    pub rules: BTreeMap<String, SeverityCounts>,
+    /// The time spent in each phase, `None` if the run was not profiled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<PhaseTimings>,
+}
+
+impl TrendSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A snapshot of the diagnostics of lint results, without timings.
+    pub fn from_results<'a, 's: 'a>(results: impl IntoIterator<Item = &'a LintResult<'s>>) -> Self {
+        let mut snapshot = Self::new();
+        for result in results {
+            snapshot.add_result(result);
+        }
+        snapshot
+    }
+
+    /// Count a file and its diagnostics.
+    pub fn add_result(&mut self, result: &LintResult) {
+        self.files += 1;
+        for diagnostic in result.diagnostics() {
+            self.add_diagnostic(diagnostic);
+        }
+    }
+
+    /// Count a diagnostic which is not part of a lint result, such as the diagnostic of a JSON file.
+    pub fn add_diagnostic(&mut self, diagnostic: &Diagnostic) {
+        let code = diagnostic.code.as_deref().unwrap_or("unknown");
+        self.rules
+            .entry(code.to_string())
+            .or_default()
+            .add(diagnostic.severity);
+    }
+
+    /// Attach the phase timings of the run, usually [`profile::phases`](crate::profile::phases).
+    pub fn with_timings(mut self, timings: PhaseTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    /// The counts of every rule combined.
+    pub fn total(&self) -> SeverityCounts {
+        let mut total = SeverityCounts::default();
+        for counts in self.rules.values() {
+            total.merge(counts);
+        }
+        total
+    }
+
+    /// Add the counts and timings of another snapshot to this one.
+    ///
+    /// Timings are added up like the counts. If only one of the snapshots has timings the merged snapshot
+    /// keeps them, so the timings only cover the runs which were profiled.
+    pub fn merge(&mut self, other: &TrendSnapshot) {
+        self.files += other.files;
+        for (rule, counts) in &other.rules {
+            self.rules.entry(rule.clone()).or_default().merge(counts);
+        }
+        if let Some(other) = &other.timings {
+            self.timings.get_or_insert(PhaseTimings::ZERO).merge(other);
+        }
+    }
+}
+
+/// Merge many snapshots into one, see [`TrendSnapshot::merge`].
+pub fn merge(snapshots: impl IntoIterator<Item = TrendSnapshot>) -> TrendSnapshot {
+    snapshots
+        .into_iter()
+        .fold(TrendSnapshot::new(), |mut merged, snapshot| {
+            merged.merge(&snapshot);
+            merged
+        })
+}
@@ -0,0 +1,85 @@
+//! Utilities for adding and removing semicolons without changing how automatic semicolon insertion (ASI)
+//! splits statements.
+//!
+//! A newline only ends a statement if the next token cannot continue it, so removing the semicolon from
+//! `foo();` in
+//!
+//! ```js
+//! foo();
+//! [1, 2].forEach(bar);
+//! ```
+//!
+//! turns the two statements into `foo()[1, 2].forEach(bar)`. Fixes which remove semicolons should check
+//! [`can_remove_semicolon`] first.
+
+use super::tokens::{last_non_trivia, next_non_trivia, trivia_after};
+use crate::rule_prelude::*;
+use SyntaxKind::*;
+
+/// The statements which end with a semicolon, or with a newline by ASI.
+pub const SEMICOLON_STMTS: [SyntaxKind; 12] = [
+    EXPR_STMT,
+    VAR_DECL,
+    RETURN_STMT,
+    THROW_STMT,
+    BREAK_STMT,
+    CONTINUE_STMT,
+    DEBUGGER_STMT,
+    DO_WHILE_STMT,
+    IMPORT_DECL,
+    EXPORT_DEFAULT_EXPR,
+    EXPORT_NAMED,
+    EXPORT_WILDCARD,
+];
+
+/// Whether a statement ends with a semicolon or by ASI, `var` declarations in the head of a `for` loop do not.
+pub fn needs_semicolon(node: &SyntaxNode) -> bool {
+    SEMICOLON_STMTS.contains(&node.kind())
+        && node.parent().map(|parent| parent.kind()) != Some(FOR_STMT_INIT)
+}
+
+/// The semicolon which ends a statement, if it has one.
+///
+/// The semicolon of `export { a };` is parsed as an empty statement after the export, it is returned as well.
+pub fn terminator(stmt: &SyntaxNode) -> Option<SyntaxToken> {
+    let last = last_non_trivia(stmt)?;
+    if last.kind() == SEMICOLON {
+        return Some(last);
+    }
+    if stmt.kind() == EXPORT_NAMED {
+        return next_non_trivia(&last)
+            .filter(|next| next.kind() == SEMICOLON && next.parent().kind() == EMPTY_STMT);
+    }
+    None
+}
+
+/// Whether an empty statement is the semicolon of the statement before it, see [`terminator`].
+pub fn is_terminator(empty: &SyntaxNode) -> bool {
+    empty
+        .prev_sibling()
+        .and_then(|prev| terminator(&prev))
+        .filter(|semicolon| Some(semicolon) == empty.first_token().as_ref())
+        .is_some()
+}
+
+/// Whether a token at the start of a line continues the statement on the line before it, such as the
+/// `(` of a call or the `/` of a division, instead of starting a new statement.
+pub fn continues_statement(token: &SyntaxToken) -> bool {
+    matches!(
+        token.kind(),
+        L_PAREN | L_BRACK | BACKTICK | PLUS | MINUS | SLASH | SLASHEQ | REGEX
+    )
+}
+
+/// Whether the semicolon ending a statement can be removed without changing the code, the next token has to be
+/// `}`, the end of the file, or a token on another line which does not continue the statement.
+pub fn can_remove_semicolon(semicolon: &SyntaxToken) -> bool {
+    let trivia = trivia_after(semicolon);
+    let has_newline = trivia.iter().any(|token| token.text().contains('\n'));
+    let next = trivia.last().unwrap_or(semicolon).next_token();
+    match next {
+        None => true,
+        Some(next) if matches!(next.kind(), EOF | T!['}']) => true,
+        Some(next) => has_newline && !continues_statement(&next),
+    }
+}
@@ -0,0 +1,603 @@
+//! Control flow graphs of statements, for rules which need to know which code is reachable or how
+//! control moves between statements.
+//!
+//! A [`ControlFlowGraph`] is built from a list of statements, usually the body of a function or a script,
+//! and splits them into basic blocks connected by edges. Nested functions and classes are not descended
+//! into since they are separate bodies which can be analyzed by building their own graph.
+//!
+//! Compound statements such as `if` and loops are recorded in the block their test is evaluated in,
+//! their nested statements are recorded in the blocks of their branches. `case` and `default` clauses
+//! are recorded as the first node of the block of their statements.
+//!
+//! The graph does not evaluate conditions, every branch is assumed to be taken except for loops with
+//! a literal `true` condition (or no condition), which are never exited unless by a jump.
+
+use crate::rule_prelude::*;
+use ast::{Finalizer, TryStmt};
+use std::collections::{HashSet, VecDeque};
+use SyntaxKind::*;
+
+/// The index of a block in a [`ControlFlowGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockId(pub usize);
+
+/// How control moves along an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// Execution continues with the next statement.
+    Normal,
+    /// The test of an `if` or a loop was truthy.
+    True,
+    /// The test of an `if` or a loop was falsey.
+    False,
+    /// The discriminant of a `switch` matched a case, or no case matched and the `default` clause is taken.
+    Case,
+    /// The statements of a case completed and execution continues in the next case.
+    Fallthrough,
+    Break,
+    Continue,
+    Return,
+    /// An exception was thrown, either explicitly or by any statement of a `try` block.
+    Throw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub to: BlockId,
+    pub kind: EdgeKind,
+}
+
+/// A sequence of nodes which are always executed one after another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub nodes: Vec<SyntaxNode>,
+    pub edges: Vec<Edge>,
+}
+
+/// The control flow graph of a list of statements.
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    blocks: Vec<BasicBlock>,
+    reachable: Vec<bool>,
+}
+
+impl ControlFlowGraph {
+    /// Build the graph of the statements of a node, this is either a block statement, a function body,
+    /// a script or module, or a single statement such as a `switch`.
+    ///
+    /// Jumps to targets outside of the statements, such as `continue` in a `switch` or `return` in any
+    /// statement, are edges to the [exit](ControlFlowGraph::exit).
+    pub fn new(node: &SyntaxNode) -> Self {
+        let stmts = match node.kind() {
+            BLOCK_STMT | SCRIPT | MODULE => node.children().collect(),
+            _ => vec![node.clone()],
+        };
+        Self::from_stmts(stmts)
+    }
+
+    /// Build the graph of a list of statements which are executed one after another.
+    pub fn from_stmts(stmts: impl IntoIterator<Item = SyntaxNode>) -> Self {
+        let mut builder = Builder {
+            blocks: vec![BasicBlock::default(), BasicBlock::default()],
+            current: ENTRY,
+            jumps: vec![],
+            frames: vec![],
+        };
+        builder.stmts(stmts);
+        builder.edge(builder.current, EXIT, EdgeKind::Normal);
+
+        let mut graph = Self {
+            blocks: builder.blocks,
+            reachable: vec![],
+        };
+        graph.reachable = graph.compute_reachable();
+        graph
+    }
+
+    /// The block execution starts in.
+    pub fn entry(&self) -> BlockId {
+        ENTRY
+    }
+
+    /// The block every path out of the statements ends in, it has no nodes.
+    pub fn exit(&self) -> BlockId {
+        EXIT
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    pub fn block(&self, id: BlockId) -> &BasicBlock {
+        &self.blocks[id.0]
+    }
+
+    /// Every edge of the graph as `(from, edge)` pairs.
+    pub fn edges(&self) -> impl Iterator<Item = (BlockId, Edge)> + '_ {
+        self.blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, block)| block.edges.iter().map(move |edge| (BlockId(idx), *edge)))
+    }
+
+    /// The blocks with an edge to a block.
+    pub fn predecessors(&self, id: BlockId) -> impl Iterator<Item = (BlockId, Edge)> + '_ {
+        self.edges().filter(move |(_, edge)| edge.to == id)
+    }
+
+    /// Whether there is a path from the entry to a block.
+    pub fn is_reachable(&self, id: BlockId) -> bool {
+        self.reachable[id.0]
+    }
+
+    /// Whether execution can complete normally by running off the end of the statements,
+    /// e.g. a function whose end is reachable implicitly returns `undefined`.
+    pub fn completes_normally(&self) -> bool {
+        self.predecessors(EXIT)
+            .any(|(from, edge)| edge.kind == EdgeKind::Normal && self.is_reachable(from))
+    }
+
+    /// The `return` statements which can be reached, sorted by their position.
+    pub fn returns(&self) -> Vec<SyntaxNode> {
+        let mut returns = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| self.reachable[*idx])
+            .flat_map(|(_, block)| block.nodes.iter())
+            .filter(|node| node.kind() == RETURN_STMT)
+            .cloned()
+            .collect::<Vec<_>>();
+        returns.sort_by_key(|node| node.text_range().start());
+        // the statements of `finally` blocks are in multiple blocks
+        returns.dedup();
+        returns
+    }
+
+    /// The outermost nodes which are only in unreachable blocks, sorted by their position.
+    ///
+    /// Nodes which are in multiple blocks, such as the statements of a `finally` block, are only
+    /// unreachable if every block they are in is unreachable.
+    pub fn unreachable_nodes(&self) -> Vec<SyntaxNode> {
+        let reachable = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| self.reachable[*idx])
+            .flat_map(|(_, block)| block.nodes.iter().cloned())
+            .collect::<HashSet<_>>();
+        let unreachable = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.reachable[*idx])
+            .flat_map(|(_, block)| block.nodes.iter().cloned())
+            .filter(|node| !reachable.contains(node))
+            .collect::<HashSet<_>>();
+
+        let mut nodes = unreachable
+            .iter()
+            .filter(|node| !node.ancestors().skip(1).any(|it| unreachable.contains(&it)))
+            .cloned()
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.text_range().start());
+        nodes
+    }
+
+    fn compute_reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut queue = VecDeque::from(vec![ENTRY]);
+        reachable[ENTRY.0] = true;
+        while let Some(id) = queue.pop_front() {
+            for edge in &self.blocks[id.0].edges {
+                if !reachable[edge.to.0] {
+                    reachable[edge.to.0] = true;
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+const ENTRY: BlockId = BlockId(0);
+const EXIT: BlockId = BlockId(1);
+
+/// A statement which `break` or `continue` can jump to.
+struct JumpTarget {
+    labels: Vec<String>,
+    /// Whether an unlabeled `break` targets this statement, which is true for loops and `switch`.
+    breakable: bool,
+    break_to: BlockId,
+    continue_to: Option<BlockId>,
+    /// The number of enclosing `try` frames when the target was entered.
+    depth: usize,
+}
+
+/// A jump out of a `try` statement which has to run the `finally` block first.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct PendingJump {
+    from: BlockId,
+    edge: Edge,
+    depth: usize,
+}
+
+/// A `try` statement whose `catch` or `finally` block is currently being built around.
+enum Frame {
+    /// Exceptions are caught by the block.
+    Catch(BlockId),
+    /// Jumps out of the statement run the `finally` block before continuing to their target.
+    Finally(Vec<PendingJump>),
+}
+
+struct Builder {
+    blocks: Vec<BasicBlock>,
+    current: BlockId,
+    jumps: Vec<JumpTarget>,
+    frames: Vec<Frame>,
+}
+
+impl Builder {
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(BasicBlock::default());
+        BlockId(self.blocks.len() - 1)
+    }
+
+    fn edge(&mut self, from: BlockId, to: BlockId, kind: EdgeKind) {
+        self.blocks[from.0].edges.push(Edge { to, kind });
+    }
+
+    fn push(&mut self, node: &SyntaxNode) {
+        self.blocks[self.current.0].nodes.push(node.clone());
+    }
+
+    /// Jump out of the current statement to a target entered at `depth` enclosing `try` frames.
+    ///
+    /// The jump goes through the innermost `finally` block between the jump and its target, or to the
+    /// innermost `catch` block for throws. Building continues in a new block, which is unreachable
+    /// unless something else jumps to it.
+    fn jump(&mut self, to: BlockId, kind: EdgeKind, depth: usize) {
+        let from = self.current;
+        let frame = self.frames[depth..]
+            .iter_mut()
+            .rev()
+            .find(|frame| kind == EdgeKind::Throw || matches!(frame, Frame::Finally(_)));
+        match frame {
+            Some(Frame::Catch(catch)) => {
+                let catch = *catch;
+                self.edge(from, catch, kind);
+            }
+            Some(Frame::Finally(pending)) => pending.push(PendingJump {
+                from,
+                edge: Edge { to, kind },
+                depth,
+            }),
+            None => self.edge(from, to, kind),
+        }
+        self.current = self.new_block();
+    }
+
+    fn stmts(&mut self, stmts: impl IntoIterator<Item = SyntaxNode>) {
+        for stmt in stmts {
+            self.stmt(&stmt, vec![]);
+        }
+    }
+
+    fn stmt(&mut self, node: &SyntaxNode, labels: Vec<String>) {
+        match node.kind() {
+            BLOCK_STMT => self.stmts(node.children()),
+            IF_STMT => self.if_stmt(node),
+            WHILE_STMT | FOR_STMT | FOR_IN_STMT | FOR_OF_STMT => self.loop_stmt(node, labels),
+            DO_WHILE_STMT => self.do_while(node, labels),
+            SWITCH_STMT => self.switch(node, labels),
+            TRY_STMT => self.try_stmt(node.to()),
+            LABELLED_STMT => self.labelled(node, labels),
+            BREAK_STMT | CONTINUE_STMT => {
+                self.push(node);
+                self.break_or_continue(node);
+            }
+            RETURN_STMT => {
+                self.push(node);
+                self.jump(EXIT, EdgeKind::Return, 0);
+            }
+            THROW_STMT => {
+                self.push(node);
+                self.jump(EXIT, EdgeKind::Throw, 0);
+            }
+            _ => self.push(node),
+        }
+    }
+
+    fn if_stmt(&mut self, node: &SyntaxNode) {
+        self.push(node);
+        let test = self.current;
+        let mut branches = node.children().filter(|child| child.kind() != CONDITION);
+        let join = self.new_block();
+
+        let cons = self.new_block();
+        self.edge(test, cons, EdgeKind::True);
+        self.current = cons;
+        if let Some(stmt) = branches.next() {
+            self.stmt(&stmt, vec![]);
+        }
+        self.edge(self.current, join, EdgeKind::Normal);
+
+        match branches.next() {
+            Some(stmt) => {
+                let alt = self.new_block();
+                self.edge(test, alt, EdgeKind::False);
+                self.current = alt;
+                self.stmt(&stmt, vec![]);
+                self.edge(self.current, join, EdgeKind::Normal);
+            }
+            None => self.edge(test, join, EdgeKind::False),
+        }
+        self.current = join;
+    }
+
+    fn loop_stmt(&mut self, node: &SyntaxNode, labels: Vec<String>) {
+        let header = self.new_block();
+        self.edge(self.current, header, EdgeKind::Normal);
+        self.current = header;
+        self.push(node);
+
+        let after = self.new_block();
+        if !is_infinite(node) {
+            self.edge(header, after, EdgeKind::False);
+        }
+        let body = self.new_block();
+        self.edge(header, body, EdgeKind::True);
+        self.current = body;
+        self.with_target(labels, after, Some(header), |this| {
+            // the body is always the last child, `for...of` loops are not `Stmt`s so they can not be cast
+            if let Some(stmt) = node.children().last() {
+                this.stmt(&stmt, vec![]);
+            }
+        });
+        self.edge(self.current, header, EdgeKind::Continue);
+        self.current = after;
+    }
+
+    fn do_while(&mut self, node: &SyntaxNode, labels: Vec<String>) {
+        let body = self.new_block();
+        self.edge(self.current, body, EdgeKind::Normal);
+        let test = self.new_block();
+        let after = self.new_block();
+
+        self.current = body;
+        self.with_target(labels, after, Some(test), |this| {
+            if let Some(stmt) = node.children().find(|child| child.kind() != CONDITION) {
+                this.stmt(&stmt, vec![]);
+            }
+        });
+        self.edge(self.current, test, EdgeKind::Normal);
+
+        self.current = test;
+        self.push(node);
+        self.edge(test, body, EdgeKind::True);
+        if !is_infinite(node) {
+            self.edge(test, after, EdgeKind::False);
+        }
+        self.current = after;
+    }
+
+    fn switch(&mut self, node: &SyntaxNode, labels: Vec<String>) {
+        self.push(node);
+        let discriminant = self.current;
+        let after = self.new_block();
+        let cases = node
+            .children()
+            .filter(|child| matches!(child.kind(), CASE_CLAUSE | DEFAULT_CLAUSE))
+            .collect::<Vec<_>>();
+        if !cases.iter().any(|case| case.kind() == DEFAULT_CLAUSE) {
+            self.edge(discriminant, after, EdgeKind::Normal);
+        }
+
+        let mut previous = None;
+        self.with_target(labels, after, None, |this| {
+            for case in &cases {
+                let entry = this.new_block();
+                this.edge(discriminant, entry, EdgeKind::Case);
+                if let Some(previous) = previous {
+                    this.edge(previous, entry, EdgeKind::Fallthrough);
+                }
+                this.current = entry;
+                this.push(case);
+                this.stmts(case_stmts(case));
+                previous = Some(this.current);
+            }
+        });
+        if let Some(last) = previous {
+            self.edge(last, after, EdgeKind::Normal);
+        }
+        self.current = after;
+    }
+
+    fn try_stmt(&mut self, stmt: TryStmt) {
+        self.push(stmt.syntax());
+        let finalizer = stmt.finalizer();
+        if finalizer.is_some() {
+            self.frames.push(Frame::Finally(vec![]));
+        }
+
+        let try_entry = self.new_block();
+        self.edge(self.current, try_entry, EdgeKind::Normal);
+        self.current = try_entry;
+        let handler = stmt.handler().map(|handler| (handler, self.new_block()));
+        if let Some((_, catch_entry)) = handler {
+            // any statement of the try block can throw, not only explicit throws
+            self.edge(try_entry, catch_entry, EdgeKind::Throw);
+            self.frames.push(Frame::Catch(catch_entry));
+        }
+        if let Some(block) = stmt.test() {
+            self.stmt(block.syntax(), vec![]);
+        }
+        let mut ends = vec![self.current];
+
+        if let Some((handler, catch_entry)) = handler {
+            self.frames.pop();
+            self.current = catch_entry;
+            self.push(handler.syntax());
+            if let Some(block) = handler.cons() {
+                self.stmt(block.syntax(), vec![]);
+            }
+            ends.push(self.current);
+        }
+
+        let join = self.new_block();
+        let (finalizer, pending) = match (finalizer, self.frames.pop()) {
+            (Some(finalizer), Some(Frame::Finally(pending))) => (finalizer, pending),
+            (_, frame) => {
+                self.frames.extend(frame);
+                for end in ends {
+                    self.edge(end, join, EdgeKind::Normal);
+                }
+                self.current = join;
+                return;
+            }
+        };
+
+        // the finally block is built once for normal completion and once for jumps out of the statement,
+        // so that e.g. code after the statement is not reachable through a return in the try block
+        let normal = self.new_block();
+        for end in ends {
+            self.edge(end, normal, EdgeKind::Normal);
+        }
+        self.current = normal;
+        self.finalizer(&finalizer);
+        self.edge(self.current, join, EdgeKind::Normal);
+
+        if !pending.is_empty() {
+            let abrupt = self.new_block();
+            for jump in &pending {
+                self.edge(jump.from, abrupt, jump.edge.kind);
+            }
+            self.current = abrupt;
+            self.finalizer(&finalizer);
+            let end = self.current;
+            let mut jumps = vec![];
+            for jump in pending {
+                if !jumps.contains(&(jump.edge, jump.depth)) {
+                    jumps.push((jump.edge, jump.depth));
+                }
+            }
+            for (edge, depth) in jumps {
+                self.current = end;
+                self.jump(edge.to, edge.kind, depth);
+            }
+        }
+        self.current = join;
+    }
+
+    fn finalizer(&mut self, finalizer: &Finalizer) {
+        self.push(finalizer.syntax());
+        if let Some(block) = finalizer.cons() {
+            self.stmt(block.syntax(), vec![]);
+        }
+    }
+
+    fn labelled(&mut self, node: &SyntaxNode, mut labels: Vec<String>) {
+        self.push(node);
+        if let Some(name) = node.child_with_kind(NAME) {
+            labels.push(name.trimmed_text().to_string());
+        }
+        let stmt = match node.children().filter(|child| child.kind() != NAME).last() {
+            Some(stmt) => stmt,
+            None => return,
+        };
+        match stmt.kind() {
+            WHILE_STMT | FOR_STMT | FOR_IN_STMT | FOR_OF_STMT | DO_WHILE_STMT | SWITCH_STMT
+            | LABELLED_STMT => self.stmt(&stmt, labels),
+            _ => {
+                let after = self.new_block();
+                self.jumps.push(JumpTarget {
+                    labels,
+                    breakable: false,
+                    break_to: after,
+                    continue_to: None,
+                    depth: self.frames.len(),
+                });
+                self.stmt(&stmt, vec![]);
+                self.jumps.pop();
+                self.edge(self.current, after, EdgeKind::Normal);
+                self.current = after;
+            }
+        }
+    }
+
+    fn break_or_continue(&mut self, node: &SyntaxNode) {
+        let is_break = node.kind() == BREAK_STMT;
+        // the label is parsed as a name reference, but the ast also allows a bare identifier token
+        let label = match node.child_with_kind(NAME_REF) {
+            Some(name) => Some(name.trimmed_text().to_string()),
+            None => node
+                .children_with_tokens()
+                .filter_map(|elem| elem.into_token())
+                .find(|token| token.kind() == T![ident])
+                .map(|token| token.text().to_string()),
+        };
+        let target = self.jumps.iter().rev().find(|target| match &label {
+            Some(label) => target.labels.contains(label),
+            None if is_break => target.breakable,
+            None => target.continue_to.is_some(),
+        });
+        let (to, depth) = match target {
+            Some(target) if is_break => (target.break_to, target.depth),
+            Some(target) => (target.continue_to.unwrap_or(EXIT), target.depth),
+            None => (EXIT, 0),
+        };
+        let kind = if is_break {
+            EdgeKind::Break
+        } else {
+            EdgeKind::Continue
+        };
+        self.jump(to, kind, depth);
+    }
+
+    fn with_target(
+        &mut self,
+        labels: Vec<String>,
+        break_to: BlockId,
+        continue_to: Option<BlockId>,
+        f: impl FnOnce(&mut Self),
+    ) {
+        self.jumps.push(JumpTarget {
+            labels,
+            breakable: true,
+            break_to,
+            continue_to,
+            depth: self.frames.len(),
+        });
+        f(self);
+        self.jumps.pop();
+    }
+}
+
+/// The statements of a `case` or `default` clause, which are the children after the colon.
+pub fn case_stmts(case: &SyntaxNode) -> impl Iterator<Item = SyntaxNode> {
+    let colon = case
+        .children_with_tokens()
+        .find(|elem| elem.kind() == T![:])
+        .map(|colon| colon.text_range().end());
+    case.children()
+        .filter(move |child| matches!(colon, Some(colon) if child.text_range().start() >= colon))
+}
+
+/// Whether a loop has no condition or its condition is the literal `true`.
+fn is_infinite(node: &SyntaxNode) -> bool {
+    let test = match node.kind() {
+        WHILE_STMT | DO_WHILE_STMT => node.child_with_kind(CONDITION),
+        FOR_STMT => match node.child_with_kind(FOR_STMT_TEST) {
+            Some(test) => Some(test),
+            None => return true,
+        },
+        _ => return false,
+    };
+    match test {
+        Some(test) => {
+            let text = test.trimmed_text().to_string();
+            text.trim_start_matches('(').trim_end_matches(')').trim() == "true"
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,434 @@
+//! A constant expression evaluator which folds expressions made only of literals.
+//!
+//! [`eval`] computes the value of an expression such as `"a" + "b"` or `2 ** 3 > 7` following the
+//! semantics of JavaScript operators. [`truthiness`] goes further and also knows the truthiness of
+//! expressions whose value is unknown, such as `foo || true` or `typeof foo`.
+
+use crate::rule_prelude::*;
+use ast::{AssignOp, BinOp, Expr, LiteralKind, UnaryOp};
+use rslint_parser::NodeOrToken;
+use std::cmp::Ordering;
+use SyntaxKind::*;
+
+/// The value of a constant expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    BigInt(BigInt),
+    String(String),
+    /// An object, array, function, class, or regex literal, only its type is known.
+    Object {
+        callable: bool,
+    },
+}
+
+impl ConstValue {
+    /// Whether the value is truthy when converted to a boolean.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ConstValue::Undefined | ConstValue::Null => false,
+            ConstValue::Bool(val) => *val,
+            ConstValue::Number(num) => *num != 0.0 && !num.is_nan(),
+            ConstValue::BigInt(bigint) => *bigint != 0.into(),
+            ConstValue::String(string) => !string.is_empty(),
+            ConstValue::Object { .. } => true,
+        }
+    }
+
+    /// Whether the value is `null` or `undefined`.
+    pub fn is_nullish(&self) -> bool {
+        matches!(self, ConstValue::Undefined | ConstValue::Null)
+    }
+
+    /// The result of `typeof` for this value.
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            ConstValue::Undefined => "undefined",
+            ConstValue::Null => "object",
+            ConstValue::Bool(_) => "boolean",
+            ConstValue::Number(_) => "number",
+            ConstValue::BigInt(_) => "bigint",
+            ConstValue::String(_) => "string",
+            ConstValue::Object { callable: true } => "function",
+            ConstValue::Object { callable: false } => "object",
+        }
+    }
+
+    /// Convert a primitive to a number, `None` for objects and bigints.
+    pub fn to_number(&self) -> Option<f64> {
+        Some(match self {
+            ConstValue::Undefined => f64::NAN,
+            ConstValue::Null => 0.0,
+            ConstValue::Bool(val) => *val as u8 as f64,
+            ConstValue::Number(num) => *num,
+            ConstValue::String(string) => string_to_number(string),
+            ConstValue::BigInt(_) | ConstValue::Object { .. } => return None,
+        })
+    }
+
+    /// Convert a primitive to a string, `None` for objects.
+    pub fn to_js_string(&self) -> Option<String> {
+        Some(match self {
+            ConstValue::Undefined => "undefined".to_string(),
+            ConstValue::Null => "null".to_string(),
+            ConstValue::Bool(val) => val.to_string(),
+            ConstValue::Number(num) => number_to_string(*num),
+            ConstValue::BigInt(bigint) => bigint.to_string(),
+            ConstValue::String(string) => string.clone(),
+            ConstValue::Object { .. } => return None,
+        })
+    }
+
+    /// The result of `==` or `===`, `None` if it depends on the conversion of an object to a primitive.
+    pub fn equals(&self, other: &ConstValue, strict: bool) -> Option<bool> {
+        use ConstValue::*;
+
+        Some(match (self, other) {
+            // object literals always create a new object
+            (Object { .. }, Object { .. }) => false,
+            (Object { .. }, _) | (_, Object { .. }) if !strict => return None,
+            (Number(a), Number(b)) => a == b,
+            (BigInt(a), BigInt(b)) => a == b,
+            (String(a), String(b)) => a == b,
+            (Bool(a), Bool(b)) => a == b,
+            (Undefined, Undefined) | (Null, Null) => true,
+            _ if strict => false,
+            (Undefined, Null) | (Null, Undefined) => true,
+            (Undefined, _) | (Null, _) | (_, Undefined) | (_, Null) => false,
+            (BigInt(_), _) | (_, BigInt(_)) => return None,
+            (a, b) => a.to_number()? == b.to_number()?,
+        })
+    }
+
+    /// The ordering of two primitives as used by `<` and `>`, `Some(None)` if they are unordered such as
+    /// when comparing with `NaN`. `None` if the comparison can not be evaluated because it depends on the
+    /// conversion of an object or a string to a bigint.
+    pub fn compare(&self, other: &ConstValue) -> Option<Option<Ordering>> {
+        use ConstValue::*;
+
+        Some(match (self, other) {
+            (String(a), String(b)) => {
+                // strings are compared by their UTF-16 code units
+                Some(a.encode_utf16().cmp(b.encode_utf16()))
+            }
+            (BigInt(a), BigInt(b)) => Some(a.cmp(b)),
+            (Object { .. }, _) | (_, Object { .. }) => return None,
+            (BigInt(_), String(_)) | (String(_), BigInt(_)) => return None,
+            (BigInt(a), b) => compare_bigint(a, b.to_number()?),
+            (a, BigInt(b)) => compare_bigint(b, a.to_number()?).map(Ordering::reverse),
+            (a, b) => a.to_number()?.partial_cmp(&b.to_number()?),
+        })
+    }
+}
+
+/// The ordering of a bigint and a number by their mathematical values, `None` if the number is `NaN`.
+fn compare_bigint(bigint: &BigInt, num: f64) -> Option<Ordering> {
+    if num.is_nan() {
+        return None;
+    }
+    if num.is_infinite() {
+        return Some(if num > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+    // floats which are not infinite print as their exact integer digits, the bigint is less than the
+    // number if it is at most the floor of the number and that floor is not the number itself
+    let floor = format!("{:.0}", num.floor()).parse::<BigInt>().ok()?;
+    Some(match bigint.cmp(&floor) {
+        Ordering::Equal if num.fract() == 0.0 => Ordering::Equal,
+        Ordering::Less | Ordering::Equal => Ordering::Less,
+        Ordering::Greater => Ordering::Greater,
+    })
+}
+
+/// Convert a string to a number like `Number(string)`.
+fn string_to_number(string: &str) -> f64 {
+    let trimmed = string.trim();
+    if trimmed.is_empty() {
+        return 0.0;
+    }
+    let radix = match trimmed.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        return u64::from_str_radix(&trimmed[2..], radix).map_or(f64::NAN, |num| num as f64);
+    }
+    match trimmed {
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // rust also accepts words such as `inf` and `nan`
+        _ if trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')) =>
+        {
+            trimmed.parse().unwrap_or(f64::NAN)
+        }
+        _ => f64::NAN,
+    }
+}
+
+/// Convert a number to a string like `String(number)`.
+fn number_to_string(num: f64) -> String {
+    if num.is_nan() {
+        "NaN".to_string()
+    } else if num.is_infinite() {
+        if num > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if num == 0.0 {
+        // `-0` is printed as `0`
+        "0".to_string()
+    } else if num.abs() >= 1e21 || num.abs() < 1e-6 {
+        let exp = format!("{:e}", num);
+        match exp.find("e-") {
+            Some(_) => exp,
+            None => exp.replacen('e', "e+", 1),
+        }
+    } else {
+        num.to_string()
+    }
+}
+
+/// Convert a number to a 32 bit integer like the bitwise operators do.
+fn to_int32(num: f64) -> i32 {
+    if !num.is_finite() {
+        return 0;
+    }
+    (num.trunc() % 4294967296.0) as i64 as u32 as i32
+}
+
+/// The value of a string literal or template chunk, `None` if it contains escapes since their value
+/// is not decoded by the parser.
+fn unescaped(text: &str) -> Option<String> {
+    if text.contains('\\') {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// Evaluate an expression made only of literals, `None` if the value of the expression is not constant
+/// or if evaluating it would throw.
+///
+/// Predefined globals such as `undefined` and `NaN` are assumed not to be shadowed.
+pub fn eval(expr: Expr) -> Option<ConstValue> {
+    Some(match expr {
+        Expr::Literal(lit) => match lit.kind() {
+            LiteralKind::Null => ConstValue::Null,
+            LiteralKind::Bool(val) => ConstValue::Bool(val),
+            LiteralKind::Number(num) => ConstValue::Number(num),
+            LiteralKind::BigInt(bigint) => ConstValue::BigInt(bigint),
+            LiteralKind::String => {
+                ConstValue::String(unescaped(&lit.inner_string_text()?.to_string())?)
+            }
+            LiteralKind::Regex => ConstValue::Object { callable: false },
+        },
+        Expr::NameRef(name) => match name.ident_token()?.text().as_str() {
+            "undefined" => ConstValue::Undefined,
+            "NaN" => ConstValue::Number(f64::NAN),
+            "Infinity" => ConstValue::Number(f64::INFINITY),
+            _ => return None,
+        },
+        Expr::Template(tpl) => {
+            if tpl.tag().is_some() {
+                return None;
+            }
+            let mut string = String::new();
+            for child in tpl.syntax().children_with_tokens() {
+                match child {
+                    NodeOrToken::Token(tok) if tok.kind() == TEMPLATE_CHUNK => {
+                        string.push_str(&unescaped(tok.text())?)
+                    }
+                    NodeOrToken::Node(node) if node.kind() == TEMPLATE_ELEMENT => {
+                        let value = eval(node.to::<ast::TemplateElement>().expr()?)?;
+                        string.push_str(&value.to_js_string()?);
+                    }
+                    _ => {}
+                }
+            }
+            ConstValue::String(string)
+        }
+        Expr::ObjectExpr(_) | Expr::ArrayExpr(_) => ConstValue::Object { callable: false },
+        Expr::FnExpr(_) | Expr::ArrowExpr(_) | Expr::ClassExpr(_) => {
+            ConstValue::Object { callable: true }
+        }
+        Expr::GroupingExpr(group) => eval(group.inner()?)?,
+        Expr::SequenceExpr(seq) => eval(seq.exprs().last()?)?,
+        Expr::AssignExpr(assign) if assign.op()? == AssignOp::Assign => eval(assign.rhs()?)?,
+        Expr::CondExpr(cond) => {
+            if eval(cond.test()?)?.is_truthy() {
+                eval(cond.cons()?)?
+            } else {
+                eval(cond.alt()?)?
+            }
+        }
+        Expr::UnaryExpr(unexpr) => {
+            let op = unexpr.op()?;
+            if op == UnaryOp::Void {
+                return Some(ConstValue::Undefined);
+            }
+            let value = eval(unexpr.expr()?)?;
+            match op {
+                UnaryOp::LogicalNot => ConstValue::Bool(!value.is_truthy()),
+                UnaryOp::Typeof => ConstValue::String(value.type_of().to_string()),
+                UnaryOp::Minus => match value {
+                    ConstValue::BigInt(bigint) => ConstValue::BigInt(-bigint),
+                    value => ConstValue::Number(-value.to_number()?),
+                },
+                UnaryOp::Plus => ConstValue::Number(value.to_number()?),
+                UnaryOp::BitwiseNot => ConstValue::Number(!to_int32(value.to_number()?) as f64),
+                _ => return None,
+            }
+        }
+        Expr::BinExpr(binexpr) => {
+            let op = binexpr.op()?;
+            let lhs = eval(binexpr.lhs()?)?;
+            match op {
+                BinOp::LogicalAnd if !lhs.is_truthy() => return Some(lhs),
+                BinOp::LogicalOr if lhs.is_truthy() => return Some(lhs),
+                BinOp::NullishCoalescing if !lhs.is_nullish() => return Some(lhs),
+                BinOp::LogicalAnd | BinOp::LogicalOr | BinOp::NullishCoalescing => {
+                    return eval(binexpr.rhs()?)
+                }
+                _ => {}
+            }
+            let rhs = eval(binexpr.rhs()?)?;
+            eval_binary(op, lhs, rhs)?
+        }
+        _ => return None,
+    })
+}
+
+fn eval_binary(op: BinOp, lhs: ConstValue, rhs: ConstValue) -> Option<ConstValue> {
+    Some(match op {
+        BinOp::Equality => ConstValue::Bool(lhs.equals(&rhs, false)?),
+        BinOp::Inequality => ConstValue::Bool(!lhs.equals(&rhs, false)?),
+        BinOp::StrictEquality => ConstValue::Bool(lhs.equals(&rhs, true)?),
+        BinOp::StrictInequality => ConstValue::Bool(!lhs.equals(&rhs, true)?),
+        // unordered values such as `NaN` make every comparison false
+        BinOp::LessThan => ConstValue::Bool(lhs.compare(&rhs)? == Some(Ordering::Less)),
+        BinOp::GreaterThan => ConstValue::Bool(lhs.compare(&rhs)? == Some(Ordering::Greater)),
+        BinOp::LessThanOrEqual => ConstValue::Bool(matches!(
+            lhs.compare(&rhs)?,
+            Some(Ordering::Less) | Some(Ordering::Equal)
+        )),
+        BinOp::GreaterThanOrEqual => ConstValue::Bool(matches!(
+            lhs.compare(&rhs)?,
+            Some(Ordering::Greater) | Some(Ordering::Equal)
+        )),
+        BinOp::Plus => match (lhs, rhs) {
+            (ConstValue::String(a), b) => ConstValue::String(a + &b.to_js_string()?),
+            (a, ConstValue::String(b)) => ConstValue::String(a.to_js_string()? + &b),
+            (ConstValue::BigInt(a), ConstValue::BigInt(b)) => ConstValue::BigInt(a + b),
+            (a, b) => ConstValue::Number(a.to_number()? + b.to_number()?),
+        },
+        BinOp::Minus | BinOp::Times => match (lhs, rhs) {
+            (ConstValue::BigInt(a), ConstValue::BigInt(b)) if op == BinOp::Minus => {
+                ConstValue::BigInt(a - b)
+            }
+            (ConstValue::BigInt(a), ConstValue::BigInt(b)) => ConstValue::BigInt(a * b),
+            (a, b) => {
+                let (a, b) = (a.to_number()?, b.to_number()?);
+                ConstValue::Number(if op == BinOp::Minus { a - b } else { a * b })
+            }
+        },
+        BinOp::Divide => ConstValue::Number(lhs.to_number()? / rhs.to_number()?),
+        BinOp::Remainder => ConstValue::Number(lhs.to_number()? % rhs.to_number()?),
+        BinOp::Exponent => {
+            let (base, exp) = (lhs.to_number()?, rhs.to_number()?);
+            // unlike `powf`, `1 ** NaN` and `(-1) ** Infinity` are NaN in JavaScript
+            if exp.is_nan() || (base.abs() == 1.0 && exp.is_infinite()) {
+                ConstValue::Number(f64::NAN)
+            } else {
+                ConstValue::Number(base.powf(exp))
+            }
+        }
+        BinOp::BitwiseAnd | BinOp::BitwiseOr | BinOp::BitwiseXor => {
+            let (a, b) = (to_int32(lhs.to_number()?), to_int32(rhs.to_number()?));
+            ConstValue::Number(match op {
+                BinOp::BitwiseAnd => a & b,
+                BinOp::BitwiseOr => a | b,
+                _ => a ^ b,
+            } as f64)
+        }
+        BinOp::LeftShift | BinOp::RightShift | BinOp::UnsignedRightShift => {
+            let a = to_int32(lhs.to_number()?);
+            let shift = to_int32(rhs.to_number()?) as u32 & 31;
+            ConstValue::Number(match op {
+                BinOp::LeftShift => a.wrapping_shl(shift) as f64,
+                BinOp::RightShift => (a >> shift) as f64,
+                _ => ((a as u32) >> shift) as f64,
+            })
+        }
+        _ => return None,
+    })
+}
+
+/// Whether an expression is always truthy or always falsey, `None` if it can be either.
+///
+/// This knows the truthiness of more expressions than [`eval`], for example `foo || true` is always truthy
+/// and `typeof foo` is always a non-empty string.
+pub fn truthiness(expr: Expr) -> Option<bool> {
+    if let Some(value) = eval(expr.clone()) {
+        return Some(value.is_truthy());
+    }
+    match expr {
+        Expr::Literal(lit) if lit.is_string() => Some(!lit.inner_string_text()?.is_empty()),
+        Expr::Template(tpl) if tpl.tag().is_none() => {
+            if tpl.quasis().any(|chunk| !chunk.text().is_empty()) {
+                Some(true)
+            } else {
+                None
+            }
+        }
+        Expr::GroupingExpr(group) => truthiness(group.inner()?),
+        Expr::SequenceExpr(seq) => truthiness(seq.exprs().last()?),
+        Expr::AssignExpr(assign) if assign.op()? == AssignOp::Assign => truthiness(assign.rhs()?),
+        Expr::CondExpr(cond) => match truthiness(cond.test()?) {
+            Some(true) => truthiness(cond.cons()?),
+            Some(false) => truthiness(cond.alt()?),
+            None => {
+                let cons = truthiness(cond.cons()?)?;
+                if truthiness(cond.alt()?)? == cons {
+                    Some(cons)
+                } else {
+                    None
+                }
+            }
+        },
+        Expr::UnaryExpr(unexpr) => match unexpr.op()? {
+            UnaryOp::LogicalNot => truthiness(unexpr.expr()?).map(|val| !val),
+            UnaryOp::Typeof => Some(true),
+            _ => None,
+        },
+        Expr::BinExpr(binexpr) => {
+            let lhs = binexpr.lhs().and_then(truthiness);
+            let rhs = binexpr.rhs().and_then(truthiness);
+            match (binexpr.op()?, lhs, rhs) {
+                (BinOp::LogicalAnd, Some(false), _) | (BinOp::LogicalAnd, _, Some(false)) => {
+                    Some(false)
+                }
+                (BinOp::LogicalAnd, Some(true), rhs) => rhs,
+                (BinOp::LogicalOr, Some(true), _) | (BinOp::LogicalOr, _, Some(true)) => Some(true),
+                (BinOp::LogicalOr, Some(false), rhs) => rhs,
+                // concatenating with a non-empty string always results in a non-empty string
+                (BinOp::Plus, _, _) => {
+                    let non_empty = |expr: Option<Expr>| matches!(expr.and_then(eval), Some(ConstValue::String(s)) if !s.is_empty());
+                    if non_empty(binexpr.lhs()) || non_empty(binexpr.rhs()) {
+                        Some(true)
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,337 @@
+//! A small abstract interpretation of expressions, which finds the values an expression can have without
+//! knowing the values of variables.
+//!
+//! The [`Domain`] of an expression is a conservative approximation, it may contain values the expression can
+//! never have but never misses a value it can have. The only exception is `.length`, which is assumed to be
+//! the length of a string or array and therefore a non-negative integer.
+
+use super::simple_bool_coerce;
+use crate::rule_prelude::*;
+use ast::{BinOp, Expr, LiteralKind, UnaryOp};
+use std::fmt;
+use std::ops::BitOr;
+
+/// The largest length of a string or array.
+const MAX_LENGTH: f64 = 9007199254740991.0;
+
+/// A set of JavaScript types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Types(u8);
+
+impl Types {
+    pub const NONE: Types = Types(0);
+    pub const UNDEFINED: Types = Types(1);
+    pub const NULL: Types = Types(1 << 1);
+    pub const BOOLEAN: Types = Types(1 << 2);
+    pub const NUMBER: Types = Types(1 << 3);
+    pub const BIGINT: Types = Types(1 << 4);
+    pub const STRING: Types = Types(1 << 5);
+    pub const SYMBOL: Types = Types(1 << 6);
+    pub const OBJECT: Types = Types(1 << 7);
+    pub const ANY: Types = Types(u8::MAX);
+
+    pub fn contains(self, other: Types) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn intersects(self, other: Types) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// The results `typeof` can have for a value of these types.
+    pub fn typeof_results(self) -> Vec<&'static str> {
+        let mut results = vec![];
+        let mut add = |types: Types, result: &'static str| {
+            if self.intersects(types) {
+                results.push(result);
+            }
+        };
+        add(Types::UNDEFINED, "undefined");
+        add(Types::NULL | Types::OBJECT, "object");
+        add(Types::BOOLEAN, "boolean");
+        add(Types::NUMBER, "number");
+        add(Types::BIGINT, "bigint");
+        add(Types::STRING, "string");
+        add(Types::SYMBOL, "symbol");
+        // functions are objects
+        add(Types::OBJECT, "function");
+        results
+    }
+}
+
+impl BitOr for Types {
+    type Output = Types;
+
+    fn bitor(self, rhs: Types) -> Types {
+        Types(self.0 | rhs.0)
+    }
+}
+
+/// The values an expression can have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Domain {
+    pub types: Types,
+    /// The strings the value can be if it is a string, `None` if it can be any string.
+    pub strings: Option<Vec<String>>,
+    /// The inclusive range the value is in if it is a number, `None` if it can be any number including `NaN`.
+    pub range: Option<(f64, f64)>,
+    /// The value if it is a boolean, `None` if it can be either.
+    pub boolean: Option<bool>,
+}
+
+impl Domain {
+    /// Any value of the types.
+    pub fn of_types(types: Types) -> Self {
+        Self {
+            types,
+            strings: None,
+            range: None,
+            boolean: None,
+        }
+    }
+
+    pub fn any() -> Self {
+        Self::of_types(Types::ANY)
+    }
+
+    /// A number in an inclusive range.
+    pub fn number(min: f64, max: f64) -> Self {
+        Self {
+            range: Some((min, max)),
+            ..Self::of_types(Types::NUMBER)
+        }
+    }
+
+    /// One of a list of strings.
+    pub fn strings(strings: Vec<String>) -> Self {
+        Self {
+            strings: Some(strings),
+            ..Self::of_types(Types::STRING)
+        }
+    }
+
+    pub fn boolean(value: Option<bool>) -> Self {
+        Self {
+            boolean: value,
+            ..Self::of_types(Types::BOOLEAN)
+        }
+    }
+
+    /// The values of an expression.
+    pub fn of(expr: Expr) -> Self {
+        match expr {
+            Expr::Literal(lit) => match lit.kind() {
+                LiteralKind::Number(num) if num.is_nan() => Self::of_types(Types::NUMBER),
+                LiteralKind::Number(num) => Self::number(num, num),
+                LiteralKind::String => match lit.inner_string_text() {
+                    Some(text) => Self::strings(vec![text.to_string()]),
+                    None => Self::of_types(Types::STRING),
+                },
+                LiteralKind::Bool(val) => Self::boolean(Some(val)),
+                LiteralKind::Null => Self::of_types(Types::NULL),
+                LiteralKind::BigInt(_) => Self::of_types(Types::BIGINT),
+                LiteralKind::Regex => Self::of_types(Types::OBJECT),
+            },
+            Expr::Template(_) => Self::of_types(Types::STRING),
+            Expr::NameRef(name) => match name.ident_token().map(|tok| tok.text().to_string()) {
+                Some(name) if name == "undefined" => Self::of_types(Types::UNDEFINED),
+                Some(name) if name == "Infinity" => Self::number(f64::INFINITY, f64::INFINITY),
+                Some(name) if name == "NaN" => Self::of_types(Types::NUMBER),
+                _ => Self::any(),
+            },
+            Expr::ArrayExpr(_)
+            | Expr::ObjectExpr(_)
+            | Expr::FnExpr(_)
+            | Expr::ArrowExpr(_)
+            | Expr::ClassExpr(_) => Self::of_types(Types::OBJECT),
+            Expr::GroupingExpr(group) => group.inner().map_or_else(Self::any, Self::of),
+            Expr::DotExpr(dot) => match dot.prop() {
+                Some(prop) if prop.syntax().text() == "length" => Self::number(0.0, MAX_LENGTH),
+                _ => Self::any(),
+            },
+            Expr::UnaryExpr(unary) => match unary.op() {
+                Some(UnaryOp::Typeof) => {
+                    let operand = unary.expr().map_or_else(Self::any, Self::of);
+                    Self::strings(
+                        operand
+                            .types
+                            .typeof_results()
+                            .into_iter()
+                            .map(|result| result.to_string())
+                            .collect(),
+                    )
+                }
+                Some(UnaryOp::Void) => Self::of_types(Types::UNDEFINED),
+                Some(UnaryOp::Delete) => Self::boolean(None),
+                Some(UnaryOp::LogicalNot) => {
+                    Self::boolean(unary.expr().and_then(simple_bool_coerce).map(|val| !val))
+                }
+                Some(UnaryOp::Minus) => {
+                    let operand = unary.expr().map_or_else(Self::any, Self::of);
+                    match operand.range {
+                        Some((min, max)) if operand.types == Types::NUMBER => {
+                            Self::number(-max, -min)
+                        }
+                        _ => Self::of_types(Types::NUMBER | Types::BIGINT),
+                    }
+                }
+                Some(UnaryOp::Plus) => Self::of_types(Types::NUMBER),
+                Some(UnaryOp::Await) | None => Self::any(),
+                Some(_) => Self::of_types(Types::NUMBER | Types::BIGINT),
+            },
+            Expr::BinExpr(bin) => match bin.op() {
+                Some(BinOp::LogicalAnd)
+                | Some(BinOp::LogicalOr)
+                | Some(BinOp::NullishCoalescing) => {
+                    let lhs = bin.lhs().map_or_else(Self::any, Self::of);
+                    let rhs = bin.rhs().map_or_else(Self::any, Self::of);
+                    lhs.union(&rhs)
+                }
+                Some(BinOp::Plus) => {
+                    let lhs = bin.lhs().map_or_else(Self::any, Self::of);
+                    let rhs = bin.rhs().map_or_else(Self::any, Self::of);
+                    if lhs.types == Types::NUMBER && rhs.types == Types::NUMBER {
+                        Self::of_types(Types::NUMBER)
+                    } else if lhs.types == Types::STRING || rhs.types == Types::STRING {
+                        Self::of_types(Types::STRING)
+                    } else {
+                        Self::of_types(Types::NUMBER | Types::BIGINT | Types::STRING)
+                    }
+                }
+                Some(BinOp::UnsignedRightShift) => Self::number(0.0, u32::MAX as f64),
+                Some(op) if bin.comparison() || op == BinOp::In || op == BinOp::Instanceof => {
+                    Self::boolean(None)
+                }
+                Some(_) => Self::of_types(Types::NUMBER | Types::BIGINT),
+                None => Self::any(),
+            },
+            Expr::CondExpr(cond) => {
+                let cons = cond.cons().map_or_else(Self::any, Self::of);
+                let alt = cond.alt().map_or_else(Self::any, Self::of);
+                cons.union(&alt)
+            }
+            Expr::AssignExpr(assign) if assign.op() == Some(ast::AssignOp::Assign) => {
+                assign.rhs().map_or_else(Self::any, Self::of)
+            }
+            Expr::SequenceExpr(seq) => seq.exprs().last().map_or_else(Self::any, Self::of),
+            _ => Self::any(),
+        }
+    }
+
+    /// The values of either domain.
+    pub fn union(&self, other: &Domain) -> Domain {
+        let strings = match (&self.strings, &other.strings) {
+            (Some(a), Some(b)) => {
+                let mut strings = a.clone();
+                strings.extend(b.iter().filter(|string| !a.contains(string)).cloned());
+                Some(strings)
+            }
+            (Some(strings), None) if !other.types.intersects(Types::STRING) => {
+                Some(strings.clone())
+            }
+            (None, Some(strings)) if !self.types.intersects(Types::STRING) => Some(strings.clone()),
+            _ => None,
+        };
+        let range = match (self.range, other.range) {
+            (Some(a), Some(b)) => Some((a.0.min(b.0), a.1.max(b.1))),
+            (Some(range), None) if !other.types.intersects(Types::NUMBER) => Some(range),
+            (None, Some(range)) if !self.types.intersects(Types::NUMBER) => Some(range),
+            _ => None,
+        };
+        let boolean = match (self.boolean, other.boolean) {
+            (Some(a), Some(b)) if a == b => Some(a),
+            (Some(val), None) if !other.types.intersects(Types::BOOLEAN) => Some(val),
+            (None, Some(val)) if !self.types.intersects(Types::BOOLEAN) => Some(val),
+            _ => None,
+        };
+        Domain {
+            types: self.types | other.types,
+            strings,
+            range,
+            boolean,
+        }
+    }
+
+    /// Whether a value of this domain can equal a value of the other domain with `===`, or with `==` if `strict` is false.
+    pub fn can_equal(&self, other: &Domain, strict: bool) -> bool {
+        // loose equality converts between types, only values of a single and the same type are compared like `===`
+        if !strict && (self.types != other.types || !self.is_single_type()) {
+            return true;
+        }
+        let common = Types(self.types.0 & other.types.0);
+        if common == Types::NONE {
+            return false;
+        }
+        // a value of any other type in common can be equal
+        let other_types = Types(common.0 & !(Types::STRING | Types::NUMBER | Types::BOOLEAN).0);
+        if other_types != Types::NONE {
+            return true;
+        }
+        let strings = common.contains(Types::STRING)
+            && match (&self.strings, &other.strings) {
+                (Some(a), Some(b)) => a.iter().any(|string| b.contains(string)),
+                _ => true,
+            };
+        let numbers = common.contains(Types::NUMBER)
+            && match (self.range, other.range) {
+                (Some(a), Some(b)) => a.0 <= b.1 && b.0 <= a.1,
+                _ => true,
+            };
+        let booleans = common.contains(Types::BOOLEAN)
+            && match (self.boolean, other.boolean) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            };
+        strings || numbers || booleans
+    }
+
+    /// Whether a value of this domain can be less than (or equal to if `or_equal` is true) a value of the other domain.
+    pub fn can_be_less(&self, other: &Domain, or_equal: bool) -> bool {
+        if self.types != Types::NUMBER || other.types != Types::NUMBER {
+            return true;
+        }
+        match (self.range, other.range) {
+            (Some(a), Some(b)) if or_equal => a.0 <= b.1,
+            (Some(a), Some(b)) => a.0 < b.1,
+            _ => true,
+        }
+    }
+
+    fn is_single_type(&self) -> bool {
+        self.types.0.count_ones() == 1
+    }
+}
+
+/// A short description of the values, such as `a non-negative integer` or `one of "a" or "b"`.
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.strings, self.range) {
+            (Some(strings), _) if self.types == Types::STRING => {
+                let strings = strings
+                    .iter()
+                    .map(|string| format!("`\"{}\"`", string))
+                    .collect::<Vec<_>>();
+                match strings.split_last() {
+                    Some((last, [])) => write!(f, "{}", last),
+                    Some((last, rest)) => write!(f, "one of {} or {}", rest.join(", "), last),
+                    None => write!(f, "no string"),
+                }
+            }
+            (_, Some((min, max))) if self.types == Types::NUMBER => {
+                if min == max {
+                    write!(f, "{}", min)
+                } else if max >= MAX_LENGTH {
+                    write!(f, "a number of at least {}", min)
+                } else if min == f64::NEG_INFINITY {
+                    write!(f, "a number of at most {}", max)
+                } else {
+                    write!(f, "a number between {} and {}", min, max)
+                }
+            }
+            _ => {
+                let types = self.types.typeof_results();
+                write!(f, "a value of type {}", types.join(" or "))
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Structural equality of expressions.
+//!
+//! Rules which look for repeated expressions, such as duplicate `switch` cases, should not care about how the
+//! expressions are written. [`expr_eq`] compares two expressions ignoring whitespace, comments, redundant
+//! parentheses, and the quotes of string literals. [`expr_hash`] hashes expressions consistently with it, and
+//! [`ExprKey`] wraps an expression so it can be used as the key of a map or set.
+
+use crate::rule_prelude::*;
+use rslint_parser::NodeOrToken;
+use std::hash::{Hash, Hasher};
+use SyntaxKind::*;
+
+/// Remove any parentheses around an expression, `a` for `((a))`.
+pub fn strip_parens(node: &SyntaxNode) -> SyntaxNode {
+    let mut node = node.clone();
+    while node.kind() == GROUPING_EXPR {
+        match node.first_child() {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
+/// The children of a node which matter for equality, with parentheses stripped from child nodes.
+fn significant(node: &SyntaxNode) -> impl Iterator<Item = SyntaxElement> {
+    node.children_with_tokens()
+        .filter(|elem| !elem.kind().is_trivia())
+        .map(|elem| match elem {
+            NodeOrToken::Node(node) => NodeOrToken::Node(strip_parens(&node)),
+            token => token,
+        })
+}
+
+/// The text of a token, without the quotes if it is a string literal.
+fn token_value(token: &SyntaxToken) -> &str {
+    let text = token.text().as_str();
+    if token.kind() == STRING && text.len() >= 2 {
+        &text[1..text.len() - 1]
+    } else {
+        text
+    }
+}
+
+/// Whether two expressions are the same expression, ignoring whitespace, comments, redundant parentheses,
+/// and whether string literals use single or double quotes.
+///
+/// Parentheses which change the meaning of an expression are kept in the structure of the tree, so
+/// `(a + b) * c` and `a + b * c` are not equal.
+pub fn expr_eq(left: &SyntaxNode, right: &SyntaxNode) -> bool {
+    let (left, right) = (strip_parens(left), strip_parens(right));
+    if left.kind() != right.kind() {
+        return false;
+    }
+    let (mut left, mut right) = (significant(&left), significant(&right));
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return true,
+            (Some(NodeOrToken::Node(l)), Some(NodeOrToken::Node(r))) => {
+                if !expr_eq(&l, &r) {
+                    return false;
+                }
+            }
+            (Some(NodeOrToken::Token(l)), Some(NodeOrToken::Token(r))) => {
+                if l.kind() != r.kind() || token_value(&l) != token_value(&r) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Hash an expression so expressions which are equal with [`expr_eq`] have the same hash.
+pub fn expr_hash<H: Hasher>(node: &SyntaxNode, state: &mut H) {
+    let node = strip_parens(node);
+    node.kind().hash(state);
+    for elem in significant(&node) {
+        match elem {
+            NodeOrToken::Node(child) => expr_hash(&child, state),
+            NodeOrToken::Token(token) => {
+                token.kind().hash(state);
+                token_value(&token).hash(state);
+            }
+        }
+    }
+}
+
+/// An expression which is compared with [`expr_eq`] and hashed with [`expr_hash`], for finding repeated
+/// expressions with a `HashMap` or `HashSet`.
+#[derive(Debug, Clone)]
+pub struct ExprKey(pub SyntaxNode);
+
+impl PartialEq for ExprKey {
+    fn eq(&self, other: &Self) -> bool {
+        expr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ExprKey {}
+
+impl Hash for ExprKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        expr_hash(&self.0, state)
+    }
+}
@@ -0,0 +1,202 @@
+//! Names of the global variables defined by JavaScript environments.
+//!
+//! Only globals which are read-only in practice are listed, globals such as `exports` in Node.js are meant
+//! to be assigned and are left out.
+//!
+//! The globals of a file are a [`Globals`], which is given to the linter with [`LinterOptions::globals`] and
+//! extended by the `/* global name */` comments of the file. Hosts can add their own sets of globals for
+//! environments such as browsers or test frameworks:
+//!
+//! ```
+//! use rslint_core::util::globals::Globals;
+//!
+//! let mut globals = Globals::default();
+//! globals.extend(&["window", "document"]).add("dataLayer", true);
+//! assert!(globals.is_readonly("window"));
+//! assert!(globals.is_writable("dataLayer"));
+//! assert!(globals.is_readonly("Object"));
+//! ```
+//!
+//! [`LinterOptions::globals`]: crate::LinterOptions::globals
+
+use std::collections::BTreeMap;
+
+/// The globals of ES5.
+pub const BUILTIN: [&str; 31] = [
+    "Array",
+    "Boolean",
+    "Date",
+    "decodeURI",
+    "decodeURIComponent",
+    "encodeURI",
+    "encodeURIComponent",
+    "Error",
+    "escape",
+    "eval",
+    "EvalError",
+    "Function",
+    "Infinity",
+    "isFinite",
+    "isNaN",
+    "JSON",
+    "Math",
+    "NaN",
+    "Number",
+    "Object",
+    "parseFloat",
+    "parseInt",
+    "RangeError",
+    "ReferenceError",
+    "RegExp",
+    "String",
+    "SyntaxError",
+    "TypeError",
+    "undefined",
+    "unescape",
+    "URIError",
+];
+
+/// The globals added from ES2015 up to ES2021.
+pub const ES2021: [&str; 28] = [
+    "AggregateError",
+    "ArrayBuffer",
+    "Atomics",
+    "BigInt",
+    "BigInt64Array",
+    "BigUint64Array",
+    "DataView",
+    "FinalizationRegistry",
+    "Float32Array",
+    "Float64Array",
+    "globalThis",
+    "Int16Array",
+    "Int32Array",
+    "Int8Array",
+    "Map",
+    "Promise",
+    "Proxy",
+    "Reflect",
+    "Set",
+    "SharedArrayBuffer",
+    "Symbol",
+    "Uint16Array",
+    "Uint32Array",
+    "Uint8Array",
+    "Uint8ClampedArray",
+    "WeakMap",
+    "WeakRef",
+    "WeakSet",
+];
+
+/// The globals of Node.js.
+pub const NODE: [&str; 19] = [
+    "__dirname",
+    "__filename",
+    "Buffer",
+    "clearImmediate",
+    "clearInterval",
+    "clearTimeout",
+    "console",
+    "global",
+    "module",
+    "process",
+    "queueMicrotask",
+    "require",
+    "setImmediate",
+    "setInterval",
+    "setTimeout",
+    "TextDecoder",
+    "TextEncoder",
+    "URL",
+    "URLSearchParams",
+];
+
+/// The globals of Node.js which only exist in CommonJS modules, they are not defined in ES modules.
+pub const COMMONJS: [&str; 4] = ["__dirname", "__filename", "module", "require"];
+
+/// The names of the built-in sets of globals, the globals of [`Globals::default`].
+pub const ENVIRONMENTS: [&str; 3] = ["builtin", "es2021", "node"];
+
+/// A built-in set of globals by its name in [`ENVIRONMENTS`].
+pub fn environment(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        "builtin" => Some(&BUILTIN),
+        "es2021" => Some(&ES2021),
+        "node" => Some(&NODE),
+        _ => None,
+    }
+}
+
+/// The global variables available to a file, and whether each of them can be assigned to.
+///
+/// The default globals are the [`BUILTIN`], [`ES2021`], and [`NODE`] globals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Globals {
+    vars: BTreeMap<String, bool>,
+}
+
+impl Default for Globals {
+    fn default() -> Self {
+        let mut globals = Self::new();
+        for name in ENVIRONMENTS.iter() {
+            globals.extend(environment(name).unwrap());
+        }
+        globals
+    }
+}
+
+impl Globals {
+    /// An empty set of globals.
+    pub fn new() -> Self {
+        Self {
+            vars: BTreeMap::new(),
+        }
+    }
+
+    /// Add a global, a global which was already added is only changed if it becomes writable.
+    pub fn add(&mut self, name: impl Into<String>, writable: bool) -> &mut Self {
+        *self.vars.entry(name.into()).or_insert(writable) |= writable;
+        self
+    }
+
+    /// Add read-only globals.
+    pub fn extend<S: AsRef<str>>(&mut self, names: impl IntoIterator<Item = S>) -> &mut Self {
+        for name in names {
+            self.add(name.as_ref(), false);
+        }
+        self
+    }
+
+    /// Add every global of another set.
+    pub fn merge(&mut self, other: &Globals) -> &mut Self {
+        for (name, writable) in other.iter() {
+            self.add(name, writable);
+        }
+        self
+    }
+
+    /// Remove a global, whether it was read-only or writable.
+    pub fn remove(&mut self, name: &str) -> &mut Self {
+        self.vars.remove(name);
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.vars.contains_key(name)
+    }
+
+    pub fn is_readonly(&self, name: &str) -> bool {
+        self.vars.get(name) == Some(&false)
+    }
+
+    pub fn is_writable(&self, name: &str) -> bool {
+        self.vars.get(name) == Some(&true)
+    }
+
+    /// Every global and whether it is writable, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.vars
+            .iter()
+            .map(|(name, writable)| (name.as_str(), *writable))
+    }
+}
@@ -1,5 +1,15 @@
 //! General utilities to make linting easier.
 
+pub mod asi;
+pub mod cfg;
+pub mod const_eval;
+pub mod domain;
+pub mod expr_eq;
+pub mod globals;
+pub mod regex;
+pub mod scope;
+pub mod tokens;
+
 use crate::rule_prelude::*;
 use ast::*;
 use rslint_parser::TextRange;
@@ -540,6 +550,15 @@ pub fn constructor_or_call_with_callee(
     }
 }
 
+/// The name of the function a call calls, `bar` for both `bar()` and `foo.bar()`.
+pub fn callee_name(call: &CallExpr) -> Option<SyntaxToken> {
+    match call.callee()? {
+        Expr::NameRef(name) => name.ident_token(),
+        Expr::DotExpr(dot) => dot.prop()?.ident_token(),
+        _ => None,
+    }
+}
+
 /// Get the first enclosing function of a node, this does not consider if the node itself is a function.
 pub fn outer_function(node: impl Borrow<SyntaxNode>) -> Option<SyntaxNode> {
     node.borrow()
@@ -547,3 +566,12 @@ pub fn outer_function(node: impl Borrow<SyntaxNode>) -> Option<SyntaxNode> {
         .skip(1)
         .find(|ancestor| matches!(ancestor.kind(), ARROW_EXPR | FN_DECL | FN_EXPR))
 }
+
+/// Shuffle a slice in place using a deterministic random number generator seeded with `seed`.
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = oorandom::Rand64::new(seed as u128);
+    for i in (1..items.len()).rev() {
+        let j = rng.rand_range(0..(i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
@@ -0,0 +1,846 @@
+//! A lightweight parser for the patterns of regular expressions.
+//!
+//! The lexer only finds where a regex literal ends, its pattern is not checked. [`parse`] parses a pattern into
+//! a small AST following the ECMAScript grammar, including the web compatibility syntax allowed in patterns
+//! without the `u` flag, such as `]` and `{` as literal characters. Ranges in the AST are byte offsets into
+//! the pattern.
+//!
+//! [`parse_literal`] parses the text of a regex literal token instead, its ranges are byte offsets into the
+//! token so adding the start of the token gives the range of a part of the pattern in the source code:
+//!
+//! ```
+//! use rslint_core::util::regex::{parse_literal, TermKind};
+//!
+//! let pattern = parse_literal("/a[]b/g").unwrap();
+//! let class = &pattern.alternatives[0].terms[1];
+//! assert!(matches!(class.kind, TermKind::Class(_)));
+//! assert_eq!(class.range, 2..4);
+//! ```
+//!
+//! [`RegexSource`] finds the regexes of the source code, regex literals and `RegExp` calls with a constant
+//! pattern.
+
+use crate::rule_prelude::*;
+use crate::util::const_eval::{eval, ConstValue};
+use ast::{CallExpr, Expr, NewExpr};
+use rslint_parser::TextRange;
+use std::ops::Range;
+use SyntaxKind::*;
+
+/// The flags which are valid in a regex.
+pub const FLAGS: &str = "gimsuy";
+
+/// A parsed regex pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub alternatives: Vec<Alternative>,
+}
+
+/// One of the alternatives of a pattern or group separated by `|`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternative {
+    pub terms: Vec<Term>,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub kind: TermKind,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermKind {
+    Char(Char),
+    /// `.`
+    Dot,
+    /// A character set escape such as `\d` or `\p{Letter}`.
+    Set(Set),
+    Class(Class),
+    Group(Group),
+    /// `\1` or `\k<name>`.
+    Backreference(Backreference),
+    Assertion(Assertion),
+    Quantified(Box<Term>, Quantifier),
+}
+
+/// A single character, which may be written as an escape such as `\x41`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Char {
+    /// The code point of the character, escapes of lone surrogates are not combined.
+    pub value: u32,
+    pub range: (usize, usize),
+}
+
+impl Char {
+    pub fn range(&self) -> Range<usize> {
+        self.range.0..self.range.1
+    }
+}
+
+/// A character set escape, `\d`, `\D`, `\w`, `\W`, `\s`, `\S`, `\p{...}`, or `\P{...}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Set {
+    /// The letter of the escape.
+    pub escape: char,
+    /// The property of a `\p` or `\P` escape.
+    pub property: Option<String>,
+}
+
+/// A character class such as `[a-z]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassItem {
+    Char(Char),
+    Range(Char, Char),
+    Set(Set, Range<usize>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub kind: GroupKind,
+    pub alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupKind {
+    /// A capturing group with an optional name.
+    Capturing(Option<String>),
+    NonCapturing,
+    Lookahead {
+        negated: bool,
+    },
+    Lookbehind {
+        negated: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backreference {
+    Index(u32),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assertion {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+}
+
+/// A quantifier such as `*` or `{2,3}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantifier {
+    pub min: u32,
+    pub max: Option<u32>,
+    pub lazy: bool,
+}
+
+/// An error in a pattern, the pattern throws a `SyntaxError` when it is compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexError {
+    pub message: String,
+    pub range: Range<usize>,
+}
+
+impl Pattern {
+    /// Call `f` with every term of the pattern, including the terms inside of groups and quantifiers.
+    pub fn walk(&self, f: &mut impl FnMut(&Term)) {
+        fn walk_alternatives(alternatives: &[Alternative], f: &mut impl FnMut(&Term)) {
+            for term in alternatives.iter().flat_map(|alt| alt.terms.iter()) {
+                walk_term(term, f);
+            }
+        }
+
+        fn walk_term(term: &Term, f: &mut impl FnMut(&Term)) {
+            f(term);
+            match &term.kind {
+                TermKind::Group(group) => walk_alternatives(&group.alternatives, f),
+                TermKind::Quantified(term, _) => walk_term(term, f),
+                _ => {}
+            }
+        }
+
+        walk_alternatives(&self.alternatives, f);
+    }
+
+    /// Every character of the pattern, including the characters of classes and ranges.
+    pub fn chars(&self) -> Vec<Char> {
+        let mut chars = vec![];
+        self.walk(&mut |term| match &term.kind {
+            TermKind::Char(c) => chars.push(*c),
+            TermKind::Class(class) => {
+                for item in &class.items {
+                    match item {
+                        ClassItem::Char(c) => chars.push(*c),
+                        ClassItem::Range(from, to) => chars.extend_from_slice(&[*from, *to]),
+                        ClassItem::Set(..) => {}
+                    }
+                }
+            }
+            _ => {}
+        });
+        chars
+    }
+
+    /// Every alternative of the pattern, including the alternatives of groups.
+    pub fn all_alternatives(&self) -> Vec<&Alternative> {
+        let mut alternatives = self.alternatives.iter().collect::<Vec<_>>();
+        let mut idx = 0;
+        while idx < alternatives.len() {
+            for term in &alternatives[idx].terms {
+                let mut term = term;
+                while let TermKind::Quantified(inner, _) = &term.kind {
+                    term = inner;
+                }
+                if let TermKind::Group(group) = &term.kind {
+                    alternatives.extend(group.alternatives.iter());
+                }
+            }
+            idx += 1;
+        }
+        alternatives
+    }
+}
+
+/// Parse the pattern of a regex with some flags, the `u` flag enables the stricter unicode grammar.
+pub fn parse(pattern: &str, flags: &str) -> Result<Pattern, RegexError> {
+    parse_from(pattern, 0, flags)
+}
+
+/// Parse the text of a regex literal token such as `/a+/g` and check its flags, the ranges of the pattern
+/// and of errors are offsets into the token rather than into the pattern.
+pub fn parse_literal(literal: &str) -> Result<Pattern, RegexError> {
+    let end = match literal.rfind('/') {
+        Some(end) if end > 0 && literal.starts_with('/') => end,
+        _ => {
+            return Err(RegexError {
+                message: "expected a regex literal".to_string(),
+                range: 0..literal.len(),
+            })
+        }
+    };
+    let flags = &literal[end + 1..];
+    validate_flags(flags, &[]).map_err(|err| RegexError {
+        range: end + 1 + err.range.start..end + 1 + err.range.end,
+        ..err
+    })?;
+    parse_from(&literal[..end], 1, flags)
+}
+
+/// Parse the part of `src` after `start` as a pattern, ranges are offsets into `src`.
+fn parse_from(src: &str, start: usize, flags: &str) -> Result<Pattern, RegexError> {
+    let pattern = &src[start..];
+    let mut parser = Parser {
+        src,
+        pos: start,
+        unicode: flags.contains('u'),
+        group_count: count_capturing_groups(pattern),
+        named_groups: has_named_groups(pattern),
+        names: vec![],
+        named_refs: vec![],
+    };
+
+    let alternatives = parser.disjunction()?;
+    if parser.pos < src.len() {
+        return Err(parser.err("unmatched `)`", parser.pos..parser.pos + 1));
+    }
+    if let Some((name, range)) = parser
+        .named_refs
+        .iter()
+        .find(|(name, _)| !parser.names.contains(name))
+    {
+        return Err(parser.err(format!("there is no group named `{}`", name), range.clone()));
+    }
+    Ok(Pattern { alternatives })
+}
+
+/// Check the flags of a regex, `extra` are flags which are allowed in addition to the standard flags.
+pub fn validate_flags(flags: &str, extra: &[char]) -> Result<(), RegexError> {
+    for (idx, c) in flags.char_indices() {
+        let range = idx..idx + c.len_utf8();
+        if !FLAGS.contains(c) && !extra.contains(&c) {
+            return Err(RegexError {
+                message: format!("invalid regex flag `{}`", c),
+                range,
+            });
+        }
+        if flags[..idx].contains(c) {
+            return Err(RegexError {
+                message: format!("duplicate regex flag `{}`", c),
+                range,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The number of capturing groups in a pattern, backreferences can refer to groups after them.
+fn count_capturing_groups(pattern: &str) -> u32 {
+    let mut count = 0;
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => {
+                let mut rest = chars.clone();
+                match (rest.next(), rest.next(), rest.next()) {
+                    (Some('?'), Some('<'), Some(c)) if c != '=' && c != '!' => count += 1,
+                    (Some('?'), _, _) => {}
+                    _ => count += 1,
+                }
+            }
+            _ => {}
+        }
+    }
+    count
+}
+
+fn has_named_groups(pattern: &str) -> bool {
+    pattern
+        .match_indices("(?<")
+        .any(|(idx, _)| !matches!(pattern[idx + 3..].chars().next(), Some('=') | Some('!')))
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    unicode: bool,
+    group_count: u32,
+    /// Whether the pattern has named groups, which makes `\k` a named backreference.
+    named_groups: bool,
+    names: Vec<String>,
+    named_refs: Vec<(String, Range<usize>)>,
+}
+
+impl<'a> Parser<'a> {
+    fn err(&self, message: impl Into<String>, range: Range<usize>) -> RegexError {
+        RegexError {
+            message: message.into(),
+            range: range.start.min(self.src.len())..range.end.min(self.src.len()),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.src[self.pos..].chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn disjunction(&mut self) -> Result<Vec<Alternative>, RegexError> {
+        let mut alternatives = vec![self.alternative()?];
+        while self.eat('|') {
+            alternatives.push(self.alternative()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn alternative(&mut self) -> Result<Alternative, RegexError> {
+        let start = self.pos;
+        let mut terms = vec![];
+        while !matches!(self.peek(), None | Some('|') | Some(')')) {
+            terms.push(self.term()?);
+        }
+        Ok(Alternative {
+            terms,
+            range: start..self.pos,
+        })
+    }
+
+    fn term(&mut self) -> Result<Term, RegexError> {
+        let start = self.pos;
+        let c = self.bump().unwrap();
+        let kind = match c {
+            '^' => TermKind::Assertion(Assertion::Start),
+            '$' => TermKind::Assertion(Assertion::End),
+            '.' => TermKind::Dot,
+            '(' => TermKind::Group(self.group(start)?),
+            '[' => TermKind::Class(self.class(start)?),
+            '\\' => self.atom_escape(start)?,
+            '*' | '+' | '?' => return Err(self.err("nothing to repeat", start..self.pos)),
+            '{' if self.unicode || self.quantifier_braces(start).is_some() => {
+                return Err(self.err("nothing to repeat", start..self.pos));
+            }
+            '}' | ']' if self.unicode => {
+                return Err(self.err(format!("lone `{}`", c), start..self.pos));
+            }
+            c => TermKind::Char(Char {
+                value: c as u32,
+                range: (start, self.pos),
+            }),
+        };
+        let term = Term {
+            kind,
+            range: start..self.pos,
+        };
+        self.quantified(term)
+    }
+
+    /// Parse the quantifier following a term if there is one.
+    fn quantified(&mut self, term: Term) -> Result<Term, RegexError> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => match self.quantifier_braces(start) {
+                Some(bounds) => bounds,
+                None if self.unicode => {
+                    return Err(self.err("incomplete quantifier", start..start + 1))
+                }
+                None => return Ok(term),
+            },
+            _ => return Ok(term),
+        };
+
+        let quantifiable = match &term.kind {
+            TermKind::Assertion(_) => false,
+            TermKind::Group(group) => match group.kind {
+                GroupKind::Lookbehind { .. } => false,
+                GroupKind::Lookahead { .. } => !self.unicode,
+                _ => true,
+            },
+            _ => true,
+        };
+        if !quantifiable {
+            return Err(self.err("nothing to repeat", term.range.clone()));
+        }
+        if self.peek() == Some('{') {
+            self.pos = self.src[start..]
+                .find('}')
+                .map_or(self.src.len(), |end| start + end + 1);
+        } else {
+            self.bump();
+        }
+        if matches!(max, Some(max) if max < min) {
+            return Err(self.err("numbers out of order in quantifier", start..self.pos));
+        }
+        let lazy = self.eat('?');
+        let range = term.range.start..self.pos;
+        Ok(Term {
+            kind: TermKind::Quantified(Box::new(term), Quantifier { min, max, lazy }),
+            range,
+        })
+    }
+
+    /// The bounds of a `{n}`, `{n,}`, or `{n,m}` quantifier starting at `start`.
+    fn quantifier_braces(&self, start: usize) -> Option<(u32, Option<u32>)> {
+        let rest = &self.src[start..];
+        let end = rest.find('}')?;
+        let inner = rest.strip_prefix('{')?.get(..end - 1)?;
+        let number = |text: &str| {
+            if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) {
+                Some(text.parse::<u32>().unwrap_or(u32::MAX))
+            } else {
+                None
+            }
+        };
+        match inner.find(',') {
+            None => number(inner).map(|n| (n, Some(n))),
+            Some(idx) if idx + 1 == inner.len() => number(&inner[..idx]).map(|n| (n, None)),
+            Some(idx) => Some((number(&inner[..idx])?, Some(number(&inner[idx + 1..])?))),
+        }
+    }
+
+    fn group(&mut self, start: usize) -> Result<Group, RegexError> {
+        let kind = if self.eat('?') {
+            match self.bump() {
+                Some(':') => GroupKind::NonCapturing,
+                Some('=') => GroupKind::Lookahead { negated: false },
+                Some('!') => GroupKind::Lookahead { negated: true },
+                Some('<') if self.eat('=') => GroupKind::Lookbehind { negated: false },
+                Some('<') if self.eat('!') => GroupKind::Lookbehind { negated: true },
+                Some('<') => {
+                    let name = self.group_name()?;
+                    if self.names.contains(&name) {
+                        return Err(
+                            self.err(format!("duplicate group name `{}`", name), start..self.pos)
+                        );
+                    }
+                    self.names.push(name.clone());
+                    GroupKind::Capturing(Some(name))
+                }
+                _ => return Err(self.err("invalid group", start..self.pos)),
+            }
+        } else {
+            GroupKind::Capturing(None)
+        };
+
+        let alternatives = self.disjunction()?;
+        if !self.eat(')') {
+            return Err(self.err("unterminated group", start..start + 1));
+        }
+        Ok(Group { kind, alternatives })
+    }
+
+    /// The name of a named group or backreference after the `<`, including the `>`.
+    fn group_name(&mut self) -> Result<String, RegexError> {
+        let start = self.pos;
+        let end = self.src[start..].find('>').map(|end| start + end);
+        let name = end.map(|end| &self.src[start..end]);
+        let valid = name.into_iter().any(|name| {
+            let mut chars = name.chars();
+            matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '$' || c == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '$' || c == '_')
+        });
+        match (name, end) {
+            (Some(name), Some(end)) if valid => {
+                self.pos = end + 1;
+                Ok(name.to_string())
+            }
+            _ => Err(self.err("invalid capture group name", start..end.unwrap_or(start))),
+        }
+    }
+
+    fn class(&mut self, start: usize) -> Result<Class, RegexError> {
+        let negated = self.eat('^');
+        let mut items = vec![];
+        loop {
+            let atom_start = self.pos;
+            let from = match self.peek() {
+                None => return Err(self.err("unterminated character class", start..start + 1)),
+                Some(']') => {
+                    self.bump();
+                    return Ok(Class { negated, items });
+                }
+                _ => self.class_atom()?,
+            };
+            if self.peek() != Some('-') || matches!(self.peek_nth(1), None | Some(']')) {
+                items.push(from);
+                continue;
+            }
+            let dash = self.pos;
+            self.bump();
+            let to = self.class_atom()?;
+            match (from, to) {
+                (ClassItem::Char(from), ClassItem::Char(to)) => {
+                    if from.value > to.value {
+                        return Err(self.err(
+                            "range out of order in character class",
+                            atom_start..self.pos,
+                        ));
+                    }
+                    items.push(ClassItem::Range(from, to));
+                }
+                _ if self.unicode => {
+                    return Err(self.err("invalid character class range", atom_start..self.pos))
+                }
+                (from, to) => {
+                    // without the unicode flag a range of a set is a set and the characters `-` and `to`
+                    items.push(from);
+                    items.push(ClassItem::Char(Char {
+                        value: '-' as u32,
+                        range: (dash, dash + 1),
+                    }));
+                    items.push(to);
+                }
+            }
+        }
+    }
+
+    fn class_atom(&mut self) -> Result<ClassItem, RegexError> {
+        let start = self.pos;
+        let c = self.bump().unwrap();
+        if c != '\\' {
+            return Ok(ClassItem::Char(Char {
+                value: c as u32,
+                range: (start, self.pos),
+            }));
+        }
+        match self.peek() {
+            Some('b') => {
+                self.bump();
+                Ok(ClassItem::Char(Char {
+                    value: 8,
+                    range: (start, self.pos),
+                }))
+            }
+            Some('-') if self.unicode => {
+                self.bump();
+                Ok(ClassItem::Char(Char {
+                    value: '-' as u32,
+                    range: (start, self.pos),
+                }))
+            }
+            _ => match self.character_escape(start, true)? {
+                TermKind::Char(c) => Ok(ClassItem::Char(c)),
+                TermKind::Set(set) => Ok(ClassItem::Set(set, start..self.pos)),
+                _ => unreachable!("escapes in classes are characters or sets"),
+            },
+        }
+    }
+
+    fn atom_escape(&mut self, start: usize) -> Result<TermKind, RegexError> {
+        match self.peek() {
+            Some('b') => {
+                self.bump();
+                Ok(TermKind::Assertion(Assertion::WordBoundary))
+            }
+            Some('B') => {
+                self.bump();
+                Ok(TermKind::Assertion(Assertion::NotWordBoundary))
+            }
+            Some('1'..='9') => {
+                let digits = self.src[self.pos..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map_or(self.src.len(), |end| self.pos + end);
+                let index = self.src[self.pos..digits]
+                    .parse::<u32>()
+                    .unwrap_or(u32::MAX);
+                if index <= self.group_count {
+                    self.pos = digits;
+                    Ok(TermKind::Backreference(Backreference::Index(index)))
+                } else if self.unicode {
+                    Err(self.err("invalid escape", start..digits))
+                } else {
+                    self.character_escape(start, false)
+                }
+            }
+            Some('k') if self.unicode || self.named_groups => {
+                self.bump();
+                if !self.eat('<') {
+                    return Err(self.err("invalid named reference", start..self.pos));
+                }
+                let name = self.group_name()?;
+                self.named_refs.push((name.clone(), start..self.pos));
+                Ok(TermKind::Backreference(Backreference::Name(name)))
+            }
+            _ => self.character_escape(start, false),
+        }
+    }
+
+    /// An escape which is a character or a character set, the `\` at `start` was already consumed.
+    fn character_escape(&mut self, start: usize, in_class: bool) -> Result<TermKind, RegexError> {
+        let c = match self.bump() {
+            Some(c) => c,
+            None => return Err(self.err("`\\` at end of pattern", start..self.pos)),
+        };
+        let value = match c {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => {
+                return Ok(TermKind::Set(Set {
+                    escape: c,
+                    property: None,
+                }))
+            }
+            'p' | 'P' if self.unicode => {
+                let end = self.src[self.pos..].find('}').map(|end| self.pos + end);
+                match end {
+                    Some(end) if self.eat('{') && end > self.pos => {
+                        let property = self.src[self.pos..end].to_string();
+                        self.pos = end + 1;
+                        return Ok(TermKind::Set(Set {
+                            escape: c,
+                            property: Some(property),
+                        }));
+                    }
+                    _ => return Err(self.err("invalid property name", start..self.pos)),
+                }
+            }
+            'f' => 0xC,
+            'n' => 0xA,
+            'r' => 0xD,
+            't' => 0x9,
+            'v' => 0xB,
+            'c' => match self.peek() {
+                Some(letter) if letter.is_ascii_alphabetic() => {
+                    self.bump();
+                    letter as u32 % 32
+                }
+                Some(digit)
+                    if in_class && !self.unicode && (digit.is_ascii_digit() || digit == '_') =>
+                {
+                    self.bump();
+                    digit as u32 % 32
+                }
+                _ if self.unicode => {
+                    return Err(self.err("invalid unicode escape", start..self.pos))
+                }
+                _ => {
+                    // `\c` without a control letter is a `\` followed by a `c`
+                    self.pos -= 1;
+                    '\\' as u32
+                }
+            },
+            '0' if !matches!(self.peek(), Some('0'..='9')) => 0,
+            '0'..='9' if self.unicode => {
+                return Err(self.err("invalid decimal escape", start..self.pos))
+            }
+            '0'..='7' => {
+                // a legacy octal escape of up to three digits with a value below 256
+                let mut value = c.to_digit(8).unwrap();
+                while let Some(digit) = self.peek().and_then(|c| c.to_digit(8)) {
+                    if value * 8 + digit > 0o377 {
+                        break;
+                    }
+                    self.bump();
+                    value = value * 8 + digit;
+                }
+                value
+            }
+            'x' => match self.hex_digits(2) {
+                Some(value) => value,
+                None if self.unicode => return Err(self.err("invalid escape", start..self.pos)),
+                None => 'x' as u32,
+            },
+            'u' => match self.unicode_escape() {
+                Some(value) => value,
+                None if self.unicode => {
+                    return Err(self.err("invalid unicode escape", start..self.pos))
+                }
+                None => 'u' as u32,
+            },
+            c if self.unicode && !"^$\\.*+?()[]{}|/".contains(c) => {
+                return Err(self.err("invalid escape", start..self.pos))
+            }
+            c => c as u32,
+        };
+        Ok(TermKind::Char(Char {
+            value,
+            range: (start, self.pos),
+        }))
+    }
+
+    fn hex_digits(&mut self, len: usize) -> Option<u32> {
+        let digits = self.src.get(self.pos..self.pos + len)?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += len;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    /// The value of a unicode escape after the `u`.
+    fn unicode_escape(&mut self) -> Option<u32> {
+        if self.unicode && self.peek() == Some('{') {
+            let end = self.pos + self.src[self.pos..].find('}')?;
+            let digits = &self.src[self.pos + 1..end];
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+            let value = u32::from_str_radix(digits, 16)
+                .ok()
+                .filter(|&v| v <= 0x10FFFF)?;
+            self.pos = end + 1;
+            Some(value)
+        } else {
+            self.hex_digits(4)
+        }
+    }
+}
+
+/// A regex of the source code, either a regex literal or a `RegExp` call whose pattern is a constant string.
+#[derive(Debug, Clone)]
+pub struct RegexSource {
+    pub pattern: String,
+    /// The flags of the regex, `None` if the flags of a `RegExp` call are not constant.
+    pub flags: Option<String>,
+    /// The node of the literal, or the first argument of the call.
+    pub node: SyntaxNode,
+    /// Whether the regex is a literal, only the ranges of literals can be mapped to the pattern.
+    pub literal: bool,
+}
+
+impl RegexSource {
+    /// The regex of a `REGEX` literal or of a `RegExp` call or `new` expression.
+    pub fn from_node(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            LITERAL => {
+                let token = node.to::<ast::Literal>().token();
+                if token.kind() != REGEX {
+                    return None;
+                }
+                let text = token.text();
+                let end = text.rfind('/')?;
+                Some(Self {
+                    pattern: text[1..end].to_string(),
+                    flags: Some(text[end + 1..].to_string()),
+                    node: node.clone(),
+                    literal: true,
+                })
+            }
+            CALL_EXPR | NEW_EXPR => {
+                let (callee, args) = if let Some(call) = node.try_to::<CallExpr>() {
+                    (call.callee()?, call.arguments()?)
+                } else {
+                    let new = node.to::<NewExpr>();
+                    (new.object()?, new.arguments()?)
+                };
+                match callee {
+                    Expr::NameRef(name) if name.syntax().text() == "RegExp" => {}
+                    _ => return None,
+                }
+                let mut args = args.args();
+                let pattern_arg = args.next()?;
+                let pattern = match eval(pattern_arg.clone())? {
+                    ConstValue::String(pattern) => pattern,
+                    _ => return None,
+                };
+                let flags = match args.next() {
+                    Some(flags) => match eval(flags) {
+                        Some(ConstValue::String(flags)) => Some(flags),
+                        _ => None,
+                    },
+                    None => Some(String::new()),
+                };
+                Some(Self {
+                    pattern,
+                    flags,
+                    node: pattern_arg.syntax().clone(),
+                    literal: false,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The range of the source code to report for a range of the pattern, the whole argument of a `RegExp`
+    /// call since its pattern is a string which may not match the source code.
+    pub fn range(&self, range: Range<usize>) -> TextRange {
+        if self.literal {
+            let start = usize::from(self.node.trimmed_range().start()) + 1;
+            TextRange::new(
+                ((start + range.start) as u32).into(),
+                ((start + range.end) as u32).into(),
+            )
+        } else {
+            self.node.trimmed_range()
+        }
+    }
+
+    /// Parse the pattern, patterns of `RegExp` calls without constant flags are parsed without the `u` flag.
+    pub fn parse(&self) -> Result<Pattern, RegexError> {
+        parse(&self.pattern, self.flags.as_deref().unwrap_or_default())
+    }
+}
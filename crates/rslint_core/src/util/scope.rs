@@ -0,0 +1,466 @@
+//! Simple syntax based scope utilities.
+//!
+//! These do not build a full scope tree, instead they resolve a single name by walking the
+//! syntax tree and skipping any nested scopes which shadow it. This is enough for rules which
+//! only need to know how a specific declaration is used.
+
+use crate::rule_prelude::*;
+use ast::*;
+use rslint_parser::TextRange;
+use SyntaxKind::*;
+
+/// How a reference to a name uses the value of the binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// The value is only read, e.g. `foo(a)`.
+    Read,
+    /// A new value is assigned without reading the old one, e.g. `a = 5` or `[a] = b`.
+    Write,
+    /// The value is read then written, e.g. `a += 5` or `a++`.
+    ReadWrite,
+}
+
+/// A single reference to a name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference {
+    /// The `NAME_REF` or `NAME` node of the reference.
+    pub node: SyntaxNode,
+    pub kind: ReferenceKind,
+}
+
+impl Reference {
+    pub fn range(&self) -> TextRange {
+        self.node.trimmed_range()
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.kind != ReferenceKind::Read
+    }
+
+    pub fn is_read(&self) -> bool {
+        self.kind != ReferenceKind::Write
+    }
+}
+
+/// All usages of a single name inside of a scope, classified by how they use the binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableUsages {
+    pub name: std::string::String,
+    pub references: Vec<Reference>,
+}
+
+impl VariableUsages {
+    /// Collect the usages of `name` inside of `scope`, see [`references`] for which references are included.
+    pub fn new(scope: &SyntaxNode, name: impl Into<std::string::String>) -> Self {
+        let name = name.into();
+        let references = references(scope, &name);
+        Self { name, references }
+    }
+
+    /// References which read the value of the binding, including read-write references.
+    pub fn reads(&self) -> impl Iterator<Item = &Reference> {
+        self.references.iter().filter(|r| r.is_read())
+    }
+
+    /// References which assign to the binding, including read-write references.
+    pub fn writes(&self) -> impl Iterator<Item = &Reference> {
+        self.references.iter().filter(|r| r.is_write())
+    }
+
+    /// References which both read and write the binding, such as `a++` or `a += 1`.
+    pub fn read_writes(&self) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(|r| r.kind == ReferenceKind::ReadWrite)
+    }
+
+    /// Whether the binding is assigned to anywhere after its declaration.
+    pub fn is_reassigned(&self) -> bool {
+        self.writes().next().is_some()
+    }
+
+    /// Whether the value of the binding is ever read.
+    pub fn is_read(&self) -> bool {
+        self.reads().next().is_some()
+    }
+}
+
+/// Whether a node creates a new function scope.
+pub fn is_function_scope(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        FN_DECL | FN_EXPR | ARROW_EXPR | METHOD | GETTER | SETTER
+    )
+}
+
+/// Whether a node creates a new scope for lexical (`let`, `const`, `class`) declarations.
+pub fn is_block_scope(node: &SyntaxNode) -> bool {
+    is_function_scope(node)
+        || matches!(
+            node.kind(),
+            BLOCK_STMT
+                | FOR_STMT
+                | FOR_IN_STMT
+                | FOR_OF_STMT
+                | SWITCH_STMT
+                | CATCH_CLAUSE
+                | SCRIPT
+                | MODULE
+        )
+}
+
+/// Get the closest function scope enclosing the node, or the root if the node is not in a function.
+pub fn enclosing_function_scope(node: &SyntaxNode) -> SyntaxNode {
+    node.ancestors()
+        .skip(1)
+        .find(is_function_scope)
+        .unwrap_or_else(|| node.ancestors().last().unwrap())
+}
+
+/// Get the closest block scope enclosing the node.
+pub fn enclosing_block_scope(node: &SyntaxNode) -> SyntaxNode {
+    node.ancestors()
+        .skip(1)
+        .find(is_block_scope)
+        .unwrap_or_else(|| node.ancestors().last().unwrap())
+}
+
+/// All of the names bound by a pattern, e.g. `a`, `b`, and `c` for `{ a, b: [b], ...c }`.
+///
+/// This does not include any names inside default values or computed keys.
+pub fn pattern_bindings(pattern: &SyntaxNode) -> Vec<Name> {
+    let mut names = vec![];
+    collect_bindings(pattern, &mut names);
+    names
+}
+
+fn collect_bindings(node: &SyntaxNode, names: &mut Vec<Name>) {
+    match node.kind() {
+        SINGLE_PATTERN => names.extend(node.to::<SinglePattern>().name()),
+        ASSIGN_PATTERN => {
+            if let Some(key) = node.to::<AssignPattern>().key() {
+                collect_bindings(key.syntax(), names);
+            }
+        }
+        REST_PATTERN | KEY_VALUE_PATTERN | OBJECT_PATTERN | ARRAY_PATTERN => {
+            // key value patterns are object pattern props and not patterns themselves
+            for child in node
+                .children()
+                .filter(|child| child.is::<Pattern>() || child.kind() == KEY_VALUE_PATTERN)
+            {
+                collect_bindings(&child, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// All of the names bound by the declarators of a variable declaration.
+pub fn var_decl_bindings(decl: &VarDecl) -> Vec<Name> {
+    decl.declared()
+        .filter_map(|declarator| declarator.pattern())
+        .flat_map(|pat| pattern_bindings(pat.syntax()))
+        .collect()
+}
+
+/// The names bound by a declarator and which of them are never reassigned after their declaration.
+#[derive(Debug, Clone)]
+pub struct DeclaratorBindings {
+    pub declarator: Declarator,
+    pub bindings: Vec<Name>,
+    /// The bindings which are initialized by the declaration and never written to afterwards.
+    pub never_reassigned: Vec<Name>,
+}
+
+impl DeclaratorBindings {
+    /// Whether the declarator binds names and none of them are ever reassigned.
+    pub fn all_never_reassigned(&self) -> bool {
+        !self.bindings.is_empty() && self.bindings.len() == self.never_reassigned.len()
+    }
+}
+
+/// The bindings of each declarator of a declaration which are never reassigned, in the scope the
+/// declaration is visible in.
+///
+/// Bindings of declarators without an initializer are assigned later so they are never included, except
+/// for the declarations of `for...in` and `for...of` loops, which are initialized by each iteration.
+pub fn never_reassigned_bindings(decl: &VarDecl) -> Vec<DeclaratorBindings> {
+    let node = decl.syntax();
+    let scope = if decl.is_var() {
+        enclosing_function_scope(node)
+    } else {
+        enclosing_block_scope(node)
+    };
+    let in_for_in_or_of = node
+        .parent()
+        .filter(|parent| parent.kind() == FOR_STMT_INIT)
+        .and_then(|init| init.parent())
+        .map(|stmt| matches!(stmt.kind(), FOR_IN_STMT | FOR_OF_STMT))
+        .unwrap_or(false);
+
+    decl.declared()
+        .filter_map(|declarator| {
+            let bindings = pattern_bindings(declarator.pattern()?.syntax());
+            let never_reassigned = if declarator.value().is_some() || in_for_in_or_of {
+                bindings
+                    .iter()
+                    .filter(|name| !VariableUsages::new(&scope, name.text()).is_reassigned())
+                    .cloned()
+                    .collect()
+            } else {
+                vec![]
+            };
+            Some(DeclaratorBindings {
+                declarator,
+                bindings,
+                never_reassigned,
+            })
+        })
+        .collect()
+}
+
+/// All of the names declared by a function's parameters.
+pub fn parameter_bindings(func: &SyntaxNode) -> Vec<Name> {
+    if let Some(name) = func
+        .try_to::<ArrowExpr>()
+        .and_then(|arrow| arrow.params())
+        .and_then(|params| params.syntax().try_to::<Name>())
+    {
+        return vec![name];
+    }
+    func.child_with_ast::<ParameterList>()
+        .map(|list| {
+            list.parameters()
+                .flat_map(|param| pattern_bindings(param.syntax()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// All `var` declarations of `name` hoisted to the function scope `scope` (or the root),
+/// including function declarations and parameters.
+pub fn var_declarations(scope: &SyntaxNode, name: &str) -> Vec<Name> {
+    var_declarations_matching(scope, &|n| n.text() == name)
+}
+
+fn var_declarations_matching(scope: &SyntaxNode, matches: &dyn Fn(&Name) -> bool) -> Vec<Name> {
+    let mut names: Vec<Name> = parameter_bindings(scope)
+        .into_iter()
+        .filter(|n| matches(n))
+        .collect();
+
+    scope.descendants_with(&mut |node| {
+        if is_function_scope(node) {
+            if let Some(fn_name) = node.try_to::<FnDecl>().and_then(|decl| decl.name()) {
+                if matches(&fn_name) {
+                    names.push(fn_name);
+                }
+            }
+            return false;
+        }
+        if let Some(decl) = node.try_to::<VarDecl>().filter(|decl| decl.is_var()) {
+            names.extend(var_decl_bindings(&decl).into_iter().filter(|n| matches(n)));
+        }
+        true
+    });
+    names
+}
+
+/// All lexical (`let`, `const`, `class`, and function) declarations of `name` directly in the block scope `scope`.
+pub fn lexical_declarations(scope: &SyntaxNode, name: &str) -> Vec<Name> {
+    lexical_declarations_matching(scope, &|n| n.text() == name)
+}
+
+fn lexical_declarations_matching(scope: &SyntaxNode, matches: &dyn Fn(&Name) -> bool) -> Vec<Name> {
+    let mut names = vec![];
+    let mut check = |node: &SyntaxNode| match node.kind() {
+        VAR_DECL => {
+            let decl = node.to::<VarDecl>();
+            if !decl.is_var() {
+                names.extend(var_decl_bindings(&decl).into_iter().filter(|n| matches(n)));
+            }
+        }
+        FN_DECL | CLASS_DECL => names.extend(node.child_with_ast::<Name>().filter(|n| matches(n))),
+        _ => {}
+    };
+
+    match scope.kind() {
+        FOR_STMT | FOR_IN_STMT | FOR_OF_STMT => {
+            if let Some(init) = scope.child_with_ast::<ForStmtInit>() {
+                init.syntax().children().for_each(|child| check(&child));
+            }
+        }
+        CATCH_CLAUSE => {
+            if let Some(pat) = scope.to::<CatchClause>().error() {
+                names.extend(
+                    pattern_bindings(pat.syntax())
+                        .into_iter()
+                        .filter(|n| matches(n)),
+                );
+            }
+        }
+        SWITCH_STMT => {
+            for case in scope.children().filter(|child| child.is::<SwitchCase>()) {
+                case.children().for_each(|child| check(&child));
+            }
+        }
+        _ if is_function_scope(scope) => {
+            if let Some(body) = scope.child_with_ast::<BlockStmt>() {
+                body.syntax().children().for_each(|child| check(&child));
+            }
+        }
+        _ => scope.children().for_each(|child| check(&child)),
+    }
+    names
+}
+
+/// Every declaration in the scope `scope` in the order they appear in the source code, these are the lexical
+/// declarations of the scope and for function scopes and the root, also the hoisted `var` declarations.
+pub fn declarations(scope: &SyntaxNode) -> Vec<Name> {
+    let mut names = lexical_declarations_matching(scope, &|_| true);
+    if is_function_scope(scope) || matches!(scope.kind(), SCRIPT | MODULE) {
+        names.extend(var_declarations_matching(scope, &|_| true));
+    }
+    names.sort_by_key(|name| name.range().start());
+    // function declarations in the body of a function are both lexical and hoisted
+    names.dedup_by_key(|name| name.range());
+    names
+}
+
+/// Whether the scope `scope` declares `name` itself, which would shadow any outer declarations of it.
+pub fn declares(scope: &SyntaxNode, name: &str) -> bool {
+    if !lexical_declarations(scope, name).is_empty() {
+        return true;
+    }
+    if is_function_scope(scope) {
+        let own_name = scope
+            .try_to::<FnExpr>()
+            .and_then(|func| func.name())
+            .map_or(false, |n| n.text() == name);
+        own_name || !var_declarations(scope, name).is_empty()
+    } else {
+        false
+    }
+}
+
+/// Whether a reference to `name` at `node` refers to a global, which means no scope enclosing the node declares
+/// the name, including `var` and function declarations at the top level.
+pub fn is_global_reference(node: &SyntaxNode, name: &str) -> bool {
+    node.ancestors().filter(is_block_scope).all(|scope| {
+        !declares(&scope, name)
+            && (scope.parent().is_some() || var_declarations(&scope, name).is_empty())
+    })
+}
+
+/// All references to `name` inside of `scope`, excluding any references inside of nested scopes
+/// which shadow the name and excluding the declarations of the name.
+pub fn references(scope: &SyntaxNode, name: &str) -> Vec<Reference> {
+    let _phase = crate::profile::start_phase(crate::profile::Phase::Scope);
+    let mut refs = vec![];
+    scope.descendants_with(&mut |node| {
+        if is_block_scope(node) && declares(node, name) {
+            return false;
+        }
+        let is_ref = match node.kind() {
+            NAME_REF => true,
+            NAME => matches!(
+                node.parent().map(|parent| parent.kind()),
+                Some(SINGLE_PATTERN) | Some(IDENT_PROP)
+            ),
+            _ => false,
+        };
+        if is_ref && node.text() == name {
+            if let Some(kind) = reference_kind(node) {
+                refs.push(Reference {
+                    node: node.to_owned(),
+                    kind,
+                });
+            }
+        }
+        true
+    });
+    refs
+}
+
+/// The first reference to `name` inside of `scope` which comes before `decl` in the source code, such as a use
+/// of a hoisted `var` before its declaration. References in functions which are called after the declaration
+/// are included as well, so a result only means using the name may be unsafe.
+pub fn use_before_declaration(
+    scope: &SyntaxNode,
+    decl: &SyntaxNode,
+    name: &str,
+) -> Option<Reference> {
+    let start = decl.text_range().start();
+    references(scope, name)
+        .into_iter()
+        .find(|reference| reference.range().start() < start)
+}
+
+/// The first reference to `name` inside of `scope` which is outside of `block`, such as a use of a function
+/// scoped `var` outside of the block it is declared in.
+pub fn use_outside_block(scope: &SyntaxNode, block: &SyntaxNode, name: &str) -> Option<Reference> {
+    let range = block.text_range();
+    references(scope, name)
+        .into_iter()
+        .find(|reference| !range.contains_range(reference.range()))
+}
+
+/// Find out how a `NAME_REF` or `NAME` uses its binding, returns `None` if the name is a declaration.
+///
+/// Parenthesized targets such as `(a) = 5` and `(a)++` are classified the same as unparenthesized ones.
+pub fn reference_kind(node: &SyntaxNode) -> Option<ReferenceKind> {
+    let parent = node.parent()?;
+    if node.kind() == NAME {
+        if parent.kind() == IDENT_PROP {
+            return Some(ReferenceKind::Read);
+        }
+        let top = node
+            .ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.is::<Pattern>() || ancestor.kind() == KEY_VALUE_PATTERN)
+            .last()?;
+        return match top.parent()?.kind() {
+            ASSIGN_EXPR => Some(ReferenceKind::Write),
+            FOR_STMT_INIT => Some(ReferenceKind::Write),
+            _ => None,
+        };
+    }
+
+    // the labels of `break` and `continue` are not variables
+    if matches!(parent.kind(), BREAK_STMT | CONTINUE_STMT) {
+        return None;
+    }
+
+    let target = node
+        .ancestors()
+        .take_while(|ancestor| ancestor == node || ancestor.kind() == GROUPING_EXPR)
+        .last()?;
+    let parent = target.parent()?;
+
+    Some(match parent.kind() {
+        ASSIGN_EXPR if parent.first_child().as_ref() == Some(&target) => {
+            if parent.to::<AssignExpr>().op() == Some(AssignOp::Assign) {
+                ReferenceKind::Write
+            } else {
+                ReferenceKind::ReadWrite
+            }
+        }
+        UNARY_EXPR
+            if matches!(
+                parent.to::<UnaryExpr>().op(),
+                Some(UnaryOp::Increment) | Some(UnaryOp::Decrement)
+            ) =>
+        {
+            ReferenceKind::ReadWrite
+        }
+        FOR_STMT_INIT
+            if matches!(
+                parent.parent().map(|p| p.kind()),
+                Some(FOR_IN_STMT) | Some(FOR_OF_STMT)
+            ) =>
+        {
+            ReferenceKind::Write
+        }
+        _ => ReferenceKind::Read,
+    })
+}
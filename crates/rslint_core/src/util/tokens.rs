@@ -0,0 +1,104 @@
+//! Queries over the tokens around a node or token for rules which check whitespace and formatting.
+//!
+//! Whitespace and comments are tokens of their own, so the token after `a` in `a /* b */ + c` is a
+//! whitespace token, not `+`. These helpers skip over that trivia and answer questions about the layout
+//! of the source such as whether two tokens are on the same line:
+//!
+//! ```
+//! use rslint_core::util::tokens::{next_non_trivia, on_same_line};
+//! use rslint_parser::{parse_expr, AstNode};
+//!
+//! let root = parse_expr("a /* b */ +\n c", 0).syntax();
+//! let a = root.first_token().unwrap();
+//! let plus = next_non_trivia(&a).unwrap();
+//! assert_eq!(plus.text(), "+");
+//!
+//! let c = next_non_trivia(&plus).unwrap();
+//! assert!(on_same_line(&a, &plus));
+//! assert!(!on_same_line(&plus, &c));
+//! ```
+
+use crate::rule_prelude::*;
+use std::iter::successors;
+
+/// The next token which is not whitespace or a comment.
+pub fn next_non_trivia(token: &SyntaxToken) -> Option<SyntaxToken> {
+    successors(token.next_token(), |token| token.next_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+/// The previous token which is not whitespace or a comment.
+pub fn prev_non_trivia(token: &SyntaxToken) -> Option<SyntaxToken> {
+    successors(token.prev_token(), |token| token.prev_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+/// The first token of a node which is not whitespace or a comment.
+pub fn first_non_trivia(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+/// The last token of a node which is not whitespace or a comment.
+pub fn last_non_trivia(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .filter(|token| !token.kind().is_trivia())
+        .last()
+}
+
+/// The whitespace and comments directly after a token, in source order.
+pub fn trivia_after(token: &SyntaxToken) -> Vec<SyntaxToken> {
+    successors(token.next_token(), |token| token.next_token())
+        .take_while(|token| token.kind().is_trivia())
+        .collect()
+}
+
+/// The whitespace and comments directly before a token, in source order.
+pub fn trivia_before(token: &SyntaxToken) -> Vec<SyntaxToken> {
+    let mut trivia = successors(token.prev_token(), |token| token.prev_token())
+        .take_while(|token| token.kind().is_trivia())
+        .collect::<Vec<_>>();
+    trivia.reverse();
+    trivia
+}
+
+/// Whether the end of one token and the start of another are on the same line, the tokens can be given
+/// in any order.
+///
+/// Only the text between the tokens is looked at, so a template literal spanning multiple lines is on
+/// the same line as the token right after it.
+pub fn on_same_line(a: &SyntaxToken, b: &SyntaxToken) -> bool {
+    let (first, last) = if a.text_range().start() <= b.text_range().start() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    successors(first.next_token(), |token| token.next_token())
+        .take_while(|token| token.text_range().start() < last.text_range().start())
+        .all(|token| !token.text().contains('\n'))
+}
+
+/// The spaces and tabs at the start of the line a token starts on.
+///
+/// If the token is not the first token on its line the indentation of the line is still returned, so
+/// `bar` in `    foo(bar)` has an indentation of four spaces.
+pub fn line_indentation(token: &SyntaxToken) -> String {
+    let mut line = vec![];
+    let mut prev = token.prev_token();
+    while let Some(token) = prev {
+        let text = token.text();
+        if let Some(idx) = text.rfind('\n') {
+            line.push(text[idx + 1..].to_string());
+            break;
+        }
+        line.push(text.to_string());
+        prev = token.prev_token();
+    }
+    line.reverse();
+    line.concat()
+        .chars()
+        .take_while(|c| matches!(c, ' ' | '\t'))
+        .collect()
+}
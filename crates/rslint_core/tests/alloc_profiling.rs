@@ -0,0 +1,17 @@
+#![cfg(feature = "alloc-profiling")]
+
+use rslint_core::{lint_file, profile, CstRuleStore};
+
+#[global_allocator]
+static ALLOC: profile::CountingAllocator = profile::CountingAllocator;
+
+#[test]
+fn allocations_of_rules_are_counted() {
+    let store = CstRuleStore::new().builtins();
+    profile::enable();
+    lint_file(0, "if (foo == bar) { var a = 1; }", false, &store, false).unwrap();
+
+    assert!(profile::counts_allocations());
+    let report = profile::report();
+    assert!(report.iter().any(|profile| profile.allocations > 0));
+}
@@ -0,0 +1,95 @@
+use rslint_core::baseline::Baseline;
+use rslint_core::{lint_file, CstRuleStore, Diagnostic};
+use std::fs;
+use std::io::ErrorKind;
+
+fn warning(rule: &str, range: std::ops::Range<usize>) -> Diagnostic {
+    Diagnostic::warning(0, rule, "problem").primary(range, "")
+}
+
+#[test]
+fn baselined_problems_are_counted() {
+    let mut baseline = Baseline::new();
+    let src = "foo; foo; bar;";
+    baseline.add("a.js", src, &[warning("a", 0..3), warning("a", 5..8)]);
+    assert_eq!(baseline.len(), 2);
+
+    // problems are matched by their code, not their position
+    let src = "bar;\nfoo; foo; foo;";
+    let mut diagnostics = vec![
+        warning("a", 5..8),
+        warning("a", 10..13),
+        warning("a", 15..18),
+        warning("b", 5..8),
+        warning("a", 0..3),
+    ];
+    assert_eq!(baseline.filter("a.js", src, &mut diagnostics), 2);
+    assert_eq!(
+        diagnostics,
+        vec![warning("a", 15..18), warning("b", 5..8), warning("a", 0..3)]
+    );
+
+    let mut diagnostics = vec![warning("a", 5..8)];
+    assert_eq!(baseline.filter("b.js", src, &mut diagnostics), 0);
+}
+
+#[test]
+fn only_problems_are_baselined() {
+    let mut baseline = Baseline::new();
+    baseline.add(
+        "a.js",
+        "foo;",
+        &[
+            Diagnostic::note(0, "a", "note").primary(0usize..3, ""),
+            Diagnostic::warning(0, "a", "no primary"),
+            warning("a", 0..3).suppressed_by(0usize..1),
+        ],
+    );
+    assert!(baseline.is_empty());
+}
+
+#[test]
+fn baselined_results() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, "debugger;\n", false, &store, false).unwrap();
+    let mut baseline = Baseline::new();
+    baseline.add("a.js", "debugger;\n", result.diagnostics());
+
+    let mut result = lint_file(0, "debugger;\ndebugger;\n", false, &store, false).unwrap();
+    assert_eq!(result.diagnostics_at(0).len(), 1);
+    assert_eq!(baseline.filter_result("a.js", &mut result), 1);
+    let rules = result
+        .diagnostics()
+        .map(|d| d.code.clone().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(rules, vec!["no-debugger"]);
+    assert!(result.diagnostics_at(0).is_empty());
+}
+
+#[test]
+fn baselines_are_saved_and_loaded() {
+    let dir = std::env::temp_dir().join(format!("rslint-baseline-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let location = dir.join(".rslintbaseline");
+
+    let mut baseline = Baseline::new();
+    baseline.add(
+        "src/a.js",
+        "foo; foo;",
+        &[warning("a", 0..3), warning("a", 5..8)],
+    );
+    baseline.add("b.js", "bar;", &[warning("b", 0..3)]);
+    baseline.save(&location).unwrap();
+    assert_eq!(Baseline::load(&location).unwrap(), baseline);
+
+    fs::write(&location, r#"{ "version": 0, "files": {} }"#).unwrap();
+    assert_eq!(
+        Baseline::load(&location).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    fs::remove_dir_all(dir).unwrap();
+    assert_eq!(
+        Baseline::load(&location).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+}
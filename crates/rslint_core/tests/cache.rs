@@ -0,0 +1,51 @@
+use rslint_core::{cache::LintCache, lint_file, CstRuleStore, LinterOptions};
+
+const SRC: &str = "debugger;\nif (a == b) {}\n";
+
+#[test]
+fn cached_results_survive_a_reload() {
+    let location = std::env::temp_dir().join(format!("rslint-cache-{}", std::process::id()));
+    let store = CstRuleStore::new().builtins();
+    let key = LintCache::key(&store, &LinterOptions::default(), "");
+    let result = lint_file(1, SRC, false, &store, false).unwrap();
+    let diagnostics = result.diagnostics().cloned().collect::<Vec<_>>();
+    assert!(!diagnostics.is_empty());
+
+    let mut cache = LintCache::load(&location);
+    assert!(cache.is_empty());
+    cache.insert("foo.js", SRC, key, diagnostics.clone());
+    cache.save().unwrap();
+
+    let cache = LintCache::load(&location);
+    std::fs::remove_file(&location).unwrap();
+    let cached = cache.get("foo.js", SRC, key, 5).unwrap();
+    assert_eq!(cached.len(), diagnostics.len());
+    assert!(cached.iter().all(|d| d.file_id == 5));
+    assert_eq!(
+        cached.iter().map(|d| &d.title).collect::<Vec<_>>(),
+        diagnostics.iter().map(|d| &d.title).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn changes_invalidate_cached_results() {
+    let store = CstRuleStore::new().builtins();
+    let options = LinterOptions::default();
+    let key = LintCache::key(&store, &options, "");
+
+    let mut cache = LintCache::new("unused");
+    cache.insert("foo.js", SRC, key, vec![]);
+    assert_eq!(cache.get("foo.js", SRC, key, 0), Some(vec![]));
+    assert_eq!(cache.get("foo.js", "debugger;\n", key, 0), None);
+    assert_eq!(cache.get("bar.js", SRC, key, 0), None);
+
+    let mut fewer_rules = store.clone();
+    fewer_rules.rules.pop();
+    assert_ne!(LintCache::key(&fewer_rules, &options, ""), key);
+    let verbose = LinterOptions {
+        verbose: true,
+        ..LinterOptions::default()
+    };
+    assert_ne!(LintCache::key(&store, &verbose, ""), key);
+    assert_ne!(LintCache::key(&store, &options, "levels"), key);
+}
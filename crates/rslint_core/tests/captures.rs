@@ -0,0 +1,38 @@
+use rslint_core::{lint_file, CstRuleStore};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+fn captures(src: &str, rule: &str) -> BTreeMap<String, Range<usize>> {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, src, false, &store, false).unwrap();
+    let diagnostic = result
+        .diagnostics()
+        .find(|diagnostic| diagnostic.code.as_deref() == Some(rule))
+        .unwrap_or_else(|| panic!("{} did not fire", rule));
+    diagnostic.captures.clone()
+}
+
+#[test]
+fn rules_capture_named_nodes() {
+    let src = "foo == bar";
+    let captured = captures(src, "eqeqeq");
+    assert_eq!(&src[captured["left"].clone()], "foo");
+    assert_eq!(&src[captured["operator"].clone()], "==");
+    assert_eq!(&src[captured["right"].clone()], "bar");
+
+    let src = "new Symbol()";
+    assert_eq!(
+        &src[captures(src, "no-new-symbol")["callee"].clone()],
+        "Symbol"
+    );
+
+    let src = "!foo in bar";
+    let captured = captures(src, "no-unsafe-negation");
+    assert_eq!(&src[captured["negation"].clone()], "!foo");
+    assert_eq!(&src[captured["argument"].clone()], "foo");
+}
+
+#[test]
+fn diagnostics_without_captures_are_empty() {
+    assert!(captures("if (a) {}", "no-empty").is_empty());
+}
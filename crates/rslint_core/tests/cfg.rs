@@ -0,0 +1,65 @@
+use rslint_core::util::cfg::{ControlFlowGraph, EdgeKind};
+use rslint_parser::{parse_text, SyntaxNodeExt};
+
+fn graph(src: &str) -> ControlFlowGraph {
+    ControlFlowGraph::new(&parse_text(src, 0).syntax())
+}
+
+fn unreachable(src: &str) -> Vec<String> {
+    graph(src)
+        .unreachable_nodes()
+        .iter()
+        .map(|node| node.trimmed_text().to_string())
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn code_after_jumps_is_unreachable() {
+    assert_eq!(unreachable("a(); return; b(); c();"), vec!["b();", "c();"]);
+    assert_eq!(unreachable("while (a) { continue; b(); }"), vec!["b();"]);
+    assert_eq!(
+        unreachable("if (a) { return; } else { throw b; } c();"),
+        vec!["c();"]
+    );
+    assert!(unreachable("if (a) { return; } b();").is_empty());
+}
+
+#[test]
+fn infinite_loops_only_exit_through_break() {
+    assert_eq!(unreachable("while (true) { a(); } b();"), vec!["b();"]);
+    assert_eq!(unreachable("for (;;) {} b();"), vec!["b();"]);
+    assert!(unreachable("while (true) { if (a) break; } b();").is_empty());
+    assert!(unreachable("outer: for (;;) { for (;;) { break outer; } } b();").is_empty());
+}
+
+#[test]
+fn try_statements() {
+    assert!(unreachable("try { throw a; } catch (e) { b(); } c();").is_empty());
+    assert_eq!(
+        unreachable("try { return; } finally { a(); } b();"),
+        vec!["b();"]
+    );
+    assert_eq!(
+        unreachable("try { a(); } finally { return; } b();"),
+        vec!["b();"]
+    );
+}
+
+#[test]
+fn switch_cases_fall_through() {
+    let graph = graph("switch (a) { case 1: b(); case 2: c(); break; case 3: }");
+    let fallthroughs = graph
+        .edges()
+        .filter(|(from, edge)| edge.kind == EdgeKind::Fallthrough && graph.is_reachable(*from))
+        .map(|(_, edge)| graph.block(edge.to).nodes[0].trimmed_text().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(fallthroughs, vec!["case 2: c(); break;"]);
+}
+
+#[test]
+fn completes_normally() {
+    assert!(graph("a();").completes_normally());
+    assert!(graph("if (a) return;").completes_normally());
+    assert!(!graph("if (a) { return; } else { return; }").completes_normally());
+    assert!(!graph("while (true) {}").completes_normally());
+}
@@ -0,0 +1,38 @@
+use rslint_core::{code_climate::code_climate_issues, lint_file, CstRuleStore};
+use rslint_errors::file::SimpleFile;
+
+#[test]
+fn code_climate_issues_locate_and_fingerprint_problems() {
+    let src = "let a = 5;\n\n{ a;; }\n{ a;; }\n";
+    let file = SimpleFile::new("./src/foo.js".to_string(), src.to_string());
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, src, false, &store, false).unwrap();
+    let issues = code_climate_issues(&[result], &file);
+
+    let semis = issues
+        .iter()
+        .filter(|issue| issue.check_name == "no-extra-semi")
+        .collect::<Vec<_>>();
+    assert_eq!(semis.len(), 2);
+    let issue = semis[0];
+    assert_eq!(issue.ty, "issue");
+    assert_eq!(issue.location.path, "src/foo.js");
+    assert_eq!(issue.severity, "major");
+    assert_eq!(issue.categories, vec!["Bug Risk"]);
+    let begin = &issue.location.positions.begin;
+    assert_eq!((begin.line, begin.column), (3, 5));
+    assert_ne!(semis[0].fingerprint, semis[1].fingerprint);
+
+    // fingerprints do not depend on the line of the problem
+    let moved = format!("\n\n{}", src);
+    let file = SimpleFile::new("./src/foo.js".to_string(), moved.clone());
+    let result = lint_file(0, &moved, false, &store, false).unwrap();
+    let moved_issues = code_climate_issues(&[result], &file);
+    let moved_semis = moved_issues
+        .iter()
+        .filter(|issue| issue.check_name == "no-extra-semi")
+        .collect::<Vec<_>>();
+    assert_eq!(moved_semis[0].location.positions.begin.line, 5);
+    assert_eq!(moved_semis[0].fingerprint, semis[0].fingerprint);
+    assert_eq!(moved_semis[1].fingerprint, semis[1].fingerprint);
+}
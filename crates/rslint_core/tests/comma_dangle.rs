@@ -0,0 +1,42 @@
+use rslint_core::groups::style::CommaDangle;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+use serde_json::{json, Value};
+
+fn fix(options: Value, src: &str) -> String {
+    let rule: CommaDangle = serde_json::from_value(options).unwrap();
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(rule) as Box<dyn CstRule>]);
+    let mut result = lint_file(0, src, true, &store, false).unwrap();
+    result.fix(false).unwrap()
+}
+
+#[test]
+fn trailing_commas_are_removed() {
+    assert_eq!(
+        fix(
+            json!({}),
+            "let a = [1, 2, ];\nfoo(a,);\nimport { b, } from 'b';\n"
+        ),
+        "let a = [1, 2 ];\nfoo(a);\nimport { b } from 'b';\n"
+    );
+}
+
+#[test]
+fn multiline_trailing_commas_are_inserted() {
+    let options = json!({ "arrays": "always-multiline", "objects": "always-multiline" });
+    let src = "let a = [\n    1,\n    2 // two\n];\nlet b = {\n    c: [1, 2,]\n};\n";
+    assert_eq!(
+        fix(options, src),
+        "let a = [\n    1,\n    2, // two\n];\nlet b = {\n    c: [1, 2],\n};\n"
+    );
+}
+
+#[test]
+fn modes_are_per_construct() {
+    let options = json!({ "functions": "only-multiline" });
+    let src = "foo(\n    a,\n);\nlet a = [\n    1,\n];\n";
+    assert_eq!(
+        fix(options, src),
+        "foo(\n    a,\n);\nlet a = [\n    1\n];\n"
+    );
+}
@@ -0,0 +1,84 @@
+use rslint_core::util::const_eval::{eval, truthiness, ConstValue};
+use rslint_parser::{ast::Expr, parse_expr};
+
+fn expr(src: &str) -> Expr {
+    parse_expr(src, 0).tree()
+}
+
+fn value(src: &str) -> Option<ConstValue> {
+    eval(expr(src))
+}
+
+fn string(src: &str) -> Option<ConstValue> {
+    Some(ConstValue::String(src.to_string()))
+}
+
+#[test]
+fn folds_arithmetic() {
+    assert_eq!(value("1 + 2 * 3"), Some(ConstValue::Number(7.0)));
+    assert_eq!(value("2 ** 10 - 24"), Some(ConstValue::Number(1000.0)));
+    assert_eq!(value("-(7 % 4)"), Some(ConstValue::Number(-3.0)));
+    assert_eq!(value("~5 | 1 << 4"), Some(ConstValue::Number(-6.0)));
+    assert_eq!(value("-1 >>> 28"), Some(ConstValue::Number(15.0)));
+    assert_eq!(value("\"6\" / \"2\""), Some(ConstValue::Number(3.0)));
+    assert!(matches!(value("1 - undefined"), Some(ConstValue::Number(n)) if n.is_nan()));
+    assert_eq!(value("1 + foo"), None);
+}
+
+#[test]
+fn folds_strings_and_templates() {
+    assert_eq!(value("\"a\" + \"b\""), string("ab"));
+    assert_eq!(value("\"a\" + 1 + 2"), string("a12"));
+    assert_eq!(value("1 + 2 + \"a\""), string("3a"));
+    assert_eq!(value("\"\" + null + true"), string("nulltrue"));
+    assert_eq!(value("`a${1 + 1}b${\"c\"}`"), string("a2bc"));
+    assert_eq!(value("`${1 / 4}${1e21}${-0}`"), string("0.251e+210"));
+    assert_eq!(value("typeof (() => {})"), string("function"));
+    assert_eq!(value("`${foo}`"), None);
+    assert_eq!(value("tag`a`"), None);
+}
+
+#[test]
+fn folds_comparisons_and_logic() {
+    assert_eq!(value("1 < 2"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("\"b\" > \"a\""), Some(ConstValue::Bool(true)));
+    assert_eq!(value("NaN >= NaN"), Some(ConstValue::Bool(false)));
+    assert_eq!(value("null == undefined"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("null === undefined"), Some(ConstValue::Bool(false)));
+    assert_eq!(value("\"1\" == 1"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("[] === []"), Some(ConstValue::Bool(false)));
+    assert_eq!(value("[] == \"\""), None);
+    assert_eq!(value("[] < 1"), None);
+    assert_eq!(value("1n < \"2\""), None);
+    assert_eq!(value("1n < 2"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("2 > 1n"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("2n <= 1.5"), Some(ConstValue::Bool(false)));
+    assert_eq!(value("2n >= 2"), Some(ConstValue::Bool(true)));
+    assert_eq!(value("1n < Infinity"), Some(ConstValue::Bool(true)));
+    assert_eq!(
+        value("1n < NaN || 1n >= NaN"),
+        Some(ConstValue::Bool(false))
+    );
+    assert_eq!(value("0 || null ?? \"x\""), string("x"));
+    assert_eq!(value("1 && 2"), Some(ConstValue::Number(2.0)));
+    assert_eq!(value("false && foo"), Some(ConstValue::Bool(false)));
+    assert_eq!(
+        value("(x = 5, true ? 1 : 2)"),
+        Some(ConstValue::Number(1.0))
+    );
+}
+
+#[test]
+fn truthiness_of_partially_known_expressions() {
+    assert_eq!(truthiness(expr("foo || 1")), Some(true));
+    assert_eq!(truthiness(expr("foo && 0")), Some(false));
+    assert_eq!(truthiness(expr("typeof foo")), Some(true));
+    assert_eq!(truthiness(expr("!{}")), Some(false));
+    assert_eq!(truthiness(expr("foo + \"a\"")), Some(true));
+    assert_eq!(truthiness(expr("`a${foo}`")), Some(true));
+    assert_eq!(truthiness(expr("\"\\n\"")), Some(true));
+    assert_eq!(truthiness(expr("foo ? 1 : \"a\"")), Some(true));
+    assert_eq!(truthiness(expr("foo ? 1 : 0")), None);
+    assert_eq!(truthiness(expr("foo || 0")), None);
+    assert_eq!(truthiness(expr("foo + bar")), None);
+}
@@ -0,0 +1,41 @@
+use rslint_core::coverage::{CorpusCoverage, FixtureCount};
+use rslint_core::{lint_file, CstRuleStore};
+use std::collections::HashMap;
+
+#[test]
+fn report_counts_rules_over_corpus() {
+    let store = CstRuleStore::new().builtins();
+    let mut coverage = CorpusCoverage::new(&store);
+    for (id, src) in ["debugger;\ndebugger;", "if (a) {}\ndebugger;", "let a = 5;"]
+        .iter()
+        .enumerate()
+    {
+        coverage.add(&lint_file(id, src, false, &store, false).unwrap());
+    }
+
+    let mut fixtures = HashMap::new();
+    fixtures.insert(
+        "no-debugger".to_string(),
+        FixtureCount {
+            invalid: 2,
+            valid: 1,
+        },
+    );
+    let report = coverage.report(&fixtures);
+
+    assert_eq!(report.files, 3);
+    assert_eq!(report.rules.len(), store.rules.len());
+    let top = &report.rules[0];
+    assert_eq!(
+        (top.name, top.diagnostics, top.files),
+        ("no-debugger", 3, 2)
+    );
+    assert_eq!(top.fixtures.unwrap().invalid, 2);
+    assert_eq!(report.rules[1].name, "no-empty");
+
+    assert!(!report.never_fired.contains(&"no-empty"));
+    assert!(report.never_fired.contains(&"for-direction"));
+    assert!(!report.untested.contains(&"no-debugger"));
+    assert!(report.untested.contains(&"no-empty"));
+    assert!(coverage.report(&HashMap::new()).untested.is_empty());
+}
@@ -0,0 +1,117 @@
+use rslint_core::{
+    daemon::{read_message, write_message, Daemon, Request, Response, MAX_MESSAGE_LEN},
+    CstRuleStore, LinterOptions,
+};
+use std::io::{self, Cursor, Read, Write};
+
+/// A stream which reads from a buffer of requests and writes responses to another buffer.
+struct Stream {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn serve(daemon: &mut Daemon, requests: &[Request]) -> (bool, Vec<Response>) {
+    let mut input = vec![];
+    for request in requests {
+        write_message(&mut input, request).unwrap();
+    }
+    let mut stream = Stream {
+        input: Cursor::new(input),
+        output: vec![],
+    };
+    let shutdown = daemon.serve(&mut stream).unwrap();
+
+    let mut output = Cursor::new(stream.output);
+    let mut responses = vec![];
+    while let Some(response) = read_message(&mut output).unwrap() {
+        responses.push(response);
+    }
+    (shutdown, responses)
+}
+
+#[test]
+fn daemon_serves_lint_requests() {
+    let mut daemon = Daemon::new(CstRuleStore::new().builtins(), LinterOptions::default());
+    let (shutdown, responses) = serve(
+        &mut daemon,
+        &[Request::Lint {
+            path: "foo.js".to_string(),
+            source: "debugger;".to_string(),
+            fix: false,
+        }],
+    );
+
+    assert!(!shutdown);
+    match &responses[..] {
+        [Response::Lint { diagnostics, fixed }] => {
+            assert_eq!(fixed, &None);
+            let diagnostics = diagnostics["diagnostics"].as_array().unwrap();
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0]["code"], "no-debugger");
+            assert_eq!(diagnostics[0]["file"], "foo.js");
+        }
+        _ => panic!("unexpected responses {:?}", responses),
+    }
+}
+
+#[test]
+fn daemon_reloads_config_and_shuts_down() {
+    let mut daemon = Daemon::new(CstRuleStore::new().builtins(), LinterOptions::default())
+        .on_reload(|| Ok(CstRuleStore::new()));
+    let lint = Request::Lint {
+        path: "foo.js".to_string(),
+        source: "debugger;".to_string(),
+        fix: false,
+    };
+    let (shutdown, responses) = serve(
+        &mut daemon,
+        &[Request::ReloadConfig, lint.clone(), Request::Shutdown, lint],
+    );
+
+    assert!(shutdown);
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0], Response::ConfigReloaded);
+    match &responses[1] {
+        Response::Lint { diagnostics, .. } => {
+            assert!(diagnostics["diagnostics"].as_array().unwrap().is_empty())
+        }
+        response => panic!("unexpected response {:?}", response),
+    }
+    assert_eq!(responses[2], Response::ShuttingDown);
+}
+
+#[test]
+fn oversized_messages_are_skipped() {
+    let len = MAX_MESSAGE_LEN + 1;
+    let mut input = len.to_be_bytes().to_vec();
+    input.resize(4 + len as usize, b' ');
+    write_message(&mut input, &Request::ReloadConfig).unwrap();
+    let mut stream = Stream {
+        input: Cursor::new(input),
+        output: vec![],
+    };
+    let mut daemon = Daemon::new(CstRuleStore::new(), LinterOptions::default());
+    assert!(!daemon.serve(&mut stream).unwrap());
+
+    let mut output = Cursor::new(stream.output);
+    let error = read_message::<Response>(&mut output).unwrap().unwrap();
+    assert!(matches!(error, Response::Error { message } if message.contains("maximum length")));
+    assert!(read_message::<Response>(&mut output).unwrap().is_some());
+    assert!(read_message::<Response>(&mut output).unwrap().is_none());
+}
@@ -0,0 +1,54 @@
+use rslint_core::{
+    degraded::{DeclarationKind, TopLevelDeclaration},
+    lint_file, CstRuleStore,
+};
+
+const BROKEN: &str = "function foo( { = = = ; }}}} ))) class { var\nconst a = 5, b = [1, 2];\nfunction* gen() {}\nclass Foo {}\n";
+
+#[test]
+fn catastrophic_files_are_linted_in_degraded_mode() {
+    let store = CstRuleStore::new().builtins();
+    let src = format!("{}debugger;\u{000B}\n", BROKEN);
+    let result = lint_file(0, &src, false, &store, false).unwrap();
+
+    assert!(result.is_degraded());
+    assert!(result
+        .parser_diagnostics
+        .iter()
+        .any(|d| d.code.as_deref() == Some("degraded")));
+    // only rules which tolerate parse errors are run
+    assert!(result.rule_results.contains_key("no-irregular-whitespace"));
+    assert!(!result.rule_results.contains_key("no-debugger"));
+    assert_eq!(
+        result.rule_results["no-irregular-whitespace"]
+            .diagnostics
+            .len(),
+        1
+    );
+
+    let declarations = result
+        .degraded_declarations
+        .unwrap()
+        .into_iter()
+        .map(|TopLevelDeclaration { kind, name, .. }| (kind, name))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        declarations,
+        vec![
+            (DeclarationKind::Function, "foo".to_string()),
+            (DeclarationKind::Const, "a".to_string()),
+            (DeclarationKind::Const, "b".to_string()),
+            (DeclarationKind::Function, "gen".to_string()),
+            (DeclarationKind::Class, "Foo".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn files_with_few_errors_are_linted_normally() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, "let a = ;\ndebugger;\n", false, &store, false).unwrap();
+
+    assert!(!result.is_degraded());
+    assert!(result.rule_results.contains_key("no-debugger"));
+}
@@ -0,0 +1,106 @@
+use rslint_core::determinism::RunFingerprint;
+use rslint_core::{
+    lint_deterministic, lint_file_with_options, CstRule, CstRuleStore, LinterOptions, Rule, RuleCtx,
+};
+use rslint_parser::SyntaxNode;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const SOURCE: &str = "\
+debugger;
+if (a) {}
+if (!!b) {}
+// rslint-ignore no-empty
+while (c) {}
+// rslint-disable-next-line no-debugger
+foo(); debugger;
+let x = typeof y === 'strng';
+";
+
+fn options() -> LinterOptions {
+    LinterOptions {
+        record_suppressed: true,
+        ..LinterOptions::default()
+    }
+}
+
+#[test]
+fn builtins_are_deterministic() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_deterministic(0, SOURCE, false, &store, options(), 8).unwrap();
+    assert!(result.diagnostics().count() > 0);
+    assert!(result.suppressed_diagnostics().count() > 0);
+}
+
+#[test]
+fn fingerprints_name_the_differing_output() {
+    let store = CstRuleStore::new().builtins();
+    let lint = |src: &str| {
+        let result = lint_file_with_options(0, src, false, &store, options()).unwrap();
+        RunFingerprint::of(&result)
+    };
+
+    let first = lint(SOURCE);
+    assert_eq!(first, lint(SOURCE));
+    assert!(first.differences(&lint(SOURCE)).is_empty());
+
+    // fixing the double negation gives the same code without its diagnostic
+    let fixed = lint(&SOURCE.replace("!!b", "b"));
+    assert_eq!(fixed.differences(&first), vec!["diagnostics", "stats"]);
+    // code without diagnostics only differs in its fixed code
+    assert_eq!(
+        lint("foo();").differences(&lint("bar();")),
+        vec!["fixed code"]
+    );
+}
+
+#[test]
+fn thread_count_does_not_change_results() {
+    let store = CstRuleStore::new().builtins();
+    let lint = |threads| {
+        let options = LinterOptions {
+            threads,
+            ..options()
+        };
+        let result = lint_file_with_options(0, SOURCE, false, &store, options).unwrap();
+        RunFingerprint::of(&result)
+    };
+
+    let pooled = lint(None);
+    for threads in [Some(1), Some(2), Some(64)].iter().copied() {
+        assert!(lint(threads).differences(&pooled).is_empty());
+    }
+}
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// A rule which reports a different diagnostic on each run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CountsRuns;
+
+impl Rule for CountsRuns {
+    fn name(&self) -> &'static str {
+        "counts-runs"
+    }
+
+    fn group(&self) -> &'static str {
+        "test"
+    }
+}
+
+#[typetag::serde]
+impl CstRule for CountsRuns {
+    fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let run = RUNS.fetch_add(1, Ordering::SeqCst);
+        ctx.diagnostic(root, format!("run {}", run)).emit();
+        None
+    }
+}
+
+#[test]
+#[should_panic(expected = "differs from the first run in its diagnostics")]
+fn nondeterministic_rules_panic() {
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(CountsRuns) as Box<dyn CstRule>]);
+    let _ = lint_deterministic(0, "foo();", false, &store, LinterOptions::default(), 2);
+}
@@ -0,0 +1,48 @@
+use rslint_core::{lint_file, CstRuleStore, Diagnostic};
+use std::ops::Range;
+
+fn brute_force<'a>(
+    diagnostics: impl Iterator<Item = &'a Diagnostic>,
+    range: &Range<usize>,
+) -> usize {
+    diagnostics
+        .filter(|d| {
+            d.primary
+                .iter()
+                .map(|p| &p.span)
+                .chain(d.suggestions.iter().map(|s| &s.span))
+                .any(|span| span.range.start <= range.end && span.range.end >= range.start)
+        })
+        .count()
+}
+
+#[test]
+fn lookups_match_a_linear_scan() {
+    let src = "debugger;\nif (a == b) {}\nvar x = 5;\n{}\nnew Symbol();\n".repeat(40);
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, &src, false, &store, false).unwrap();
+    assert!(result.diagnostics().count() > 100);
+
+    for offset in (0..src.len()).step_by(7) {
+        let range = offset..offset;
+        let found = result.diagnostics_at(offset);
+        assert_eq!(
+            found.len(),
+            brute_force(result.diagnostics(), &range),
+            "{}",
+            offset
+        );
+        let range = offset..offset + 20;
+        assert_eq!(
+            result.diagnostics_in(range.clone()).len(),
+            brute_force(result.diagnostics(), &range),
+        );
+    }
+
+    let starts = result
+        .diagnostics_in(0..src.len())
+        .iter()
+        .filter_map(|d| Some(d.primary.as_ref()?.span.range.start))
+        .collect::<Vec<_>>();
+    assert!(starts.windows(2).all(|w| w[0] <= w[1]));
+}
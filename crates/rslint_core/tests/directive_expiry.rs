@@ -0,0 +1,65 @@
+use rslint_core::{directives::Date, lint_file, CstRuleStore};
+
+fn lint<'s>(src: &str, store: &'s CstRuleStore) -> rslint_core::LintResult<'s> {
+    lint_file(0, src, false, store, false).unwrap()
+}
+
+#[test]
+fn expired_directives_do_not_ignore_rules() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint(
+        "// rslint-ignore no-empty -- until:2000-01-01\nif (foo) {}\n",
+        &store,
+    );
+
+    assert_eq!(res.rule_results["no-empty"].diagnostics.len(), 1);
+    assert_eq!(res.directive_diagnostics.len(), 1);
+    assert_eq!(
+        res.directive_diagnostics[0].title,
+        "suppression expired on 2000-01-01"
+    );
+}
+
+#[test]
+fn unexpired_directives_ignore_rules() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint(
+        "// rslint-ignore no-empty -- until:9999-12-31 waiting on a refactor\nif (foo) {}\n",
+        &store,
+    );
+
+    assert!(!res.rule_results.contains_key("no-empty"));
+    assert!(res.directive_diagnostics.is_empty());
+}
+
+#[test]
+fn invalid_expiry_dates_are_reported() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint(
+        "if (foo) {\n  // rslint-ignore -- until:2020-13-01\n  {}\n}\n",
+        &store,
+    );
+
+    assert_eq!(res.directive_diagnostics.len(), 1);
+    assert_eq!(
+        res.directive_diagnostics[0].title,
+        "invalid directive expiry date"
+    );
+    let range = res.directive_diagnostics[0]
+        .primary
+        .as_ref()
+        .unwrap()
+        .span
+        .range
+        .clone();
+    assert_eq!(&res.parsed.to_string()[range], "until:2020-13-01");
+}
+
+#[test]
+fn dates_are_parsed_and_compared() {
+    let date = Date::parse("2021-02-03").unwrap();
+    assert_eq!(date.to_string(), "2021-02-03");
+    assert!(date < Date::parse("2021-10-01").unwrap());
+    assert!(Date::parse("2021-2-3").is_none());
+    assert!(Date::today() > date);
+}
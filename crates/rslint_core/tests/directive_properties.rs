@@ -0,0 +1,178 @@
+//! Property tests for directives, random code is linted with random directives to make sure a diagnostic
+//! is thrown out exactly when a directive covering it exists.
+
+use quickcheck::{Arbitrary, Gen};
+use quickcheck_macros::quickcheck;
+use rslint_core::directives::{skip_node, LazyDirectives};
+use rslint_core::{get_rule_by_name, lint_file, CstRuleStore, Diagnostic};
+use std::fmt;
+
+const RULES: [&str; 2] = ["no-debugger", "no-empty"];
+
+const STATEMENTS: [&str; 7] = [
+    "debugger;",
+    "{}",
+    "{ debugger; }",
+    "if (a) { debugger; } else {}",
+    "function foo() {\n    debugger;\n}",
+    "while (a) {\n    {}\n}",
+    "foo();",
+];
+
+const DIRECTIVES: [&str; 12] = [
+    "// rslint-ignore",
+    "// rslint-ignore no-debugger",
+    "// rslint-ignore no-empty",
+    "// rslint-ignore no-debugger, no-empty",
+    "/* rslint-ignore no-debugger */",
+    "// rslint-disable-next-line",
+    "// rslint-disable-next-line no-empty",
+    "// rslint-disable",
+    "// rslint-disable no-debugger",
+    "// rslint-enable",
+    "// rslint-enable no-debugger",
+    "// rslint-ignore no-empty -- because",
+];
+
+/// A line of a random program, a statement optionally preceded by a directive.
+#[derive(Debug, Clone)]
+struct Line {
+    directive: Option<usize>,
+    blank: bool,
+    stmt: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Program(Vec<Line>);
+
+/// A random number below `n`.
+fn pick<G: Gen>(g: &mut G, n: usize) -> usize {
+    u32::arbitrary(g) as usize % n
+}
+
+impl Arbitrary for Program {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let len = 1 + pick(g, 7);
+        Program(
+            (0..len)
+                .map(|_| Line {
+                    directive: if pick(g, 5) < 3 {
+                        Some(pick(g, DIRECTIVES.len()))
+                    } else {
+                        None
+                    },
+                    blank: pick(g, 5) == 0,
+                    stmt: pick(g, STATEMENTS.len()),
+                })
+                .collect(),
+        )
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            self.0
+                .shrink()
+                .filter(|lines| !lines.is_empty())
+                .map(Program),
+        )
+    }
+}
+
+impl Arbitrary for Line {
+    fn arbitrary<G: Gen>(_: &mut G) -> Self {
+        unreachable!("lines are generated by `Program`")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let mut smaller = vec![];
+        if self.directive.is_some() {
+            smaller.push(Line {
+                directive: None,
+                ..self.clone()
+            });
+        }
+        if self.blank {
+            smaller.push(Line {
+                blank: false,
+                ..self.clone()
+            });
+        }
+        Box::new(smaller.into_iter())
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.0 {
+            if let Some(directive) = line.directive {
+                writeln!(f, "{}", DIRECTIVES[directive])?;
+            }
+            if line.blank {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", STATEMENTS[line.stmt])?;
+        }
+        Ok(())
+    }
+}
+
+fn store() -> CstRuleStore {
+    let mut store = CstRuleStore::new();
+    store.load_rules(RULES.iter().map(|name| get_rule_by_name(name).unwrap()));
+    store
+}
+
+/// The diagnostics of every rule, by rule.
+fn rule_diagnostics(src: &str, store: &CstRuleStore) -> Vec<(&'static str, Diagnostic)> {
+    lint_file(0, src, false, store, false)
+        .unwrap()
+        .rule_results
+        .into_iter()
+        .flat_map(|(name, result)| result.diagnostics.into_iter().map(move |d| (name, d)))
+        .collect()
+}
+
+#[quickcheck]
+fn diagnostics_are_suppressed_iff_a_directive_covers_them(program: Program) -> bool {
+    let src = program.to_string();
+    // the same code with comments which are not directives, so every diagnostic is emitted
+    let unsuppressed = src.replace("rslint-", "tslint-");
+    let store = store();
+
+    let root = lint_file(0, &src, false, &store, false).unwrap().parsed;
+    let directives = LazyDirectives::new(root.clone(), 0, &store)
+        .into_inner()
+        .unwrap();
+    let suppressed = rule_diagnostics(&src, &store);
+
+    for (rule, diagnostic) in rule_diagnostics(&unsuppressed, &store) {
+        let covered = directives.suppression(&root, rule, &diagnostic).is_some();
+        let emitted = suppressed.contains(&(rule, diagnostic.clone()));
+        if covered == emitted {
+            eprintln!(
+                "`{}` diagnostic at {:?} was emitted: {}, is covered: {}\n{}",
+                rule, diagnostic.primary, emitted, covered, src
+            );
+            return false;
+        }
+    }
+    // directives never add rule diagnostics
+    suppressed.len() <= rule_diagnostics(&unsuppressed, &store).len()
+}
+
+#[quickcheck]
+fn index_agrees_with_skip_node(program: Program) -> bool {
+    let src = program.to_string();
+    let store = store();
+    let root = lint_file(0, &src, false, &store, false).unwrap().parsed;
+    let directives = LazyDirectives::new(root.clone(), 0, &store)
+        .into_inner()
+        .unwrap();
+
+    root.descendants().all(|node| {
+        store.rules.iter().all(|rule| {
+            skip_node(&directives.directives, &node, &**rule)
+                == directives.index.suppressed(&node, rule.name())
+        })
+    })
+}
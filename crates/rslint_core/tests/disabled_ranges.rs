@@ -0,0 +1,119 @@
+use rslint_core::{get_rule_by_name, lint_file, CstRuleStore, LintResult};
+
+fn store() -> CstRuleStore {
+    let mut store = CstRuleStore::new();
+    store.load_rules(
+        ["no-empty", "no-debugger"]
+            .iter()
+            .map(|name| get_rule_by_name(name).unwrap()),
+    );
+    store
+}
+
+fn lint<'a>(src: &str, store: &'a CstRuleStore) -> LintResult<'a> {
+    lint_file(0, src, false, store, false).unwrap()
+}
+
+/// The rule and line of every rule diagnostic.
+fn problems(src: &str) -> Vec<(&'static str, usize)> {
+    let store = store();
+    let res = lint(src, &store);
+    let mut problems = res
+        .rule_results
+        .iter()
+        .flat_map(|(name, result)| {
+            result.diagnostics.iter().map(move |d| {
+                let start = d.primary.as_ref().unwrap().span.range.start;
+                (name, src[..start].matches('\n').count() + 1)
+            })
+        })
+        .collect::<Vec<_>>();
+    problems.sort_unstable();
+    problems
+}
+
+#[test]
+fn disable_next_line() {
+    let src = "foo;\n// rslint-disable-next-line\n{} debugger;\n{}\n";
+    assert_eq!(problems(src), vec![("no-empty", 4)]);
+}
+
+#[test]
+fn disable_next_line_for_rules() {
+    let src = "foo;\n// rslint-disable-next-line no-empty\n{} debugger;\n{}\n";
+    assert_eq!(problems(src), vec![("no-debugger", 3), ("no-empty", 4)]);
+}
+
+#[test]
+fn disable_and_enable_region() {
+    let src = "
+foo;
+/* rslint-disable no-empty */
+if (a) {}
+if (b) {}
+debugger;
+/* rslint-enable no-empty */
+if (c) {}
+";
+    assert_eq!(problems(src), vec![("no-debugger", 6), ("no-empty", 8)]);
+}
+
+#[test]
+fn disable_without_enable_lasts_until_the_end() {
+    let src = "foo;\n// rslint-disable\n{}\ndebugger;\n";
+    assert!(problems(src).is_empty());
+}
+
+#[test]
+fn enable_before_closing_brace() {
+    let src = "
+foo;
+// rslint-disable
+function f() {
+    {}
+    // rslint-enable
+}
+{}
+";
+    assert_eq!(problems(src), vec![("no-empty", 8)]);
+}
+
+#[test]
+fn enable_without_disable_warns() {
+    let store = store();
+    let res = lint("foo;\n// rslint-enable no-empty\nif (a) {}\n", &store);
+    assert_eq!(res.directive_diagnostics.len(), 1);
+    assert_eq!(
+        res.directive_diagnostics[0].title,
+        "`no-empty` is not disabled by a previous `rslint-disable`"
+    );
+    assert_eq!(res.rule_results["no-empty"].diagnostics.len(), 1);
+}
+
+#[test]
+fn disabled_fixes_are_not_applied() {
+    let store = CstRuleStore::new().builtins();
+    let src = "foo;\n// rslint-disable-next-line\nlet a = 5;\nlet b = 5;\n";
+    let mut res = lint(src, &store);
+    assert_eq!(
+        res.fix(false).unwrap(),
+        "foo;\n// rslint-disable-next-line\nlet a = 5;\nconst b = 5;\n"
+    );
+}
+
+#[test]
+fn misspelled_command() {
+    let store = store();
+    let err = lint_file(
+        0,
+        "foo;\n// rslint-disable-next-lin\n{}\n",
+        false,
+        &store,
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.code.as_deref(),
+        Some("unknown directive command `disable-next-lin`")
+    );
+}
@@ -0,0 +1,71 @@
+use rslint_core::rule_prelude::*;
+use rslint_core::{lint_file_with_options, CstRuleStore, FileKind, LinterOptions};
+
+declare_lint! {
+    #[derive(Default)]
+    ModulesOnly,
+    test_plugin,
+    "test-plugin/modules-only"
+}
+
+#[typetag::serde]
+impl CstRule for ModulesOnly {
+    fn check_root(&self, root: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        let err = ctx.err(self.name(), "linted a module");
+        ctx.add_err(err.primary(root.text_range(), ""));
+        None
+    }
+
+    fn applies_to(&self, kind: FileKind) -> bool {
+        kind == FileKind::Module
+    }
+}
+
+fn store() -> CstRuleStore {
+    let mut store = CstRuleStore::new().builtins();
+    store.load_rules(vec![Box::new(ModulesOnly::default()) as Box<dyn CstRule>]);
+    store
+}
+
+fn disabled_rules_note(src: &str, module: bool, report: bool) -> Option<Diagnostic> {
+    let store = store();
+    let options = LinterOptions {
+        report_disabled_rules: report,
+        ..LinterOptions::default()
+    };
+    let result = lint_file_with_options(0, src, module, &store, options).unwrap();
+    assert_eq!(
+        result.rule_results.contains_key("test-plugin/modules-only"),
+        module
+    );
+    result
+        .parser_diagnostics
+        .into_iter()
+        .find(|d| d.code.as_deref() == Some("disabled-rules"))
+}
+
+#[test]
+fn rules_are_not_run_on_files_they_do_not_apply_to() {
+    assert!(disabled_rules_note("foo;", false, false).is_none());
+    assert!(disabled_rules_note("foo;", true, true).is_none());
+
+    let note = disabled_rules_note("foo;", false, true).unwrap();
+    assert_eq!(note.severity, Severity::Note);
+    assert_eq!(note.title, "1 rule was not run on this file");
+    assert_eq!(
+        note.footers[0].msg,
+        "`test-plugin/modules-only` does not apply to scripts"
+    );
+}
+
+#[test]
+fn rules_disabled_by_degraded_mode_are_listed() {
+    let src = "function foo( { = = = ; }}}} ))) class { var\nconst a = 5, b = [1, 2];\nfunction* gen() {}\nclass Foo {}\n";
+    let note = disabled_rules_note(src, false, true).unwrap();
+    assert!(note.footers.len() > 1);
+    assert!(note
+        .footers
+        .iter()
+        .any(|footer| footer.msg
+            == "`no-debugger` does not run on files with too many syntax errors"));
+}
@@ -0,0 +1,70 @@
+use rslint_core::{docgen::generate, CstRuleStore};
+use serde_json::json;
+
+#[test]
+fn generated_pages_include_options_and_examples() {
+    let pages = generate(&CstRuleStore::new().builtins());
+    let page = pages
+        .iter()
+        .find(|page| page.name == "prefer-const")
+        .unwrap();
+
+    assert_eq!(page.group, "style");
+    assert_eq!(page.path.to_str(), Some("rules/style/prefer-const.md"));
+    let option = page
+        .options
+        .iter()
+        .find(|option| option.name == "splitDeclarations")
+        .unwrap();
+    assert_eq!(option.ty, "boolean");
+    assert_eq!(option.default, json!(false));
+
+    assert_eq!(page.invalid_examples[0], "let a = 5;\nconsole.log(a);");
+    assert!(!page.valid_examples.is_empty());
+    assert!(page.markdown.starts_with("<!--"));
+    assert!(page
+        .markdown
+        .contains("| `splitDeclarations` | boolean | `false` |"));
+    assert!(!page.markdown.contains("```ignore"));
+}
+
+#[test]
+fn examples_of_block_comment_docs_are_unindented() {
+    let page = generate(&CstRuleStore::new().builtins())
+        .into_iter()
+        .find(|page| page.name == "no-empty")
+        .unwrap();
+    assert_eq!(page.invalid_examples, vec!["{}", "if (foo) {\n\n}"]);
+    assert_eq!(page.valid_examples, vec!["if (foo) {\n    /* todo */\n}"]);
+}
+
+#[test]
+fn rules_without_options_have_no_config_table() {
+    let page = generate(&CstRuleStore::new().builtins())
+        .into_iter()
+        .find(|page| page.name == "no-debugger")
+        .unwrap();
+    assert!(page.options.is_empty());
+    assert!(!page.markdown.contains("## Config"));
+}
+
+#[test]
+fn option_descriptions_add_a_column() {
+    let mut page = generate(&CstRuleStore::new().builtins())
+        .into_iter()
+        .find(|page| page.name == "prefer-const")
+        .unwrap();
+    let option = page
+        .options
+        .iter_mut()
+        .find(|option| option.name == "splitDeclarations")
+        .unwrap();
+    option.description = Some("Split\ndeclarations.".to_string());
+
+    let markdown = page.render();
+    assert!(markdown.contains("| Name | Type | Default | Description |"));
+    assert!(
+        markdown.contains("| `splitDeclarations` | boolean | `false` | Split<br>declarations. |")
+    );
+    assert!(markdown.contains("| `destructuring` | string | `\"any\"` |  |"));
+}
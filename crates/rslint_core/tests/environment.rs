@@ -0,0 +1,109 @@
+use rslint_core::environment::{Environment, PackageType, PackageTypes};
+use rslint_core::util::globals::Globals;
+use rslint_core::{lint_stdin, CstRuleStore, FileKind, LinterOptions};
+use std::fs;
+
+#[test]
+fn kind_from_extension_and_package() {
+    let kind = |path, package| Environment::resolve(path, "", package).kind;
+    assert_eq!(kind("a.mjs", None), FileKind::Module);
+    assert_eq!(kind("a.cjs", Some(PackageType::Module)), FileKind::Script);
+    assert_eq!(kind("a.js", None), FileKind::Script);
+    assert_eq!(kind("a.js", Some(PackageType::CommonJs)), FileKind::Script);
+    assert_eq!(kind("a.js", Some(PackageType::Module)), FileKind::Module);
+}
+
+#[test]
+fn node_from_extension_and_shebang() {
+    let node = |path, src| Environment::resolve(path, src, None).node;
+    assert!(node("a.mjs", ""));
+    assert!(node("a.cjs", ""));
+    assert!(node("a.js", "#!/usr/bin/env node\nfoo();"));
+    assert!(node("a.js", "#!/usr/local/bin/node"));
+    assert!(!node("a.js", "#!/usr/bin/env deno\nfoo();"));
+    assert!(!node("a.js", "foo(); // node"));
+}
+
+#[test]
+fn environment_globals() {
+    let mut base = Globals::new();
+    base.extend(["window"].iter()).add("require", true);
+
+    let script = Environment::resolve("a.cjs", "", None).globals(&base);
+    assert!(script.is_readonly("process"));
+    assert!(script.is_writable("require"));
+    assert!(script.is_readonly("window"));
+
+    let module = Environment::resolve("a.mjs", "", None).globals(&base);
+    assert!(module.is_readonly("process"));
+    assert!(!module.contains("require"));
+    assert!(!module.contains("__dirname"));
+
+    let browser = Environment::resolve("a.js", "", None).globals(&base);
+    assert_eq!(browser, base);
+}
+
+#[test]
+fn package_type_from_manifest() {
+    assert_eq!(
+        PackageType::from_manifest(r#"{ "type": "module" }"#),
+        Some(PackageType::Module)
+    );
+    assert_eq!(
+        PackageType::from_manifest(r#"{ "type": "commonjs" }"#),
+        Some(PackageType::CommonJs)
+    );
+    assert_eq!(
+        PackageType::from_manifest(r#"{ "name": "a" }"#),
+        Some(PackageType::CommonJs)
+    );
+    assert_eq!(PackageType::from_manifest("[]"), None);
+}
+
+#[test]
+fn closest_package() {
+    let root = std::env::temp_dir().join(format!("rslint-env-{}", std::process::id()));
+    let nested = root.join("packages").join("esm");
+    fs::create_dir_all(nested.join("src")).unwrap();
+    fs::write(root.join("package.json"), r#"{ "name": "root" }"#).unwrap();
+    fs::write(nested.join("package.json"), r#"{ "type": "module" }"#).unwrap();
+
+    let mut packages = PackageTypes::default();
+    assert_eq!(
+        packages.of(root.join("index.js")),
+        Some(PackageType::CommonJs)
+    );
+    assert_eq!(
+        packages.of(nested.join("src").join("index.js")),
+        Some(PackageType::Module)
+    );
+    assert_eq!(
+        packages.of(root.join("packages").join("index.js")),
+        Some(PackageType::CommonJs)
+    );
+
+    // `import` is a syntax error in scripts, and `require` is not a global of modules
+    let store = CstRuleStore::new().builtins();
+    let src = "import a from 'a';\nrequire = a;\n";
+    let path = nested.join("src").join("index.js");
+    let result = lint_stdin(src.as_bytes(), &path, &store, LinterOptions::default()).unwrap();
+    assert_eq!(FileKind::of(&result.parsed), FileKind::Module);
+    assert!(result.parser_diagnostics.is_empty());
+    assert!(result
+        .for_rule("no-global-assign")
+        .unwrap()
+        .diagnostics
+        .is_empty());
+
+    let result = lint_stdin(
+        src.as_bytes(),
+        root.join("index.js"),
+        &store,
+        LinterOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(FileKind::of(&result.parsed), FileKind::Script);
+    assert!(!result.parser_diagnostics.is_empty());
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,46 @@
+use rslint_core::util::expr_eq::{expr_eq, ExprKey};
+use rslint_parser::{parse_expr, AstNode, SyntaxNode};
+use std::collections::HashSet;
+
+fn expr(src: &str) -> SyntaxNode {
+    parse_expr(src, 0).tree().syntax().clone()
+}
+
+fn eq(left: &str, right: &str) -> bool {
+    expr_eq(&expr(left), &expr(right))
+}
+
+#[test]
+fn ignores_whitespace_comments_and_parentheses() {
+    assert!(eq("a.b", "a . b"));
+    assert!(eq("foo(a, b)", "foo( a /* first */, b )"));
+    assert!(eq("(a)", "a"));
+    assert!(eq("((a + b))", "a + b"));
+    assert!(eq("(a) + (b)", "a + b"));
+    assert!(eq("(foo)()", "foo()"));
+}
+
+#[test]
+fn ignores_string_quotes() {
+    assert!(eq("'a'", "\"a\""));
+    assert!(eq("foo['bar']", "foo[\"bar\"]"));
+    assert!(!eq("'a'", "'b'"));
+}
+
+#[test]
+fn keeps_meaningful_structure() {
+    assert!(!eq("(a + b) * c", "a + b * c"));
+    assert!(!eq("a - b", "b - a"));
+    assert!(!eq("a === b", "a == b"));
+    assert!(!eq("a.b", "a[b]"));
+    assert!(!eq("foo(a)", "foo(a, b)"));
+}
+
+#[test]
+fn keys_hash_like_they_compare() {
+    let set = ["a + b", "(a) + b", "a + (b)", "'x'", "\"x\"", "a - b"]
+        .iter()
+        .map(|src| ExprKey(expr(src)))
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 3);
+}
@@ -0,0 +1,133 @@
+use rslint_core::{
+    extract::{extractor_for_path, front_matter, Extractor, Html, Markdown, Vue},
+    project::ProjectLinter,
+    CstRuleStore, FileKind,
+};
+
+const COMPONENT: &str = r#"<template>
+  <div :class="cls">{{ debugger }}</div>
+  <template v-if="a"><span>{{ a ? b : c }}</span></template>
+</template>
+
+<script>
+import Child from "./Child.vue";
+export default { components: { Child } };
+</script>
+
+<script setup lang="js">
+debugger;
+</script>
+
+<style>
+a { color: red; }
+</style>
+"#;
+
+#[test]
+fn vue_extracts_scripts_and_template_expressions() {
+    let files = Vue.extract(COMPONENT);
+    let parts = files
+        .iter()
+        .map(|file| (file.part, file.kind, file.source.trim()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        parts,
+        vec![
+            ("template", FileKind::Script, "debugger"),
+            ("template", FileKind::Script, "a ? b : c"),
+            (
+                "script",
+                FileKind::Module,
+                "import Child from \"./Child.vue\";\nexport default { components: { Child } };"
+            ),
+            ("script setup", FileKind::Module, "debugger;"),
+        ]
+    );
+    for file in &files {
+        assert_eq!(
+            &COMPONENT[file.offset..file.offset + file.source.len()],
+            file.source
+        );
+    }
+}
+
+#[test]
+fn html_skips_front_matter_and_non_javascript_scripts() {
+    let src = "---\ntitle: <script>front</script>\n---\n<!-- <script>commented</script> -->\n\
+               <script type=\"module\">a</script><script src=\"b.js\"></script>\
+               <script type=\"text/template\">c</script><SCRIPT>d</SCRIPT>";
+    assert_eq!(front_matter(src), Some(0..38));
+    let files = Html.extract(src);
+    let sources = files
+        .iter()
+        .map(|file| (file.source.as_str(), file.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![("a", FileKind::Module), ("d", FileKind::Script)]
+    );
+    assert!(front_matter("---\nunterminated").is_none());
+}
+
+#[test]
+fn markdown_extracts_javascript_code_blocks() {
+    let src = "---\ntitle: a\n---\n# Example\n\n```js\nimport a from \"a\";\n```\n\n  ~~~~cjs title\nrequire(\"a\");\n~~~\n~~~~\n\n```ts\nlet a: number;\n```\n\n    ```js\n    indented;\n\n````JavaScript\n```\nnested;\n````\n\n```mjs\nunclosed;\n";
+    let files = Markdown.extract(src);
+    let parts = files
+        .iter()
+        .map(|file| (file.part, file.kind, file.source.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        parts,
+        vec![
+            ("code block", FileKind::Module, "import a from \"a\";\n"),
+            ("code block", FileKind::Script, "require(\"a\");\n~~~\n"),
+            ("code block", FileKind::Module, "```\nnested;\n"),
+            ("code block", FileKind::Module, "unclosed;\n"),
+        ]
+    );
+    for file in &files {
+        assert_eq!(
+            &src[file.offset..file.offset + file.source.len()],
+            file.source
+        );
+    }
+}
+
+#[test]
+fn extractors_by_extension() {
+    assert_eq!(extractor_for_path("a/b.vue").unwrap().name(), "vue");
+    assert_eq!(extractor_for_path("index.htm").unwrap().name(), "html");
+    assert_eq!(extractor_for_path("README.md").unwrap().name(), "markdown");
+    assert!(extractor_for_path("a.js").is_none());
+}
+
+#[test]
+fn project_results_are_mapped_to_the_physical_file() {
+    let store = CstRuleStore::new().builtins();
+    let mut linter = ProjectLinter::new(&store);
+    linter
+        .add_file(0, "Child.vue.js", "export default {};", true)
+        .add_extracted_file(1, "App.vue", COMPONENT, &Vue);
+    let result = linter.lint();
+
+    let debuggers = result
+        .file_diagnostics(1)
+        .filter(|d| d.code.as_deref() == Some("no-debugger"))
+        .map(|d| {
+            let range = d.primary.as_ref().unwrap().span.range.clone();
+            &COMPONENT[range]
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(debuggers, vec!["debugger", "debugger;"]);
+
+    // both scripts are merged into a single module
+    let module = result.graph.module(1).unwrap();
+    assert_eq!(module.imports.len(), 1);
+    assert_eq!(module.imports[0].resolved, Some(0));
+    assert_eq!(module.exports.len(), 1);
+    let import = &module.imports[0];
+    assert!(
+        COMPONENT[import.range.start().into()..import.range.end().into()].starts_with("import")
+    );
+}
@@ -0,0 +1,44 @@
+use rslint_core::autofix::FixPolicy;
+use rslint_core::{
+    get_rule_by_name, lint_file_with_options, CstRuleStore, LinterOptions, Severity,
+};
+
+const SRC: &str = "let a = 5;;\n";
+
+fn fix(policy: FixPolicy) -> String {
+    let mut store = CstRuleStore::new();
+    store.load_rules(
+        ["prefer-const", "no-extra-semi"]
+            .iter()
+            .map(|name| get_rule_by_name(name).unwrap()),
+    );
+    let options = LinterOptions {
+        fix_policy: policy,
+        ..LinterOptions::default()
+    };
+    let mut res = lint_file_with_options(0, SRC, false, &store, options).unwrap();
+    res.fix(false).unwrap()
+}
+
+#[test]
+fn all_fixes_are_applied_by_default() {
+    assert_eq!(fix(FixPolicy::default()), "const a = 5;\n");
+}
+
+#[test]
+fn only_fixes_of_listed_rules_are_applied() {
+    assert_eq!(
+        fix(FixPolicy::Rules(vec!["no-extra-semi".to_string()])),
+        "let a = 5;\n"
+    );
+    assert_eq!(fix(FixPolicy::Rules(vec![])), SRC);
+}
+
+#[test]
+fn fixes_are_selected_by_severity() {
+    assert_eq!(
+        fix(FixPolicy::MinSeverity(Severity::Warning)),
+        "const a = 5;\n"
+    );
+    assert_eq!(fix(FixPolicy::MinSeverity(Severity::Bug)), SRC);
+}
@@ -0,0 +1,77 @@
+use rslint_core::cache::LintCache;
+use rslint_core::directives::GlobalDeclaration;
+use rslint_core::util::globals::Globals;
+use rslint_core::{
+    get_rule_by_name, lint_file_with_options, CstRuleStore, DirectiveParser, LinterOptions,
+};
+use rslint_parser::parse_text;
+
+fn declarations(src: &str) -> Vec<GlobalDeclaration> {
+    let store = CstRuleStore::new();
+    DirectiveParser::new(parse_text(src, 0).syntax(), 0, &store).global_declarations()
+}
+
+/// The names assigned to which are reported by `no-global-assign`.
+fn reported(src: &str, globals: Globals) -> Vec<String> {
+    let mut store = CstRuleStore::new();
+    store.load_rules(get_rule_by_name("no-global-assign"));
+    let options = LinterOptions {
+        globals,
+        ..LinterOptions::default()
+    };
+    lint_file_with_options(0, src, false, &store, options)
+        .unwrap()
+        .diagnostics()
+        .map(|d| src[d.primary.as_ref().unwrap().span.range.clone()].to_string())
+        .collect()
+}
+
+#[test]
+fn global_comments_are_parsed() {
+    let src = "/* global foo, bar: writable */\nfoo();\n/*globals baz:readonly,\n  qux */\n// global notThis\n";
+    let found = declarations(src)
+        .into_iter()
+        .map(|decl| (src[decl.range].to_string(), decl.name, decl.writable))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        vec![
+            ("foo".to_string(), "foo".to_string(), false),
+            ("bar".to_string(), "bar".to_string(), true),
+            ("baz".to_string(), "baz".to_string(), false),
+            ("qux".to_string(), "qux".to_string(), false),
+        ]
+    );
+    assert!(declarations("/* globalThis is used */").is_empty());
+}
+
+#[test]
+fn globals_of_the_options_are_used() {
+    let src = "window = 1;\nprocess = 2;\nObject = 3;\n";
+    assert_eq!(reported(src, Globals::default()), vec!["process", "Object"]);
+
+    let mut globals = Globals::new();
+    globals.extend(["window", "Object"].iter());
+    assert_eq!(reported(src, globals), vec!["window", "Object"]);
+}
+
+#[test]
+fn comments_make_globals_writable() {
+    let src = "/* global Object: writable, window */\nObject = 1;\nwindow = 2;\n";
+    assert_eq!(reported(src, Globals::default()), vec!["window"]);
+}
+
+#[test]
+fn globals_are_part_of_the_cache_key() {
+    let store = CstRuleStore::new().builtins();
+    let mut globals = Globals::default();
+    globals.add("jQuery", false);
+    let options = LinterOptions {
+        globals,
+        ..LinterOptions::default()
+    };
+    assert_ne!(
+        LintCache::key(&store, &LinterOptions::default(), ""),
+        LintCache::key(&store, &options, "")
+    );
+}
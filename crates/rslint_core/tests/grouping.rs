@@ -0,0 +1,64 @@
+use rslint_core::grouping::group_diagnostics;
+use rslint_core::{lint_file, CstRuleStore, Diagnostic};
+
+fn error(code: &str, range: std::ops::Range<usize>) -> Diagnostic {
+    Diagnostic::error(0, code, "").primary(range, "")
+}
+
+fn codes(groups: &[rslint_core::grouping::DiagnosticGroup]) -> Vec<Vec<String>> {
+    groups
+        .iter()
+        .map(|group| group.iter().map(|d| d.code.clone().unwrap()).collect())
+        .collect()
+}
+
+#[test]
+fn same_span_is_grouped() {
+    let others = vec![error("a", 0..3), error("b", 4..6), error("c", 0..3)];
+    let groups = group_diagnostics(&[], &others);
+    assert_eq!(codes(&groups), vec![vec!["a", "c"], vec!["b"]]);
+    assert_eq!(groups[0].duplicates.len(), 1);
+    assert!(groups[1].is_single());
+}
+
+#[test]
+fn rules_overlapping_parse_errors_are_grouped() {
+    let parser = vec![error("parse", 5..10), error("empty", 20..20)];
+    let others = vec![
+        error("inside", 6..8),
+        error("touching", 10..12),
+        error("at empty", 18..20),
+        error("after", 21..22),
+    ];
+    let groups = group_diagnostics(&parser, &others);
+    assert_eq!(
+        codes(&groups),
+        vec![
+            vec!["parse", "inside"],
+            vec!["empty", "at empty"],
+            vec!["touching"],
+            vec!["after"]
+        ]
+    );
+}
+
+#[test]
+fn diagnostics_without_primary_are_not_grouped() {
+    let others = vec![Diagnostic::error(0, "a", ""), Diagnostic::error(0, "b", "")];
+    assert_eq!(group_diagnostics(&[], &others).len(), 2);
+}
+
+#[test]
+fn grouped_diagnostics_of_lint_result() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint_file(0, "a == -0;\n", false, &store, false).unwrap();
+    let groups = res.grouped_diagnostics();
+    let group = groups
+        .iter()
+        .find(|group| !group.is_single())
+        .expect("eqeqeq and no-compare-neg-zero are grouped");
+    let mut rules: Vec<_> = group.iter().map(|d| d.code.clone().unwrap()).collect();
+    rules.sort();
+    assert_eq!(rules, vec!["eqeqeq", "no-compare-neg-zero"]);
+    assert_eq!(groups.len(), res.diagnostics().count() - 1);
+}
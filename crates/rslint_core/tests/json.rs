@@ -0,0 +1,118 @@
+use rslint_core::json::{
+    lint_json, parse_json, JsonDocument, JsonKind, JsonRuleStore, JsonValue, JsonValueKind,
+    SchemaValidator, SchemaViolation, TrailingCommas, TrailingCommasPolicy,
+};
+
+fn codes(src: &str, kind: JsonKind, store: &JsonRuleStore) -> Vec<String> {
+    lint_json(0, src, kind, store)
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+#[test]
+fn parses_values() {
+    let (doc, diagnostics) = parse_json(
+        0,
+        r#"{ "a": [1, -2.5e3, true, null], "b\n": "A\"" }"#,
+        JsonKind::Json,
+    );
+    assert!(diagnostics.is_empty());
+    let value = doc.value.unwrap();
+    match &value.get("a").unwrap().kind {
+        JsonValueKind::Array(elements) => {
+            assert_eq!(elements[1].kind, JsonValueKind::Number(-2500.0));
+            assert_eq!(elements[3].kind, JsonValueKind::Null);
+        }
+        kind => panic!("expected an array, found {:?}", kind),
+    }
+    assert_eq!(
+        value.get("b\n").unwrap().kind,
+        JsonValueKind::String("A\"".to_string())
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    let store = JsonRuleStore::new().builtins();
+    for src in &[
+        "{ \"a\": 1 // comment\n}",
+        "{ a: 1 }",
+        "[01]",
+        "[1] [2]",
+        "\"unterminated",
+        "",
+    ] {
+        assert_eq!(
+            codes(src, JsonKind::Json, &store),
+            vec!["SyntaxError"],
+            "{}",
+            src
+        );
+    }
+}
+
+#[test]
+fn jsonc_allows_comments() {
+    let src = "/* header */ { \"a\": 1 // trailing\n}";
+    let (doc, diagnostics) = parse_json(0, src, JsonKind::Jsonc);
+    assert!(diagnostics.is_empty());
+    assert_eq!(doc.comments.len(), 2);
+}
+
+#[test]
+fn duplicate_keys() {
+    let store = JsonRuleStore::new().builtins();
+    let src = r#"{ "a": 1, "b": { "a": 2, "c": 3, "c": 4 }, "a": 5 }"#;
+    assert_eq!(
+        codes(src, JsonKind::Json, &store),
+        vec!["json/duplicate-keys", "json/duplicate-keys"]
+    );
+}
+
+#[test]
+fn trailing_commas() {
+    let mut store = JsonRuleStore::new().builtins();
+    assert_eq!(
+        codes("[1, 2,]", JsonKind::Jsonc, &store),
+        vec!["json/trailing-commas"]
+    );
+
+    store.set(Box::new(TrailingCommas {
+        policy: TrailingCommasPolicy::AlwaysMultiline,
+    }));
+    assert!(codes("{\n  \"a\": [1, 2],\n}", JsonKind::Jsonc, &store).is_empty());
+    assert_eq!(
+        codes("{\n  \"a\": [1, 2,]\n}", JsonKind::Jsonc, &store),
+        vec!["json/trailing-commas", "json/trailing-commas"]
+    );
+    // strict JSON never allows trailing commas
+    assert_eq!(
+        codes("[\n  1,\n]", JsonKind::Json, &store),
+        vec!["json/trailing-commas"]
+    );
+}
+
+struct RequireName;
+
+impl SchemaValidator for RequireName {
+    fn validate(&self, _: &JsonDocument, value: &JsonValue) -> Vec<SchemaViolation> {
+        match value.get("name").map(|name| &name.kind) {
+            Some(JsonValueKind::String(_)) => vec![],
+            _ => vec![SchemaViolation {
+                range: value.range.clone(),
+                message: "expected a `name`".to_string(),
+            }],
+        }
+    }
+}
+
+#[test]
+fn schema_validation() {
+    let store = JsonRuleStore::new().builtins().schema(RequireName);
+    assert!(codes(r#"{ "name": "rslint" }"#, JsonKind::Json, &store).is_empty());
+    assert_eq!(
+        codes(r#"{ "name": 1 }"#, JsonKind::Json, &store),
+        vec!["json/schema"]
+    );
+}
@@ -0,0 +1,52 @@
+use rslint_core::{
+    directives::{skip_node, LazyDirectives},
+    lint_file, CstRuleStore, Rule,
+};
+use rslint_parser::parse_text;
+
+const SRC: &str = "
+// rslint-ignore no-empty
+{}
+
+// rslint-ignore
+debugger;
+
+{}
+debugger;
+";
+
+#[test]
+fn suppressions_match_skip_node() {
+    let store = CstRuleStore::new().builtins();
+    let root = parse_text(SRC, 0).syntax();
+    let lazy = LazyDirectives::new(root.clone(), 0, &store);
+    let directives = lazy.get().unwrap().directives.clone();
+    assert_eq!(directives.len(), 2);
+
+    for node in root.descendants() {
+        for rule in &store.rules {
+            assert_eq!(
+                lazy.suppressed(&node, rule.name()),
+                skip_node(&directives, &node, &**rule),
+                "{} on {:?}",
+                rule.name(),
+                node
+            );
+        }
+    }
+}
+
+#[test]
+fn node_and_file_directives_are_applied() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, SRC, false, &store, false).unwrap();
+    assert_eq!(result.rule_results["no-empty"].diagnostics.len(), 1);
+    assert_eq!(result.rule_results["no-debugger"].diagnostics.len(), 1);
+
+    let src = format!("// rslint-ignore no-debugger\nfoo;\n{}", SRC);
+    let result = lint_file(0, src, false, &store, false).unwrap();
+    assert!(!result
+        .rule_results
+        .contains_key(rslint_core::groups::errors::NoDebugger::new().name()));
+    assert_eq!(result.rule_results["no-empty"].diagnostics.len(), 1);
+}
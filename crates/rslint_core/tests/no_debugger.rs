@@ -0,0 +1,25 @@
+use rslint_core::groups::errors::NoDebugger;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+
+fn fix(src: &str) -> String {
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(NoDebugger::default()) as Box<dyn CstRule>]);
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+    result.fix(false).unwrap()
+}
+
+#[test]
+fn debugger_statements_are_removed() {
+    assert_eq!(
+        fix("foo();\ndebugger;\nfunction bar() {\n    debugger;\n    baz();\n}\n"),
+        "foo();\nfunction bar() {\n    baz();\n}\n"
+    );
+}
+
+#[test]
+fn statement_bodies_are_not_removed() {
+    assert_eq!(
+        fix("if (a) debugger;\nfoo();\n"),
+        "if (a) debugger;\nfoo();\n"
+    );
+}
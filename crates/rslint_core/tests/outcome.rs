@@ -0,0 +1,85 @@
+use rslint_core::{
+    lint_file, CstRuleStore, Diagnostic, FailurePolicy, Outcome, OutcomePolicy, OutcomeSummary,
+    Severity,
+};
+
+#[test]
+fn summaries_count_diagnostics_by_severity() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, "debugger;\ndebugger;\n", false, &store, false).unwrap();
+    let summary = result.summary();
+    assert_eq!(summary.errors, 2);
+    assert_eq!(summary.problems(), 2);
+    assert_eq!(summary.outcome(), result.outcome());
+    assert_eq!(Outcome::from(summary), Outcome::Failure);
+}
+
+#[test]
+fn failure_policies() {
+    let warnings = OutcomeSummary::from(vec![
+        Diagnostic::warning(0, "a", ""),
+        Diagnostic::warning(0, "b", ""),
+        Diagnostic::note(0, "c", ""),
+    ]);
+    assert_eq!(warnings.outcome(), Outcome::Warning);
+    assert!(!warnings.failing(FailurePolicy::Errors));
+    assert!(warnings.failing(FailurePolicy::Warnings));
+    assert!(warnings.failing(FailurePolicy::MaxWarnings(1)));
+    assert!(!warnings.failing(FailurePolicy::MaxWarnings(2)));
+
+    let total = vec![
+        warnings,
+        OutcomeSummary::from(vec![Diagnostic::error(0, "d", "")]),
+    ]
+    .into_iter()
+    .sum::<OutcomeSummary>();
+    assert_eq!((total.errors, total.warnings, total.notes), (1, 2, 1));
+    assert_eq!(total.outcome(), Outcome::Failure);
+}
+
+#[test]
+fn outcomes_under_failure_policies() {
+    let warnings = OutcomeSummary::from(vec![Diagnostic::warning(0, "a", "")]);
+    assert_eq!(
+        warnings.outcome_with(FailurePolicy::Errors),
+        Outcome::Warning
+    );
+    assert_eq!(
+        warnings.outcome_with(FailurePolicy::Warnings),
+        Outcome::Failure
+    );
+    assert_eq!(
+        warnings.outcome_with(FailurePolicy::MaxWarnings(1)),
+        Outcome::Warning
+    );
+    assert_eq!(
+        OutcomeSummary::default().outcome_with(FailurePolicy::Warnings),
+        Outcome::Success
+    );
+}
+
+#[test]
+fn severity_floors() {
+    let policy = OutcomePolicy::default()
+        .floor("no-console", Severity::Error)
+        .floor("no-debugger", Severity::Warning);
+    let diagnostics = vec![
+        Diagnostic::warning(0, "no-console", ""),
+        Diagnostic::error(0, "no-debugger", ""),
+        Diagnostic::note(0, "no-console", ""),
+        Diagnostic::warning(0, "no-empty", ""),
+    ];
+    let summary = policy.summary(&diagnostics);
+    assert_eq!((summary.errors, summary.warnings, summary.notes), (3, 1, 0));
+    assert_eq!(policy.outcome(&diagnostics), Outcome::Failure);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, "debugger;\n", false, &store, false).unwrap();
+    assert_eq!(
+        result.outcome_with(&OutcomePolicy::default()),
+        result.outcome()
+    );
+    let suppressed = Diagnostic::warning(0, "no-console", "").suppressed_by(0usize..1);
+    assert_eq!(policy.outcome(vec![suppressed]), Outcome::Success);
+}
@@ -0,0 +1,79 @@
+use rslint_core::{lint_file, CstRuleStore, DirectiveParser, FileKind, ParserMode};
+use rslint_parser::parse_text;
+
+fn lint<'s>(src: &str, module: bool, store: &'s CstRuleStore) -> rslint_core::LintResult<'s> {
+    lint_file(0, src, module, store, false).unwrap()
+}
+
+#[test]
+fn directive_overrides_parser_mode() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint(
+        "// rslint-parser module\nimport a from 'a';\n",
+        false,
+        &store,
+    );
+    assert_eq!(FileKind::of(&res.parsed), FileKind::Module);
+    assert!(res.parser_diagnostics.is_empty());
+    assert!(res.directive_diagnostics.is_empty());
+
+    let res = lint("/* rslint-parser script */\nwith (a) {}\n", true, &store);
+    assert_eq!(FileKind::of(&res.parsed), FileKind::Script);
+    assert!(res.parser_diagnostics.is_empty());
+}
+
+#[test]
+fn parser_mode_is_only_read_from_the_top_of_the_file() {
+    let store = CstRuleStore::new();
+    let mode = |src| DirectiveParser::new(parse_text(src, 0).syntax(), 0, &store).parser_mode();
+    assert_eq!(
+        mode("// rslint-parser module\nfoo;"),
+        Some(ParserMode::Module)
+    );
+    assert_eq!(
+        mode("// rslint-parser script\n// rslint-parser module\nfoo;"),
+        Some(ParserMode::Script)
+    );
+    assert_eq!(mode("foo;\n// rslint-parser module\nbar;"), None);
+    assert_eq!(mode("// rslint-parser modul\nfoo;"), None);
+}
+
+#[test]
+fn unsupported_parser_modes_are_reported() {
+    let store = CstRuleStore::new().builtins();
+    let res = lint("// rslint-parser jsx\nfoo;\n", false, &store);
+    assert_eq!(FileKind::of(&res.parsed), FileKind::Script);
+    assert_eq!(res.directive_diagnostics.len(), 1);
+    assert_eq!(
+        res.directive_diagnostics[0].title,
+        "the parser does not support JSX yet"
+    );
+    let range = res.directive_diagnostics[0]
+        .primary
+        .as_ref()
+        .unwrap()
+        .span
+        .range
+        .clone();
+    assert_eq!(range, 17..20);
+}
+
+#[test]
+fn invalid_parser_directives_are_reported() {
+    let store = CstRuleStore::new().builtins();
+    let err = lint_file(0, "// rslint-parser modul\nfoo;\n", false, &store, false).unwrap_err();
+    assert_eq!(err.code.as_deref(), Some("unknown parser mode `modul`"));
+
+    let err = lint_file(
+        0,
+        "foo;\n// rslint-parser module\nbar;\n",
+        false,
+        &store,
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.code.as_deref(),
+        Some("the `parser` command can only be used at the top of the file")
+    );
+}
@@ -0,0 +1,39 @@
+use rslint_core::{lint_file_with_options, CstRuleStore, LinterOptions, Outcome, Severity};
+
+// the redundant strict mode declaration in a module is a parser warning
+const SRC: &str = "\"use strict\";\n";
+
+fn lint(severities: &[(&str, Option<Severity>)]) -> (Vec<Severity>, Outcome) {
+    let store = CstRuleStore::new();
+    let options = LinterOptions {
+        parser_severities: severities
+            .iter()
+            .map(|(code, severity)| (code.to_string(), *severity))
+            .collect(),
+        ..LinterOptions::default()
+    };
+    let result = lint_file_with_options(0, SRC, true, &store, options).unwrap();
+    let severities = result
+        .parser_diagnostics
+        .iter()
+        .map(|d| d.severity)
+        .collect();
+    (severities, result.outcome())
+}
+
+#[test]
+fn parser_warnings_can_be_escalated() {
+    assert_eq!(lint(&[]), (vec![Severity::Warning], Outcome::Warning));
+    assert_eq!(
+        lint(&[("ParserWarning", Some(Severity::Error))]),
+        (vec![Severity::Error], Outcome::Failure)
+    );
+}
+
+#[test]
+fn parser_diagnostics_can_be_silenced() {
+    assert_eq!(
+        lint(&[("ParserWarning", None), ("SyntaxError", None)]),
+        (vec![], Outcome::Success)
+    );
+}
@@ -0,0 +1,77 @@
+use rslint_core::rule_prelude::*;
+use rslint_core::{lint_file, plugin, register_plugin, CstRuleStore};
+
+declare_lint! {
+    #[derive(Default)]
+    NoFoo,
+    test_plugin,
+    "test-plugin/no-foo"
+}
+
+#[typetag::serde]
+impl CstRule for NoFoo {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if node.kind() == SyntaxKind::NAME_REF && node.text() == "foo" {
+            let err = ctx.err(self.name(), "foo is not allowed").primary(node, "");
+            ctx.add_err(err);
+        }
+        None
+    }
+}
+
+mod not_namespaced {
+    use rslint_core::rule_prelude::*;
+
+    declare_lint! {
+        #[derive(Default)]
+        NotNamespaced,
+        test_plugin,
+        "not-namespaced"
+    }
+
+    #[typetag::serde]
+    impl CstRule for NotNamespaced {}
+}
+
+register_plugin!("test-plugin", NoFoo);
+
+#[test]
+fn plugins_are_discovered() {
+    let plugin = plugin::get_plugin("test-plugin").expect("plugin was not registered");
+    assert_eq!((plugin.rules)().len(), 1);
+    assert!(CstRuleStore::new()
+        .builtins()
+        .external()
+        .get("test-plugin/no-foo")
+        .is_some());
+    assert_eq!(
+        rslint_core::get_group_rules_by_name("test-plugin").map(|rules| rules.len()),
+        Some(1)
+    );
+}
+
+#[test]
+fn external_rules_are_namespaced_in_diagnostics_and_directives() {
+    let store = CstRuleStore::new().external();
+    let res = lint_file(0, "foo;", false, &store, false).unwrap();
+    let diagnostics = &res.rule_results["test-plugin/no-foo"].diagnostics;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("test-plugin/no-foo"));
+
+    let res = lint_file(
+        0,
+        "// rslint-ignore test-plugin/no-foo\nfoo;",
+        false,
+        &store,
+        false,
+    )
+    .unwrap();
+    assert!(res.directive_diagnostics.is_empty());
+    assert!(res.diagnostics().next().is_none());
+}
+
+#[test]
+#[should_panic]
+fn external_rules_must_be_namespaced() {
+    CstRuleStore::new().register_external(Box::new(not_namespaced::NotNamespaced::new()));
+}
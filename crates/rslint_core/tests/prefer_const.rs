@@ -0,0 +1,48 @@
+use rslint_core::groups::style::PreferConst;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+use serde_json::{json, Value};
+
+fn fix(config: Value, src: &str) -> String {
+    let rule: PreferConst = serde_json::from_value(config).unwrap();
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(rule) as Box<dyn CstRule>]);
+    let mut result = lint_file(0, src, true, &store, false).unwrap();
+    result.fix(false).unwrap()
+}
+
+#[test]
+fn declarations_are_split() {
+    let split = json!({ "splitDeclarations": true });
+    assert_eq!(
+        fix(split.clone(), "let a = 1, b = 2; b++;"),
+        "const a = 1; let b = 2; b++;"
+    );
+    assert_eq!(
+        fix(split.clone(), "let a = 1, b = 2, c = 3; b++;"),
+        "const a = 1; let b = 2; const c = 3; b++;"
+    );
+    // without the option only declarations which can be `const` as a whole are fixed
+    assert_eq!(
+        fix(json!({}), "let a = 1, b = 2; b++;"),
+        "let a = 1, b = 2; b++;"
+    );
+}
+
+#[test]
+fn partially_reassigned_patterns_are_not_split() {
+    let all = json!({ "destructuring": "all", "splitDeclarations": true });
+    assert_eq!(
+        fix(all.clone(), "let { a, b } = c; a = 5;"),
+        "let { a, b } = c; a = 5;"
+    );
+    assert_eq!(
+        fix(all, "let { a, b } = c, d = 1; a = 5;"),
+        "let { a, b } = c; const d = 1; a = 5;"
+    );
+
+    let any = json!({ "splitDeclarations": true });
+    assert_eq!(
+        fix(any, "let { a, b } = c; a = 5;"),
+        "let { a, b } = c; a = 5;"
+    );
+}
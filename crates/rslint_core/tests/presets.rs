@@ -0,0 +1,56 @@
+use rslint_core::{groups::errors::NoEmpty, preset::Preset, CstRule, CstRuleStore};
+
+fn names(store: &CstRuleStore) -> Vec<&'static str> {
+    let mut names = store
+        .rules
+        .iter()
+        .map(|rule| rule.name())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn builtin_presets_match_groups() {
+    let errors = CstRuleStore::new().errors();
+    assert!(errors.get("no-empty").is_some());
+    assert!(errors.rules.iter().all(|rule| rule.group() == "errors"));
+    assert_eq!(names(&CstRuleStore::new().recommended()), names(&errors));
+    assert_eq!(
+        names(
+            &CstRuleStore::new()
+                .errors()
+                .style()
+                .promises()
+                .node()
+                .regex()
+        ),
+        names(&CstRuleStore::new().builtins())
+    );
+    assert_eq!(
+        names(&CstRuleStore::new().preset(&Preset::builtin("all").unwrap())),
+        names(&CstRuleStore::new().builtins())
+    );
+    assert!(Preset::builtin("strict").is_none());
+}
+
+#[test]
+fn custom_presets_compose_rules() {
+    let no_empty = NoEmpty {
+        allow_empty_catch: true,
+        ..NoEmpty::default()
+    };
+    let preset = Preset::new("custom")
+        .extend(&Preset::errors())
+        .with_rules(vec![Box::new(no_empty) as Box<dyn CstRule>])
+        .without(&["for-direction"]);
+    assert_eq!(preset.name(), "custom");
+    assert_eq!(preset.rules().len(), Preset::errors().rules().len() - 1);
+
+    // loading a preset replaces rules which are already in the store
+    let store = CstRuleStore::new().builtins().preset(&preset);
+    assert_eq!(names(&store), names(&CstRuleStore::new().builtins()));
+    let rule = serde_json::to_value(&*store.get("no-empty").unwrap()).unwrap();
+    assert_eq!(rule["NoEmpty"]["allowEmptyCatch"], true);
+    assert!(store.get("for-direction").is_some());
+}
@@ -0,0 +1,18 @@
+use rslint_core::{lint_file, profile, CstRuleStore};
+
+#[test]
+fn profiles_every_rule_run() {
+    let store = CstRuleStore::new().builtins();
+    lint_file(0, "foo == bar", false, &store, false).unwrap();
+    assert!(profile::report().is_empty());
+
+    profile::enable();
+    lint_file(0, "foo == bar", false, &store, false).unwrap();
+    lint_file(1, "if (a) {}", false, &store, false).unwrap();
+    let report = profile::report();
+    assert_eq!(report.len(), store.rules.len());
+    assert!(report.iter().all(|profile| profile.runs == 2));
+
+    profile::reset();
+    assert!(profile::report().is_empty());
+}
@@ -0,0 +1,233 @@
+use rslint_core::{
+    project::{lint_files, LintInput, ModuleGraph, ProjectLinter, StreamOptions},
+    project_rules::MaxDirectives,
+    CstRuleStore, Diagnostic, FileKind, LinterOptions, ProjectRule, Rule,
+};
+
+/// Reports every export which is not imported by any other file.
+#[derive(Debug, Clone)]
+struct NoUnusedExports;
+
+impl Rule for NoUnusedExports {
+    fn name(&self) -> &'static str {
+        "no-unused-exports"
+    }
+
+    fn group(&self) -> &'static str {
+        "test"
+    }
+}
+
+impl ProjectRule for NoUnusedExports {
+    fn check_project(&self, graph: &ModuleGraph, diagnostics: &mut Vec<Diagnostic>) {
+        for module in graph.modules() {
+            for export in &module.exports {
+                let used = graph.imports_of(module.file_id).any(|(_, import)| {
+                    import
+                        .names
+                        .iter()
+                        .any(|name| name == &export.name || name == "*")
+                });
+                if !used {
+                    diagnostics.push(
+                        Diagnostic::error(module.file_id, self.name(), "unused export")
+                            .primary(export.range, ""),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn module_graph_resolves_imports_between_files() {
+    let store = CstRuleStore::new();
+    let mut linter = ProjectLinter::new(&store);
+    linter
+        .add_file(
+            1,
+            "src/main.mjs",
+            "import foo, { bar } from './lib'; import 'left-pad';",
+            true,
+        )
+        .add_file(
+            2,
+            "src/lib/index.js",
+            "export default 5; export { baz as bar } from '../util.mjs';",
+            true,
+        )
+        .add_file(
+            3,
+            "src/util.mjs",
+            "export const baz = 1, unused = 2; export function qux() {}",
+            true,
+        );
+    let graph = linter.lint().graph;
+
+    assert_eq!(graph.dependencies(1), vec![2]);
+    assert_eq!(graph.dependencies(2), vec![3]);
+    assert_eq!(graph.dependents(3), vec![2]);
+
+    let main = graph.module(1).unwrap();
+    assert_eq!(main.imports[0].names, vec!["default", "bar"]);
+    assert_eq!(main.imports[1].specifier, "left-pad");
+    assert_eq!(main.imports[1].resolved, None);
+
+    let util = graph.module(3).unwrap();
+    let exports = util
+        .exports
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(exports, vec!["baz", "unused", "qux"]);
+}
+
+#[test]
+fn project_rules_see_every_file() {
+    let store = CstRuleStore::new().builtins();
+    let mut linter = ProjectLinter::new(&store);
+    linter
+        .add_file(1, "a.js", "import { used } from './b'; {}", true)
+        .add_file(
+            2,
+            "b.js",
+            "export const used = 1; export const unused = 2;",
+            true,
+        )
+        .add_project_rule(Box::new(NoUnusedExports));
+    let result = linter.lint();
+
+    assert_eq!(result.results.len(), 2);
+    assert!(result
+        .diagnostics()
+        .any(|d| d.code.as_deref() == Some("no-empty")));
+    let project = &result.project_diagnostics["no-unused-exports"];
+    assert_eq!(project.len(), 1);
+    assert_eq!(project[0].file_id, 2);
+}
+
+#[test]
+fn max_directives_counts_suppressions_per_file() {
+    let store = CstRuleStore::new().builtins();
+    let over = "// rslint-disable-next-line no-empty\n{}\n// rslint-ignore no-empty, no-debugger\ndebugger;\n// rslint-disable\n{}\n// rslint-enable\n";
+    let mut linter = ProjectLinter::new(&store);
+    linter
+        .add_file(1, "a.js", "// rslint-ignore no-empty\n{}\n", false)
+        .add_file(2, "b.js", over, false)
+        .add_project_rule(Box::new(MaxDirectives::new(2)));
+    let result = linter.lint();
+
+    let stats = &result.graph.module(2).unwrap().suppressions;
+    assert_eq!(stats.total(), 3);
+    assert_eq!(stats.all_rules, 1);
+    assert_eq!(stats.rules["no-empty"], 2);
+    assert_eq!(stats.rules["no-debugger"], 1);
+
+    let project = &result.project_diagnostics["max-directives"];
+    assert_eq!(project.len(), 1);
+    assert_eq!(project[0].file_id, 2);
+    let start = over.find("// rslint-disable\n").unwrap();
+    assert_eq!(project[0].primary.as_ref().unwrap().span.range.start, start);
+}
+
+#[test]
+fn streaming_reports_every_file_and_runs_project_rules() {
+    let store = CstRuleStore::new().builtins();
+    let mut linter = ProjectLinter::new(&store);
+    linter
+        .add_file(1, "a.js", "import { used } from './b'; {}", true)
+        .add_file(
+            2,
+            "b.js",
+            "export const used = 1; export const unused = 2;",
+            true,
+        )
+        .add_project_rule(Box::new(NoUnusedExports));
+
+    let mut reports = vec![];
+    let result = linter.lint_streaming(&StreamOptions::default(), |report| reports.push(report));
+    reports.sort_by_key(|report| report.file_id);
+
+    assert_eq!(reports.len(), 2);
+    let a = reports[0].result.as_ref().unwrap();
+    assert!(a.iter().any(|d| d.code.as_deref() == Some("no-empty")));
+    assert!(reports[1].result.as_ref().unwrap().is_empty());
+    assert_eq!(result.graph.dependencies(1), vec![2]);
+    assert_eq!(result.project_diagnostics["no-unused-exports"].len(), 1);
+}
+
+#[test]
+fn streaming_lints_fewer_files_at_once_when_the_consumer_lags() {
+    let store = CstRuleStore::new().builtins();
+    let mut linter = ProjectLinter::new(&store);
+    for id in 0..16 {
+        linter.add_file(id, format!("{}.js", id), "foo;", false);
+    }
+    let options = StreamOptions {
+        capacity: 1,
+        max_workers: 4,
+        min_workers: 1,
+    };
+
+    let mut count = 0;
+    let result = linter.lint_streaming(&options, |_| {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        count += 1;
+    });
+    assert_eq!(count, 16);
+    assert!(result.throttled > 0);
+    assert_eq!(result.graph.modules().count(), 16);
+}
+
+#[test]
+fn lint_files_with_their_own_stores() {
+    let all = CstRuleStore::new().builtins();
+    let none = CstRuleStore::new();
+    let input = |file_id, path: &str, source: &str, kind, store| LintInput {
+        file_id,
+        path: path.into(),
+        source: source.to_string(),
+        kind,
+        store,
+        options: LinterOptions::default(),
+    };
+    let files = (0..16)
+        .map(|id| {
+            input(
+                id,
+                &format!("src/{}.js", id),
+                "import a from './a';\ndebugger;\n",
+                FileKind::Module,
+                if id % 2 == 0 { &all } else { &none },
+            )
+        })
+        .chain(vec![
+            input(16, "src/a.js", "export default 1;", FileKind::Module, &all),
+            input(
+                17,
+                "invalid.js",
+                "// rslint-parser modul\n",
+                FileKind::Script,
+                &all,
+            ),
+        ])
+        .collect::<Vec<_>>();
+
+    for threads in [None, Some(1), Some(4)].iter().copied() {
+        let result = lint_files(files.clone(), threads);
+        let ids = result
+            .results
+            .iter()
+            .map(|res| res.file_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, (0..17).collect::<Vec<_>>());
+        for res in &result.results[..16] {
+            let debugger = res.for_rule("no-debugger").is_some();
+            assert_eq!(debugger, res.file_id % 2 == 0);
+        }
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].file_id, 17);
+        assert_eq!(result.graph.dependents(16).len(), 16);
+        assert!(result.project_diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+use rslint_core::groups::style::Quotes;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+use serde_json::json;
+
+fn fix(style: &str, src: &str) -> String {
+    let rule: Quotes = serde_json::from_value(json!({ "style": style })).unwrap();
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(rule) as Box<dyn CstRule>]);
+    let mut result = lint_file(0, src, true, &store, false).unwrap();
+    result.fix(false).unwrap()
+}
+
+#[test]
+fn strings_are_requoted() {
+    assert_eq!(fix("double", "let a = 'a';"), r#"let a = "a";"#);
+    assert_eq!(fix("single", r#"let a = "a";"#), "let a = 'a';");
+    assert_eq!(fix("backtick", "let a = 'a';"), "let a = `a`;");
+    assert_eq!(fix("double", "import a from 'a';"), r#"import a from "a";"#);
+}
+
+#[test]
+fn quotes_are_escaped_and_unescaped() {
+    assert_eq!(
+        fix("double", r#"let a = 'say "hi", it\'s';"#),
+        r#"let a = "say \"hi\", it's";"#
+    );
+    assert_eq!(
+        fix("single", r#"let a = "it's \"ok\"";"#),
+        r#"let a = 'it\'s "ok"';"#
+    );
+    assert_eq!(
+        fix("single", r"let a = `it's \` \${}`;"),
+        r"let a = 'it\'s ` ${}';"
+    );
+}
+
+#[test]
+fn template_syntax_is_escaped() {
+    assert_eq!(
+        fix("backtick", r"let a = '`${a}` $b \n';"),
+        r"let a = `\`\${a}\` $b \n`;"
+    );
+}
+
+#[test]
+fn octal_escapes_are_not_fixed_to_templates() {
+    let src = r"let a = '\1';";
+    assert_eq!(fix("backtick", src), src);
+    assert_eq!(fix("backtick", r"let a = '\0';"), r"let a = `\0`;");
+}
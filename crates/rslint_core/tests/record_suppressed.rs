@@ -0,0 +1,79 @@
+use rslint_core::{lint_file_with_options, CstRuleStore, LintResult, LinterOptions, Outcome};
+
+const SOURCE: &str = "\
+foo();
+// rslint-ignore no-empty
+if (a) {}
+debugger;
+// rslint-disable-next-line no-debugger
+foo(); debugger;
+";
+
+fn lint(src: &str, record_suppressed: bool) -> LintResult<'static> {
+    let store = Box::leak(Box::new(CstRuleStore::new().builtins()));
+    let options = LinterOptions {
+        record_suppressed,
+        ..LinterOptions::default()
+    };
+    lint_file_with_options(0, src, false, store, options).unwrap()
+}
+
+/// The source text of the directive which suppressed each suppressed diagnostic.
+fn directives<'a>(result: &LintResult, src: &'a str) -> Vec<(&'a str, &'a str)> {
+    result
+        .suppressed_diagnostics()
+        .map(|diagnostic| {
+            let range = diagnostic.suppressed_by.clone().unwrap();
+            let primary = diagnostic.primary.as_ref().unwrap().span.range.clone();
+            (src[primary].trim(), &src[range])
+        })
+        .collect()
+}
+
+#[test]
+fn suppressed_diagnostics_are_recorded_with_their_directive() {
+    let result = lint(SOURCE, true);
+    assert_eq!(result.diagnostics().count(), 1);
+    assert_eq!(
+        directives(&result, SOURCE),
+        vec![
+            ("debugger;", "// rslint-disable-next-line no-debugger"),
+            ("{}", "// rslint-ignore no-empty"),
+        ]
+    );
+}
+
+#[test]
+fn recording_does_not_change_the_emitted_diagnostics() {
+    let recorded = lint(SOURCE, true);
+    let dropped = lint(SOURCE, false);
+    assert_eq!(
+        recorded.diagnostics().collect::<Vec<_>>(),
+        dropped.diagnostics().collect::<Vec<_>>()
+    );
+    assert_eq!(dropped.suppressed_diagnostics().count(), 0);
+}
+
+#[test]
+fn file_ignores_are_recorded() {
+    let src = "// rslint-ignore\nif (a) {}\ndebugger;\n";
+    let result = lint(src, true);
+    assert_eq!(result.diagnostics().count(), 0);
+    assert_eq!(result.outcome(), Outcome::Success);
+    assert_eq!(
+        directives(&result, src),
+        vec![
+            ("debugger;", "// rslint-ignore"),
+            ("{}", "// rslint-ignore")
+        ]
+    );
+}
+
+#[test]
+fn suppressed_fixes_are_not_applied() {
+    let src = "foo();\n// rslint-ignore no-extra-boolean-cast\nif (!!a) {}\nif (!!b) {}\n";
+    let mut result = lint(src, true);
+    let fixed = result.fix(false).unwrap();
+    assert!(fixed.contains("!!a"));
+    assert!(!fixed.contains("!!b"));
+}
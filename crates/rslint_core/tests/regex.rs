@@ -0,0 +1,194 @@
+use rslint_core::util::regex::{
+    parse, parse_literal, validate_flags, Assertion, Backreference, ClassItem, GroupKind, Pattern,
+    Quantifier, TermKind,
+};
+
+fn pattern(src: &str) -> Pattern {
+    parse(src, "").unwrap()
+}
+
+/// The kinds of the terms of the first alternative.
+fn terms(src: &str) -> Vec<TermKind> {
+    pattern(src).alternatives[0]
+        .terms
+        .iter()
+        .map(|term| term.kind.clone())
+        .collect()
+}
+
+/// The message and the text of the range of the error of an invalid pattern.
+fn error<'a>(src: &'a str, flags: &str) -> (String, &'a str) {
+    let err = parse(src, flags).unwrap_err();
+    (err.message, &src[err.range])
+}
+
+#[test]
+fn parses_alternatives_and_groups() {
+    let parsed = pattern("a|(?:b|c)d");
+    assert_eq!(parsed.alternatives.len(), 2);
+    assert_eq!(parsed.alternatives[1].range, 2..10);
+    match &parsed.alternatives[1].terms[0].kind {
+        TermKind::Group(group) => {
+            assert_eq!(group.kind, GroupKind::NonCapturing);
+            assert_eq!(group.alternatives.len(), 2);
+        }
+        kind => panic!("expected a group, found {:?}", kind),
+    }
+    assert_eq!(parsed.all_alternatives().len(), 4);
+}
+
+#[test]
+fn parses_quantifiers() {
+    let quantifiers = terms("a*b+?c{2}d{2,}e{2,3}")
+        .into_iter()
+        .map(|kind| match kind {
+            TermKind::Quantified(_, quantifier) => quantifier,
+            kind => panic!("expected a quantifier, found {:?}", kind),
+        })
+        .collect::<Vec<_>>();
+    let q = |min, max, lazy| Quantifier { min, max, lazy };
+    assert_eq!(
+        quantifiers,
+        vec![
+            q(0, None, false),
+            q(1, None, true),
+            q(2, Some(2), false),
+            q(2, None, false),
+            q(2, Some(3), false)
+        ]
+    );
+}
+
+#[test]
+fn parses_escapes() {
+    let values = pattern("\\x41\\u0042\\n\\cJ\\0\\101")
+        .chars()
+        .iter()
+        .map(|c| c.value)
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![0x41, 0x42, 0xA, 0xA, 0, 0o101]);
+    assert_eq!(parse("\\u{1F600}", "u").unwrap().chars()[0].value, 0x1F600);
+
+    assert_eq!(
+        terms("^\\b\\B$")
+            .into_iter()
+            .map(|kind| match kind {
+                TermKind::Assertion(assertion) => assertion,
+                kind => panic!("expected an assertion, found {:?}", kind),
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            Assertion::Start,
+            Assertion::WordBoundary,
+            Assertion::NotWordBoundary,
+            Assertion::End
+        ]
+    );
+}
+
+#[test]
+fn parses_backreferences() {
+    assert!(matches!(
+        &terms("(a)\\1")[1],
+        TermKind::Backreference(Backreference::Index(1))
+    ));
+    assert!(matches!(
+        &terms("\\1(a)")[0],
+        TermKind::Backreference(Backreference::Index(1))
+    ));
+    // without a group `\2` is a legacy octal escape
+    assert!(matches!(&terms("(a)\\2")[1], TermKind::Char(c) if c.value == 2));
+    assert!(matches!(
+        &terms("(?<x>a)\\k<x>")[1],
+        TermKind::Backreference(Backreference::Name(name)) if name == "x"
+    ));
+    // without named groups `\k` is the letter `k`
+    assert!(matches!(&terms("\\k<x>")[0], TermKind::Char(c) if c.value == 'k' as u32));
+}
+
+#[test]
+fn parses_classes() {
+    let class = match &terms("[^a-c\\d-]")[0] {
+        TermKind::Class(class) => class.clone(),
+        kind => panic!("expected a class, found {:?}", kind),
+    };
+    assert!(class.negated);
+    assert!(
+        matches!(class.items[0], ClassItem::Range(from, to) if from.value == 'a' as u32 && to.value == 'c' as u32)
+    );
+    assert!(matches!(&class.items[1], ClassItem::Set(set, _) if set.escape == 'd'));
+    assert!(matches!(class.items[2], ClassItem::Char(c) if c.value == '-' as u32));
+    assert_eq!(terms("[]").len(), 1);
+}
+
+#[test]
+fn allows_web_compatibility_syntax_without_unicode() {
+    for src in &[
+        "]", "{", "a{,2}", "}", "\\c", "[\\c_]", "\\a", "(?=a)+", "\\8",
+    ] {
+        assert!(parse(src, "").is_ok(), "{} should be valid", src);
+    }
+    for src in &["]", "{", "a{,2}", "}", "\\c", "\\a", "(?=a)+", "\\8"] {
+        assert!(
+            parse(src, "u").is_err(),
+            "{} should be invalid with `u`",
+            src
+        );
+    }
+}
+
+#[test]
+fn reports_errors_with_their_range() {
+    assert_eq!(error("a(b", ""), ("unterminated group".to_string(), "("));
+    assert_eq!(error("ab)", ""), ("unmatched `)`".to_string(), ")"));
+    assert_eq!(error("a|+", ""), ("nothing to repeat".to_string(), "+"));
+    assert_eq!(error("^*", ""), ("nothing to repeat".to_string(), "^"));
+    assert_eq!(
+        error("a{3,2}", ""),
+        ("numbers out of order in quantifier".to_string(), "{3,2}")
+    );
+    assert_eq!(
+        error("[a-z0-9_-.]", ""),
+        ("range out of order in character class".to_string(), "_-.")
+    );
+    assert_eq!(
+        error("[abc", ""),
+        ("unterminated character class".to_string(), "[")
+    );
+    assert_eq!(
+        error("(?<a>.)\\k<b>", ""),
+        ("there is no group named `b`".to_string(), "\\k<b>")
+    );
+    assert_eq!(
+        error("a\\", ""),
+        ("`\\` at end of pattern".to_string(), "\\")
+    );
+}
+
+#[test]
+fn validates_flags() {
+    assert!(validate_flags("gimsuy", &[]).is_ok());
+    assert_eq!(validate_flags("gig", &[]).unwrap_err().range, 2..3);
+    assert!(validate_flags("d", &[]).is_err());
+    assert!(validate_flags("dg", &['d']).is_ok());
+}
+
+#[test]
+fn literal_ranges_are_relative_to_the_token() {
+    let literal = r"/a(b|\x01)[c-d]/gu";
+    let parsed = parse_literal(literal).unwrap();
+    let chars = parsed
+        .chars()
+        .iter()
+        .map(|c| &literal[c.range()])
+        .collect::<Vec<_>>();
+    assert_eq!(chars, vec!["a", "b", r"\x01", "c", "d"]);
+    assert_eq!(parsed.alternatives[0].range, 1..15);
+
+    let err = parse_literal("/a)/").unwrap_err();
+    assert_eq!(&"/a)/"[err.range], ")");
+    let err = parse_literal("/a/gx").unwrap_err();
+    assert_eq!(err.message, "invalid regex flag `x`");
+    assert_eq!(&"/a/gx"[err.range], "x");
+    assert!(parse_literal("a/").is_err());
+}
@@ -0,0 +1,58 @@
+use rslint_core::remap::{remap_diagnostic, remap_range};
+use rslint_core::Diagnostic;
+use rslint_parser::{TextRange, TextSize};
+use rslint_text_edit::TextEdit;
+
+fn range(start: u32, end: u32) -> TextRange {
+    TextRange::new(TextSize::from(start), TextSize::from(end))
+}
+
+#[test]
+fn ranges_are_moved_by_edits_before_them() {
+    let edit = TextEdit::insert(2.into(), "abc".into());
+    assert_eq!(remap_range(5..8, &edit), Some(8..11));
+    // insertions at the start move the range, insertions at the end do not change it
+    assert_eq!(remap_range(2..4, &edit), Some(5..7));
+    assert_eq!(remap_range(0..2, &edit), Some(0..2));
+    assert_eq!(remap_range(0..5, &edit), None);
+
+    let edit = TextEdit::delete(range(1, 3));
+    assert_eq!(remap_range(5..8, &edit), Some(3..6));
+    assert_eq!(remap_range(3..4, &edit), Some(1..2));
+    assert_eq!(remap_range(2..4, &edit), None);
+}
+
+#[test]
+fn multiple_indels_are_applied_together() {
+    let mut builder = TextEdit::builder();
+    builder.replace(range(0, 1), "xyz".into());
+    builder.delete(range(10, 12));
+    builder.insert(20.into(), "a".into());
+    let edit = builder.finish();
+
+    assert_eq!(remap_range(4..6, &edit), Some(6..8));
+    assert_eq!(remap_range(14..16, &edit), Some(14..16));
+    assert_eq!(remap_range(22..23, &edit), Some(23..24));
+    assert_eq!(remap_range(11..14, &edit), None);
+}
+
+#[test]
+fn invalidated_labels_are_removed() {
+    let diagnostic = Diagnostic::error(0, "rule", "message")
+        .primary(10usize..15, "")
+        .secondary(2usize..4, "")
+        .secondary(20usize..22, "");
+
+    let edit = TextEdit::replace(range(3, 5), "abcd".into());
+    let remapped = remap_diagnostic(&diagnostic, &edit).unwrap();
+    assert_eq!(remapped.primary.unwrap().span.range, 12..17);
+    let children = remapped
+        .children
+        .iter()
+        .map(|child| child.span.range.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(children, vec![22..24]);
+
+    let edit = TextEdit::insert(12.into(), ";".into());
+    assert!(remap_diagnostic(&diagnostic, &edit).is_none());
+}
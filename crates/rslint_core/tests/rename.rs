@@ -0,0 +1,132 @@
+use rslint_core::rename::RuleRename;
+use std::fs;
+
+fn rename() -> RuleRename {
+    RuleRename::new("no-empty", "no-empty-block")
+}
+
+#[test]
+fn directives_are_renamed() {
+    let src = "\
+// rslint-ignore no-empty, no-debugger
+foo();
+// rslint-disable-next-line no-empty
+if (a) {}
+/* rslint-disable no-empty-function, no-empty */
+// rslint-ignore no-emptyy
+";
+    let fixed = rename().directives_fix(0, src, false).unwrap().apply();
+    assert_eq!(
+        fixed,
+        "\
+// rslint-ignore no-empty-block, no-debugger
+foo();
+// rslint-disable-next-line no-empty-block
+if (a) {}
+/* rslint-disable no-empty-function, no-empty-block */
+// rslint-ignore no-emptyy
+"
+    );
+}
+
+#[test]
+fn config_tables_are_renamed() {
+    let src = r#"
+[rules]
+groups = ["errors"]
+allowed = [
+    "no-debugger",
+    "noEmpty", # not needed
+]
+errors.no_empty = {}
+
+[rules.errors]
+"no-empty" = { disallowEmptyFunctions = true }
+no-empty-character-class = {}
+
+[rules.warnings.no-empty]
+allowEmptyCatch = true
+
+[env]
+no-empty = true
+"#;
+    let fixed = rename().config_fix(src).apply();
+    assert_eq!(
+        fixed,
+        r#"
+[rules]
+groups = ["errors"]
+allowed = [
+    "no-debugger",
+    "no-empty-block", # not needed
+]
+errors.no-empty-block = {}
+
+[rules.errors]
+"no-empty-block" = { disallowEmptyFunctions = true }
+no-empty-character-class = {}
+
+[rules.warnings.no-empty-block]
+allowEmptyCatch = true
+
+[env]
+no-empty = true
+"#
+    );
+}
+
+#[test]
+fn only_files_mentioning_the_rule_are_edited() {
+    let mut rename = rename();
+    rename
+        .add_file("a.js", "// rslint-ignore no-empty\n{}\n", false)
+        .unwrap()
+        .add_file("b.mjs", "export {};\n", true)
+        .unwrap()
+        .add_config("rslintrc.toml", "[rules]\nallowed = ['no-empty']\n")
+        .add_config("sub/rslintrc.toml", "[rules.errors]\nno-debugger = {}\n");
+
+    let paths = rename
+        .edits()
+        .iter()
+        .map(|edit| edit.path.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["a.js", "rslintrc.toml"]);
+}
+
+#[test]
+fn malformed_directives_fail_the_file() {
+    assert!(rename()
+        .add_file("a.js", "// rslint-frobnicate no-empty\n{}\n", false)
+        .is_err());
+}
+
+#[test]
+fn failed_commits_restore_written_files() {
+    let dir = std::env::temp_dir().join(format!("rslint-rename-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("rslintrc.toml");
+    let src = "[rules.errors]\nno-empty = {}\n";
+    fs::write(&config, src).unwrap();
+
+    let mut rename = rename();
+    rename.add_config(&config, src);
+    rename.commit().unwrap();
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "[rules.errors]\nno-empty-block = {}\n"
+    );
+
+    // the second file can't be written since its directory does not exist
+    let renamed = fs::read_to_string(&config).unwrap();
+    let mut rename = RuleRename::new("no-empty-block", "no-empty");
+    rename
+        .add_config(&config, &renamed)
+        .add_config(dir.join("missing/rslintrc.toml"), &renamed);
+    assert!(rename.commit().is_err());
+    assert_eq!(
+        fs::read_to_string(&config).unwrap(),
+        "[rules.errors]\nno-empty-block = {}\n"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,72 @@
+use rslint_core::rule_prelude::*;
+use rslint_core::{lint_file, CstRuleStore};
+use std::sync::Arc;
+
+declare_lint! {
+    #[derive(Default)]
+    NoFoo,
+    test_plugin,
+    "test-plugin/no-foo"
+}
+
+#[typetag::serde]
+impl CstRule for NoFoo {
+    fn check_node(&self, node: &SyntaxNode, ctx: &mut RuleCtx) -> Option<()> {
+        if node.kind() == SyntaxKind::NAME_REF && node.text() == "foo" {
+            ctx.diagnostic(node, "`foo` is not allowed")
+                .secondary(node.text_range().start()..node.text_range().start(), "here")
+                .footer_help("use `bar` instead")
+                .emit();
+        }
+        None
+    }
+}
+
+fn ctx(src: &str) -> RuleCtx {
+    RuleCtx {
+        file_id: 3,
+        rule: "test-plugin/no-foo",
+        verbose: false,
+        diagnostics: vec![],
+        fixer: None,
+        src: Arc::new(src.to_string()),
+        globals: Default::default(),
+    }
+}
+
+#[test]
+fn diagnostics_have_the_rule_code_and_a_primary_label() {
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(NoFoo::default()) as Box<dyn CstRule>]);
+    let result = lint_file(0, "let a = foo;", false, &store, false).unwrap();
+
+    let diagnostics = result.diagnostics().collect::<Vec<_>>();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("test-plugin/no-foo"));
+    assert_eq!(diagnostics[0].primary.as_ref().unwrap().span.range, 8..11);
+    assert_eq!(diagnostics[0].children.len(), 1);
+}
+
+#[test]
+fn primary_label_can_be_moved_and_labelled() {
+    let mut ctx = ctx("foo bar");
+    ctx.diagnostic(0usize..3, "title")
+        .primary(4usize..7, "moved")
+        .primary_label("renamed")
+        .emit();
+
+    let primary = ctx.diagnostics[0].primary.as_ref().unwrap();
+    assert_eq!(primary.span.range, 4..7);
+    assert_eq!(primary.msg, "renamed");
+    assert_eq!(ctx.diagnostics[0].file_id, 3);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "made a diagnostic with the span 5..20 in a file of 7 bytes")]
+fn spans_past_the_end_of_the_file_are_rejected() {
+    let mut ctx = ctx("foo bar");
+    ctx.diagnostic(0usize..3, "title")
+        .secondary(5usize..20, "")
+        .emit();
+}
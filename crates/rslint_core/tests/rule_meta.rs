@@ -0,0 +1,46 @@
+use rslint_core::{groups::errors::NoEmpty, CstRule, CstRuleStore};
+use serde_json::json;
+
+#[test]
+fn builtin_rule_metadata() {
+    let store = CstRuleStore::new().builtins();
+    let meta = store
+        .metadata()
+        .find(|meta| meta.name == "no-extra-semi")
+        .unwrap();
+    assert_eq!(meta.group, "errors");
+    assert!(meta.recommended);
+    assert!(meta.fixable);
+    assert!(!meta.description.is_empty());
+    assert_eq!(
+        meta.docs_url.as_deref(),
+        Some("https://rdambrosio016.github.io/RSLint/rules/errors/no-extra-semi.html")
+    );
+
+    let eqeqeq = store.get("eqeqeq").unwrap().meta();
+    assert!(!eqeqeq.recommended);
+    assert!(eqeqeq.fixable);
+    assert!(!store.get("no-empty").unwrap().meta().fixable);
+    assert_eq!(store.metadata().count(), store.rules.len());
+
+    let no_empty = store.get("no-empty").unwrap().meta();
+    assert_eq!(no_empty.description, "Disallow empty block statements.");
+    assert_eq!(no_empty.invalid_examples[0], "{}");
+    assert_eq!(no_empty.valid_examples.len(), 1);
+}
+
+#[test]
+fn options_use_default_config() {
+    let rule: Box<dyn CstRule> = Box::new(NoEmpty {
+        disallow_empty_functions: true,
+        ..NoEmpty::default()
+    });
+    let option = rule
+        .meta()
+        .options
+        .into_iter()
+        .find(|option| option.name == "disallowEmptyFunctions")
+        .unwrap();
+    assert_eq!(option.ty, "boolean");
+    assert_eq!(option.default, json!(false));
+}
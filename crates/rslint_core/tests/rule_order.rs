@@ -0,0 +1,66 @@
+//! Rules must not depend on the order they are run in or the order nodes are visited in,
+//! this makes sure every builtin rule produces the same diagnostics regardless of the order.
+
+use rslint_core::{lint_file_with_options, CstRuleStore, LinterOptions};
+use std::collections::BTreeMap;
+
+const SOURCE: &str = r#"
+var a = 5;
+let b = 6, c = 7;
+c++;
+if (typeof a == "strnig") {}
+if (a = b) {
+    debugger;
+}
+for (var i = 0; i < 10; i--) {
+    async function foo() { await bar; }
+}
+const obj = { a: 1, a: 2, hasOwnProperty() {} };
+obj.hasOwnProperty("a");
+switch (a) {
+    case 1: break;
+    case 1: break;
+}
+if (!!a) {};;
+new Symbol();
+if (a === NaN || a === -0) {}
+function get() { return void 0 }
+[1,,2];
+try {} finally { return; }
+"#;
+
+fn diagnostics(options: LinterOptions) -> BTreeMap<&'static str, Vec<String>> {
+    let store = CstRuleStore::new().builtins();
+    let res = lint_file_with_options(0, SOURCE, false, &store, options).expect("linting failed");
+    res.rule_results
+        .into_iter()
+        .map(|(name, result)| {
+            let mut diagnostics: Vec<_> = result
+                .diagnostics
+                .iter()
+                .map(|d| format!("{:?}", d))
+                .collect();
+            diagnostics.sort();
+            (name, diagnostics)
+        })
+        .collect()
+}
+
+#[test]
+fn diagnostics_are_independent_of_rule_order() {
+    let expected = diagnostics(LinterOptions::default());
+    assert!(expected.values().any(|d| !d.is_empty()));
+
+    for seed in 0..20 {
+        let options = LinterOptions {
+            shuffle_seed: Some(seed),
+            ..LinterOptions::default()
+        };
+        assert_eq!(
+            expected,
+            diagnostics(options),
+            "diagnostics differ with shuffle seed {}",
+            seed
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use rslint_core::groups::errors::NoEmpty;
+use rslint_core::session::LintSession;
+use rslint_core::{get_rule_by_name, lint_file_with_options, CstRule, CstRuleStore, LinterOptions};
+
+const SOURCE: &str = "if (a) {} debugger; try {} catch (e) {}";
+
+fn names(store: &CstRuleStore) -> Vec<&'static str> {
+    store.rules.iter().map(|rule| rule.name()).collect()
+}
+
+#[test]
+fn results_are_in_registration_order() {
+    let store = CstRuleStore::new().builtins();
+    for seed in 0..5 {
+        let options = LinterOptions {
+            shuffle_seed: Some(seed),
+            ..LinterOptions::default()
+        };
+        let result = lint_file_with_options(0, SOURCE, false, &store, options).unwrap();
+        assert_eq!(
+            result.rule_results.keys().collect::<Vec<_>>(),
+            names(&store)
+        );
+    }
+}
+
+#[test]
+fn results_can_be_looked_up_by_rule() {
+    let store = CstRuleStore::new().builtins();
+    let result =
+        lint_file_with_options(0, SOURCE, false, &store, LinterOptions::default()).unwrap();
+
+    assert_eq!(result.for_rule("no-empty").unwrap().diagnostics.len(), 3);
+    assert_eq!(result.for_rule("no-debugger").unwrap().diagnostics.len(), 1);
+    assert!(result.for_rule("no-such-rule").is_none());
+}
+
+#[test]
+fn relinted_rules_keep_their_position() {
+    let store = CstRuleStore::new().builtins();
+    let mut session = LintSession::new(store.clone(), LinterOptions::default());
+    session.lint(0, SOURCE, false).unwrap();
+
+    let no_empty = NoEmpty {
+        allow_empty_catch: true,
+        ..NoEmpty::default()
+    };
+    let reloaded = store.with_overrides(vec![Box::new(no_empty) as _]);
+    session.reload_config(reloaded);
+    let result = session.lint(0, SOURCE, false).unwrap();
+
+    assert_eq!(result.for_rule("no-empty").unwrap().diagnostics.len(), 2);
+    assert_eq!(
+        result.rule_results.keys().collect::<Vec<_>>(),
+        names(&store)
+    );
+}
+
+#[test]
+fn kept_results_follow_the_order_of_a_reloaded_store() {
+    let no_empty = NoEmpty {
+        allow_empty_catch: true,
+        ..NoEmpty::default()
+    };
+    let mut store = CstRuleStore::new();
+    store.load_rules(get_rule_by_name("no-debugger"));
+    store.load_rules(get_rule_by_name("no-empty"));
+    let mut session = LintSession::new(store, LinterOptions::default());
+    session.lint(0, SOURCE, false).unwrap();
+
+    let mut reloaded = CstRuleStore::new();
+    reloaded.load_rules(vec![Box::new(no_empty) as Box<dyn CstRule>]);
+    reloaded.load_rules(get_rule_by_name("no-debugger"));
+    session.reload_config(reloaded);
+    let result = session.lint(0, SOURCE, false).unwrap();
+
+    assert_eq!(
+        result.rule_results.keys().collect::<Vec<_>>(),
+        vec!["no-empty", "no-debugger"]
+    );
+}
@@ -0,0 +1,61 @@
+use rslint_core::{lint_file, sarif::sarif_log, CstRuleStore};
+use rslint_errors::file::SimpleFile;
+
+#[test]
+fn sarif_log_describes_rules_regions_and_fixes() {
+    let src = "let a = 5;\n\n{ a;; }\n";
+    let file = SimpleFile::new("src/foo.js".to_string(), src.to_string());
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, src, false, &store, false).unwrap();
+    let log = sarif_log(&[result], &file);
+
+    assert_eq!(log.version, "2.1.0");
+    let run = &log.runs[0];
+    assert_eq!(run.artifacts.len(), 1);
+    assert_eq!(run.artifacts[0].location.uri, "src/foo.js");
+
+    let res = run
+        .results
+        .iter()
+        .find(|res| res.rule_id == "no-extra-semi")
+        .expect("no-extra-semi did not fire");
+    let rule = &run.tool.driver.rules[res.rule_index.unwrap()];
+    assert_eq!(rule.id, "no-extra-semi");
+    assert!(rule
+        .help_uri
+        .as_ref()
+        .unwrap()
+        .ends_with("/rules/errors/no-extra-semi.html"));
+
+    let region = &res.locations[0].physical_location.region;
+    assert_eq!((region.start_line, region.start_column), (3, 5));
+    assert_eq!((region.byte_offset, region.byte_length), (16, 1));
+
+    let replacement = &res.fixes[0].artifact_changes[0].replacements[0];
+    assert_eq!(replacement.deleted_region, *region);
+    assert_eq!(replacement.inserted_content.text, "");
+}
+
+#[test]
+fn sarif_results_include_captures() {
+    let src = "if (foo == -0) {}\n";
+    let file = SimpleFile::new("foo.js".to_string(), src.to_string());
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, src, false, &store, false).unwrap();
+    let log = sarif_log(&[result], &file);
+
+    let res = log.runs[0]
+        .results
+        .iter()
+        .find(|res| res.rule_id == "no-compare-neg-zero")
+        .expect("no-compare-neg-zero did not fire");
+    let captures = &res.properties.as_ref().unwrap().captures;
+    let argument = &captures["argument"];
+    assert_eq!((argument.byte_offset, argument.byte_length), (4, 3));
+    assert_eq!(captures["operator"].start_column, 9);
+    assert!(log.runs[0]
+        .results
+        .iter()
+        .filter(|res| res.rule_id == "no-empty")
+        .all(|res| res.properties.is_none()));
+}
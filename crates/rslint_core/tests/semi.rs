@@ -0,0 +1,28 @@
+use rslint_core::groups::style::Semi;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+use serde_json::json;
+
+fn fix(mode: &str, src: &str) -> String {
+    let rule: Semi = serde_json::from_value(json!({ "mode": mode })).unwrap();
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![Box::new(rule) as Box<dyn CstRule>]);
+    let mut result = lint_file(0, src, true, &store, false).unwrap();
+    result.fix(false).unwrap()
+}
+
+#[test]
+fn missing_semicolons_are_inserted() {
+    assert_eq!(
+        fix("always", "let a = 5 // five\nfoo()\nexport { a }\n"),
+        "let a = 5; // five\nfoo();\nexport { a };\n"
+    );
+}
+
+#[test]
+fn semicolons_are_only_removed_if_asi_ends_the_statement() {
+    let src = "let a = 5;\nfoo();\n[1, 2].forEach(bar);\n(a || b).c();\nbaz(); qux();\n";
+    assert_eq!(
+        fix("never", src),
+        "let a = 5\nfoo();\n[1, 2].forEach(bar);\n(a || b).c()\nbaz(); qux()\n"
+    );
+}
@@ -0,0 +1,127 @@
+use rslint_core::groups::errors::NoEmpty;
+use rslint_core::rule_prelude::*;
+use rslint_core::session::LintSession;
+use rslint_core::{get_rule_by_name, CstRuleStore, LinterOptions};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+declare_lint! {
+    #[derive(Default)]
+    CountRuns,
+    test_plugin,
+    "test-plugin/count-runs"
+}
+
+#[typetag::serde]
+impl CstRule for CountRuns {
+    fn check_root(&self, _root: &SyntaxNode, _ctx: &mut RuleCtx) -> Option<()> {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        None
+    }
+}
+
+fn store(no_empty: NoEmpty) -> CstRuleStore {
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![
+        Box::new(no_empty) as Box<dyn CstRule>,
+        Box::new(CountRuns::default()),
+    ]);
+    store
+}
+
+fn problems(session: &mut LintSession, file_id: usize, src: &str) -> usize {
+    session
+        .lint(file_id, src, false)
+        .unwrap()
+        .diagnostics()
+        .count()
+}
+
+#[test]
+fn only_changed_rules_are_run_again() {
+    let src = "try { foo() } catch (e) {}";
+    let mut session = LintSession::new(store(NoEmpty::default()), LinterOptions::default());
+    assert_eq!(problems(&mut session, 0, src), 1);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    // nothing changed, the cached results are used
+    assert_eq!(problems(&mut session, 0, src), 1);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    let change = session.reload_config(store(NoEmpty {
+        allow_empty_catch: true,
+        ..NoEmpty::default()
+    }));
+    assert_eq!(change.changed, vec!["no-empty"]);
+    assert!(change.added.is_empty() && change.removed.is_empty());
+    assert_eq!(change.affected_files, vec![0]);
+    assert_eq!(problems(&mut session, 0, src), 0);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    // a changed file is linted fully
+    assert_eq!(problems(&mut session, 0, "{}"), 1);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn unchanged_config_affects_nothing() {
+    let mut session = LintSession::new(CstRuleStore::new().builtins(), LinterOptions::default());
+    problems(&mut session, 0, "{}");
+    let change = session.reload_config(CstRuleStore::new().builtins());
+    assert!(change.is_empty());
+    assert!(change.affected_files.is_empty());
+}
+
+#[test]
+fn added_and_removed_rules() {
+    let mut store = CstRuleStore::new();
+    store.load_rules(get_rule_by_name("no-empty"));
+    let mut session = LintSession::new(store, LinterOptions::default());
+    assert_eq!(problems(&mut session, 0, "{}\ndebugger;"), 1);
+    assert_eq!(problems(&mut session, 1, "foo;"), 0);
+    assert_eq!(
+        problems(&mut session, 2, "// rslint-ignore no-debugger\n\ndebugger;"),
+        1
+    );
+    assert_eq!(problems(&mut session, 3, "// rslint-ignore\n\n{}"), 0);
+
+    let mut debugger = CstRuleStore::new();
+    debugger.load_rules(get_rule_by_name("no-debugger"));
+    let change = session.reload_config(debugger);
+    assert_eq!(change.added, vec!["no-debugger"]);
+    assert_eq!(change.removed, vec!["no-empty"]);
+    // the ignore directive of the third file is no longer redundant, the last file ignores every rule
+    assert_eq!(change.affected_files, vec![0, 1, 2]);
+
+    assert_eq!(problems(&mut session, 0, "{}\ndebugger;"), 1);
+    assert_eq!(problems(&mut session, 1, "foo;"), 0);
+    assert_eq!(
+        problems(&mut session, 2, "// rslint-ignore no-debugger\n\ndebugger;"),
+        0
+    );
+}
+
+#[test]
+fn only_rules_named_by_directives_affect_ignored_files() {
+    let mut store = CstRuleStore::new();
+    store.load_rules(get_rule_by_name("no-empty"));
+    let mut session = LintSession::new(store, LinterOptions::default());
+    problems(&mut session, 0, "// rslint-ignore\n\nfoo('no-empty');");
+    problems(
+        &mut session,
+        1,
+        "// rslint-ignore\n// rslint-disable-next-line no-empty\n{}",
+    );
+    problems(
+        &mut session,
+        2,
+        "// rslint-ignore\n// rslint-disable-next-line my-plugin/no-empty\n{}",
+    );
+
+    let mut debugger = CstRuleStore::new();
+    debugger.load_rules(get_rule_by_name("no-debugger"));
+    let change = session.reload_config(debugger);
+    assert_eq!(change.removed, vec!["no-empty"]);
+    assert_eq!(change.affected_files, vec![1]);
+}
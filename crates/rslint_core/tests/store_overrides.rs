@@ -0,0 +1,53 @@
+use rslint_core::groups::errors::NoEmpty;
+use rslint_core::{lint_file, CstRule, CstRuleStore};
+use std::sync::Arc;
+
+fn no_empty(allow_empty_catch: bool) -> Box<dyn CstRule> {
+    Box::new(NoEmpty {
+        allow_empty_catch,
+        ..NoEmpty::default()
+    })
+}
+
+#[test]
+fn unchanged_rules_are_shared() {
+    let store = CstRuleStore::new().builtins();
+    let overridden = store.with_overrides(vec![no_empty(true)]);
+
+    assert_eq!(store.rules.len(), overridden.rules.len());
+    for (old, new) in store.rules.iter().zip(overridden.rules.iter()) {
+        assert_eq!(old.name(), new.name());
+        assert_eq!(Arc::ptr_eq(old, new), old.name() != "no-empty");
+    }
+}
+
+#[test]
+fn overrides_with_the_same_config_are_shared() {
+    let store = CstRuleStore::new().builtins();
+    let overridden = store.with_overrides(vec![no_empty(false)]);
+
+    assert!(store
+        .rules
+        .iter()
+        .zip(overridden.rules.iter())
+        .all(|(old, new)| Arc::ptr_eq(old, new)));
+}
+
+#[test]
+fn overrides_for_missing_rules_are_ignored() {
+    let store = CstRuleStore::new();
+    assert!(store.with_overrides(vec![no_empty(true)]).rules.is_empty());
+}
+
+#[test]
+fn overridden_config_is_used() {
+    let mut store = CstRuleStore::new();
+    store.load_rules(vec![no_empty(false)]);
+    let overridden = store.with_overrides(vec![no_empty(true)]);
+    let src = "try { foo() } catch (e) {}";
+
+    let res = lint_file(0, src, false, &store, false).unwrap();
+    assert_eq!(res.diagnostics().count(), 1);
+    let res = lint_file(0, src, false, &overridden, false).unwrap();
+    assert_eq!(res.diagnostics().count(), 0);
+}
@@ -0,0 +1,107 @@
+use rslint_core::{lint_file, CstRuleStore};
+use rslint_errors::Applicability;
+
+#[test]
+fn suggestions_are_not_applied_by_autofix() {
+    let store = CstRuleStore::new().builtins();
+    let src = "if (foo == bar) {}\n";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+
+    let diagnostic = &result.rule_results["eqeqeq"].diagnostics[0];
+    let suggestion = &diagnostic.suggestions[0];
+    assert_eq!(suggestion.applicability, Applicability::MaybeIncorrect);
+    assert_eq!(suggestion.replacement(src).as_deref(), Some("==="));
+    assert_eq!(&src[suggestion.span.range.clone()], "==");
+
+    assert_eq!(result.fix(false).as_deref(), Some(src));
+}
+
+#[test]
+fn comparisons_of_the_same_type_are_fixed() {
+    let mut store = CstRuleStore::new().builtins();
+    store.rules.retain(|rule| rule.name() == "eqeqeq");
+    let src = "if (typeof foo == 'string' || 'id-' + id != `id-${1 + 1}` || 1 + 2 == 3) {}\n";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+    assert_eq!(
+        result.fix(false).as_deref(),
+        Some("if (typeof foo === 'string' || 'id-' + id !== `id-${1 + 1}` || 1 + 2 === 3) {}\n")
+    );
+
+    // `foo + 1` may be a number or a string
+    let src = "foo + 1 == 'a1'";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+    assert_eq!(result.fix(false).as_deref(), Some(src));
+}
+
+#[test]
+fn var_declarations_are_only_fixed_if_they_are_not_hoisted() {
+    let mut store = CstRuleStore::new().builtins();
+    store.rules.retain(|rule| rule.name() == "no-var");
+    let src = "function foo() { for (var a of b) { use(a); } var c = 1; c++; }\n";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+    assert_eq!(
+        result.fix(false).as_deref(),
+        Some("function foo() { for (const a of b) { use(a); } let c = 1; c++; }\n")
+    );
+
+    let src = "function foo() { use(a); var a = 1; if (b) { var c = 2; } return c; }\n";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+    assert_eq!(result.fix(false).as_deref(), Some(src));
+
+    // `let` and `const` can not be the body of a statement
+    for src in [
+        "function foo() { if (x) var a = 1; }\n",
+        "function foo() { while (x) var a = 1; }\n",
+    ]
+    .iter()
+    .copied()
+    {
+        let mut result = lint_file(0, src, false, &store, false).unwrap();
+        assert_eq!(result.for_rule("no-var").unwrap().diagnostics.len(), 1);
+        assert_eq!(result.fix(false).as_deref(), Some(src));
+    }
+}
+
+#[test]
+fn redundant_boolean_casts_are_removed() {
+    let mut store = CstRuleStore::new().builtins();
+    store
+        .rules
+        .retain(|rule| rule.name() == "no-extra-boolean-cast");
+    let fix = |src: &str| {
+        let mut result = lint_file(0, src, false, &store, false).unwrap();
+        result.fix(false).unwrap()
+    };
+
+    assert_eq!(fix("if (!!foo) {}"), "if (foo) {}");
+    assert_eq!(fix("while (Boolean(a && b)) {}"), "while (a && b) {}");
+    assert_eq!(fix("!!!foo"), "!foo");
+    assert_eq!(fix("!Boolean(a && b)"), "!(a && b)");
+    assert_eq!(fix("Boolean(a ? b : c) ? d : e"), "(a ? b : c) ? d : e");
+    assert_eq!(fix("if (Boolean()) {}"), "if (false) {}");
+    assert_eq!(fix("!Boolean(Boolean(!!a))"), "!a");
+    // comments would be lost and multiple arguments may have side effects
+    assert_eq!(fix("if (!/**/!foo) {}"), "if (!/**/!foo) {}");
+    assert_eq!(fix("if (Boolean(a, b())) {}"), "if (Boolean(a, b())) {}");
+    assert_eq!(fix("if (Boolean(...a)) {}"), "if (Boolean(...a)) {}");
+}
+
+#[test]
+fn only_typeof_literals_are_fixed() {
+    let mut store = CstRuleStore::new().builtins();
+    store.rules.retain(|rule| rule.name() == "valid-typeof");
+    let src = "typeof a === 'String'; typeof b === 'Num' + 'ber';\n";
+    let mut result = lint_file(0, src, false, &store, false).unwrap();
+
+    let diagnostics = &result.for_rule("valid-typeof").unwrap().diagnostics;
+    assert_eq!(diagnostics.len(), 2);
+    let folded = &diagnostics[1].suggestions[0];
+    assert_eq!(folded.applicability, Applicability::MaybeIncorrect);
+    assert_eq!(&src[folded.span.range.clone()], "'Num' + 'ber'");
+    assert_eq!(folded.replacement(src).as_deref(), Some("\"number\""));
+
+    assert_eq!(
+        result.fix(false).as_deref(),
+        Some("typeof a === 'string'; typeof b === 'Num' + 'ber';\n")
+    );
+}
@@ -0,0 +1,69 @@
+use rslint_core::util::tokens::*;
+use rslint_parser::{parse_text, SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// The first token with the given text.
+fn token(root: &SyntaxNode, text: &str) -> SyntaxToken {
+    root.descendants_with_tokens()
+        .filter_map(|elem| elem.into_token())
+        .find(|token| token.text() == text)
+        .unwrap()
+}
+
+#[test]
+fn skips_whitespace_and_comments() {
+    let src = "foo /* a */ ( // b\n bar);";
+    let root = parse_text(src, 0).syntax();
+    let open = token(&root, "(");
+    assert_eq!(prev_non_trivia(&open).unwrap().text(), "foo");
+    assert_eq!(next_non_trivia(&open).unwrap().text(), "bar");
+    assert_eq!(
+        trivia_after(&open)
+            .iter()
+            .map(|token| token.text().to_string())
+            .collect::<Vec<_>>(),
+        vec![" ", "// b", "\n "]
+    );
+    assert_eq!(
+        trivia_before(&open)
+            .iter()
+            .map(|token| token.text().to_string())
+            .collect::<Vec<_>>(),
+        vec![" ", "/* a */", " "]
+    );
+}
+
+#[test]
+fn first_and_last_tokens_of_nodes() {
+    let stmt = token(&parse_text("foo(a) /* b */;", 0).syntax(), "foo")
+        .parent()
+        .ancestors()
+        .find(|node| node.kind() == SyntaxKind::EXPR_STMT)
+        .unwrap();
+    assert_eq!(first_non_trivia(&stmt).unwrap().text(), "foo");
+    assert_eq!(last_non_trivia(&stmt).unwrap().text(), ";");
+}
+
+#[test]
+fn same_line() {
+    let src = "a + /* \n */ b\n+ c";
+    let root = parse_text(src, 0).syntax();
+    let a = token(&root, "a");
+    let b = token(&root, "b");
+    let plus = token(&root, "+");
+    assert!(on_same_line(&a, &plus));
+    assert!(on_same_line(&plus, &a));
+    assert!(!on_same_line(&plus, &b));
+    assert!(!on_same_line(&b, &token(&root, "c")));
+    assert!(on_same_line(&a, &a));
+}
+
+#[test]
+fn indentation() {
+    let src = "if (a) {\n    foo(bar);\n\tbaz();\n}";
+    let root = parse_text(src, 0).syntax();
+    assert_eq!(line_indentation(&token(&root, "if")), "");
+    assert_eq!(line_indentation(&token(&root, "foo")), "    ");
+    assert_eq!(line_indentation(&token(&root, "bar")), "    ");
+    assert_eq!(line_indentation(&token(&root, "baz")), "\t");
+    assert_eq!(line_indentation(&token(&root, "}")), "");
+}
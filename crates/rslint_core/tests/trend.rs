@@ -0,0 +1,87 @@
+use rslint_core::profile::{self, Phase, PhaseTimings};
+use rslint_core::trend::{self, TrendSnapshot};
+use rslint_core::{autofix::recursively_apply_fixes, lint_file, CstRuleStore};
+use std::time::Duration;
+
+#[test]
+fn counts_diagnostics_by_rule_and_severity() {
+    let store = CstRuleStore::new().builtins();
+    let a = lint_file(0, "if (true) {}", false, &store, false).unwrap();
+    let b = lint_file(1, "if (false) {} let", false, &store, false).unwrap();
+    let snapshot = TrendSnapshot::from_results(&[a, b]);
+
+    assert_eq!(snapshot.files, 2);
+    assert_eq!(snapshot.rules["no-constant-condition"].errors, 2);
+    assert!(snapshot.total().errors >= 3);
+    assert_eq!(snapshot.timings, None);
+}
+
+#[test]
+fn merging_adds_counts_and_timings() {
+    let timings = PhaseTimings {
+        parse: Duration::from_millis(2),
+        rules: Duration::from_millis(5),
+        ..PhaseTimings::default()
+    };
+    let mut a = TrendSnapshot::new().with_timings(timings);
+    a.files = 1;
+    a.rules.entry("eqeqeq".into()).or_default().warnings = 2;
+    let mut b = TrendSnapshot::new();
+    b.files = 3;
+    b.rules.entry("eqeqeq".into()).or_default().errors = 1;
+    b.rules.entry("no-empty".into()).or_default().errors = 4;
+    let c = TrendSnapshot::new().with_timings(timings);
+
+    let merged = trend::merge(vec![a, b, c]);
+    assert_eq!(merged.files, 4);
+    assert_eq!(merged.rules["eqeqeq"].errors, 1);
+    assert_eq!(merged.rules["eqeqeq"].warnings, 2);
+    assert_eq!(merged.rules["no-empty"].total(), 4);
+
+    let merged_timings = merged.timings.unwrap();
+    assert_eq!(merged_timings.parse, Duration::from_millis(4));
+    assert_eq!(merged_timings.get(Phase::Rules), Duration::from_millis(10));
+    assert_eq!(merged_timings.fix, Duration::ZERO);
+}
+
+#[test]
+fn snapshots_roundtrip_through_json() {
+    let store = CstRuleStore::new().builtins();
+    let result = lint_file(0, "if (true) {}", false, &store, false).unwrap();
+    let snapshot = TrendSnapshot::from_results(&[result]);
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert!(!json.contains("timings"));
+    assert_eq!(
+        serde_json::from_str::<TrendSnapshot>(&json).unwrap(),
+        snapshot
+    );
+
+    let snapshot = snapshot.with_timings(PhaseTimings {
+        scope: Duration::from_micros(300),
+        ..PhaseTimings::default()
+    });
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        serde_json::from_str::<TrendSnapshot>(&json).unwrap(),
+        snapshot
+    );
+}
+
+#[test]
+fn profiling_records_phase_timings() {
+    let store = CstRuleStore::new().builtins();
+    profile::enable();
+    let mut result = lint_file(0, "let a = 5; a == 5; a;", false, &store, false).unwrap();
+    recursively_apply_fixes(&mut result);
+
+    let phases = profile::phases();
+    assert!(phases.parse > Duration::ZERO);
+    assert!(phases.rules > Duration::ZERO);
+    assert!(phases.fix > Duration::ZERO);
+
+    let snapshot = TrendSnapshot::from_results(&[result]).with_timings(phases);
+    assert_eq!(snapshot.timings, Some(phases));
+
+    profile::reset();
+    assert_eq!(profile::phases(), PhaseTimings::ZERO);
+}
@@ -7,8 +7,8 @@ use std::ops::Range;
 /// A severity level for diagnostic messages.
 ///
 /// These are ordered in the following way:
-#[derive(Copy, Clone, PartialEq, Hash, Debug)]
-#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Severity {
     /// An unexpected bug.
     Bug,
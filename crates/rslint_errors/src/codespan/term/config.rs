@@ -25,6 +25,10 @@ pub struct Config {
     ///
     /// Defaults to: `1`.
     pub end_context_lines: usize,
+    /// Whether to show how many lines were skipped when a section of source is elided.
+    ///
+    /// Defaults to: `true`.
+    pub show_elided_line_count: bool,
 }
 
 impl Default for Config {
@@ -36,6 +40,7 @@ impl Default for Config {
             chars: Chars::default(),
             start_context_lines: 3,
             end_context_lines: 1,
+            show_elided_line_count: true,
         }
     }
 }
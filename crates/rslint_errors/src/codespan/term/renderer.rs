@@ -616,7 +616,7 @@ impl<'writer, 'config> Renderer<'writer, 'config> {
     /// A broken source line, for labeling skipped sections of source.
     ///
     /// ```text
-    /// · │ │
+    /// · │ │  … 42 lines …
     /// ```
     pub fn render_snippet_break(
         &mut self,
@@ -624,10 +624,21 @@ impl<'writer, 'config> Renderer<'writer, 'config> {
         severity: Severity,
         num_multi_labels: usize,
         multi_labels: &[(usize, LabelStyle, MultiLabel<'_>)],
+        elided_lines: usize,
     ) -> Result<(), Error> {
         self.outer_gutter(outer_padding)?;
         self.border_left_break()?;
         self.inner_gutter(severity, num_multi_labels, multi_labels)?;
+        if self.config.show_elided_line_count && elided_lines > 0 {
+            self.set_color(&self.styles().source_border)?;
+            write!(
+                self,
+                " … {} line{} …",
+                elided_lines,
+                if elided_lines == 1 { "" } else { "s" }
+            )?;
+            self.reset()?;
+        }
         writeln!(self)?;
         Ok(())
     }
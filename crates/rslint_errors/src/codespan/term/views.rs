@@ -364,6 +364,7 @@ where
                                 self.diagnostic.severity,
                                 labeled_file.num_multi_labels,
                                 &line.multi_labels,
+                                next_line_index.saturating_sub(*line_index + 1),
                             )?;
                         }
                     }
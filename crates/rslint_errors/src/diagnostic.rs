@@ -4,10 +4,13 @@ use crate::{
     Applicability, CodeSuggestion, DiagnosticTag, Severity, SuggestionStyle,
 };
 use rslint_text_edit::*;
+use std::collections::BTreeMap;
+use std::ops::Range;
 
 /// A diagnostic message that can give information
 /// like errors or warnings.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Diagnostic {
     pub file_id: FileId,
 
@@ -20,6 +23,14 @@ pub struct Diagnostic {
     pub children: Vec<SubDiagnostic>,
     pub suggestions: Vec<CodeSuggestion>,
     pub footers: Vec<Footer>,
+    /// Named spans of the nodes the diagnostic is about (e.g. `callee`), for tools which act on diagnostics
+    /// without parsing their message. The spans are in the file of the diagnostic.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub captures: BTreeMap<String, Range<usize>>,
+    /// The span of the directive comment which suppressed this diagnostic. Suppressed diagnostics are
+    /// only kept when asked for, formatters render them grayed out and pointing to the directive.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub suppressed_by: Option<Range<usize>>,
 }
 
 impl Diagnostic {
@@ -67,6 +78,8 @@ impl Diagnostic {
             children: vec![],
             suggestions: vec![],
             footers: vec![],
+            captures: BTreeMap::new(),
+            suppressed_by: None,
         }
     }
 
@@ -395,11 +408,28 @@ impl Diagnostic {
     pub fn footer_note(self, msg: impl Into<String>) -> Self {
         self.footer(Severity::Note, msg)
     }
+
+    /// Attaches a named span to this `Diagnostic`, which is not rendered but is included in
+    /// machine readable output such as JSON and SARIF.
+    ///
+    /// Capturing a name again replaces its span.
+    pub fn capture(mut self, name: impl Into<String>, span: impl Span) -> Self {
+        self.captures.insert(name.into(), span.as_range());
+        self
+    }
+
+    /// Marks this `Diagnostic` as suppressed by the directive comment at `span`, which is in the file
+    /// of the diagnostic.
+    pub fn suppressed_by(mut self, span: impl Span) -> Self {
+        self.suppressed_by = Some(span.as_range());
+        self
+    }
 }
 
 /// Everything that can be added to a diagnostic, like
 /// a suggestion that will be displayed under the actual error.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SubDiagnostic {
     pub severity: Severity,
     pub msg: String,
@@ -408,6 +438,7 @@ pub struct SubDiagnostic {
 
 /// A note or help that is displayed under the diagnostic.
 #[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Footer {
     pub msg: String,
     pub severity: Severity,
@@ -6,11 +6,11 @@ use crate::codespan::diagnostic::{Diagnostic as CodespanDiag, Label, LabelStyle,
 use crate::codespan::files::Error;
 use crate::codespan::term::{
     emit,
-    termcolor::{ColorChoice, StandardStream, WriteColor},
+    termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor},
     *,
 };
 use crate::{
-    file::{Files, SimpleFile},
+    file::{column_width, Files, SimpleFile},
     suggestion::*,
     Diagnostic,
 };
@@ -95,6 +95,26 @@ impl<'a> codespan::files::Files<'a> for EmitterFiles<'_> {
                 }),
         }
     }
+
+    fn column_number(
+        &self,
+        id: EmitterFileId,
+        line_index: usize,
+        byte_index: usize,
+    ) -> Result<usize, Error> {
+        let source = self.source(id)?;
+        let line_range = self.line_range(id, line_index)?;
+        let mut end = byte_index.min(line_range.end).min(source.len());
+        while !source.is_char_boundary(end) {
+            end -= 1;
+        }
+        let tab_width = match id {
+            EmitterFileId::Real(_) => self.real_files.tab_width(),
+            EmitterFileId::Virtual(_) => 1,
+        };
+        let line = source.get(line_range.start..end).unwrap_or_default();
+        Ok(column_width(line, tab_width) + 1)
+    }
 }
 
 fn default_config() -> Config {
@@ -104,16 +124,104 @@ fn default_config() -> Config {
     config
 }
 
+/// The config used for suppressed diagnostics, which renders everything grayed out.
+fn suppressed_config() -> Config {
+    let mut config = default_config();
+    let gray = ColorSpec::new().set_fg(Some(Color::Ansi256(244))).clone();
+    let styles = &mut config.styles;
+    for style in [
+        &mut styles.header_bug,
+        &mut styles.header_error,
+        &mut styles.header_warning,
+        &mut styles.header_note,
+        &mut styles.header_help,
+        &mut styles.header_message,
+        &mut styles.primary_label_bug,
+        &mut styles.primary_label_error,
+        &mut styles.primary_label_warning,
+        &mut styles.primary_label_note,
+        &mut styles.primary_label_help,
+        &mut styles.secondary_label,
+    ] {
+        *style = gray.clone();
+    }
+    config
+}
+
+/// Shrink a label's range so it does not start or end on whitespace, and grow it so it
+/// does not start or end in the middle of an identifier or number.
+fn snap_range(src: &str, range: Range<usize>) -> Range<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let (mut start, mut end) = (range.start.min(src.len()), range.end.min(src.len()));
+    if !src.is_char_boundary(start) || !src.is_char_boundary(end) || start >= end {
+        return range;
+    }
+
+    let text = &src[start..end];
+    if !text.trim().is_empty() {
+        start += text.len() - text.trim_start().len();
+        end -= text.len() - text.trim_end().len();
+    }
+
+    while let Some(c) = src[..start].chars().next_back().filter(|c| is_word(*c)) {
+        if !src[start..].starts_with(is_word) {
+            break;
+        }
+        start -= c.len_utf8();
+    }
+    while let Some(c) = src[end..].chars().next().filter(|c| is_word(*c)) {
+        if !src[..end].ends_with(is_word) {
+            break;
+        }
+        end += c.len_utf8();
+    }
+    start..end
+}
+
+/// Cut a label message down to `max` characters, ending it with `…` if it was cut.
+fn truncate_message(msg: String, max: usize) -> String {
+    if msg.chars().count() <= max {
+        return msg;
+    }
+    let mut truncated = msg
+        .chars()
+        .take(max.saturating_sub(1))
+        .collect::<String>()
+        .trim_end()
+        .to_string();
+    truncated.push('…');
+    truncated
+}
+
 /// The emitter is responsible for emitting
 /// diagnostics to a given output.
 pub struct Emitter<'files> {
     files: &'files dyn Files,
+    max_label_width: Option<usize>,
+    snap_labels: bool,
 }
 
 impl<'files> Emitter<'files> {
     /// Creates a new `Emitter`.
     pub fn new(files: &'files dyn Files) -> Self {
-        Self { files }
+        Self {
+            files,
+            max_label_width: None,
+            snap_labels: true,
+        }
+    }
+
+    /// Truncate label messages longer than `width` characters, by default messages are never truncated.
+    pub fn with_max_label_width(mut self, width: impl Into<Option<usize>>) -> Self {
+        self.max_label_width = width.into();
+        self
+    }
+
+    /// Whether to snap label ranges to token boundaries, this trims any leading or trailing
+    /// whitespace and extends ranges which cut an identifier in half. Enabled by default.
+    pub fn snap_labels(mut self, snap: bool) -> Self {
+        self.snap_labels = snap;
+        self
     }
 }
 
@@ -149,7 +257,7 @@ impl Emitter<'_> {
         d: &Diagnostic,
         writer: &mut dyn WriteColor,
     ) -> Result<(), Error> {
-        let labels = d
+        let mut labels = d
             .children
             .iter()
             .chain(d.primary.as_ref())
@@ -159,14 +267,28 @@ impl Emitter<'_> {
                 } else {
                     LabelStyle::Secondary
                 };
+                let range = match self.files.source(d.file_id) {
+                    Some(src) if self.snap_labels => snap_range(src, sub.span.range.clone()),
+                    _ => sub.span.range.clone(),
+                };
+                let msg = match self.max_label_width {
+                    Some(width) => truncate_message(sub.msg.clone(), width),
+                    None => sub.msg.clone(),
+                };
+                Label::new(style, EmitterFileId::Real(d.file_id), range).with_message(msg)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(directive) = d.suppressed_by.clone() {
+            labels.push(
                 Label::new(
-                    style,
+                    LabelStyle::Secondary,
                     EmitterFileId::Real(d.file_id),
-                    sub.span.range.clone(),
+                    directive,
                 )
-                .with_message(sub.msg.clone())
-            })
-            .collect::<Vec<_>>();
+                .with_message("suppressed by this directive"),
+            );
+        }
 
         let mut diagnostic = CodespanDiag {
             severity: d.severity,
@@ -262,8 +384,13 @@ impl Emitter<'_> {
             real_files: self.files,
             virtual_files,
         };
+        let config = if d.suppressed_by.is_some() {
+            suppressed_config()
+        } else {
+            default_config()
+        };
         for diag in iter {
-            emit(writer, &default_config(), &files, &diag)?;
+            emit(writer, &config, &files, &diag)?;
         }
         writer.write(b"\n").map(|_| ()).map_err(Error::Io)
     }
@@ -118,7 +118,7 @@ impl Span for TextRange {
 pub type FileId = usize;
 
 /// A range that is indexed in a specific file.
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FileSpan {
     pub file: FileId,
@@ -160,6 +160,40 @@ pub trait Files {
 
     /// The byte range of line in the source of the file.
     fn line_range(&self, id: FileId, line_index: usize) -> Option<Range<usize>>;
+
+    /// The number of columns a tab advances to the next tab stop in reported columns,
+    /// `1` counts a tab as a single column like any other character.
+    fn tab_width(&self) -> usize {
+        1
+    }
+
+    /// The one based line and column of a byte index, see [`column_width`] for how columns are counted.
+    fn location(&self, file_id: FileId, byte_index: usize) -> Option<(usize, usize)> {
+        let line_index = self.line_index(file_id, byte_index)?;
+        let line_start = self.line_range(file_id, line_index)?.start;
+        let line = self.source(file_id)?.get(line_start..byte_index)?;
+        Some((line_index + 1, column_width(line, self.tab_width()) + 1))
+    }
+}
+
+/// The number of columns the text of a line takes up, this is the number of unicode scalar values
+/// with tabs advancing to the next multiple of `tab_width`.
+///
+/// ```
+/// use rslint_errors::file::column_width;
+///
+/// assert_eq!(column_width("\tfoo", 1), 4);
+/// assert_eq!(column_width("\tfoo", 4), 7);
+/// assert_eq!(column_width("a\tb", 4), 5);
+/// ```
+pub fn column_width(text: &str, tab_width: usize) -> usize {
+    text.chars().fold(0, |column, c| {
+        if c == '\t' && tab_width > 1 {
+            column + tab_width - column % tab_width
+        } else {
+            column + 1
+        }
+    })
 }
 
 /// A file database that contains only one file.
@@ -168,6 +202,7 @@ pub struct SimpleFile {
     name: String,
     source: String,
     line_starts: Vec<usize>,
+    tab_width: usize,
 }
 
 impl SimpleFile {
@@ -177,9 +212,16 @@ impl SimpleFile {
             line_starts: line_starts(&source).collect(),
             name,
             source,
+            tab_width: 1,
         }
     }
 
+    /// Expand tabs to `tab_width` columns in reported columns.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
     fn line_start(&self, line_index: usize) -> Option<usize> {
         use std::cmp::Ordering;
 
@@ -214,6 +256,10 @@ impl Files for SimpleFile {
 
         Some(line_start..next_line_start)
     }
+
+    fn tab_width(&self) -> usize {
+        self.tab_width
+    }
 }
 
 /// A file database that stores multiple files.
@@ -259,6 +305,165 @@ impl Files for SimpleFiles {
     }
 }
 
+/// Renders the paths of files in reports, relative to a root directory and with forward slashes, so reports
+/// generated on different platforms are identical.
+///
+/// Paths are normalized lexically, `.` segments are removed and `..` segments are resolved without touching the file system.
+/// Both `/` and `\\` are treated as separators on every platform.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathDisplay {
+    root: Option<NormalizedPath>,
+    base: Option<NormalizedPath>,
+}
+
+impl PathDisplay {
+    /// Render paths relative to `root`. Relative paths are relative to `base`, usually the current directory,
+    /// and relative roots are relative to `base` too.
+    pub fn relative_to(root: &str, base: &str) -> Self {
+        let base = NormalizedPath::new(base);
+        Self {
+            root: Some(base.join(NormalizedPath::new(root))),
+            base: Some(base),
+        }
+    }
+
+    /// The normalized path, relative to the root if there is one and the path is inside of it.
+    /// Paths outside of the root are absolute.
+    pub fn display(&self, path: &str) -> String {
+        let path = NormalizedPath::new(path);
+        let root = match self.root {
+            Some(ref root) => root,
+            None => return path.to_string(),
+        };
+        let path = match self.base {
+            Some(ref base) => base.join(path),
+            None => path,
+        };
+        if !path.prefix.eq_ignore_ascii_case(&root.prefix)
+            || !path.segments.starts_with(&root.segments)
+        {
+            return path.to_string();
+        }
+        NormalizedPath {
+            prefix: String::new(),
+            segments: path.segments[root.segments.len()..].to_vec(),
+        }
+        .to_string()
+    }
+}
+
+/// A lexically normalized path, `prefix` is `/`, a drive such as `C:/`, or empty for relative paths.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NormalizedPath {
+    prefix: String,
+    segments: Vec<String>,
+}
+
+impl NormalizedPath {
+    fn new(path: &str) -> Self {
+        let path = path.replace('\\', "/");
+        let (prefix, rest) = if let Some(rest) = path.strip_prefix('/') {
+            ("/".to_string(), rest)
+        } else if path.len() >= 2 && path.as_bytes()[1] == b':' && path.is_char_boundary(2) {
+            (
+                format!("{}/", &path[..2]),
+                path[2..].trim_start_matches('/'),
+            )
+        } else {
+            (String::new(), path.as_str())
+        };
+
+        let mut segments: Vec<String> = vec![];
+        for segment in rest.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." if matches!(segments.last(), Some(last) if last != "..") => {
+                    segments.pop();
+                }
+                // the parent of the root is the root
+                ".." if !prefix.is_empty() => {}
+                _ => segments.push(segment.to_string()),
+            }
+        }
+        Self { prefix, segments }
+    }
+
+    fn join(&self, other: NormalizedPath) -> NormalizedPath {
+        if !other.prefix.is_empty() {
+            return other;
+        }
+        let joined = format!("{}/{}", self, other);
+        NormalizedPath::new(&joined)
+    }
+}
+
+impl std::fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.prefix.is_empty() && self.segments.is_empty() {
+            return write!(f, ".");
+        }
+        write!(f, "{}{}", self.prefix, self.segments.join("/"))
+    }
+}
+
+/// A file database which presents the names of the files of another database with a [`PathDisplay`].
+#[derive(Clone)]
+pub struct DisplayFiles<'a> {
+    files: &'a dyn Files,
+    names: HashMap<FileId, String>,
+    tab_width: usize,
+}
+
+impl<'a> DisplayFiles<'a> {
+    /// Render the names of the files with `ids` in `files`, other files keep their name.
+    pub fn new(
+        files: &'a dyn Files,
+        paths: &PathDisplay,
+        ids: impl IntoIterator<Item = FileId>,
+    ) -> Self {
+        let names = ids
+            .into_iter()
+            .filter_map(|id| Some((id, paths.display(files.name(id)?))))
+            .collect();
+        Self {
+            tab_width: files.tab_width(),
+            files,
+            names,
+        }
+    }
+
+    /// Expand tabs to `tab_width` columns in reported columns.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+}
+
+impl Files for DisplayFiles<'_> {
+    fn name(&self, id: FileId) -> Option<&str> {
+        match self.names.get(&id) {
+            Some(name) => Some(name),
+            None => self.files.name(id),
+        }
+    }
+
+    fn source(&self, id: FileId) -> Option<&str> {
+        self.files.source(id)
+    }
+
+    fn line_index(&self, id: FileId, byte_index: usize) -> Option<usize> {
+        self.files.line_index(id, byte_index)
+    }
+
+    fn line_range(&self, id: FileId, line_index: usize) -> Option<Range<usize>> {
+        self.files.line_range(id, line_index)
+    }
+
+    fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+}
+
 /// Computes the byte indicies of every line start.
 pub fn line_starts(source: &str) -> impl '_ + Iterator<Item = usize> {
     std::iter::once(0).chain(
@@ -267,3 +472,37 @@ pub fn line_starts(source: &str) -> impl '_ + Iterator<Item = usize> {
             .map(|(i, _)| i + 1),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Files, PathDisplay, SimpleFile};
+
+    #[test]
+    fn columns_expand_tabs() {
+        let source = "{\n\t\tfoo;\n}\n".to_string();
+        let file = SimpleFile::new("foo.js".to_string(), source);
+        assert_eq!(file.location(0, 4), Some((2, 3)));
+        assert_eq!(file.with_tab_width(4).location(0, 4), Some((2, 9)));
+    }
+
+    #[test]
+    fn paths_are_normalized() {
+        let paths = PathDisplay::default();
+        assert_eq!(paths.display("./src/foo.js"), "src/foo.js");
+        assert_eq!(paths.display(".\\src\\lib\\..\\foo.js"), "src/foo.js");
+        assert_eq!(paths.display("C:\\proj\\foo.js"), "C:/proj/foo.js");
+        assert_eq!(paths.display("../foo.js"), "../foo.js");
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_root() {
+        let unix = PathDisplay::relative_to("src", "/proj");
+        assert_eq!(unix.display("./src/foo.js"), "foo.js");
+        assert_eq!(unix.display("/proj/src/a/foo.js"), "a/foo.js");
+        assert_eq!(unix.display("lib/foo.js"), "/proj/lib/foo.js");
+
+        let windows = PathDisplay::relative_to("C:\\proj", "c:\\proj\\src");
+        assert_eq!(windows.display(".\\foo.js"), "src/foo.js");
+        assert_eq!(windows.display("D:\\foo.js"), "D:/foo.js");
+    }
+}
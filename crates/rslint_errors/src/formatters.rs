@@ -3,7 +3,7 @@ use crate::*;
 use codespan::files::Error;
 use colored::*;
 use file::Files;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io;
 
 /// A trait describing a struct which can render diagnostics to a writer such as stderr.
@@ -58,12 +58,10 @@ impl Formatter for ShortFormatter {
             let mut line_starts = vec![];
 
             for diag in cur_diags.clone() {
-                let line_index = files
-                    .line_index(id, diag.primary.as_ref().unwrap().span.range.start)
+                let (line, column) = files
+                    .location(id, diag.primary.as_ref().unwrap().span.range.start)
                     .expect("Line index out of bounds");
-                let line_span = files.line_range(id, line_index).unwrap();
-                let column = diag.primary.as_ref().unwrap().span.range.start - line_span.start;
-                line_starts.push((line_index, column));
+                line_starts.push((line - 1, column - 1));
             }
             let max_msg_len = cur_diags
                 .clone()
@@ -73,7 +71,7 @@ impl Formatter for ShortFormatter {
 
             let max_severity_len = cur_diags
                 .clone()
-                .map(|x| format!("{:?}", x.severity).len())
+                .map(|x| severity_label(x).len())
                 .max()
                 .unwrap();
 
@@ -96,23 +94,22 @@ impl Formatter for ShortFormatter {
                     ":".truecolor(140, 140, 140),
                     column.to_string().truecolor(140, 140, 140)
                 )?;
-                let color = match diag.severity {
-                    Severity::Bug | Severity::Error => Color::BrightRed,
-                    Severity::Note => Color::BrightCyan,
-                    Severity::Warning => Color::BrightYellow,
-                    Severity::Help => Color::BrightGreen,
+                let severity_string = severity_label(diag);
+                let severity_colored = if diag.suppressed_by.is_some() {
+                    severity_string.truecolor(140, 140, 140)
+                } else {
+                    severity_string.color(severity_color(diag.severity))
                 };
-                let severity_string = format!("{:?}", diag.severity).to_ascii_lowercase();
                 write!(
                     writer,
                     "{}{}  ",
                     " ".repeat(max_severity_len - severity_string.len()),
-                    severity_string.color(color)
+                    severity_colored
                 )?;
                 write!(
                     writer,
                     "{}{}  ",
-                    diag.title.trim(),
+                    title(diag),
                     " ".repeat(max_msg_len - diag.title.trim().len())
                 )?;
                 if let Some(code) = diag.code.clone() {
@@ -151,3 +148,118 @@ impl Formatter for LongFormatter {
         Ok(())
     }
 }
+
+/// A formatter which groups diagnostics by the rule which emitted them, showing how many times each
+/// rule fired and a compact list of every occurrence.
+///
+/// Rules are ordered by how many times they fired, this makes it easy to scan output in which a single
+/// rule fires hundreds of times.
+#[derive(Debug, Copy, Clone)]
+pub struct GroupedFormatter;
+
+impl Formatter for GroupedFormatter {
+    fn emit_with_writer(
+        &mut self,
+        diagnostics: &[Diagnostic],
+        files: &dyn Files,
+        writer: &mut dyn WriteColor,
+    ) -> io::Result<()> {
+        let mut groups: HashMap<&str, Vec<(Location<'_>, &Diagnostic)>> = HashMap::new();
+        for diag in diagnostics {
+            let code = diag.code.as_deref().unwrap_or("other");
+            groups
+                .entry(code)
+                .or_default()
+                .push((location(diag, files), diag));
+        }
+
+        let mut groups = groups.into_iter().collect::<Vec<_>>();
+        groups.sort_by(|(a_code, a), (b_code, b)| b.len().cmp(&a.len()).then(a_code.cmp(b_code)));
+
+        for (code, mut occurrences) in groups {
+            occurrences.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let severity = occurrences
+                .iter()
+                .filter(|(_, diag)| diag.suppressed_by.is_none())
+                .map(|(_, diag)| diag.severity)
+                .fold(Severity::Help, |a, b| if b > a { b } else { a });
+            writeln!(
+                writer,
+                "{} {}",
+                code.color(severity_color(severity)).bold(),
+                format!("({})", occurrences.len()).truecolor(140, 140, 140)
+            )?;
+
+            let locations = occurrences
+                .iter()
+                .map(|(loc, _)| loc.to_string())
+                .collect::<Vec<_>>();
+            let max_loc = locations.iter().map(|loc| loc.len()).max().unwrap();
+            for (loc, (_, diag)) in locations.into_iter().zip(occurrences) {
+                writeln!(
+                    writer,
+                    "  {}{}  {}",
+                    loc.truecolor(140, 140, 140),
+                    " ".repeat(max_loc - loc.len()),
+                    title(diag)
+                )?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// The file name and one based line and column of a diagnostic's primary label.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Location<'a> {
+    name: &'a str,
+    line_col: Option<(usize, usize)>,
+}
+
+impl std::fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line_col {
+            Some((line, col)) => write!(f, "{}:{}:{}", self.name, line, col),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+fn location<'a>(diag: &Diagnostic, files: &'a dyn Files) -> Location<'a> {
+    let name = files.name(diag.file_id).unwrap_or_default();
+    let line_col = diag.primary.as_ref().map(|primary| {
+        files
+            .location(diag.file_id, primary.span.range.start)
+            .expect("Line index out of bounds")
+    });
+    Location { name, line_col }
+}
+
+/// The severity shown by the short formatter, suppressed diagnostics are shown as `suppressed`.
+fn severity_label(diag: &Diagnostic) -> String {
+    if diag.suppressed_by.is_some() {
+        "suppressed".to_string()
+    } else {
+        format!("{:?}", diag.severity).to_ascii_lowercase()
+    }
+}
+
+/// The trimmed title of a diagnostic, struck through if the diagnostic is suppressed.
+fn title(diag: &Diagnostic) -> ColoredString {
+    let title = diag.title.trim();
+    if diag.suppressed_by.is_some() {
+        title.strikethrough().dimmed()
+    } else {
+        title.normal()
+    }
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Bug | Severity::Error => Color::BrightRed,
+        Severity::Note => Color::BrightCyan,
+        Severity::Warning => Color::BrightYellow,
+        Severity::Help => Color::BrightGreen,
+    }
+}
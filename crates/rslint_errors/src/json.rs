@@ -0,0 +1,218 @@
+//! A formatter which renders diagnostics as JSON for consumption by other tools.
+//!
+//! The output is a single JSON object of the following shape, the `version` is bumped
+//! on any breaking change to the schema:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "diagnostics": [
+//!     {
+//!       "file": "foo.js",
+//!       "severity": "error",
+//!       "code": "no-empty",
+//!       "message": "empty block statements are not allowed",
+//!       "primary": { "message": "...", "span": { "start": 0, "end": 2, "startLine": 1, "startColumn": 1, "endLine": 1, "endColumn": 3 } },
+//!       "secondary": [],
+//!       "notes": [{ "severity": "help", "message": "..." }],
+//!       "suggestions": [{ "message": "...", "applicability": "always", "span": { ... }, "replacement": "..." }],
+//!       "captures": { "callee": { ... } },
+//!       "suppressedBy": null
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Byte offsets are zero based and end exclusive, lines and columns are one based and columns
+//! count unicode scalar values, with tabs expanded to the [tab width](Files::tab_width) of the files. `file`, `code`, `primary`, and `span` may be `null`. `captures` maps the names
+//! of the nodes a rule captured (see [`Diagnostic::capture`]) to their spans. `suppressedBy` is the span of
+//! the directive comment which suppressed the diagnostic, suppressed diagnostics are only emitted when the
+//! linter is asked to keep them.
+
+use crate::termcolor::{ColorChoice, StandardStream, WriteColor};
+use crate::*;
+use file::{FileId, Files};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::ops::Range;
+
+/// The version of the JSON schema emitted by [`JsonFormatter`].
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// A formatter which emits all diagnostics as a single JSON document.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct JsonFormatter {
+    /// Whether to pretty print the document instead of emitting it on a single line.
+    pub pretty: bool,
+}
+
+impl Formatter for JsonFormatter {
+    /// Emit the document to stdout, unlike the other formatters which emit to stderr for humans to read, so
+    /// the document can be piped to other tools.
+    fn emit_stdout(&mut self, diagnostics: &[Diagnostic], files: &dyn Files) -> io::Result<()> {
+        let stdout = StandardStream::stdout(ColorChoice::Never);
+        let mut out = stdout.lock();
+        self.emit_with_writer(diagnostics, files, &mut out)
+    }
+
+    fn emit_with_writer(
+        &mut self,
+        diagnostics: &[Diagnostic],
+        files: &dyn Files,
+        writer: &mut dyn WriteColor,
+    ) -> io::Result<()> {
+        let document = JsonDocument {
+            version: JSON_SCHEMA_VERSION,
+            diagnostics: diagnostics
+                .iter()
+                .map(|d| JsonDiagnostic::new(d, files))
+                .collect(),
+        };
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut *writer, &document)?;
+        } else {
+            serde_json::to_writer(&mut *writer, &document)?;
+        }
+        writeln!(writer)
+    }
+}
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    version: u32,
+    diagnostics: Vec<JsonDiagnostic<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    file: Option<&'a str>,
+    severity: &'static str,
+    code: Option<&'a str>,
+    message: &'a str,
+    primary: Option<JsonLabel<'a>>,
+    secondary: Vec<JsonLabel<'a>>,
+    notes: Vec<JsonNote<'a>>,
+    suggestions: Vec<JsonSuggestion<'a>>,
+    captures: BTreeMap<&'a str, JsonSpan>,
+    #[serde(rename = "suppressedBy")]
+    suppressed_by: Option<JsonSpan>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    message: &'a str,
+    span: Option<JsonSpan>,
+}
+
+#[derive(Serialize)]
+struct JsonNote<'a> {
+    severity: &'static str,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSuggestion<'a> {
+    message: &'a str,
+    applicability: &'static str,
+    span: Option<JsonSpan>,
+    replacement: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSpan {
+    start: usize,
+    end: usize,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    fn new(d: &'a Diagnostic, files: &'a dyn Files) -> Self {
+        let label = |sub: &'a SubDiagnostic| JsonLabel {
+            message: &sub.msg,
+            span: JsonSpan::new(files, sub.span.file, sub.span.range.clone()),
+        };
+
+        Self {
+            file: files.name(d.file_id),
+            severity: severity_name(d.severity),
+            code: d.code.as_deref(),
+            message: &d.title,
+            primary: d.primary.as_ref().map(label),
+            secondary: d.children.iter().map(label).collect(),
+            notes: d
+                .footers
+                .iter()
+                .map(|footer| JsonNote {
+                    severity: severity_name(footer.severity),
+                    message: &footer.msg,
+                })
+                .collect(),
+            suggestions: d
+                .suggestions
+                .iter()
+                .map(|suggestion| JsonSuggestion {
+                    message: &suggestion.msg,
+                    applicability: applicability_name(suggestion.applicability),
+                    span: JsonSpan::new(files, suggestion.span.file, suggestion.span.range.clone()),
+                    replacement: files
+                        .source(suggestion.span.file)
+                        .and_then(|source| suggestion.replacement(source)),
+                })
+                .collect(),
+            captures: d
+                .captures
+                .iter()
+                .filter_map(|(name, range)| {
+                    Some((
+                        name.as_str(),
+                        JsonSpan::new(files, d.file_id, range.clone())?,
+                    ))
+                })
+                .collect(),
+            suppressed_by: d
+                .suppressed_by
+                .clone()
+                .and_then(|range| JsonSpan::new(files, d.file_id, range)),
+        }
+    }
+}
+
+impl JsonSpan {
+    fn new(files: &dyn Files, file_id: FileId, range: Range<usize>) -> Option<Self> {
+        let (start_line, start_column) = files.location(file_id, range.start)?;
+        let (end_line, end_column) = files.location(file_id, range.end)?;
+        Some(Self {
+            start: range.start,
+            end: range.end,
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        })
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn applicability_name(applicability: Applicability) -> &'static str {
+    match applicability {
+        Applicability::Always => "always",
+        Applicability::MaybeIncorrect => "maybe-incorrect",
+        Applicability::HasPlaceholders => "has-placeholders",
+        Applicability::Unspecified => "unspecified",
+    }
+}
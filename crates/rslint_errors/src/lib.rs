@@ -1,6 +1,8 @@
 #![deny(rust_2018_idioms)]
 
 pub mod file;
+#[cfg(feature = "json")]
+mod json;
 #[cfg(feature = "lsp")]
 pub mod lsp;
 
@@ -14,13 +16,15 @@ pub use diagnostic::{Diagnostic, SubDiagnostic};
 pub use emit::Emitter;
 pub use file::Span;
 pub use formatters::*;
+#[cfg(feature = "json")]
+pub use json::{JsonFormatter, JSON_SCHEMA_VERSION};
 pub use suggestion::*;
 
 pub use codespan::diagnostic::Severity;
 pub use termcolor;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DiagnosticTag {
     Unnecessary,
     Deprecated,
@@ -39,7 +43,7 @@ impl DiagnosticTag {
 
 /// Indicicates how a tool should manage this suggestion.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Applicability {
     /// The suggestion is definitely what the user intended.
     /// This suggestion should be automatically applied.
@@ -6,6 +6,7 @@ use std::ops::Range;
 /// can be reported to the user, and can be automatically
 /// applied if it has the right [`Applicability`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeSuggestion {
     /// If the `FileId` is `None`, it's in the same file as
     /// his parent.
@@ -17,13 +18,30 @@ pub struct CodeSuggestion {
     pub labels: Vec<Range<usize>>,
 }
 
+impl CodeSuggestion {
+    /// The text the suggestion's span is replaced with, `source` must be the source code of the suggestion's file.
+    pub fn replacement(&self, source: &str) -> Option<String> {
+        match &self.substitution {
+            SuggestionChange::String(string) => Some(string.clone()),
+            SuggestionChange::Indels(indels) => {
+                let mut old = source.get(self.span.range.clone())?.to_owned();
+                rslint_text_edit::apply_indels(indels, &mut old);
+                Some(old)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SuggestionChange {
+    #[cfg_attr(feature = "serde", serde(with = "indels"))]
     Indels(Vec<Indel>),
     String(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SuggestionStyle {
     /// Do not show the suggestion at all
     DontShow,
@@ -40,3 +58,39 @@ pub enum SuggestionStyle {
     /// ```
     Full,
 }
+
+/// Indels are (de)serialized as their replacement text and deleted range.
+#[cfg(feature = "serde")]
+mod indels {
+    use rslint_text_edit::{Indel, TextRange, TextSize};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct RawIndel {
+        insert: String,
+        start: u32,
+        end: u32,
+    }
+
+    pub fn serialize<S: Serializer>(indels: &[Indel], serializer: S) -> Result<S::Ok, S::Error> {
+        indels
+            .iter()
+            .map(|indel| RawIndel {
+                insert: indel.insert.clone(),
+                start: indel.delete.start().into(),
+                end: indel.delete.end().into(),
+            })
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Indel>, D::Error> {
+        Ok(Vec::<RawIndel>::deserialize(deserializer)?
+            .into_iter()
+            .map(|raw| Indel {
+                insert: raw.insert,
+                delete: TextRange::new(TextSize::from(raw.start), TextSize::from(raw.end)),
+            })
+            .collect())
+    }
+}
@@ -1,9 +1,9 @@
 //! Core definitions related to documents.
 
 use crate::core::language::{Language, LanguageId};
-use rslint_errors::file::SimpleFiles;
+use rslint_errors::{file::SimpleFiles, Diagnostic as RslintDiagnostic};
 use rslint_parser::{ast, parse_module, parse_text, GreenNode, Parse, ParserError};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::OnceLock};
 use tower_lsp::lsp_types::*;
 
 /// Trait for working with Parse<T> for a document.
@@ -48,6 +48,11 @@ pub struct Document {
     pub parse: Box<dyn DocumentParse>,
     /// The textual content of the document.
     pub text: String,
+    /// The rule diagnostics which were last published for the document, quick fixes are computed from them.
+    pub diagnostics: Vec<RslintDiagnostic>,
+    /// The text of the document after applying every autofix, computed once for the fix all code action.
+    /// `None` inside if the document could not be linted or fixed.
+    pub fixed: OnceLock<Option<String>>,
 }
 
 impl Document {
@@ -77,6 +82,8 @@ impl Document {
             language_id: LanguageId(language_id),
             parse,
             text,
+            diagnostics: vec![],
+            fixed: OnceLock::new(),
         };
 
         Ok(document)
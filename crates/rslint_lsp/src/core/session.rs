@@ -19,7 +19,9 @@ impl Session {
     /// Create a new session.
     pub fn new(client: Option<Client>) -> anyhow::Result<Self> {
         let documents = DashMap::new();
-        let store = CstRuleStore::new().builtins();
+        let mut store = CstRuleStore::new().builtins();
+        // the server does not read configs, so it can't know whether documents are run by node
+        store.rules.retain(|rule| rule.group() != "node");
         Ok(Session {
             client,
             documents,
@@ -1,6 +1,6 @@
 //! Definitions for the LSP server instance.
 
-use crate::{lsp::server::Server, service::synchronizer};
+use crate::{core::error::IntoJsonRpcError, lsp::server::Server, provider, service::synchronizer};
 use tower_lsp::{jsonrpc::Result, lsp_types::*, LanguageServer};
 
 #[tower_lsp::async_trait]
@@ -35,6 +35,13 @@ impl LanguageServer for Server {
             .unwrap()
     }
 
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let session = self.session.clone();
+        provider::code_actions::code_actions(session, params)
+            .await
+            .map_err(|err| IntoJsonRpcError(err).into())
+    }
+
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let session = self.session.clone();
         synchronizer::document::close(session, params)
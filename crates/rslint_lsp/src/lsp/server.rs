@@ -25,13 +25,22 @@ pub fn capabilities() -> ServerCapabilities {
     let text_document_sync = Some(TextDocumentSyncCapability::Options(
         TextDocumentSyncOptions {
             open_close: Some(true),
-            change: Some(TextDocumentSyncKind::Full),
+            change: Some(TextDocumentSyncKind::Incremental),
             ..Default::default()
         },
     ));
 
+    let code_action_provider = Some(CodeActionProviderCapability::Options(CodeActionOptions {
+        code_action_kinds: Some(vec![
+            CodeActionKind::QUICKFIX,
+            crate::provider::code_actions::SOURCE_FIX_ALL,
+        ]),
+        work_done_progress_options: Default::default(),
+    }));
+
     ServerCapabilities {
         text_document_sync,
+        code_action_provider,
         ..Default::default()
     }
 }
@@ -0,0 +1,150 @@
+//! Provider for LSP code actions.
+
+use crate::core::{document::Document, language::Language, session::Session};
+use rslint_core::lint_file;
+use rslint_errors::{
+    lsp::{byte_span_to_range, convert_to_lsp_diagnostic, range_to_byte_span},
+    Applicability, Diagnostic as RslintDiagnostic,
+};
+use std::{collections::HashMap, sync::Arc};
+use tower_lsp::lsp_types::*;
+
+/// The kind of the code action which applies every autofix in a document.
+pub(crate) const SOURCE_FIX_ALL: CodeActionKind = CodeActionKind::new("source.fixAll.rslint");
+
+/// Whether the client asked for actions of this kind.
+fn kind_requested(only: &Option<Vec<CodeActionKind>>, kind: &CodeActionKind) -> bool {
+    let only = match only {
+        Some(only) => only,
+        None => return true,
+    };
+    only.iter().any(|requested| {
+        kind.as_str() == requested.as_str()
+            || kind
+                .as_str()
+                .starts_with(&format!("{}.", requested.as_str()))
+    })
+}
+
+fn edit(uri: &Url, range: Range, new_text: String) -> WorkspaceEdit {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), vec![TextEdit { range, new_text }]);
+    WorkspaceEdit {
+        changes: Some(changes),
+        ..WorkspaceEdit::default()
+    }
+}
+
+/// Quick fixes for the suggestions of a diagnostic which overlap the requested span.
+fn suggestion_actions(
+    document: &Document,
+    uri: &Url,
+    diagnostic: &RslintDiagnostic,
+    requested: &std::ops::Range<usize>,
+    out: &mut Vec<CodeActionOrCommand>,
+) {
+    let files = &document.files;
+    let file_id = document.file_id;
+    let mut suggestions = diagnostic
+        .suggestions
+        .iter()
+        .filter(|suggestion| {
+            let span = &suggestion.span.range;
+            suggestion.span.file == file_id
+                && span.start <= requested.end
+                && requested.start <= span.end
+        })
+        .peekable();
+    if suggestions.peek().is_none() {
+        return;
+    }
+    let lsp_diagnostic = convert_to_lsp_diagnostic(
+        diagnostic.clone(),
+        files,
+        file_id,
+        uri.clone(),
+        Some("rslint".to_string()),
+    );
+
+    for suggestion in suggestions {
+        let span = suggestion.span.range.clone();
+        let (replacement, range) = match (
+            suggestion.replacement(&document.text),
+            byte_span_to_range(files, file_id, span),
+        ) {
+            (Some(replacement), Ok(range)) => (replacement, range),
+            _ => continue,
+        };
+
+        let title = if !replacement.is_empty() && !replacement.contains('\n') {
+            format!(
+                "{}: `{}`",
+                suggestion.msg.trim_end_matches(':'),
+                replacement
+            )
+        } else {
+            suggestion.msg.clone()
+        };
+        out.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title,
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: lsp_diagnostic.clone().map(|diagnostic| vec![diagnostic]),
+            edit: Some(edit(uri, range, replacement)),
+            command: None,
+            is_preferred: Some(suggestion.applicability == Applicability::Always),
+        }));
+    }
+}
+
+/// Compute the code actions for a span of a document. These are quick fixes for the suggestions of diagnostics
+/// in the span, which include suggestions that are never applied by autofix, and an action which applies every autofix.
+///
+/// Quick fixes are computed from the diagnostics which were last published for the document, the document is
+/// only linted again for the fixed text of the first fix all action after a change.
+pub async fn code_actions(
+    session: Arc<Session>,
+    params: CodeActionParams,
+) -> anyhow::Result<Option<CodeActionResponse>> {
+    let CodeActionParams {
+        text_document: TextDocumentIdentifier { uri },
+        range,
+        context,
+        ..
+    } = params;
+    let document = session.get_document(&uri).await?;
+    let span = range_to_byte_span(&document.files, document.file_id, &range)?;
+
+    let mut actions = vec![];
+    if kind_requested(&context.only, &CodeActionKind::QUICKFIX) {
+        for diagnostic in &document.diagnostics {
+            suggestion_actions(&document, &uri, diagnostic, &span, &mut actions);
+        }
+    }
+
+    if kind_requested(&context.only, &SOURCE_FIX_ALL) {
+        let fixed = document.fixed.get_or_init(|| {
+            let module = document.language == Language::JavaScriptModule;
+            lint_file(
+                document.file_id,
+                &document.text,
+                module,
+                &session.store,
+                false,
+            )
+            .ok()?
+            .fix(false)
+        });
+        if let Some(fixed) = fixed.as_ref().filter(|fixed| *fixed != &document.text) {
+            let whole_document =
+                byte_span_to_range(&document.files, document.file_id, 0..document.text.len())?;
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Fix all auto-fixable problems".to_string(),
+                kind: Some(SOURCE_FIX_ALL),
+                edit: Some(edit(&uri, whole_document, fixed.clone())),
+                ..CodeAction::default()
+            }));
+        }
+    }
+
+    Ok(Some(actions))
+}
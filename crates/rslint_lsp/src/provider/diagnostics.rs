@@ -2,7 +2,9 @@
 
 use crate::core::{document::Document, session::Session};
 use rayon::prelude::*;
-use rslint_core::{apply_top_level_directives, run_rule, DirectiveParser};
+use rslint_core::{
+    apply_top_level_directives, directives::DisabledRanges, run_rule, DirectiveParser,
+};
 use rslint_errors::{lsp::convert_to_lsp_diagnostic, Diagnostic as RslintDiagnostic};
 use rslint_parser::SyntaxNode;
 use std::{collections::HashMap, sync::Arc};
@@ -59,19 +61,24 @@ pub async fn publish_diagnostics(session: Arc<Session>, uri: Url) -> anyhow::Res
                 &mut directive_diagnostics,
                 file_id,
             );
+            let disabled = DisabledRanges::new(
+                &directives,
+                &document.text,
+                file_id,
+                &mut directive_diagnostics,
+            );
 
             let verbose = false;
             let src = Arc::new(document.text.clone());
-            let rule_diagnostics: HashMap<&str, Vec<rslint_errors::Diagnostic>> = new_store
+            let rule_diagnostics: HashMap<&str, Vec<RslintDiagnostic>> = new_store
                 .rules
                 .par_iter()
                 .map(|rule| {
                     let root = SyntaxNode::new_root(document.parse.green());
-                    (
-                        rule.name(),
-                        run_rule(&**rule, file_id, root, verbose, &directives, src.clone())
-                            .diagnostics,
-                    )
+                    let mut result =
+                        run_rule(&**rule, file_id, root, verbose, &directives, src.clone());
+                    disabled.apply(rule.name(), &mut result);
+                    (rule.name(), result.diagnostics)
                 })
                 .collect();
 
@@ -84,9 +91,18 @@ pub async fn publish_diagnostics(session: Arc<Session>, uri: Url) -> anyhow::Res
                 &mut diags,
             );
 
-            for (_, diagnostics) in rule_diagnostics {
-                process_diagnostics(&document, uri.clone(), diagnostics.to_owned(), &mut diags);
+            let rule_diagnostics = rule_diagnostics.into_values().flatten().collect::<Vec<_>>();
+            process_diagnostics(&document, uri.clone(), rule_diagnostics.clone(), &mut diags);
+
+            // code actions are computed from the published diagnostics, the text is checked in case the
+            // document was changed while linting
+            let text = document.text.clone();
+            drop(document);
+            let mut document = session.get_mut_document(&uri).await?;
+            if document.text == text {
+                document.diagnostics = rule_diagnostics;
             }
+            drop(document);
 
             let version = Default::default();
             session
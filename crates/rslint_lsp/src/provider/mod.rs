@@ -1,4 +1,6 @@
 //! Providers for LSP features.
 
+// Provider for LSP code actions.
+pub(crate) mod code_actions;
 // Provider for LSP diagnostics.
 pub(crate) mod diagnostics;
@@ -10,9 +10,12 @@ pub(crate) mod document {
         },
         provider,
     };
-    use rslint_errors::file::SimpleFiles;
+    use rslint_errors::{
+        file::{SimpleFile, SimpleFiles},
+        lsp::range_to_byte_span,
+    };
     use rslint_parser::{parse_module, parse_text};
-    use std::sync::Arc;
+    use std::sync::{Arc, OnceLock};
     use tower_lsp::lsp_types::*;
 
     /// Handle a document "change" event.
@@ -24,19 +27,33 @@ pub(crate) mod document {
             text_document: VersionedTextDocumentIdentifier { uri, .. },
             content_changes,
         } = params;
-        let TextDocumentContentChangeEvent { text, .. } = content_changes[0].clone();
 
         // NOTE: We use an explicit scope here because document (below) must be
         // dropped before calling publish_diagnostics (further below) otherwise
         // the server will block.
         {
+            let mut document = session.get_mut_document(&uri).await?;
+            let mut text = document.text.clone();
+            // changes are applied in order, each range refers to the text after the previous changes
+            for change in content_changes {
+                match change.range {
+                    Some(range) => {
+                        let file = SimpleFile::new(uri.to_string(), text.clone());
+                        let span = range_to_byte_span(&file, 0, &range)?;
+                        text.replace_range(span, &change.text);
+                    }
+                    None => text = change.text,
+                }
+            }
+
             let mut files = SimpleFiles::new();
             let file_id = files.add(uri.to_string(), text.clone());
 
-            let mut document = session.get_mut_document(&uri).await?;
             document.files = files;
             document.file_id = file_id;
             document.text = text.clone();
+            document.diagnostics.clear();
+            document.fixed = OnceLock::new();
 
             document.parse = if document.language == Language::JavaScriptModule {
                 Box::new(parse_module(&text, file_id)) as Box<dyn DocumentParse>
@@ -26,21 +26,16 @@ pub enum Event {
     },
 
     /// Complete the previous `Start` event
-    Finish {
-        end: usize,
-    },
+    Finish { end: usize },
 
     /// Produce a single leaf-element.
     /// `n_raw_tokens` is used to glue complex contextual tokens.
     /// For example, lexer tokenizes `>>` as `>`, `>`, and
     /// `n_raw_tokens = 2` is used to produced a single `>>`.
-    Token {
-        kind: SyntaxKind,
-    },
+    Token { kind: SyntaxKind },
 
-    Error {
-        err: ParserError,
-    },
+    /// Errors are rare, they are boxed so they do not make every event larger.
+    Error { err: Box<ParserError> },
 }
 
 impl Event {
@@ -104,7 +99,7 @@ pub fn process(sink: &mut dyn TreeSink, mut events: Vec<Event>) {
             Event::Token { kind } => {
                 sink.token(kind);
             }
-            Event::Error { err } => sink.error(err),
+            Event::Error { err } => sink.error(*err),
         }
     }
 }
@@ -267,7 +267,9 @@ impl<'t> Parser<'t> {
 
     /// Add an error event
     pub fn error(&mut self, err: impl Into<ParserError>) {
-        self.push_event(Event::Error { err: err.into() });
+        self.push_event(Event::Error {
+            err: Box::new(err.into()),
+        });
     }
 
     /// Check if the parser's current token is contained in a token set
@@ -3,16 +3,18 @@ mod extract;
 use crate::project_root;
 use convert_case::{Case, Casing};
 use extract::*;
-use quote::ToTokens;
+use rslint_core::docgen::{generate, RuleDocPage};
+use rslint_core::CstRuleStore;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::{read_dir, read_to_string, write};
 
-const GROUPS_ROOT: &str = "crates/rslint_core/src/groups";
+pub(crate) const GROUPS_ROOT: &str = "crates/rslint_core/src/groups";
 
 const REPO: &str = "https://github.com/RDambrosio016/RSLint/tree/master";
 
 pub fn run() {
+    let pages = generate(&CstRuleStore::new().builtins());
     let mut summary_res = "- [Rules](rules/README.md)\n".to_string();
     let mut groups = vec![];
     for file in read_dir(project_root().join(GROUPS_ROOT))
@@ -46,9 +48,13 @@ pub fn run() {
                     meta.name,
                     replaced.strip_suffix(".rs").unwrap()
                 ));
+                let page = pages
+                    .iter()
+                    .find(|page| page.name == rule.lint_declaration.name)
+                    .expect("Rule is not a builtin rule");
                 write(
                     dir.join(name.replace("_", "-")).with_extension("md"),
-                    rule_markdown(rule, &meta),
+                    rule_markdown(page.clone(), rule, &meta),
                 )
                 .expect("Failed to write rule markdown");
             }
@@ -156,44 +162,22 @@ pub fn extract_group(group_name: &str) -> Result<HashMap<String, RuleFile>, Box<
     Ok(res)
 }
 
-pub fn rule_markdown(rule: RuleFile, group: &Group) -> String {
-    let mut ret = rule
-        .lint_declaration
-        .docstring
-        .unwrap_or_default()
-        .replace("```ignore", "```js");
-    ret.insert_str(
-        0,
-        &format!(
-            "<!--\n generated docs file, do not edit by hand, see xtask/docgen \n-->\n# {}\n\n",
-            rule.lint_declaration.name
-        ),
-    );
-
-    if !rule.lint_declaration.config_fields.is_empty() {
-        ret.push_str("\n## Config\n");
-        ret.push_str("| Name | Type | Description |\n");
-        ret.push_str("| ---- | ---- | ----------- |\n");
-
-        for config in rule.lint_declaration.config_fields.iter() {
-            ret.push_str(&format!(
-                "| `{}` | {} | {} |\n",
-                config
-                    .field
-                    .ident
-                    .as_ref()
-                    .unwrap()
-                    .to_string()
-                    .to_case(Case::Camel),
-                config.field.ty.to_token_stream().to_string(),
-                config
-                    .docstring
-                    .clone()
-                    .unwrap_or_default()
-                    .replace("\n", "<br>")
-            ));
+/// Render the page of a rule with [`RuleDocPage::render`], adding what is only in the source of the rule: the
+/// docs of its options, the examples of its tests, and a link to it.
+pub fn rule_markdown(mut page: RuleDocPage, rule: RuleFile, group: &Group) -> String {
+    for config in rule.lint_declaration.config_fields.iter() {
+        let name = config
+            .field
+            .ident
+            .as_ref()
+            .unwrap()
+            .to_string()
+            .to_case(Case::Camel);
+        if let Some(option) = page.options.iter_mut().find(|option| option.name == name) {
+            option.description = config.docstring.clone();
         }
     }
+    let mut ret = page.render();
 
     if let Some(tests) = rule.tests {
         if !tests.err_examples.is_empty() {
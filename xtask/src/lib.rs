@@ -5,6 +5,7 @@ pub mod codegen;
 pub mod coverage;
 pub mod docgen;
 pub mod glue;
+pub mod rule_coverage;
 
 use std::{
     env,
@@ -1,9 +1,10 @@
 use pico_args::Arguments;
+use std::path::PathBuf;
 use xtask::{
     codegen::{self, Mode},
     coverage, docgen,
     glue::pushd,
-    project_root, run_rustfmt, Result,
+    project_root, rule_coverage, run_rustfmt, Result,
 };
 
 fn main() -> Result<()> {
@@ -38,6 +39,11 @@ fn main() -> Result<()> {
             coverage::run(query);
             Ok(())
         }
+        "rule-coverage" => {
+            let corpus: PathBuf = args.free_from_str()?.unwrap_or_else(|| ".".into());
+            args.finish()?;
+            rule_coverage::run(&corpus)
+        }
         _ => {
             eprintln!(
                 "\
@@ -50,7 +56,8 @@ SUBCOMMANDS:
     codegen
     syntax
     docgen
-    coverage"
+    coverage
+    rule-coverage <corpus>"
             );
             Ok(())
         }
@@ -0,0 +1,67 @@
+//! Lint a corpus of files and report how often each rule fires and how many test fixtures it has,
+//! see [`rslint_core::coverage`].
+
+use crate::docgen::{extract_group, GROUPS_ROOT};
+use crate::{project_root, Result};
+use rayon::prelude::*;
+use rslint_cli::is_linted_file;
+use rslint_core::coverage::{CorpusCoverage, FixtureCount};
+use rslint_core::environment::Environment;
+use rslint_core::{lint_file, CstRuleStore, FileKind};
+use std::collections::HashMap;
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Lint every file in `corpus` which the CLI would lint with all builtin rules and print the report as JSON.
+pub fn run(corpus: &Path) -> Result<()> {
+    let store = CstRuleStore::new().builtins();
+    let paths = WalkDir::new(corpus)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| is_linted_file(path))
+        .collect::<Vec<_>>();
+
+    let results = paths
+        .par_iter()
+        .enumerate()
+        .filter_map(|(id, path)| {
+            let source = read_to_string(path).ok()?;
+            let module = Environment::resolve(path, &source, None).kind == FileKind::Module;
+            lint_file(id, &source, module, &store, false).ok()
+        })
+        .collect::<Vec<_>>();
+
+    let mut coverage = CorpusCoverage::new(&store);
+    for result in &results {
+        coverage.add(result);
+    }
+    let report = coverage.report(&fixtures()?);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+/// The number of `rule_tests!` fixtures of every builtin rule.
+fn fixtures() -> Result<HashMap<String, FixtureCount>> {
+    let mut fixtures = HashMap::new();
+    for entry in read_dir(project_root().join(GROUPS_ROOT))? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let group = extract_group(&entry.file_name().to_string_lossy())
+            .map_err(|err| anyhow::anyhow!("failed to extract rules: {}", err))?;
+        for rule in group.into_values() {
+            let count = rule
+                .tests
+                .map_or_else(FixtureCount::default, |tests| FixtureCount {
+                    invalid: tests.err_examples.len(),
+                    valid: tests.ok_examples.len(),
+                });
+            fixtures.insert(rule.lint_declaration.name, count);
+        }
+    }
+    Ok(fixtures)
+}